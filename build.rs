@@ -1,7 +1,85 @@
 use std::env;
+use std::path::PathBuf;
 use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 fn main() {
+    emit_build_info();
+    check_macos_openssl();
+}
+
+/// Expose the git sha and build date to `sv version` as SV_GIT_SHA and
+/// SV_BUILD_DATE. SOURCE_DATE_EPOCH pins the date for reproducible builds.
+fn emit_build_info() {
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    watch_git_head();
+
+    let sha = git_output(&["rev-parse", "--short=12", "HEAD"]).unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=SV_GIT_SHA={}", sha);
+
+    let epoch = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or(0)
+        });
+    println!(
+        "cargo:rustc-env=SV_BUILD_DATE={}",
+        civil_date(epoch / 86_400)
+    );
+}
+
+/// Rebuild when HEAD moves. In a worktree HEAD lives in the worktree's git dir
+/// while branch refs live in the common dir; a packaged crate has no git dir,
+/// so nothing is watched and cargo falls back to its default checks.
+fn watch_git_head() {
+    let Some(git_dir) = git_output(&["rev-parse", "--git-dir"]).map(PathBuf::from) else {
+        return;
+    };
+    let mut watched = vec![git_dir.join("HEAD")];
+    if let Some(head_ref) = git_output(&["symbolic-ref", "-q", "HEAD"]) {
+        let common_dir = git_output(&["rev-parse", "--git-common-dir"])
+            .map(PathBuf::from)
+            .unwrap_or(git_dir);
+        watched.push(common_dir.join(head_ref));
+        watched.push(common_dir.join("packed-refs"));
+    }
+    for path in watched.iter().filter(|path| path.is_file()) {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+fn git_output(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// YYYY-MM-DD for a count of days since 1970-01-01 (proleptic Gregorian)
+fn civil_date(days: u64) -> String {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn check_macos_openssl() {
     let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
     if target_os != "macos" {
         return;
@@ -13,28 +13,57 @@ use crate::error::{Error, Result};
 
 const ACTOR_FILENAME: &str = "actor";
 
+/// Where a resolved actor came from, in resolution order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorSource {
+    /// `--actor`
+    Cli,
+    /// `SV_ACTOR`
+    Env,
+    /// `.sv/actor`
+    Persisted,
+    /// `actor.default` in config
+    Config,
+    /// Nothing set; the actor is "unknown"
+    Default,
+}
+
 /// Resolve the current actor using CLI, environment, persisted value, and config.
 pub fn resolve_actor(repo_root: Option<&Path>, cli_actor: Option<&str>) -> Result<String> {
+    resolve_actor_with_source(repo_root, cli_actor).map(|(actor, _)| actor)
+}
+
+/// Like [`resolve_actor`], also reporting which source supplied the actor.
+pub fn resolve_actor_with_source(
+    repo_root: Option<&Path>,
+    cli_actor: Option<&str>,
+) -> Result<(String, ActorSource)> {
     if let Some(actor) = non_empty(cli_actor) {
-        return Ok(actor.to_string());
+        return Ok((actor.to_string(), ActorSource::Cli));
     }
 
     if let Ok(env_actor) = std::env::var("SV_ACTOR") {
         if let Some(actor) = non_empty(Some(env_actor.as_str())) {
-            return Ok(actor.to_string());
+            return Ok((actor.to_string(), ActorSource::Env));
         }
     }
 
     if let Some(root) = repo_root {
         if let Some(actor) = load_persisted_actor(root)? {
-            return Ok(actor);
+            return Ok((actor, ActorSource::Persisted));
         }
 
         let config = Config::load_from_repo(root);
-        return Ok(config.actor.default);
+        let source = if config.actor.default == "unknown" {
+            ActorSource::Default
+        } else {
+            ActorSource::Config
+        };
+        return Ok((config.actor.default, source));
     }
 
-    Ok("unknown".to_string())
+    Ok(("unknown".to_string(), ActorSource::Default))
 }
 
 /// Resolve the current actor, returning None when it resolves to "unknown".
@@ -168,6 +197,28 @@ mod tests {
         assert_eq!(actor, "config-actor");
     }
 
+    #[test]
+    fn resolve_actor_with_source_reports_origin() {
+        let _lock = ENV_LOCK.lock().expect("env lock");
+        let _env = EnvGuard::remove("SV_ACTOR");
+        let dir = tempfile::tempdir().expect("tempdir");
+
+        let resolved = resolve_actor_with_source(Some(dir.path()), None).expect("resolve");
+        assert_eq!(resolved, ("unknown".to_string(), ActorSource::Default));
+
+        write_config(dir.path(), "config-actor");
+        let resolved = resolve_actor_with_source(Some(dir.path()), None).expect("resolve");
+        assert_eq!(resolved.1, ActorSource::Config);
+
+        write_actor_file(dir.path(), "persisted-actor");
+        let resolved = resolve_actor_with_source(Some(dir.path()), None).expect("resolve");
+        assert_eq!(resolved.1, ActorSource::Persisted);
+
+        let resolved =
+            resolve_actor_with_source(Some(dir.path()), Some("cli-actor")).expect("resolve");
+        assert_eq!(resolved, ("cli-actor".to_string(), ActorSource::Cli));
+    }
+
     #[test]
     fn persist_actor_rejects_empty() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -3,7 +3,7 @@
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
-use git2::{Commit, Oid, Repository};
+use git2::{Commit, Oid, Repository, Sort};
 use uuid::Uuid;
 
 use crate::error::Result;
@@ -196,6 +196,67 @@ pub fn dedup_commits_by_change_id(
     })
 }
 
+/// Missing and duplicated Change-Id trailers in a commit range.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeIdAudit {
+    /// Commits without a Change-Id trailer, oldest first.
+    pub missing: Vec<Oid>,
+    /// Commits sharing a Change-Id, grouped in order of first appearance.
+    pub duplicates: Vec<Vec<Oid>>,
+    /// Number of commits inspected.
+    pub checked: usize,
+}
+
+impl ChangeIdAudit {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.duplicates.is_empty()
+    }
+}
+
+/// Walk `base..head` and report commits with missing or shared Change-Ids.
+///
+/// Merge commits are skipped: they are usually created by `git merge`
+/// rather than `sv commit`, so they carry no trailer. Two commits sharing a
+/// Change-Id in one branch usually means a rebase kept both the old and the
+/// rewritten commit.
+pub fn audit_change_ids(
+    repo: &Repository,
+    base_ref: &str,
+    head_ref: &str,
+) -> Result<ChangeIdAudit> {
+    let base = repo.revparse_single(base_ref)?.peel_to_commit()?.id();
+    let head = repo.revparse_single(head_ref)?.peel_to_commit()?.id();
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    revwalk.push(head)?;
+    revwalk.hide(base)?;
+
+    let mut audit = ChangeIdAudit::default();
+    let mut groups: Vec<(String, Vec<Oid>)> = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        audit.checked += 1;
+        match change_id_from_commit(&commit) {
+            Some(change_id) => match groups.iter_mut().find(|(id, _)| *id == change_id) {
+                Some((_, oids)) => oids.push(commit.id()),
+                None => groups.push((change_id, vec![commit.id()])),
+            },
+            None => audit.missing.push(commit.id()),
+        }
+    }
+    audit.duplicates = groups
+        .into_iter()
+        .filter(|(_, oids)| oids.len() > 1)
+        .map(|(_, oids)| oids)
+        .collect();
+
+    Ok(audit)
+}
+
 fn append_change_id(message: &str, change_id: &str) -> String {
     let trimmed = message.trim_end_matches(['\n', '\r']);
     if trimmed.is_empty() {
@@ -384,4 +445,36 @@ mod tests {
         assert_eq!(resolved.warnings.len(), 1);
         assert!(resolved.conflicts.is_empty());
     }
+
+    #[test]
+    fn audit_reports_missing_and_duplicate_change_ids() {
+        let (_dir, repo) = init_repo();
+        let base = commit_on_ref(&repo, "HEAD", None, "file.txt", "base", "Base\n");
+        let first = commit_on_ref(
+            &repo,
+            "HEAD",
+            Some(base),
+            "file.txt",
+            "one",
+            "One\n\nChange-Id: CID-1",
+        );
+        let missing = commit_on_ref(&repo, "HEAD", Some(first), "file.txt", "two", "Two\n");
+        let duplicate = commit_on_ref(
+            &repo,
+            "HEAD",
+            Some(missing),
+            "file.txt",
+            "three",
+            "One again\n\nChange-Id: CID-1",
+        );
+
+        let audit = audit_change_ids(&repo, &base.to_string(), "HEAD").unwrap();
+        assert_eq!(audit.checked, 3);
+        assert_eq!(audit.missing, vec![missing]);
+        assert_eq!(audit.duplicates, vec![vec![first, duplicate]]);
+        assert!(!audit.is_clean());
+
+        let clean = audit_change_ids(&repo, &base.to_string(), &first.to_string()).unwrap();
+        assert!(clean.is_clean());
+    }
 }
@@ -0,0 +1,157 @@
+//! sv change-id command implementation
+//!
+//! Generates Change-Ids and reads them back from commits, for scripts that
+//! need to correlate commits across rebases, and checks a branch for missing
+//! or duplicated trailers.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::change_id::{audit_change_ids, find_change_id, generate_change_id};
+use crate::config::Config;
+use crate::error::Result;
+use crate::git;
+use crate::output::{emit_success, outln, HumanOutput, OutputOptions};
+
+/// Options for `sv change-id`
+pub struct ChangeIdOptions {
+    /// Read the Change-Id of this revision instead of generating one
+    pub of: Option<String>,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+/// Options for `sv change-id verify`
+pub struct VerifyOptions {
+    pub base: Option<String>,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ChangeIdReport {
+    change_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<String>,
+}
+
+/// Run `sv change-id [--of <rev>]`.
+///
+/// Human output is the bare Change-Id so it can be captured by scripts; a
+/// commit without a trailer prints nothing on stdout.
+pub fn run(options: ChangeIdOptions) -> Result<()> {
+    let report = match options.of.as_deref() {
+        Some(rev) => {
+            let repo = git::open_repo(options.repo.as_deref())?;
+            let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+            ChangeIdReport {
+                change_id: find_change_id(commit.message().unwrap_or_default()),
+                commit: Some(commit.id().to_string()),
+            }
+        }
+        None => ChangeIdReport {
+            change_id: Some(generate_change_id()),
+            commit: None,
+        },
+    };
+
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "change-id",
+        &report,
+        None,
+    )?;
+
+    if !options.json && !options.quiet {
+        match (&report.change_id, options.of.as_deref()) {
+            (Some(change_id), _) => outln!("{change_id}"),
+            (None, Some(rev)) => eprintln!("no Change-Id trailer in {rev}"),
+            (None, None) => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct VerifyReport {
+    base: String,
+    checked: usize,
+    missing: Vec<String>,
+    duplicates: Vec<Vec<String>>,
+}
+
+/// Run `sv change-id verify [--base <ref>]`.
+pub fn run_verify(options: VerifyOptions) -> Result<()> {
+    let repo = git::open_repo(options.repo.as_deref())?;
+    let workdir = git::workdir(&repo)?;
+    let base = options
+        .base
+        .unwrap_or_else(|| Config::load_from_repo(&workdir).base);
+    let audit = audit_change_ids(&repo, &base, "HEAD")?;
+
+    let report = VerifyReport {
+        base: base.clone(),
+        checked: audit.checked,
+        missing: audit.missing.iter().map(|oid| oid.to_string()).collect(),
+        duplicates: audit
+            .duplicates
+            .iter()
+            .map(|group| group.iter().map(|oid| oid.to_string()).collect())
+            .collect(),
+    };
+
+    let header = if audit.is_clean() {
+        format!("Change-Ids ok ({base}..HEAD)")
+    } else {
+        format!("Change-Id problems ({base}..HEAD)")
+    };
+    let mut human = HumanOutput::new(header);
+    human.push_summary("Checked", report.checked.to_string());
+    human.push_summary("Missing", report.missing.len().to_string());
+    human.push_summary("Duplicates", report.duplicates.len().to_string());
+    for oid in &audit.missing {
+        human.push_detail(format!("missing: {}", commit_line(&repo, *oid)));
+    }
+    for group in &audit.duplicates {
+        let commits: Vec<String> = group.iter().map(|oid| commit_line(&repo, *oid)).collect();
+        human.push_detail(format!("duplicate: {}", commits.join(" / ")));
+    }
+    if !audit.duplicates.is_empty() {
+        human.push_warning(
+            "commits share a Change-Id; a rebase may have kept both the old and new commit"
+                .to_string(),
+        );
+    }
+    if !audit.missing.is_empty() {
+        human.push_next_step(format!(
+            "git rebase -i {base} and reword the commits, adding a trailer from sv change-id"
+        ));
+    }
+
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "change-id verify",
+        &report,
+        Some(&human),
+    )
+}
+
+fn commit_line(repo: &git2::Repository, oid: git2::Oid) -> String {
+    let id = oid.to_string();
+    let summary = repo
+        .find_commit(oid)
+        .ok()
+        .and_then(|commit| commit.summary().map(str::to_string))
+        .unwrap_or_default();
+    format!("{} {}", &id[..8], summary)
+}
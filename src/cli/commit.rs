@@ -3,7 +3,8 @@
 //! Wraps git commit with sv-specific checks:
 //! - Protected path enforcement
 //! - Lease conflict checking
-//! - Change-Id injection (future)
+//! - Change-Id injection
+//! - Task trailers for forge commit linking (opt-in)
 //!
 //! This is the basic wrapper that passes through to git commit.
 
@@ -13,12 +14,16 @@ use std::process::Command;
 use crate::actor;
 use crate::change_id;
 use crate::config::Config;
-use crate::error::{Error, Result};
+use crate::error::{Error, ProtectedViolation, Result};
+use crate::events::{Event, EventDestination, EventKind};
 use crate::git;
-use crate::lease::{Lease, LeaseScope, LeaseStore, LeaseStrength};
+use crate::integrations::forge;
+use crate::lease::{self, Lease, LeaseScope, LeaseStore, LeaseStrength, Policy};
 use crate::oplog::{CommitDetails, OpDetails, OpLog, OpRecord, RefUpdate, UndoData};
+use crate::output::outln;
 use crate::protect;
 use crate::storage::Storage;
+use crate::task::TaskStore;
 
 /// Options for the commit command
 pub struct CommitOptions {
@@ -28,8 +33,12 @@ pub struct CommitOptions {
     pub all: bool,
     pub no_edit: bool,
     pub allow_protected: bool,
+    pub override_readonly: bool,
+    pub reason: Option<String>,
     pub force_lease: bool,
+    pub dry_run: bool,
     pub actor: Option<String>,
+    pub events: Option<String>,
     pub repo: Option<PathBuf>,
     pub json: bool,
     pub quiet: bool,
@@ -38,6 +47,8 @@ pub struct CommitOptions {
 /// Result of commit operation
 #[derive(serde::Serialize)]
 struct CommitResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    would: Option<&'static str>,
     success: bool,
     commit_hash: Option<String>,
     message: Option<String>,
@@ -56,6 +67,16 @@ struct ProtectedFileInfo {
     mode: String,
 }
 
+impl From<&ProtectedFileInfo> for ProtectedViolation {
+    fn from(info: &ProtectedFileInfo) -> Self {
+        Self {
+            path: info.file.clone(),
+            pattern: info.pattern.clone(),
+            mode: info.mode.clone(),
+        }
+    }
+}
+
 /// Information about a lease conflict
 #[derive(Clone, serde::Serialize)]
 struct LeaseConflictInfo {
@@ -63,6 +84,7 @@ struct LeaseConflictInfo {
     lease_id: String,
     holder: String,
     strength: String,
+    reason: String,
 }
 
 /// Run the commit command
@@ -80,17 +102,19 @@ pub fn run(options: CommitOptions) -> Result<()> {
         .workdir()
         .ok_or_else(|| Error::NotARepo(start.clone()))?;
 
-    // If -a flag, stage all modified files
+    // If -a flag, stage all modified files (in memory only for dry runs)
+    let mut index = repository.index()?;
     if options.all {
-        stage_all_modified(&repository)?;
+        stage_all_modified(&mut index, !options.dry_run)?;
     }
 
     // Get list of files to be committed
-    let staged_files = get_staged_files(&repository)?;
+    let staged_files = get_staged_files(&repository, &index)?;
 
     if staged_files.is_empty() && !options.amend {
         if options.json {
             let result = CommitResult {
+                would: None,
                 success: false,
                 commit_hash: None,
                 message: Some("Nothing to commit".to_string()),
@@ -98,15 +122,17 @@ pub fn run(options: CommitOptions) -> Result<()> {
                 protected_files: vec![],
                 lease_conflicts: vec![],
             };
-            println!("{}", serde_json::to_string_pretty(&result).unwrap());
+            outln!("{}", serde_json::to_string_pretty(&result).unwrap());
         } else if !options.quiet {
-            println!("Nothing to commit (no staged files)");
+            outln!("Nothing to commit (no staged files)");
         }
         return Ok(());
     }
 
     // Check protected paths (sv-8jf.4.5)
-    let (protected_guard, protected_warn) = check_protected_paths(&repository, &staged_files)?;
+    let config = Config::load_from_repo(workdir);
+    let (protected_guard, protected_readonly, protected_warn) =
+        check_protected_paths(&repository, &config, &staged_files)?;
 
     // Warn about warn-mode protected files
     if !protected_warn.is_empty() && !options.quiet {
@@ -116,25 +142,83 @@ pub fn run(options: CommitOptions) -> Result<()> {
         }
     }
 
-    // Block on guard-mode protected files unless --allow-protected
-    if !protected_guard.is_empty() && !options.allow_protected {
+    // Bypassing protection leaves a trail in the oplog and event stream
+    let bypass_reason = match options.reason.as_deref().map(str::trim) {
+        Some("") => {
+            return Err(Error::InvalidArgument(
+                "--reason cannot be empty".to_string(),
+            ))
+        }
+        Some(_) if !options.allow_protected && !options.override_readonly => {
+            return Err(Error::InvalidArgument(
+                "--reason requires --allow-protected or --override-readonly".to_string(),
+            ))
+        }
+        reason => reason.map(str::to_string),
+    };
+
+    // Guard paths yield to --allow-protected; readonly paths only to
+    // --override-readonly
+    let blocked: Vec<ProtectedViolation> = protected_guard
+        .iter()
+        .filter(|_| !options.allow_protected)
+        .chain(
+            protected_readonly
+                .iter()
+                .filter(|_| !options.override_readonly),
+        )
+        .map(ProtectedViolation::from)
+        .collect();
+    if !blocked.is_empty() {
         // Return error with exit code 3 (policy blocked)
-        return Err(Error::ProtectedPath(protected_guard[0].file.clone().into()));
+        return Err(Error::ProtectedViolations(blocked));
     }
 
-    // Inject Change-Id trailer if missing (sv-8jf.5.2)
+    let protected_bypassed: Vec<ProtectedFileInfo> = protected_guard
+        .into_iter()
+        .chain(protected_readonly)
+        .collect();
+    if !protected_bypassed.is_empty() {
+        if bypass_reason.is_none() && config.protect.require_bypass_reason {
+            return Err(Error::InvalidArgument(
+                "bypassing protection requires --reason (protect.require_bypass_reason is set)"
+                    .to_string(),
+            ));
+        }
+        if !options.quiet {
+            eprintln!("Warning: Bypassing protection for:");
+            for pf in &protected_bypassed {
+                eprintln!("  {} (pattern: {}, mode: {})", pf.file, pf.pattern, pf.mode);
+            }
+        }
+    }
+
+    // Inject Change-Id trailer if missing (sv-8jf.5.2), plus Task trailers
+    // for the workspace's active tasks when forge commit linking is on.
     let mut message = options.message.clone();
     let file = options.file.clone();
     let mut use_no_edit = options.no_edit;
+    let task_ids = linked_task_ids(&repository, workdir);
+    let add_trailers = |msg: &str| {
+        let (updated, id_changed) = change_id::ensure_change_id(msg);
+        let (updated, task_changed) = forge::ensure_task_trailers(&updated, &task_ids);
+        (updated, id_changed || task_changed)
+    };
 
     if let Some(msg) = message.as_deref() {
-        let (updated, _) = change_id::ensure_change_id(msg);
+        let (updated, _) = add_trailers(msg);
         message = Some(updated);
     } else if let Some(path) = file.as_ref() {
-        let _ = change_id::ensure_change_id_file(path)?;
+        let contents = std::fs::read_to_string(path)?;
+        let (updated, changed) = add_trailers(&contents);
+        if options.dry_run {
+            message = Some(updated);
+        } else if changed {
+            std::fs::write(path, updated)?;
+        }
     } else if options.amend {
         let existing = git::head_commit_message(&repository)?;
-        let (updated, changed) = change_id::ensure_change_id(&existing);
+        let (updated, changed) = add_trailers(&existing);
         if changed {
             message = Some(updated);
             use_no_edit = false;
@@ -158,6 +242,13 @@ pub fn run(options: CommitOptions) -> Result<()> {
         options.actor.as_deref(),
     )?;
 
+    if !lease_conflicts.is_empty() && options.force_lease && !options.quiet {
+        eprintln!("Warning: Committing over lease conflicts (--force-lease):");
+        for conflict in &lease_conflicts {
+            eprintln!("  {}: {}", conflict.file, conflict.reason);
+        }
+    }
+
     if !lease_conflicts.is_empty() && !options.force_lease {
         // Return error with exit code 3 (policy blocked)
         return Err(Error::LeaseConflict {
@@ -167,6 +258,38 @@ pub fn run(options: CommitOptions) -> Result<()> {
         });
     }
 
+    if options.dry_run {
+        let subject = message
+            .as_deref()
+            .and_then(|msg| msg.lines().next())
+            .map(str::to_string);
+        if options.json {
+            let result = CommitResult {
+                would: Some("commit"),
+                success: true,
+                commit_hash: None,
+                message: subject,
+                files_committed: staged_files,
+                protected_files: protected_warn,
+                lease_conflicts,
+            };
+            outln!("{}", serde_json::to_string_pretty(&result).unwrap());
+        } else if !options.quiet {
+            let verb = if options.amend { "amend" } else { "commit" };
+            outln!(
+                "sv commit (dry run): would {verb} {} file(s)",
+                staged_files.len()
+            );
+            if let Some(subject) = subject {
+                outln!("  message: {subject}");
+            }
+            for file in &staged_files {
+                outln!("  {file}");
+            }
+        }
+        return Ok(());
+    }
+
     // Capture old HEAD for undo support
     let old_head = repository
         .head()
@@ -234,6 +357,10 @@ pub fn run(options: CommitOptions) -> Result<()> {
 
         let mut record = OpRecord::new(format!("sv commit -m \"{}\"", msg_summary), actor_name);
         record.affected_refs = head_ref.iter().cloned().collect();
+        record.affected_paths = staged_files.clone();
+        if let Ok(head) = repository.head().and_then(|head| head.peel_to_commit()) {
+            record.commits = vec![head.id().to_string()];
+        }
         record.details = Some(OpDetails {
             commit: Some(CommitDetails {
                 commit_hash: commit_hash.clone(),
@@ -244,12 +371,19 @@ pub fn run(options: CommitOptions) -> Result<()> {
                 } else {
                     None
                 },
+                protected_bypassed: protected_bypassed
+                    .iter()
+                    .map(|pf| pf.file.clone())
+                    .collect(),
+                bypass_reason: bypass_reason.clone(),
+                override_readonly: options.override_readonly.then_some(true),
                 force_lease: if options.force_lease {
                     Some(true)
                 } else {
                     None
                 },
             }),
+            ..OpDetails::default()
         });
         record.undo_data = Some(UndoData {
             ref_updates: head_ref
@@ -265,41 +399,137 @@ pub fn run(options: CommitOptions) -> Result<()> {
         });
         // Best effort - don't fail the command if oplog write fails
         let _ = oplog.append(&record);
+
+        if let Err(err) = emit_protection_events(
+            &options,
+            &config,
+            record.actor.clone(),
+            &commit_hash,
+            (&protected_bypassed, bypass_reason.as_deref()),
+            &protected_warn,
+        ) {
+            if !options.quiet {
+                eprintln!("Warning: event output failed: {err}");
+            }
+        }
     }
 
     if options.json {
         let result = CommitResult {
+            would: None,
             success: true,
             commit_hash: Some(commit_hash),
             message: None,
             files_committed: staged_files,
-            protected_files: vec![],
+            protected_files: protected_warn,
             lease_conflicts: vec![],
         };
-        println!("{}", serde_json::to_string_pretty(&result).unwrap());
+        outln!("{}", serde_json::to_string_pretty(&result).unwrap());
     } else if !options.quiet {
-        print!("{}", stdout);
+        crate::output::write_result(format_args!("{stdout}"));
     }
 
     Ok(())
 }
 
+/// Ids of active tasks bound to the current workspace, when `sv commit` should
+/// link commits to them. Best effort: lookup failures link nothing.
+fn linked_task_ids(repository: &git2::Repository, workdir: &std::path::Path) -> Vec<String> {
+    if !forge::link_commits_enabled(workdir) {
+        return Vec::new();
+    }
+    let storage = Storage::new(
+        workdir.to_path_buf(),
+        git::common_dir(repository),
+        workdir.to_path_buf(),
+    );
+    let Some(entry) = storage.read_workspaces().ok().and_then(|registry| {
+        registry
+            .workspaces
+            .into_iter()
+            .find(|entry| entry.path == workdir)
+    }) else {
+        return Vec::new();
+    };
+    let config = Config::load_from_repo(workdir);
+    let mut ids: Vec<String> = TaskStore::new(storage, config.tasks)
+        .active_tasks_for_workspaces(
+            std::slice::from_ref(&entry.id),
+            std::slice::from_ref(&entry.name),
+        )
+        .unwrap_or_default()
+        .into_iter()
+        .map(|task| task.id)
+        .collect();
+    ids.sort();
+    ids
+}
+
+/// Emit `protection_bypassed` (guard paths committed under `--allow-protected`)
+/// and `protection_warned` (warn-mode paths) events for a finished commit.
+fn emit_protection_events(
+    options: &CommitOptions,
+    config: &Config,
+    actor: Option<String>,
+    commit_hash: &str,
+    (bypassed, reason): (&[ProtectedFileInfo], Option<&str>),
+    warned: &[ProtectedFileInfo],
+) -> Result<()> {
+    if bypassed.is_empty() && warned.is_empty() {
+        return Ok(());
+    }
+    let Some(destination) = EventDestination::parse(options.events.as_deref()) else {
+        return Ok(());
+    };
+    let mut sink = destination.open_with(&config.events)?;
+
+    let paths = |files: &[ProtectedFileInfo]| {
+        files
+            .iter()
+            .map(ProtectedViolation::from)
+            .collect::<Vec<_>>()
+    };
+    if !bypassed.is_empty() {
+        let event = Event::new(EventKind::ProtectionBypassed, actor.clone()).with_data(
+            serde_json::json!({
+                "commit": commit_hash,
+                "reason": reason,
+                "paths": paths(bypassed),
+            }),
+        )?;
+        sink.emit(&event)?;
+    }
+    if !warned.is_empty() {
+        let event =
+            Event::new(EventKind::ProtectionWarned, actor).with_data(serde_json::json!({
+                "commit": commit_hash,
+                "paths": paths(warned),
+            }))?;
+        sink.emit(&event)?;
+    }
+    Ok(())
+}
+
 /// Check for protected path violations on files being committed
 ///
-/// Returns two lists:
-/// - guard mode files (should block commit)
+/// Returns three lists:
+/// - guard mode files (block unless `--allow-protected`)
+/// - readonly mode files (block unless `--override-readonly`; `sv protect off`
+///   does not lift them)
 /// - warn mode files (just emit warning)
 fn check_protected_paths(
     repo: &git2::Repository,
+    config: &Config,
     staged_files: &[String],
-) -> Result<(Vec<ProtectedFileInfo>, Vec<ProtectedFileInfo>)> {
+) -> Result<(
+    Vec<ProtectedFileInfo>,
+    Vec<ProtectedFileInfo>,
+    Vec<ProtectedFileInfo>,
+)> {
     let workdir = repo
         .workdir()
         .ok_or_else(|| Error::OperationFailed("no working directory".to_string()))?;
 
-    // Load config from workspace root
-    let config = Config::load_from_repo(workdir);
-
     // Get the common git dir (handles worktrees correctly)
     let git_dir = git::common_dir(repo);
 
@@ -311,14 +541,15 @@ fn check_protected_paths(
     let staged_paths: Vec<PathBuf> = staged_files.iter().map(PathBuf::from).collect();
 
     // Compute protection status
-    let status = protect::compute_status(&config, override_data.as_ref(), &staged_paths)?;
+    let status = protect::compute_status(config, override_data.as_ref(), &staged_paths)?;
 
     let mut guard_files = Vec::new();
+    let mut readonly_files = Vec::new();
     let mut warn_files = Vec::new();
 
     for rule_status in &status.rules {
-        // Skip disabled patterns
-        if rule_status.disabled {
+        // Skip disabled patterns; readonly rules apply in every workspace
+        if rule_status.disabled && rule_status.rule.mode != "readonly" {
             continue;
         }
 
@@ -331,14 +562,14 @@ fn check_protected_paths(
 
             match rule_status.rule.mode.as_str() {
                 "guard" => guard_files.push(info),
+                "readonly" => readonly_files.push(info),
                 "warn" => warn_files.push(info),
-                // "readonly" would be handled at file system level, not here
                 _ => guard_files.push(info), // Default to guard for unknown modes
             }
         }
     }
 
-    Ok((guard_files, warn_files))
+    Ok((guard_files, readonly_files, warn_files))
 }
 
 /// Check for lease conflicts on files being committed
@@ -363,57 +594,46 @@ fn check_lease_conflicts(
 
     // Load lease store using proper paths for worktrees
     let storage = Storage::new(workdir.to_path_buf(), git_dir, workdir.to_path_buf());
-    let existing_leases: Vec<Lease> = storage.read_jsonl(&storage.leases_file())?;
+    let existing_leases: Vec<Lease> = storage.read_leases()?;
     let mut store = LeaseStore::from_vec(existing_leases);
 
     // Expire stale leases
     store.expire_stale();
 
     let mut conflicts = Vec::new();
+    // Committing counts as a cooperative edit, so strong and exclusive
+    // leases held by other actors block it.
+    let policy = Policy::default();
+
+    // Commits made inside a registered workspace carry its scope, so
+    // leases scoped to other workspaces don't apply.
+    let scope = storage
+        .read_workspaces()
+        .ok()
+        .and_then(|registry| {
+            registry
+                .workspaces
+                .into_iter()
+                .find(|entry| entry.path == workdir)
+        })
+        .map(|entry| LeaseScope::Workspace(entry.name))
+        .unwrap_or_default();
 
     for file in staged_files {
+        let mut requested =
+            Lease::probe(file, LeaseStrength::Cooperative, current_actor.as_deref());
+        requested.scope = scope.clone();
+
         // Find active leases that conflict with this file
         for lease in store.active() {
-            // Skip own leases
-            if let (Some(ref current), Some(ref lease_actor)) = (&current_actor, &lease.actor) {
-                if current == lease_actor {
+            // Branch-scoped leases only apply on their branch
+            if let (LeaseScope::Branch(branch), Some(current)) = (&lease.scope, current_branch) {
+                if current != branch {
                     continue;
                 }
             }
-            // Ownerless leases are advisory and should not block commits.
-            if lease.actor.is_none() {
-                continue;
-            }
 
-            // Check lease scope - skip if scope doesn't apply to current context
-            match &lease.scope {
-                LeaseScope::Repo => {
-                    // Repo-wide leases always apply
-                }
-                LeaseScope::Branch(branch) => {
-                    // Only conflict if we're on the same branch
-                    if let Some(current) = current_branch {
-                        if current != branch {
-                            continue;
-                        }
-                    }
-                }
-                LeaseScope::Workspace(ws) => {
-                    // Workspace-scoped leases - check if we're in that workspace
-                    // For now, skip workspace-scoped leases from other workspaces
-                    // (would need workspace detection to do this properly)
-                    let _ = ws; // Suppress unused warning for now
-                }
-            }
-
-            // Check if lease pathspec overlaps with file
-            if !lease.pathspec_overlaps(file) {
-                continue;
-            }
-
-            // Only block on exclusive or strong leases
-            if lease.strength == LeaseStrength::Exclusive || lease.strength == LeaseStrength::Strong
-            {
+            if let Some(reason) = lease::conflicts(lease, &requested, &policy) {
                 conflicts.push(LeaseConflictInfo {
                     file: file.clone(),
                     lease_id: lease.id.to_string()[..8].to_string(),
@@ -422,6 +642,7 @@ fn check_lease_conflicts(
                         .clone()
                         .unwrap_or_else(|| "(ownerless)".to_string()),
                     strength: lease.strength.to_string(),
+                    reason: reason.to_string(),
                 });
             }
         }
@@ -431,26 +652,26 @@ fn check_lease_conflicts(
 }
 
 /// Stage all modified tracked files (equivalent to git add -u)
-fn stage_all_modified(repo: &git2::Repository) -> Result<()> {
-    let mut index = repo.index()?;
-
+///
+/// When `write` is false the index is only updated in memory.
+fn stage_all_modified(index: &mut git2::Index, write: bool) -> Result<()> {
     index.update_all(["*"].iter(), None)?;
 
-    index.write()?;
+    if write {
+        index.write()?;
+    }
 
     Ok(())
 }
 
-/// Get list of staged files
-fn get_staged_files(repo: &git2::Repository) -> Result<Vec<String>> {
+/// Get list of files staged in `index`
+fn get_staged_files(repo: &git2::Repository, index: &git2::Index) -> Result<Vec<String>> {
     let head = match repo.head() {
         Ok(head) => Some(head.peel_to_tree()?),
         Err(_) => None, // Initial commit, no HEAD yet
     };
 
-    let index = repo.index()?;
-
-    let diff = repo.diff_tree_to_index(head.as_ref(), Some(&index), None)?;
+    let diff = repo.diff_tree_to_index(head.as_ref(), Some(index), None)?;
 
     let mut files = Vec::new();
     diff.foreach(
@@ -495,7 +716,8 @@ mod tests {
     #[test]
     fn test_get_staged_files_empty() {
         let (_temp, repo) = setup_test_repo();
-        let files = get_staged_files(&repo).unwrap();
+        let index = repo.index().unwrap();
+        let files = get_staged_files(&repo, &index).unwrap();
         assert!(files.is_empty());
     }
 
@@ -511,7 +733,7 @@ mod tests {
         index.add_path(std::path::Path::new("test.txt")).unwrap();
         index.write().unwrap();
 
-        let files = get_staged_files(&repo).unwrap();
+        let files = get_staged_files(&repo, &index).unwrap();
         assert_eq!(files, vec!["test.txt"]);
     }
 }
@@ -0,0 +1,25 @@
+//! sv completions command implementation
+//!
+//! Generates static shell completion scripts from the clap command tree.
+
+use std::io::Write;
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+use super::Cli;
+use crate::error::Result;
+use crate::output::result_writer;
+
+/// Print the completion script for `shell` to the result stream.
+pub fn run(shell: Shell) -> Result<()> {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    // Generate into memory first: clap_complete panics on write errors
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut command, name, &mut script);
+    let mut writer = result_writer();
+    writer.write_all(&script)?;
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! sv config command implementation
+//!
+//! Shows the repo configuration, or the effective one with the
+//! worktree-local `.sv.local.toml` overlay applied.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::config::{Config, ConfigSource, REPO_CONFIG_FILE};
+use crate::error::Result;
+use crate::git;
+use crate::output::{emit_success, outln, OutputOptions};
+
+/// Options for `sv config show`
+pub struct ShowOptions {
+    pub effective: bool,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+#[derive(serde::Serialize)]
+struct ConfigShowReport {
+    config: Config,
+    sources: ConfigSources,
+    /// Source of each key set by a file; omitted keys are defaults
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    origins: BTreeMap<String, ConfigSource>,
+}
+
+#[derive(serde::Serialize)]
+struct ConfigSources {
+    repo: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    local: Option<PathBuf>,
+}
+
+pub fn run_show(options: ShowOptions) -> Result<()> {
+    let repo = git::open_repo(options.repo.as_deref())?;
+    let workdir = git::workdir(&repo)?;
+
+    let report = if options.effective {
+        let layered = Config::load_layered(&workdir)?;
+        ConfigShowReport {
+            config: layered.config,
+            sources: ConfigSources {
+                repo: layered.repo_file,
+                local: layered.local_file,
+            },
+            origins: layered.origins,
+        }
+    } else {
+        let path = workdir.join(REPO_CONFIG_FILE);
+        let exists = path.exists();
+        ConfigShowReport {
+            config: if exists {
+                Config::load(&path)?
+            } else {
+                Config::default()
+            },
+            sources: ConfigSources {
+                repo: exists.then_some(path),
+                local: None,
+            },
+            origins: BTreeMap::new(),
+        }
+    };
+
+    if options.json {
+        return emit_success(
+            OutputOptions {
+                json: true,
+                quiet: options.quiet,
+            },
+            "config show",
+            &report,
+            None,
+        );
+    }
+    if options.quiet {
+        return Ok(());
+    }
+
+    let describe = |path: &Option<PathBuf>| {
+        path.as_ref()
+            .map_or("(none)".to_string(), |p| p.display().to_string())
+    };
+    if options.effective {
+        outln!("# effective config (local > repo > defaults)");
+        outln!("# repo:  {}", describe(&report.sources.repo));
+        outln!("# local: {}", describe(&report.sources.local));
+        let local_keys: Vec<&str> = report
+            .origins
+            .iter()
+            .filter(|(_, source)| **source == ConfigSource::Local)
+            .map(|(key, _)| key.as_str())
+            .collect();
+        if !local_keys.is_empty() {
+            outln!("# from local: {}", local_keys.join(", "));
+        }
+    } else {
+        outln!("# repo: {}", describe(&report.sources.repo));
+    }
+    outln!("{}", toml::to_string_pretty(&report.config)?);
+    Ok(())
+}
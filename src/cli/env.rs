@@ -0,0 +1,192 @@
+//! sv env command implementation
+//!
+//! Reports the environment sv resolves for a command: repository, state
+//! directories, actor, config files, libgit2, and the env vars sv reads.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::actor::{self, ActorSource};
+use crate::config::Config;
+use crate::error::Result;
+use crate::git;
+use crate::output::{emit_success, HumanOutput, OutputOptions};
+use crate::storage::Storage;
+
+/// Environment variables sv reads
+const ENV_VARS: &[&str] = &[
+    "SV_REPO",
+    "SV_ACTOR",
+    "SV_ACTOR_FILTER",
+    "SV_EPIC",
+    "SV_PROJECT",
+    "SV_LEASE_ID_SEED",
+    "NO_COLOR",
+];
+
+/// Options for `sv env`
+pub struct EnvOptions {
+    pub repo: Option<PathBuf>,
+    pub actor: Option<String>,
+    /// Whether `actor` came from an explicit --actor flag
+    pub actor_flag: bool,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+#[derive(serde::Serialize)]
+struct EnvReport {
+    cwd: PathBuf,
+    /// Worktree root; unset outside a repository
+    repo: Option<PathBuf>,
+    /// Git common dir shared by all worktrees
+    git_common_dir: Option<PathBuf>,
+    state: Option<StateDirs>,
+    actor: ActorInfo,
+    config: ConfigFiles,
+    libgit2_version: String,
+    sv_version: &'static str,
+    /// Unset variables are null
+    env: BTreeMap<&'static str, Option<String>>,
+}
+
+#[derive(serde::Serialize)]
+struct StateDirs {
+    local: PathBuf,
+    shared: PathBuf,
+    initialized: bool,
+}
+
+#[derive(serde::Serialize)]
+struct ActorInfo {
+    name: String,
+    source: ActorSource,
+}
+
+#[derive(serde::Serialize)]
+struct ConfigFiles {
+    repo: Option<PathBuf>,
+    local: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+pub fn run(options: EnvOptions) -> Result<()> {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let start = options.repo.clone().unwrap_or_else(|| cwd.clone());
+    let repository = git::open_repo(Some(start.as_path())).ok();
+    let workdir = repository.as_ref().and_then(|repo| git::workdir(repo).ok());
+    let common_dir = repository.as_ref().map(git::common_dir);
+
+    let state = match (&workdir, &common_dir) {
+        (Some(workdir), Some(common_dir)) => {
+            let storage = Storage::new(workdir.clone(), common_dir.clone(), workdir.clone());
+            Some(StateDirs {
+                local: storage.local_dir(),
+                shared: storage.shared_dir(),
+                initialized: storage.is_initialized(),
+            })
+        }
+        _ => None,
+    };
+
+    // clap fills --actor from SV_ACTOR; only an explicit flag counts as cli
+    let cli_actor = options.actor.as_deref().filter(|_| options.actor_flag);
+    let (actor_name, actor_source) =
+        actor::resolve_actor_with_source(workdir.as_deref(), cli_actor)?;
+
+    let config = match &workdir {
+        Some(workdir) => match Config::load_layered(workdir) {
+            Ok(layered) => ConfigFiles {
+                repo: layered.repo_file,
+                local: layered.local_file,
+                error: None,
+            },
+            Err(err) => ConfigFiles {
+                repo: None,
+                local: None,
+                error: Some(err.to_string()),
+            },
+        },
+        None => ConfigFiles {
+            repo: None,
+            local: None,
+            error: None,
+        },
+    };
+
+    let (major, minor, rev) = git2::Version::get().libgit2_version();
+    let env: BTreeMap<&'static str, Option<String>> = ENV_VARS
+        .iter()
+        .map(|name| (*name, std::env::var(name).ok()))
+        .collect();
+
+    let describe = |path: &Option<PathBuf>| {
+        path.as_ref()
+            .map_or("(none)".to_string(), |p| p.display().to_string())
+    };
+    let mut human = HumanOutput::new("sv env");
+    human.push_summary("cwd", cwd.display().to_string());
+    human.push_summary("repo", describe(&workdir));
+    human.push_summary("git common dir", describe(&common_dir));
+    if let Some(state) = &state {
+        human.push_summary("state (local)", state.local.display().to_string());
+        human.push_summary("state (shared)", state.shared.display().to_string());
+    }
+    human.push_summary(
+        "actor",
+        format!("{actor_name} (from {})", actor_source_label(actor_source)),
+    );
+    human.push_summary("config (repo)", describe(&config.repo));
+    human.push_summary("config (local)", describe(&config.local));
+    human.push_summary("libgit2", format!("{major}.{minor}.{rev}"));
+    human.push_summary("sv", env!("CARGO_PKG_VERSION"));
+    for (name, value) in &env {
+        human.push_detail(format!("{name}={}", value.as_deref().unwrap_or("(unset)")));
+    }
+    if workdir.is_none() {
+        human.push_warning("not inside a git repository");
+    }
+    if state.as_ref().is_some_and(|state| !state.initialized) {
+        human.push_warning("sv not initialized");
+        human.push_next_step("sv init");
+    }
+    if let Some(error) = &config.error {
+        human.push_warning(format!("config failed to load: {error}"));
+    }
+
+    let report = EnvReport {
+        cwd,
+        repo: workdir,
+        git_common_dir: common_dir,
+        state,
+        actor: ActorInfo {
+            name: actor_name,
+            source: actor_source,
+        },
+        config,
+        libgit2_version: format!("{major}.{minor}.{rev}"),
+        sv_version: env!("CARGO_PKG_VERSION"),
+        env,
+    };
+
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "env",
+        &report,
+        Some(&human),
+    )
+}
+
+fn actor_source_label(source: ActorSource) -> &'static str {
+    match source {
+        ActorSource::Cli => "--actor",
+        ActorSource::Env => "SV_ACTOR",
+        ActorSource::Persisted => ".sv/actor",
+        ActorSource::Config => "actor.default",
+        ActorSource::Default => "default",
+    }
+}
@@ -0,0 +1,91 @@
+//! sv events command implementation
+//!
+//! Validates captured JSONL event streams (as written by `--events <path>`)
+//! against the event envelope schema.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::error::{Error, Result};
+use crate::events::{validate_event_line, EVENT_SCHEMA_VERSION};
+use crate::output::{emit_success, HumanOutput, OutputOptions};
+
+/// Options for `sv events validate`
+pub struct ValidateOptions {
+    pub file: PathBuf,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ValidateReport {
+    file: String,
+    schema_version: &'static str,
+    lines: usize,
+    valid: usize,
+    invalid: Vec<InvalidLine>,
+}
+
+#[derive(Debug, Serialize)]
+struct InvalidLine {
+    line: usize,
+    error: String,
+}
+
+/// Run `sv events validate <file>`.
+///
+/// Blank lines are ignored; every other line must be a valid event envelope.
+pub fn run_validate(options: ValidateOptions) -> Result<()> {
+    let contents = std::fs::read_to_string(&options.file).map_err(|err| {
+        Error::InvalidArgument(format!("cannot read {}: {err}", options.file.display()))
+    })?;
+
+    let mut lines = 0;
+    let mut valid = 0;
+    let mut invalid = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        lines += 1;
+        match validate_event_line(line) {
+            Ok(_) => valid += 1,
+            Err(error) => invalid.push(InvalidLine {
+                line: idx + 1,
+                error,
+            }),
+        }
+    }
+
+    let file = options.file.display().to_string();
+    let header = if invalid.is_empty() {
+        format!("Events ok ({file})")
+    } else {
+        format!("Event problems ({file})")
+    };
+    let mut human = HumanOutput::new(header);
+    human.push_summary("Schema", EVENT_SCHEMA_VERSION);
+    human.push_summary("Events", lines.to_string());
+    human.push_summary("Valid", valid.to_string());
+    human.push_summary("Invalid", invalid.len().to_string());
+    for entry in &invalid {
+        human.push_detail(format!("{file}:{} {}", entry.line, entry.error));
+    }
+
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "events validate",
+        &ValidateReport {
+            file,
+            schema_version: EVENT_SCHEMA_VERSION,
+            lines,
+            valid,
+            invalid,
+        },
+        Some(&human),
+    )
+}
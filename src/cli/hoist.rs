@@ -0,0 +1,331 @@
+//! sv hoist subcommands
+//!
+//! Implements `sv hoist resolve`, which continues a hoist that stopped on
+//! conflicts (`--continue-on-conflict --no-propagate-conflicts`).
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use chrono::Utc;
+use git2::Oid;
+use serde::Serialize;
+
+use crate::error::{Error, Result};
+use crate::git;
+use crate::hoist::{replay_commits, ReplayOptions};
+use crate::output::{emit_success, HumanOutput, OutputOptions};
+use crate::storage::{HoistCommitStatus, HoistState, HoistStatus, Storage};
+
+/// Options for `sv hoist resolve`
+pub struct ResolveOptions {
+    pub commit: String,
+    pub dest: Option<String>,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ResolveReport {
+    hoist_id: String,
+    dest_ref: String,
+    integration_ref: String,
+    resolved: String,
+    /// `applied`, or `skipped` when the resolution left no changes
+    resolution: &'static str,
+    applied: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_conflict: Option<RemainingConflict>,
+    remaining_conflicts: Vec<RemainingConflict>,
+    status: HoistStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct RemainingConflict {
+    commit_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+}
+
+/// Run `sv hoist resolve <commit-id>`.
+///
+/// Expects the integration branch to be checked out with the conflicting
+/// commit applied and fixed in the working tree (`git cherry-pick -n <id>`).
+/// Stages the working tree, commits it with the original message and
+/// author, then replays the commits that were skipped after it. If the
+/// replay hits the next conflict, that commit is applied to the working tree
+/// with conflict markers so it can be fixed and resolved the same way.
+pub fn run_resolve(opts: ResolveOptions) -> Result<()> {
+    let repo = git::open_repo(opts.repo.as_deref())?;
+    let workdir = git::workdir(&repo)?;
+    let storage = Storage::new(workdir.clone(), git::common_dir(&repo), workdir.clone());
+
+    let head_branch = git::head_info(&repo).ok().and_then(|info| info.shorthand);
+    let mut state = match opts.dest {
+        Some(dest) => storage.read_hoist_state(&dest)?.ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "no hoist in progress for '{dest}' (run sv hoist first)"
+            ))
+        })?,
+        // The checked-out integration branch identifies the hoist, including
+        // one run with --name.
+        None => {
+            let branch = head_branch.as_deref().ok_or_else(|| {
+                Error::InvalidArgument(
+                    "--dest is required unless the hoist's integration branch is checked out"
+                        .to_string(),
+                )
+            })?;
+            storage
+                .list_hoist_states()?
+                .into_iter()
+                .find(|state| state.integration_ref == branch)
+                .ok_or_else(|| {
+                    Error::InvalidArgument(format!(
+                        "no hoist uses the checked-out branch '{branch}' (pass --dest <ref>)"
+                    ))
+                })?
+        }
+    };
+    // A squashed workspace is one commit: the commits before the conflict
+    // were never applied on their own, so replaying only the rest would
+    // lose them.
+    if state.squash {
+        return Err(Error::InvalidArgument(format!(
+            "hoist {} was run with --squash, which sv hoist resolve cannot continue; re-run sv hoist --force without --squash or without --no-propagate-conflicts",
+            state.hoist_id
+        )));
+    }
+    let idx = find_commit(&state, &opts.commit)?;
+    let entry = &state.commits[idx];
+    if entry.status != HoistCommitStatus::Conflict {
+        return Err(Error::InvalidArgument(format!(
+            "commit {} is not in conflict (status: {:?})",
+            short(&entry.commit_id),
+            entry.status
+        )));
+    }
+    if head_branch.as_deref() != Some(state.integration_ref.as_str()) {
+        return Err(Error::InvalidArgument(format!(
+            "check out {} and apply the commit there (git cherry-pick -n {}) before resolving",
+            state.integration_ref,
+            short(&entry.commit_id)
+        )));
+    }
+
+    let marked = files_with_conflict_markers(&repo)?;
+    if !marked.is_empty() {
+        return Err(Error::OperationFailed(format!(
+            "conflict markers remain in: {}",
+            marked.join(", ")
+        )));
+    }
+
+    // Stage the resolution and commit it as the original commit.
+    let output = Command::new("git")
+        .args(["add", "-u"])
+        .current_dir(&workdir)
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(Error::OperationFailed(format!(
+            "failed to stage resolution: {stderr}"
+        )));
+    }
+    let original = repo.find_commit(Oid::from_str(&entry.commit_id)?)?;
+    let mut index = repo.index()?;
+    index.read(true)?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let parent = repo.head()?.peel_to_commit()?;
+    let resolution = if tree.id() == parent.tree_id() {
+        state.commits[idx].status = HoistCommitStatus::Skipped;
+        "skipped"
+    } else {
+        let committer = repo.signature().unwrap_or_else(|_| original.committer());
+        repo.commit(
+            Some("HEAD"),
+            &original.author(),
+            &committer,
+            original.message().unwrap_or_default(),
+            &tree,
+            &[&parent],
+        )?;
+        state.commits[idx].status = HoistCommitStatus::Applied;
+        "applied"
+    };
+    repo.cleanup_state()?;
+
+    // Advance: replay the commits skipped after the resolved one.
+    let pending: Vec<usize> = (idx + 1..state.commits.len())
+        .filter(|&i| {
+            matches!(
+                state.commits[i].status,
+                HoistCommitStatus::Pending | HoistCommitStatus::Skipped
+            )
+        })
+        .collect();
+    let oids = pending
+        .iter()
+        .map(|&i| Oid::from_str(&state.commits[i].commit_id).map_err(Error::from))
+        .collect::<Result<Vec<_>>>()?;
+    let outcome = replay_commits(
+        &repo,
+        &state.integration_ref,
+        &oids,
+        &ReplayOptions {
+            continue_on_conflict: false,
+            propagate_conflicts: false,
+        },
+    )?;
+    // The resolution was just committed, so tracked files match the old tip
+    // and can be overwritten; a safe checkout would not create files added by
+    // the replayed commits because HEAD has already moved.
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.force();
+    repo.checkout_head(Some(&mut checkout))?;
+
+    let mut applied = Vec::new();
+    let mut next_conflict = None;
+    for (replayed, &i) in outcome.entries.iter().zip(&pending) {
+        let commit = &mut state.commits[i];
+        commit.status = replayed.status.clone();
+        match replayed.status {
+            HoistCommitStatus::Applied => applied.push(commit.commit_id.clone()),
+            HoistCommitStatus::Conflict => {
+                next_conflict = Some(remaining(commit));
+            }
+            _ => {}
+        }
+    }
+    if let Some(conflict) = next_conflict.as_ref() {
+        // Leave the conflict in the working tree for the next resolve; the
+        // cherry-pick is expected to exit non-zero.
+        let _ = Command::new("git")
+            .args(["cherry-pick", "--no-commit", &conflict.commit_id])
+            .current_dir(&workdir)
+            .output()?;
+    }
+
+    let remaining_conflicts: Vec<RemainingConflict> = state
+        .commits
+        .iter()
+        .filter(|commit| commit.status == HoistCommitStatus::Conflict)
+        .map(remaining)
+        .collect();
+    state.status = if remaining_conflicts.is_empty() {
+        HoistStatus::Completed
+    } else {
+        HoistStatus::InProgress
+    };
+    state.updated_at = Utc::now();
+    storage.write_hoist_state(&state)?;
+
+    let resolved = state.commits[idx].commit_id.clone();
+    let mut human = HumanOutput::new("Hoist conflict resolved");
+    human.push_summary("ID", state.hoist_id.clone());
+    human.push_summary("Resolved", format!("{} ({resolution})", short(&resolved)));
+    human.push_summary("Replayed", applied.len().to_string());
+    human.push_summary("Remaining conflicts", remaining_conflicts.len().to_string());
+    for conflict in &remaining_conflicts {
+        human.push_detail(format!(
+            "conflict: {} {}",
+            short(&conflict.commit_id),
+            conflict.summary.as_deref().unwrap_or_default()
+        ));
+    }
+    if let Some(conflict) = next_conflict.as_ref() {
+        human.push_warning(format!(
+            "conflict applying {}; fix the working tree and resolve it",
+            short(&conflict.commit_id)
+        ));
+        human.push_next_step(format!("sv hoist resolve {}", short(&conflict.commit_id)));
+    } else if let Some(conflict) = remaining_conflicts.first() {
+        human.push_next_step(format!(
+            "git cherry-pick -n {} && sv hoist resolve {}",
+            short(&conflict.commit_id),
+            short(&conflict.commit_id)
+        ));
+    } else {
+        human.push_next_step(format!(
+            "git checkout {} && git merge --ff-only {}",
+            state.dest_ref, state.integration_ref
+        ));
+    }
+
+    let report = ResolveReport {
+        hoist_id: state.hoist_id,
+        dest_ref: state.dest_ref,
+        integration_ref: state.integration_ref,
+        resolved,
+        resolution,
+        applied,
+        next_conflict,
+        remaining_conflicts,
+        status: state.status,
+    };
+    emit_success(
+        OutputOptions {
+            json: opts.json,
+            quiet: opts.quiet,
+        },
+        "hoist resolve",
+        &report,
+        Some(&human),
+    )
+}
+
+/// Find a hoist commit by full or abbreviated id.
+fn find_commit(state: &HoistState, commit: &str) -> Result<usize> {
+    let matches: Vec<usize> = state
+        .commits
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry.commit_id.starts_with(commit))
+        .map(|(idx, _)| idx)
+        .collect();
+    match matches.as_slice() {
+        [idx] => Ok(*idx),
+        [] => Err(Error::InvalidArgument(format!(
+            "commit '{commit}' is not part of hoist {}",
+            state.hoist_id
+        ))),
+        _ => Err(Error::InvalidArgument(format!(
+            "commit '{commit}' is ambiguous in hoist {}",
+            state.hoist_id
+        ))),
+    }
+}
+
+/// Changed files in the working tree that still contain conflict markers.
+fn files_with_conflict_markers(repo: &git2::Repository) -> Result<Vec<String>> {
+    let workdir = git::workdir(repo)?;
+    let mut marked = Vec::new();
+    for change in git::working_tree_changes(repo)? {
+        let Ok(contents) = std::fs::read_to_string(workdir.join(&change.path)) else {
+            continue;
+        };
+        if contents
+            .lines()
+            .any(|line| line.starts_with("<<<<<<< ") || line.starts_with(">>>>>>> "))
+        {
+            marked.push(change.path.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    marked.sort();
+    marked.dedup();
+    Ok(marked)
+}
+
+fn remaining(commit: &crate::storage::HoistCommit) -> RemainingConflict {
+    RemainingConflict {
+        commit_id: commit.commit_id.clone(),
+        workspace: commit.workspace.clone(),
+        summary: commit.summary.clone(),
+    }
+}
+
+fn short(commit_id: &str) -> &str {
+    &commit_id[..8.min(commit_id.len())]
+}
@@ -182,6 +182,11 @@ fn ensure_gitignore(repo_root: &Path) -> Result<bool> {
         additions.push(".sv/".to_string());
     }
 
+    if !contains_any(&entries, &[".sv.local.toml", "/.sv.local.toml"]) {
+        additions.push("# sv worktree-local config".to_string());
+        additions.push(".sv.local.toml".to_string());
+    }
+
     let mut task_patterns = Vec::new();
     if !contains_any(&entries, &[".tasks/*.lock", "/.tasks/*.lock"]) {
         task_patterns.push(".tasks/*.lock".to_string());
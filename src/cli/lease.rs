@@ -1,32 +1,44 @@
 //! sv lease subcommand implementations
 //!
-//! Provides lease management commands: ls, who, renew, break, wait
+//! Provides lease management commands: ls, who, renew, break, wait,
+//! keepalive, export, import, doctor
 
+use std::collections::{BTreeMap, HashSet};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration as StdDuration, Instant};
 
-// chrono::Utc is used via Lease methods
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use super::interrupt_flag;
+use crate::actor;
 use crate::config::Config;
 use crate::error::{Error, Result};
+use crate::events::{Event, EventDestination, EventKind};
 use crate::git;
-use crate::lease::{parse_duration, Lease, LeaseStatus, LeaseStore};
+use crate::lease::{self, parse_duration, Lease, LeaseStatus, LeaseStore, LeaseStrength, Policy};
 use crate::lock::{FileLock, DEFAULT_LOCK_TIMEOUT_MS};
-use crate::oplog::{LeaseChange, OpLog, OpOutcome, OpRecord, UndoData};
-use crate::storage::Storage;
+use crate::oplog::{
+    LeaseChange, LeaseHistoryEntry, OpDetails, OpLog, OpOutcome, OpRecord, UndoData,
+};
+use crate::output::{format_human, outln, HumanOutput};
+use crate::storage::{Storage, WorkspaceEntry};
 
 /// Options for the lease ls command
 pub struct LsOptions {
     pub selector: Option<String>,
     pub actor: Option<String>,
+    pub path: Option<String>,
+    pub format: Option<String>,
+    pub by_actor: bool,
     pub repo: Option<PathBuf>,
     pub json: bool,
     pub quiet: bool,
 }
 
 /// Lease entry for display/JSON output
-#[derive(serde::Serialize)]
+#[derive(Clone, serde::Serialize)]
 struct LeaseEntry {
     id: String,
     pathspec: String,
@@ -40,6 +52,30 @@ struct LeaseEntry {
     created_at: String,
 }
 
+/// Output format for lease ls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LsFormat {
+    Text,
+    Json,
+    /// One compact JSON object per line
+    Jsonl,
+}
+
+impl std::str::FromStr for LsFormat {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(LsFormat::Text),
+            "json" => Ok(LsFormat::Json),
+            "jsonl" | "json-stream" => Ok(LsFormat::Jsonl),
+            other => Err(Error::InvalidArgument(format!(
+                "invalid format '{other}' (expected text, json, or jsonl)"
+            ))),
+        }
+    }
+}
+
 /// Result of lease ls command
 #[derive(serde::Serialize)]
 struct LsReport {
@@ -48,8 +84,58 @@ struct LsReport {
     active: usize,
 }
 
+/// Per-actor rollup for `sv lease ls --by-actor`
+#[derive(Debug, Default, PartialEq, Eq, serde::Serialize)]
+struct ActorSummary {
+    total: usize,
+    observe: usize,
+    cooperative: usize,
+    strong: usize,
+    exclusive: usize,
+    soonest_expiry: Option<DateTime<Utc>>,
+}
+
+/// Result of lease ls --by-actor
+#[derive(serde::Serialize)]
+struct ByActorReport {
+    actors: BTreeMap<String, ActorSummary>,
+    total: usize,
+    active: usize,
+}
+
+/// Aggregate leases per actor; ownerless leases are grouped under `(ownerless)`.
+fn summarize_by_actor<'a>(
+    leases: impl IntoIterator<Item = &'a Lease>,
+) -> BTreeMap<String, ActorSummary> {
+    let mut actors: BTreeMap<String, ActorSummary> = BTreeMap::new();
+    for lease in leases {
+        let key = lease.actor.as_deref().unwrap_or("(ownerless)").to_string();
+        let summary = actors.entry(key).or_default();
+        summary.total += 1;
+        match lease.strength {
+            LeaseStrength::Observe => summary.observe += 1,
+            LeaseStrength::Cooperative => summary.cooperative += 1,
+            LeaseStrength::Strong => summary.strong += 1,
+            LeaseStrength::Exclusive => summary.exclusive += 1,
+        }
+        if summary
+            .soonest_expiry
+            .is_none_or(|soonest| lease.expires_at < soonest)
+        {
+            summary.soonest_expiry = Some(lease.expires_at);
+        }
+    }
+    actors
+}
+
 /// Run the lease ls command
 pub fn run_ls(options: LsOptions) -> Result<()> {
+    let format = match options.format.as_deref() {
+        Some(value) => value.parse()?,
+        None if options.json => LsFormat::Json,
+        None => LsFormat::Text,
+    };
+
     // Discover repository
     let start = options
         .repo
@@ -81,7 +167,7 @@ pub fn run_ls(options: LsOptions) -> Result<()> {
     let config = Config::load_from_repo(&workdir);
 
     // Load existing leases
-    let existing_leases: Vec<Lease> = storage.read_jsonl(&storage.leases_file())?;
+    let existing_leases: Vec<Lease> = storage.read_leases()?;
     let mut store = LeaseStore::from_vec(existing_leases);
 
     // Expire stale leases (updates status but keeps them in store)
@@ -89,41 +175,109 @@ pub fn run_ls(options: LsOptions) -> Result<()> {
     let grace = parse_duration(&config.leases.expiration_grace)?;
     let _expired = store.cleanup_expired(grace);
 
-    // Get all leases and filter
+    // Resolve --path like take/who so subdirectory and root queries agree
+    let path_filter = match options.path.as_deref() {
+        Some(raw) => {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| workdir.clone());
+            Some(lease::resolve_pathspec(&workdir, &cwd, raw)?)
+        }
+        None => None,
+    };
+
+    let selected = match options.selector.as_deref() {
+        Some(selector) => {
+            let me = actor::resolve_actor_optional(Some(&workdir), None)?;
+            Some(select_leases(store.all(), selector, me.as_deref())?)
+        }
+        None => None,
+    };
+
+    // Filter active leases in a single pass
     let total = store.all().len();
-    let mut leases: Vec<&Lease> = store.active().collect();
+    let leases: Vec<&Lease> = store
+        .active()
+        .filter(|l| {
+            options
+                .actor
+                .as_ref()
+                .is_none_or(|actor_filter| l.actor.as_ref() == Some(actor_filter))
+        })
+        .filter(|l| {
+            path_filter
+                .as_deref()
+                .is_none_or(|path| l.pathspec_overlaps(path))
+        })
+        .filter(|l| selected.as_ref().is_none_or(|ids| ids.contains(&l.id)))
+        .collect();
 
-    // Filter by actor if specified
-    if let Some(ref actor_filter) = options.actor {
-        leases.retain(|l| l.actor.as_ref().map(|a| a == actor_filter).unwrap_or(false));
-    }
+    let active_count = leases.len();
 
-    // TODO: Apply selector filter when selector language is implemented
-    if options.selector.is_some() {
-        // For now, selector is a stub - just log it
-        if !options.quiet && !options.json {
-            eprintln!("Note: selector filtering not yet implemented");
+    if options.by_actor {
+        if format == LsFormat::Jsonl {
+            return Err(Error::InvalidArgument(
+                "--by-actor supports text or json output".to_string(),
+            ));
+        }
+        let report = ByActorReport {
+            actors: summarize_by_actor(leases.iter().copied()),
+            total,
+            active: active_count,
+        };
+        if format == LsFormat::Json {
+            outln!("{}", serde_json::to_string_pretty(&report)?);
+        } else if !options.quiet {
+            if report.actors.is_empty() {
+                outln!("No active leases.");
+            } else {
+                let mut human =
+                    HumanOutput::new(format!("Active leases by actor ({}):", report.active));
+                human.push_table(
+                    [
+                        "ACTOR",
+                        "TOTAL",
+                        "OBSERVE",
+                        "COOPERATIVE",
+                        "STRONG",
+                        "EXCLUSIVE",
+                        "SOONEST EXPIRY",
+                    ],
+                    report.actors.iter().map(|(actor, summary)| {
+                        vec![
+                            actor.clone(),
+                            summary.total.to_string(),
+                            summary.observe.to_string(),
+                            summary.cooperative.to_string(),
+                            summary.strong.to_string(),
+                            summary.exclusive.to_string(),
+                            summary
+                                .soonest_expiry
+                                .as_ref()
+                                .map(format_relative_time)
+                                .unwrap_or_default(),
+                        ]
+                    }),
+                );
+                outln!("{}", format_human(&human));
+            }
         }
+        return Ok(());
     }
 
-    let active_count = leases.len();
+    // Stream one lease per line without building the full report
+    if format == LsFormat::Jsonl {
+        use std::io::Write;
+
+        let mut out = std::io::BufWriter::new(crate::output::result_writer());
+        for lease in &leases {
+            serde_json::to_writer(&mut out, &lease_entry(lease))?;
+            writeln!(out)?;
+        }
+        out.flush()?;
+        return Ok(());
+    }
 
     // Convert to display format
-    let entries: Vec<LeaseEntry> = leases
-        .iter()
-        .map(|l| LeaseEntry {
-            id: l.id.to_string(),
-            pathspec: l.pathspec.clone(),
-            strength: l.strength.to_string(),
-            intent: l.intent.to_string(),
-            actor: l.actor.clone(),
-            scope: l.scope.to_string(),
-            expires_at: l.expires_at.to_rfc3339(),
-            note: l.note.clone(),
-            status: format_status(&l.status),
-            created_at: l.created_at.to_rfc3339(),
-        })
-        .collect();
+    let entries: Vec<LeaseEntry> = leases.iter().map(|l| lease_entry(l)).collect();
 
     // Output results
     let report = LsReport {
@@ -132,26 +286,120 @@ pub fn run_ls(options: LsOptions) -> Result<()> {
         active: active_count,
     };
 
-    if options.json {
-        println!("{}", serde_json::to_string_pretty(&report)?);
+    if format == LsFormat::Json {
+        outln!("{}", serde_json::to_string_pretty(&report)?);
     } else if !options.quiet {
         if report.leases.is_empty() {
-            println!("No active leases.");
+            outln!("No active leases.");
         } else {
-            println!("Active leases ({}):", report.active);
-            println!();
-            for lease in &report.leases {
-                print_lease(lease);
-            }
+            let mut human = HumanOutput::new(format!("Active leases ({}):", report.active));
+            human.push_table(
+                ["ID", "PATH", "STRENGTH", "ACTOR", "EXPIRES", "NOTE"],
+                report.leases.iter().map(lease_row),
+            );
+            outln!("{}", format_human(&human));
         }
     }
 
     Ok(())
 }
 
+/// Evaluate a selector against `leases` and return the ids it selects.
+///
+/// Lease predicates: `active`, `stale` (past expiry), `held("<actor>")` with
+/// `"me"` standing for `me`, `expiring("<duration>")`, `touching("<path>")`,
+/// `overlaps("<pathspec>")` and `name~"<text>"` on the pathspec.
+pub(crate) fn select_leases(
+    leases: &[Lease],
+    selector: &str,
+    me: Option<&str>,
+) -> Result<HashSet<Uuid>> {
+    use crate::selector::{EntityKind, Predicate, SelectorContext, SelectorItem};
+
+    let expr = crate::selector::parse_selector(selector).map_err(|err| {
+        Error::InvalidArgument(format!(
+            "invalid selector: {}\n  {}\n  {}^",
+            err,
+            selector,
+            " ".repeat(err.position)
+        ))
+    })?;
+
+    let mut windows = BTreeMap::new();
+    for predicate in expr.predicates() {
+        if let Predicate::Expiring(window) = predicate {
+            windows.insert(window.clone(), parse_duration(window)?);
+        }
+    }
+
+    let now = Utc::now();
+    let items: Vec<SelectorItem> = leases
+        .iter()
+        .map(|lease| SelectorItem::new(lease.id.to_string(), lease.pathspec.clone()))
+        .collect();
+    let ctx = SelectorContext::new(&[], &items, &[], |kind, item, predicate| {
+        let Some(lease) = leases
+            .iter()
+            .find(|lease| kind == EntityKind::Lease && lease.id.to_string() == item.id)
+        else {
+            return false;
+        };
+        match predicate {
+            Predicate::Active => lease.is_active(),
+            Predicate::Stale => match lease.status {
+                LeaseStatus::Expired => true,
+                LeaseStatus::Active => lease.is_expired(),
+                _ => false,
+            },
+            Predicate::Held(holder) => {
+                let holder = if holder == "me" {
+                    me
+                } else {
+                    Some(holder.as_str())
+                };
+                lease.actor.as_deref() == holder
+            }
+            Predicate::Expiring(window) => {
+                lease.is_active()
+                    && windows
+                        .get(window)
+                        .is_some_and(|d| lease.expires_at <= now + *d)
+            }
+            Predicate::Touching(path) => lease.matches_path(path),
+            Predicate::Overlaps(pathspec) => lease.pathspec_overlaps(pathspec),
+            Predicate::Blocked | Predicate::Ahead(_) | Predicate::NameMatches(_) => false,
+        }
+    });
+
+    crate::selector::evaluate_selector(&expr, &ctx)
+        .into_iter()
+        .map(|hit| {
+            Uuid::parse_str(&hit.item.id)
+                .map_err(|_| Error::OperationFailed(format!("invalid lease id: {}", hit.item.id)))
+        })
+        .collect()
+}
+
+fn lease_entry(lease: &Lease) -> LeaseEntry {
+    LeaseEntry {
+        id: lease.id.to_string(),
+        pathspec: lease.pathspec.clone(),
+        strength: lease.strength.to_string(),
+        intent: lease.intent.to_string(),
+        actor: lease.actor.clone(),
+        scope: lease.scope.to_string(),
+        expires_at: lease.expires_at.to_rfc3339(),
+        note: lease.note.clone(),
+        status: format_status(&lease.status),
+        created_at: lease.created_at.to_rfc3339(),
+    }
+}
+
 /// Options for the lease who command
 pub struct WhoOptions {
     pub path: String,
+    /// Report holders at this RFC3339 timestamp, replayed from the oplog
+    pub at: Option<String>,
     pub repo: Option<PathBuf>,
     pub json: bool,
     pub quiet: bool,
@@ -160,6 +408,17 @@ pub struct WhoOptions {
 /// Result of lease who command
 #[derive(serde::Serialize)]
 struct WhoReport {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    at: Option<String>,
+    leases: Vec<LeaseEntry>,
+    /// For glob queries, the matching leases grouped by their pathspec
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    groups: Vec<WhoGroup>,
+}
+
+#[derive(serde::Serialize)]
+struct WhoGroup {
     path: String,
     leases: Vec<LeaseEntry>,
 }
@@ -196,49 +455,110 @@ pub fn run_who(options: WhoOptions) -> Result<()> {
     // Load config
     let config = Config::load_from_repo(&workdir);
 
-    // Load existing leases
-    let existing_leases: Vec<Lease> = storage.read_jsonl(&storage.leases_file())?;
-    let mut store = LeaseStore::from_vec(existing_leases);
-
-    // Expire stale leases
-    store.expire_stale();
-    let grace = parse_duration(&config.leases.expiration_grace)?;
-    let _expired = store.cleanup_expired(grace);
+    let at = options
+        .at
+        .as_deref()
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|ts| ts.with_timezone(&Utc))
+                .map_err(|err| {
+                    Error::InvalidArgument(format!("invalid --at timestamp '{value}': {err}"))
+                })
+        })
+        .transpose()?;
+
+    // Resolve the query like take does so subdirectory and root queries agree
+    let cwd = std::env::current_dir().unwrap_or_else(|_| workdir.clone());
+    let path = lease::resolve_pathspec(&workdir, &cwd, &options.path)?;
+    let glob = lease::is_glob(&path);
+
+    // Historical queries replay the oplog; otherwise read the live lease file
+    let store = match at {
+        Some(at) => LeaseStore::from_vec(OpLog::for_storage(&storage).leases_at(at)?),
+        None => {
+            let existing_leases: Vec<Lease> = storage.read_leases()?;
+            let mut store = LeaseStore::from_vec(existing_leases);
+
+            // Expire stale leases
+            store.expire_stale();
+            let grace = parse_duration(&config.leases.expiration_grace)?;
+            let _expired = store.cleanup_expired(grace);
+            store
+        }
+    };
 
-    // Find leases overlapping with the given path
-    let leases: Vec<&Lease> = store.overlapping_path(&options.path).collect();
+    // Find leases overlapping with the given path, or intersecting a glob.
+    // Replayed leases were active at `at` but may have expired since.
+    let leases: Vec<&Lease> = match (at, glob) {
+        (Some(_), true) => store
+            .all()
+            .iter()
+            .filter(|l| l.pathspec_overlaps(&path))
+            .collect(),
+        (Some(_), false) => store
+            .all()
+            .iter()
+            .filter(|l| l.matches_path(&path))
+            .collect(),
+        (None, true) => store.overlapping_pathspec(&path).collect(),
+        (None, false) => store.overlapping_path(&path).collect(),
+    };
 
     // Convert to display format
-    let entries: Vec<LeaseEntry> = leases
-        .iter()
-        .map(|l| LeaseEntry {
-            id: l.id.to_string(),
-            pathspec: l.pathspec.clone(),
-            strength: l.strength.to_string(),
-            intent: l.intent.to_string(),
-            actor: l.actor.clone(),
-            scope: l.scope.to_string(),
-            expires_at: l.expires_at.to_rfc3339(),
-            note: l.note.clone(),
-            status: format_status(&l.status),
-            created_at: l.created_at.to_rfc3339(),
-        })
-        .collect();
+    let entries: Vec<LeaseEntry> = leases.iter().map(|l| lease_entry(l)).collect();
+
+    let mut groups: Vec<WhoGroup> = Vec::new();
+    if glob {
+        for entry in &entries {
+            match groups.iter_mut().find(|group| group.path == entry.pathspec) {
+                Some(group) => group.leases.push(entry.clone()),
+                None => groups.push(WhoGroup {
+                    path: entry.pathspec.clone(),
+                    leases: vec![entry.clone()],
+                }),
+            }
+        }
+        groups.sort_by(|a, b| a.path.cmp(&b.path));
+    }
 
     // Output results
     let report = WhoReport {
-        path: options.path.clone(),
+        path,
+        at: at.map(|ts| ts.to_rfc3339()),
         leases: entries,
+        groups,
     };
+    let when = report
+        .at
+        .as_deref()
+        .map(|ts| format!(" at {ts}"))
+        .unwrap_or_default();
 
     if options.json {
-        println!("{}", serde_json::to_string_pretty(&report)?);
+        outln!("{}", serde_json::to_string_pretty(&report)?);
     } else if !options.quiet {
         if report.leases.is_empty() {
-            println!("No active leases on '{}'", options.path);
+            outln!("No active leases on '{}'{when}", report.path);
+        } else if glob {
+            outln!(
+                "Leases matching '{}'{when} ({}):",
+                report.path,
+                report.leases.len()
+            );
+            for group in &report.groups {
+                outln!();
+                outln!("{}:", group.path);
+                for lease in &group.leases {
+                    print_lease(lease);
+                }
+            }
         } else {
-            println!("Leases on '{}' ({}):", options.path, report.leases.len());
-            println!();
+            outln!(
+                "Leases on '{}'{when} ({}):",
+                report.path,
+                report.leases.len()
+            );
+            outln!();
             for lease in &report.leases {
                 print_lease(lease);
             }
@@ -248,6 +568,97 @@ pub fn run_who(options: WhoOptions) -> Result<()> {
     Ok(())
 }
 
+/// Options for the lease blame command
+pub struct BlameOptions {
+    pub path: String,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+#[derive(serde::Serialize)]
+struct BlameReport {
+    path: String,
+    entries: Vec<LeaseHistoryEntry>,
+}
+
+/// Run the lease blame command: the oplog-replayed coordination history of
+/// every lease that covered (or, for globs, overlapped) a path.
+pub fn run_blame(options: BlameOptions) -> Result<()> {
+    let start = options
+        .repo
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+
+    let repository =
+        git2::Repository::discover(&start).map_err(|_| Error::RepoNotFound(start.clone()))?;
+
+    let workdir = repository
+        .workdir()
+        .ok_or_else(|| Error::NotARepo(start.clone()))?
+        .to_path_buf();
+
+    let common_dir = resolve_common_dir(&repository)?;
+    let storage = Storage::new(workdir.clone(), common_dir, workdir.clone());
+    if !storage.is_initialized() {
+        return Err(Error::OperationFailed(
+            "sv not initialized. Run 'sv init' first.".to_string(),
+        ));
+    }
+
+    let cwd = std::env::current_dir().unwrap_or_else(|_| workdir.clone());
+    let path = lease::resolve_pathspec(&workdir, &cwd, &options.path)?;
+    let glob = lease::is_glob(&path);
+
+    let entries = OpLog::for_storage(&storage).lease_history(|l| {
+        if glob {
+            l.pathspec_overlaps(&path)
+        } else {
+            l.matches_path(&path)
+        }
+    })?;
+    let report = BlameReport { path, entries };
+
+    if options.json {
+        outln!("{}", serde_json::to_string_pretty(&report)?);
+    } else if !options.quiet {
+        if report.entries.is_empty() {
+            outln!("No recorded lease history for '{}'", report.path);
+        } else {
+            outln!(
+                "Lease history for '{}' ({}):",
+                report.path,
+                report.entries.len()
+            );
+            outln!();
+            for entry in &report.entries {
+                let holder = entry.holder.as_deref().unwrap_or("(ownerless)");
+                let by = match entry.by.as_deref() {
+                    Some(by) if by != holder => format!(" by {by}"),
+                    _ => String::new(),
+                };
+                outln!(
+                    "{}  {:<9} {holder}{by}  {} [{}/{}]",
+                    entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                    entry.action,
+                    entry.pathspec,
+                    entry.strength,
+                    entry.intent
+                );
+                if let Some(reason) = &entry.reason {
+                    outln!("    reason: {reason}");
+                } else if let (Some(expires), "take" | "renew" | "keepalive") =
+                    (entry.expires_at, entry.action.as_str())
+                {
+                    outln!("    expires: {}", expires.format("%Y-%m-%d %H:%M:%S"));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 // =============================================================================
 // Helper functions
 // =============================================================================
@@ -307,17 +718,40 @@ fn print_lease(lease: &LeaseEntry) {
     // Actor display
     let actor_display = lease.actor.as_deref().unwrap_or("(ownerless)");
 
-    println!(
+    outln!(
         "  {} {} [{}] by {} (expires {})",
-        short_id, lease.pathspec, lease.strength, actor_display, expires_relative,
+        short_id,
+        lease.pathspec,
+        lease.strength,
+        actor_display,
+        expires_relative,
     );
 
     // Show note if present (indented)
     if let Some(ref note) = lease.note {
-        println!("       └─ {}", note);
+        outln!("       └─ {}", note);
     }
 }
 
+fn lease_row(lease: &LeaseEntry) -> Vec<String> {
+    // Parse the expiry for relative time display
+    let expires_relative = chrono::DateTime::parse_from_rfc3339(&lease.expires_at)
+        .map(|dt| format_relative_time(&dt.with_timezone(&chrono::Utc)))
+        .unwrap_or_else(|_| lease.expires_at.clone());
+
+    // Short ID (first segment of UUID)
+    let short_id = lease.id.split('-').next().unwrap_or(&lease.id);
+
+    vec![
+        short_id.to_string(),
+        lease.pathspec.clone(),
+        lease.strength.clone(),
+        lease.actor.as_deref().unwrap_or("(ownerless)").to_string(),
+        expires_relative,
+        lease.note.clone().unwrap_or_default(),
+    ]
+}
+
 // =============================================================================
 // sv lease renew
 // =============================================================================
@@ -363,6 +797,8 @@ struct RenewReport {
     not_found: Vec<String>,
     not_owned: Vec<NotOwnedInfo>,
     not_active: Vec<NotActiveInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
 }
 
 /// Run the lease renew command
@@ -408,7 +844,7 @@ pub fn run_renew(options: RenewOptions) -> Result<()> {
     let lock_path = leases_file.with_extension("lock");
     let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
 
-    let mut store = LeaseStore::from_vec(storage.read_jsonl(&leases_file)?);
+    let mut store = LeaseStore::from_vec(storage.read_leases()?);
     store.expire_stale();
     let mut leases = store.into_vec();
 
@@ -416,6 +852,7 @@ pub fn run_renew(options: RenewOptions) -> Result<()> {
     let mut not_found = Vec::new();
     let mut not_owned = Vec::new();
     let mut not_active = Vec::new();
+    let mut warnings = Vec::new();
 
     for target in &options.ids {
         let idx = match find_lease_index(&leases, target) {
@@ -458,7 +895,14 @@ pub fn run_renew(options: RenewOptions) -> Result<()> {
             }
         });
 
-        lease.renew(ttl.clone())?;
+        let limited = config.leases.limit_ttl(&ttl)?;
+        if let Some(warning) = limited.warning() {
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+
+        lease.renew(limited.ttl)?;
         renewed.push(RenewedLeaseInfo {
             id: lease.id.to_string(),
             pathspec: lease.pathspec.clone(),
@@ -469,13 +913,15 @@ pub fn run_renew(options: RenewOptions) -> Result<()> {
     }
 
     if !renewed.is_empty() {
-        write_leases_jsonl(&storage.leases_file(), &leases)?;
+        lease::write_lease_log(&storage.leases_file(), &leases)?;
 
         let oplog = OpLog::for_storage(&storage);
         let mut record = OpRecord::new(
             format!("sv lease renew {}", options.ids.join(" ")),
             current_actor.clone(),
         );
+        record.affected_paths = renewed.iter().map(|r| r.pathspec.clone()).collect();
+        record.details = Some(renewed_details(&leases, &renewed));
         record.outcome = OpOutcome::success();
         let _ = oplog.append(&record);
     }
@@ -485,43 +931,50 @@ pub fn run_renew(options: RenewOptions) -> Result<()> {
         not_found: not_found.clone(),
         not_owned: not_owned.clone(),
         not_active: not_active.clone(),
+        warnings: warnings.clone(),
     };
 
     if options.json {
-        println!("{}", serde_json::to_string_pretty(&report)?);
+        outln!("{}", serde_json::to_string_pretty(&report)?);
     } else if !options.quiet {
         if !renewed.is_empty() {
-            println!("Renewed {} lease(s):", renewed.len());
+            outln!("Renewed {} lease(s):", renewed.len());
             for info in &renewed {
                 let short_id = info.id.split('-').next().unwrap_or(&info.id);
                 let actor_display = info.actor.as_deref().unwrap_or("(ownerless)");
-                println!(
+                outln!(
                     "  {} {} by {} (expires {})",
-                    short_id, info.pathspec, actor_display, info.expires_at
+                    short_id,
+                    info.pathspec,
+                    actor_display,
+                    info.expires_at
                 );
             }
         }
         if !not_owned.is_empty() {
-            println!("\nNot owned ({}):", not_owned.len());
+            outln!("\nNot owned ({}):", not_owned.len());
             for info in &not_owned {
                 let short_id = info.lease_id.split('-').next().unwrap_or(&info.lease_id);
                 let owner_display = info.owner.as_deref().unwrap_or("(ownerless)");
-                println!("  {} (owner: {})", short_id, owner_display);
+                outln!("  {} (owner: {})", short_id, owner_display);
             }
         }
         if !not_active.is_empty() {
-            println!("\nNot active ({}):", not_active.len());
+            outln!("\nNot active ({}):", not_active.len());
             for info in &not_active {
                 let short_id = info.lease_id.split('-').next().unwrap_or(&info.lease_id);
-                println!("  {} (status: {})", short_id, info.status);
+                outln!("  {} (status: {})", short_id, info.status);
             }
         }
         if !not_found.is_empty() {
-            println!("\nNot found ({}):", not_found.len());
+            outln!("\nNot found ({}):", not_found.len());
             for id in &not_found {
-                println!("  {}", id);
+                outln!("  {}", id);
             }
         }
+        for warning in &warnings {
+            eprintln!("Warning: {warning}");
+        }
     }
 
     if renewed.is_empty() && !not_found.is_empty() && not_owned.is_empty() && not_active.is_empty()
@@ -622,7 +1075,7 @@ pub fn run_break(options: BreakOptions) -> Result<()> {
     }
 
     // Load existing leases
-    let existing_leases: Vec<Lease> = storage.read_jsonl(&storage.leases_file())?;
+    let existing_leases: Vec<Lease> = storage.read_leases()?;
     let mut store = LeaseStore::from_vec(existing_leases);
 
     // Expire stale leases first
@@ -662,11 +1115,12 @@ pub fn run_break(options: BreakOptions) -> Result<()> {
 
     // Save updated leases - rewrite all leases since we modified in-place
     if !broken.is_empty() {
-        write_leases_jsonl(&storage.leases_file(), store.all())?;
+        lease::write_lease_log(&storage.leases_file(), store.all())?;
 
         // Record in oplog
         let oplog = OpLog::for_storage(&storage);
         let mut record = OpRecord::new("lease break", options.actor.clone());
+        record.affected_paths = broken.iter().map(|b| b.pathspec.clone()).collect();
         record.outcome = OpOutcome::success();
         record.undo_data = Some(UndoData {
             lease_changes: broken
@@ -688,24 +1142,26 @@ pub fn run_break(options: BreakOptions) -> Result<()> {
     };
 
     if options.json {
-        println!("{}", serde_json::to_string_pretty(&report)?);
+        outln!("{}", serde_json::to_string_pretty(&report)?);
     } else if !options.quiet {
         if !broken.is_empty() {
-            println!("Broken {} lease(s):", broken.len());
+            outln!("Broken {} lease(s):", broken.len());
             for info in &broken {
                 let short_id = info.id.split('-').next().unwrap_or(&info.id);
                 let actor_display = info.actor.as_deref().unwrap_or("(ownerless)");
-                println!(
+                outln!(
                     "  {} {} (was held by {})",
-                    short_id, info.pathspec, actor_display
+                    short_id,
+                    info.pathspec,
+                    actor_display
                 );
             }
-            println!("\nReason: {}", options.reason);
+            outln!("\nReason: {}", options.reason);
         }
         if !not_found.is_empty() {
-            println!("\nNot found ({}):", not_found.len());
+            outln!("\nNot found ({}):", not_found.len());
             for id in &not_found {
-                println!("  {}", id);
+                outln!("  {}", id);
             }
         }
     }
@@ -790,7 +1246,7 @@ pub fn run_wait(options: WaitOptions) -> Result<()> {
     let start_time = Instant::now();
 
     loop {
-        let existing_leases: Vec<Lease> = storage.read_jsonl(&storage.leases_file())?;
+        let existing_leases: Vec<Lease> = storage.read_leases()?;
         let mut store = LeaseStore::from_vec(existing_leases);
         store.expire_stale();
 
@@ -808,13 +1264,13 @@ pub fn run_wait(options: WaitOptions) -> Result<()> {
                     waited_ms,
                     timeout_ms: timeout_duration.map(|d| d.as_millis() as u64),
                 };
-                println!("{}", serde_json::to_string_pretty(&report)?);
+                outln!("{}", serde_json::to_string_pretty(&report)?);
             } else if !options.quiet {
                 let waited = format_elapsed(start_time.elapsed());
                 if waited_ms == 0 {
-                    println!("No active leases for {}", options.targets.join(", "));
+                    outln!("No active leases for {}", options.targets.join(", "));
                 } else {
-                    println!(
+                    outln!(
                         "Leases expired for {} (waited {})",
                         options.targets.join(", "),
                         waited
@@ -900,6 +1356,243 @@ fn wait_target_statuses(
     (active_targets, missing_ids)
 }
 
+// =============================================================================
+// sv lease keepalive
+// =============================================================================
+
+/// Options for the lease keepalive command
+pub struct KeepaliveOptions {
+    pub interval: String,
+    pub renew_to: Option<String>,
+    pub count: Option<u64>,
+    pub actor: Option<String>,
+    pub events: Option<String>,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+#[derive(serde::Serialize)]
+struct KeepaliveReport {
+    actor: String,
+    rounds: u64,
+    renewed: usize,
+    interrupted: bool,
+}
+
+/// Run the lease keepalive command
+///
+/// Loops until interrupted (or `--count` rounds), renewing the actor's active
+/// leases that would otherwise expire before the next round. Ctrl-C stops the
+/// loop between rounds; leases are left as they are.
+pub fn run_keepalive(options: KeepaliveOptions) -> Result<()> {
+    let interval = parse_positive_duration("interval", &options.interval)?;
+
+    let start = options
+        .repo
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+
+    let repository =
+        git2::Repository::discover(&start).map_err(|_| Error::RepoNotFound(start.clone()))?;
+
+    let workdir = repository
+        .workdir()
+        .ok_or_else(|| Error::NotARepo(start.clone()))?
+        .to_path_buf();
+
+    let common_dir = resolve_common_dir(&repository)?;
+    let storage = Storage::new(workdir.clone(), common_dir.clone(), workdir.clone());
+
+    if !storage.is_initialized() {
+        return Err(Error::OperationFailed(
+            "sv not initialized. Run 'sv init' first.".to_string(),
+        ));
+    }
+
+    let config = Config::load_from_repo(&workdir);
+    let actor = actor::resolve_actor_optional(Some(&workdir), options.actor.as_deref())?
+        .ok_or_else(|| {
+            Error::InvalidArgument("lease keepalive requires an actor (--actor or SV_ACTOR)".into())
+        })?;
+
+    let renew_to = options
+        .renew_to
+        .unwrap_or_else(|| config.leases.default_ttl.clone());
+    let limited = config.leases.limit_ttl(&renew_to)?;
+    if parse_positive_duration("renew-to", &limited.ttl)? <= interval {
+        return Err(Error::InvalidArgument(format!(
+            "--renew-to ({}) must be longer than --interval ({})",
+            limited.ttl, options.interval
+        )));
+    }
+
+    let event_destination = EventDestination::parse(options.events.as_deref());
+    let mut event_sink = event_destination
+        .as_ref()
+        .map(|dest| dest.open_with(&config.events))
+        .transpose()?;
+    let events_to_stdout = matches!(event_destination, Some(EventDestination::Stdout));
+    let chatty = !options.quiet && !options.json && !events_to_stdout;
+
+    if let Some(warning) = limited.warning() {
+        eprintln!("Warning: {warning}");
+    }
+    if chatty {
+        eprintln!(
+            "Keeping leases alive for {actor} every {} (renew to {}). Press Ctrl-C to stop.",
+            options.interval, limited.ttl
+        );
+    }
+
+    // Anything expiring before the round after next gets renewed now, which
+    // leaves a full interval of slack for slow rounds.
+    let horizon = chrono::Duration::from_std(interval * 2)
+        .map_err(|_| Error::InvalidArgument("interval is too large".to_string()))?;
+    let interrupted = interrupt_flag();
+    let mut rounds = 0u64;
+    let mut total_renewed = 0usize;
+    let oplog = OpLog::for_storage(&storage);
+
+    loop {
+        let (renewed, snapshots) = keepalive_round(&storage, &actor, &limited.ttl, horizon)?;
+        rounds += 1;
+        total_renewed += renewed.len();
+        // One record per round that renewed something, so `lease who --at`
+        // sees each extension even if the session ends abruptly
+        if !snapshots.is_empty() {
+            let mut record = OpRecord::new("sv lease keepalive", Some(actor.clone()));
+            record.affected_paths = snapshots
+                .iter()
+                .map(|lease| lease.pathspec.clone())
+                .collect();
+            record.details = Some(OpDetails {
+                leases: snapshots,
+                ..OpDetails::default()
+            });
+            record.outcome = OpOutcome::success();
+            let _ = oplog.append(&record);
+        }
+
+        if let Some(sink) = event_sink.as_mut() {
+            for info in &renewed {
+                let event =
+                    Event::new(EventKind::LeaseRenewed, Some(actor.clone())).with_data(info)?;
+                if let Err(err) = sink.emit(&event) {
+                    eprintln!("Warning: event output failed: {err}");
+                }
+            }
+            // Rounds are an interval apart, so a coalesced run never spans
+            // two; don't hold it back until the next round.
+            if let Err(err) = sink.flush_pending() {
+                eprintln!("Warning: event output failed: {err}");
+            }
+        }
+        if chatty && !renewed.is_empty() {
+            outln!(
+                "[{}] renewed {} lease(s) to {}",
+                chrono::Utc::now().format("%H:%M:%S"),
+                renewed.len(),
+                limited.ttl
+            );
+        }
+
+        if options.count.is_some_and(|count| rounds >= count)
+            || sleep_until_interrupted(interval, &interrupted)
+        {
+            break;
+        }
+    }
+
+    let report = KeepaliveReport {
+        actor,
+        rounds,
+        renewed: total_renewed,
+        interrupted: interrupted.load(Ordering::SeqCst),
+    };
+
+    if options.json {
+        outln!("{}", serde_json::to_string_pretty(&report)?);
+    } else if chatty {
+        outln!(
+            "Stopped after {} round(s); renewed {} lease(s) in total.",
+            report.rounds,
+            report.renewed
+        );
+    }
+
+    Ok(())
+}
+
+/// Renew the actor's active leases expiring within `horizon`, under the lease lock.
+///
+/// Returns the renewal summaries along with snapshots of the renewed leases.
+fn keepalive_round(
+    storage: &Storage,
+    actor: &str,
+    ttl: &str,
+    horizon: chrono::Duration,
+) -> Result<(Vec<RenewedLeaseInfo>, Vec<Lease>)> {
+    let leases_file = storage.leases_file();
+    let lock_path = leases_file.with_extension("lock");
+    let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
+
+    let mut store = LeaseStore::from_vec(storage.read_leases()?);
+    store.expire_stale();
+    let mut leases = store.into_vec();
+
+    let due = chrono::Utc::now() + horizon;
+    let mut renewed = Vec::new();
+    for lease in leases.iter_mut() {
+        if !lease.is_active() || lease.actor.as_deref() != Some(actor) || lease.expires_at > due {
+            continue;
+        }
+        lease.renew(ttl)?;
+        renewed.push(RenewedLeaseInfo {
+            id: lease.id.to_string(),
+            pathspec: lease.pathspec.clone(),
+            actor: lease.actor.clone(),
+            ttl: lease.ttl.clone(),
+            expires_at: lease.expires_at.to_rfc3339(),
+        });
+    }
+
+    if !renewed.is_empty() {
+        lease::write_lease_log(&leases_file, &leases)?;
+    }
+    let snapshots = leases
+        .into_iter()
+        .filter(|lease| renewed.iter().any(|r| r.id == lease.id.to_string()))
+        .collect();
+
+    Ok((renewed, snapshots))
+}
+
+/// Oplog details carrying snapshots of the renewed leases.
+fn renewed_details(leases: &[Lease], renewed: &[RenewedLeaseInfo]) -> OpDetails {
+    OpDetails {
+        leases: leases
+            .iter()
+            .filter(|lease| renewed.iter().any(|r| r.id == lease.id.to_string()))
+            .cloned()
+            .collect(),
+        ..OpDetails::default()
+    }
+}
+
+/// Sleep for `duration` in short slices; returns true if interrupted.
+fn sleep_until_interrupted(duration: StdDuration, interrupted: &AtomicBool) -> bool {
+    let deadline = Instant::now() + duration;
+    while !interrupted.load(Ordering::SeqCst) {
+        let now = Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        std::thread::sleep((deadline - now).min(StdDuration::from_millis(100)));
+    }
+    true
+}
+
 fn parse_positive_duration(label: &str, value: &str) -> Result<StdDuration> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -947,6 +1640,560 @@ fn sleep_with_timeout(poll: StdDuration, timeout: Option<StdDuration>, start_tim
     }
 }
 
+// =============================================================================
+// sv lease export / import
+// =============================================================================
+
+/// Options for the lease export command
+pub struct ExportOptions {
+    pub repo: Option<PathBuf>,
+}
+
+/// Run the lease export command
+///
+/// Writes every lease record (active and historical) as JSONL. The global
+/// `--output <path>` sends it to a file instead of stdout.
+pub fn run_export(options: ExportOptions) -> Result<()> {
+    let storage = open_lease_storage(options.repo)?;
+    let leases: Vec<Lease> = storage.read_leases()?;
+    for lease in &leases {
+        outln!("{}", serde_json::to_string(lease)?);
+    }
+    Ok(())
+}
+
+/// Options for the lease import command
+pub struct ImportOptions {
+    pub file: PathBuf,
+    pub actor: Option<String>,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+/// An imported lease that was not applied: its ID is held by a different
+/// actor locally, or it overlaps a local lease of another actor
+#[derive(Clone, serde::Serialize)]
+struct ImportConflict {
+    id: String,
+    pathspec: String,
+    local_actor: Option<String>,
+    incoming_actor: Option<String>,
+    /// Local lease the incoming one conflicts with (set for overlaps)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    overlaps: Option<String>,
+}
+
+/// Report for lease import command
+#[derive(serde::Serialize)]
+struct ImportReport {
+    imported: Vec<String>,
+    updated: Vec<String>,
+    unchanged: usize,
+    skipped_expired: usize,
+    conflicts: Vec<ImportConflict>,
+}
+
+/// How an imported lease relates to the local record with the same ID
+#[derive(Debug, PartialEq, Eq)]
+enum ImportDecision {
+    Keep,
+    Replace,
+    Conflict,
+}
+
+/// Decide whether `incoming` should replace `local` (same lease ID).
+///
+/// History wins over an active record (a release/break seen elsewhere ends
+/// the lease here too); between two active records the later `expires_at`
+/// wins. Different owners never overwrite each other.
+fn import_decision(local: &Lease, incoming: &Lease) -> ImportDecision {
+    if local.actor.is_some() && incoming.actor.is_some() && local.actor != incoming.actor {
+        return ImportDecision::Conflict;
+    }
+    let local_active = local.status == LeaseStatus::Active;
+    let incoming_active = incoming.status == LeaseStatus::Active;
+    match (local_active, incoming_active) {
+        (true, false) => ImportDecision::Replace,
+        (true, true) if incoming.expires_at > local.expires_at => ImportDecision::Replace,
+        _ => ImportDecision::Keep,
+    }
+}
+
+/// Run the lease import command
+///
+/// Merges leases from a JSONL export by ID. Expired leases are skipped and
+/// conflicting owners are reported rather than overwritten. Active leases go
+/// through the same conflict and quota checks as `sv take`.
+pub fn run_import(options: ImportOptions) -> Result<()> {
+    let storage = open_lease_storage(options.repo)?;
+    let config = Config::load_from_repo(storage.workspace_root());
+    let policy = Policy::from_compat(&config.leases.compat, false);
+    let content = if options.file.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(&options.file).map_err(|err| {
+            Error::InvalidArgument(format!(
+                "failed to read {}: {}",
+                options.file.display(),
+                err
+            ))
+        })?
+    };
+    let mut incoming = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let lease: Lease = serde_json::from_str(line).map_err(|err| {
+            Error::InvalidArgument(format!(
+                "{}:{}: invalid lease record: {}",
+                options.file.display(),
+                index + 1,
+                err
+            ))
+        })?;
+        incoming.push(lease);
+    }
+
+    let leases_file = storage.leases_file();
+    let lock_path = leases_file.with_extension("lock");
+    let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
+
+    let mut store = LeaseStore::from_vec(storage.read_leases()?);
+    store.expire_stale();
+    let mut leases = store.into_vec();
+    let before = leases.clone();
+
+    let mut report = ImportReport {
+        imported: Vec::new(),
+        updated: Vec::new(),
+        unchanged: 0,
+        skipped_expired: 0,
+        conflicts: Vec::new(),
+    };
+    let mut affected_paths = Vec::new();
+    let mut snapshots = Vec::new();
+    let mut lease_changes = Vec::new();
+
+    for lease in incoming {
+        if lease.status == LeaseStatus::Expired || lease.is_expired() {
+            report.skipped_expired += 1;
+            continue;
+        }
+        if lease.status == LeaseStatus::Active {
+            let overlapping = leases
+                .iter()
+                .filter(|existing| existing.id != lease.id)
+                .find(|existing| lease::conflicts(existing, &lease, &policy).is_some());
+            if let Some(existing) = overlapping {
+                report.conflicts.push(ImportConflict {
+                    id: lease.id.to_string(),
+                    pathspec: lease.pathspec.clone(),
+                    local_actor: existing.actor.clone(),
+                    incoming_actor: lease.actor.clone(),
+                    overlaps: Some(existing.id.to_string()),
+                });
+                continue;
+            }
+        }
+        match leases.iter().position(|existing| existing.id == lease.id) {
+            None => {
+                if lease.status != LeaseStatus::Active {
+                    report.unchanged += 1;
+                    continue;
+                }
+                report.imported.push(lease.id.to_string());
+                affected_paths.push(lease.pathspec.clone());
+                lease_changes.push(LeaseChange {
+                    lease_id: lease.id.to_string(),
+                    action: "create".to_string(),
+                });
+                snapshots.push(lease.clone());
+                leases.push(lease);
+            }
+            Some(idx) => match import_decision(&leases[idx], &lease) {
+                ImportDecision::Keep => report.unchanged += 1,
+                ImportDecision::Replace => {
+                    report.updated.push(lease.id.to_string());
+                    affected_paths.push(lease.pathspec.clone());
+                    if lease.status != LeaseStatus::Active {
+                        // An active local lease ended elsewhere; undo restores it.
+                        lease_changes.push(LeaseChange {
+                            lease_id: lease.id.to_string(),
+                            action: "release".to_string(),
+                        });
+                    }
+                    snapshots.push(lease.clone());
+                    leases[idx] = lease;
+                }
+                ImportDecision::Conflict => report.conflicts.push(ImportConflict {
+                    id: lease.id.to_string(),
+                    pathspec: lease.pathspec.clone(),
+                    local_actor: leases[idx].actor.clone(),
+                    incoming_actor: lease.actor.clone(),
+                    overlaps: None,
+                }),
+            },
+        }
+    }
+
+    let quota = config.leases.actor_quota();
+    let merged = LeaseStore::from_vec(leases.clone());
+    let actors: HashSet<&str> = snapshots
+        .iter()
+        .filter_map(|lease| lease.actor.as_deref())
+        .collect();
+    for actor_name in actors {
+        let held: Vec<Lease> = before
+            .iter()
+            .filter(|lease| lease.actor.as_deref() == Some(actor_name) && lease.is_active())
+            .cloned()
+            .collect();
+        quota.check(actor_name, &held, &merged)?;
+    }
+
+    if !report.imported.is_empty() || !report.updated.is_empty() {
+        lease::write_lease_log(&leases_file, &leases)?;
+
+        let oplog = OpLog::for_storage(&storage);
+        let mut record = OpRecord::new(
+            format!("sv lease import {}", options.file.display()),
+            options.actor.clone(),
+        );
+        record.affected_paths = affected_paths;
+        record.details = Some(OpDetails {
+            leases: snapshots,
+            ..OpDetails::default()
+        });
+        record.undo_data = Some(UndoData {
+            lease_changes,
+            ..UndoData::default()
+        });
+        record.outcome = OpOutcome::success();
+        let _ = oplog.append(&record);
+    }
+
+    if options.json {
+        outln!("{}", serde_json::to_string_pretty(&report)?);
+    } else if !options.quiet {
+        outln!(
+            "Imported {} lease(s), updated {}, unchanged {}, skipped {} expired",
+            report.imported.len(),
+            report.updated.len(),
+            report.unchanged,
+            report.skipped_expired
+        );
+        for conflict in &report.conflicts {
+            let short_id = conflict.id.split('-').next().unwrap_or(&conflict.id);
+            if let Some(local_id) = &conflict.overlaps {
+                eprintln!(
+                    "Warning: lease {} ({}) of {} conflicts with lease {} held by {}; skipped",
+                    short_id,
+                    conflict.pathspec,
+                    conflict.incoming_actor.as_deref().unwrap_or("(ownerless)"),
+                    local_id.split('-').next().unwrap_or(local_id),
+                    conflict.local_actor.as_deref().unwrap_or("(ownerless)")
+                );
+                continue;
+            }
+            eprintln!(
+                "Warning: lease {} ({}) is held by {} locally but {} in the import; kept local",
+                short_id,
+                conflict.pathspec,
+                conflict.local_actor.as_deref().unwrap_or("(ownerless)"),
+                conflict.incoming_actor.as_deref().unwrap_or("(ownerless)")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// sv lease doctor
+// =============================================================================
+
+/// Options for the lease doctor command
+pub struct DoctorOptions {
+    pub repair: bool,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+/// A lease ID recorded more than once in the log
+#[derive(Clone, serde::Serialize)]
+struct DuplicateLease {
+    id: String,
+    records: usize,
+    /// 1-based index among parsed records of the copy repair keeps
+    kept_record: usize,
+}
+
+/// An active lease scoped to a workspace that is not registered
+#[derive(Clone, serde::Serialize)]
+struct OrphanedLease {
+    id: String,
+    pathspec: String,
+    workspace: String,
+}
+
+/// An active lease held by an actor sv has no record of
+#[derive(Clone, serde::Serialize)]
+struct UnknownActorLease {
+    id: String,
+    pathspec: String,
+    actor: String,
+}
+
+/// Report for lease doctor command
+#[derive(serde::Serialize)]
+struct DoctorReport {
+    log_path: String,
+    leases: usize,
+    malformed: Vec<lease::MalformedLease>,
+    duplicate_ids: Vec<DuplicateLease>,
+    unknown_workspaces: Vec<OrphanedLease>,
+    unknown_actors: Vec<UnknownActorLease>,
+    expired_active: Vec<String>,
+    repaired: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    set_aside_path: Option<String>,
+}
+
+/// Order of lease records for deduplication: latest status change, then
+/// latest expiry
+fn record_recency(lease: &Lease) -> (DateTime<Utc>, DateTime<Utc>) {
+    (
+        lease.status_changed_at.unwrap_or(lease.created_at),
+        lease.expires_at,
+    )
+}
+
+/// Index of the record to keep for each duplicated ID (newest wins; later
+/// records win ties)
+fn duplicate_lease_ids(leases: &[Lease]) -> Vec<(Uuid, Vec<usize>, usize)> {
+    let mut by_id: BTreeMap<Uuid, Vec<usize>> = BTreeMap::new();
+    for (idx, lease) in leases.iter().enumerate() {
+        by_id.entry(lease.id).or_default().push(idx);
+    }
+    by_id
+        .into_iter()
+        .filter(|(_, indexes)| indexes.len() > 1)
+        .map(|(id, indexes)| {
+            let kept = indexes
+                .iter()
+                .copied()
+                .max_by_key(|&idx| (record_recency(&leases[idx]), idx))
+                .unwrap_or(indexes[0]);
+            (id, indexes, kept)
+        })
+        .collect()
+}
+
+/// Run the lease doctor command
+///
+/// Reports malformed lines, duplicate lease IDs, leases scoped to
+/// unregistered workspaces, and active leases past their expiry. With
+/// `--repair`, malformed lines are moved to `leases.malformed.jsonl`,
+/// duplicates are collapsed to the newest record and expired leases are
+/// marked, then the log is rewritten.
+pub fn run_doctor(options: DoctorOptions) -> Result<()> {
+    let storage = open_lease_storage(options.repo)?;
+    let leases_file = storage.leases_file();
+    let lock_path = leases_file.with_extension("lock");
+    let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
+
+    let (leases, malformed) = lease::read_lease_log(&leases_file)?;
+    let duplicates = duplicate_lease_ids(&leases);
+
+    let workspaces = storage.list_workspaces()?;
+    let actors = known_actors(&storage, &workspaces)?;
+    let now = Utc::now();
+    let mut unknown_workspaces = Vec::new();
+    let mut unknown_actors = Vec::new();
+    let mut expired_active = Vec::new();
+    for lease in leases.iter().filter(|l| l.status == LeaseStatus::Active) {
+        if let Some(actor) = lease.actor.as_deref().filter(|a| !actors.contains(*a)) {
+            unknown_actors.push(UnknownActorLease {
+                id: lease.id.to_string(),
+                pathspec: lease.pathspec.clone(),
+                actor: actor.to_string(),
+            });
+        }
+        if let lease::LeaseScope::Workspace(workspace) = &lease.scope {
+            if !workspaces
+                .iter()
+                .any(|entry| &entry.name == workspace || &entry.id == workspace)
+            {
+                unknown_workspaces.push(OrphanedLease {
+                    id: lease.id.to_string(),
+                    pathspec: lease.pathspec.clone(),
+                    workspace: workspace.clone(),
+                });
+            }
+        }
+        if lease.expires_at <= now {
+            expired_active.push(lease.id.to_string());
+        }
+    }
+
+    let needs_repair =
+        !malformed.is_empty() || !duplicates.is_empty() || !expired_active.is_empty();
+    let repaired = options.repair && needs_repair;
+    let mut set_aside_path = None;
+    if repaired {
+        use std::io::Write;
+
+        if !malformed.is_empty() {
+            let aside = leases_file.with_extension("malformed.jsonl");
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&aside)?;
+            for entry in &malformed {
+                writeln!(file, "{}", entry.content)?;
+            }
+            set_aside_path = Some(aside.display().to_string());
+        }
+
+        let dropped: std::collections::HashSet<usize> = duplicates
+            .iter()
+            .flat_map(|(_, indexes, kept)| indexes.iter().copied().filter(move |idx| idx != kept))
+            .collect();
+        let kept = leases
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !dropped.contains(idx))
+            .map(|(_, lease)| lease.clone())
+            .collect();
+        let mut store = LeaseStore::from_vec(kept);
+        store.expire_stale_at(now);
+        lease::write_clean_lease_log(&leases_file, store.all())?;
+    }
+
+    let report = DoctorReport {
+        log_path: leases_file.display().to_string(),
+        leases: leases.len(),
+        malformed,
+        duplicate_ids: duplicates
+            .iter()
+            .map(|(id, indexes, kept)| DuplicateLease {
+                id: id.to_string(),
+                records: indexes.len(),
+                kept_record: kept + 1,
+            })
+            .collect(),
+        unknown_workspaces,
+        unknown_actors,
+        expired_active,
+        repaired,
+        set_aside_path,
+    };
+
+    if options.json {
+        outln!("{}", serde_json::to_string_pretty(&report)?);
+    } else if !options.quiet {
+        let clean = report.malformed.is_empty()
+            && report.duplicate_ids.is_empty()
+            && report.unknown_workspaces.is_empty()
+            && report.unknown_actors.is_empty()
+            && report.expired_active.is_empty();
+        if clean {
+            outln!("Lease log OK: {} lease record(s)", report.leases);
+            return Ok(());
+        }
+        outln!("Lease log issues in {}:", report.log_path);
+        for entry in &report.malformed {
+            outln!("  malformed line {}: {}", entry.line, entry.error);
+        }
+        for entry in &report.duplicate_ids {
+            outln!("  duplicate id {} ({} records)", entry.id, entry.records);
+        }
+        for entry in &report.unknown_workspaces {
+            let short_id = entry.id.split('-').next().unwrap_or(&entry.id);
+            outln!(
+                "  lease {} ({}) is scoped to unknown workspace '{}'; release with: sv release {}",
+                short_id,
+                entry.pathspec,
+                entry.workspace,
+                entry.id
+            );
+        }
+        for entry in &report.unknown_actors {
+            let short_id = entry.id.split('-').next().unwrap_or(&entry.id);
+            outln!(
+                "  lease {} ({}) is held by unknown actor '{}'; break with: sv lease break {} --reason \"unknown actor\"",
+                short_id,
+                entry.pathspec,
+                entry.actor,
+                entry.id
+            );
+        }
+        for id in &report.expired_active {
+            let short_id = id.split('-').next().unwrap_or(id);
+            outln!(
+                "  lease {} is past its expiry but still marked active",
+                short_id
+            );
+        }
+        if report.repaired {
+            match &report.set_aside_path {
+                Some(path) => outln!("\nRepaired (malformed lines moved to {})", path),
+                None => outln!("\nRepaired"),
+            }
+        } else if needs_repair {
+            outln!("\nRun: sv lease doctor --repair");
+        }
+    }
+
+    Ok(())
+}
+
+/// Actors sv has a record of: those that recorded an operation, the actors of
+/// registered workspaces, actors persisted in `.sv/actor` of the repo or a
+/// workspace, and the configured default actor.
+fn known_actors(storage: &Storage, workspaces: &[WorkspaceEntry]) -> Result<HashSet<String>> {
+    let mut actors: HashSet<String> = OpLog::for_storage(storage)
+        .read_all()?
+        .into_iter()
+        .filter_map(|record| record.actor)
+        .collect();
+    let root = storage.workspace_root();
+    for entry in workspaces {
+        actors.extend(entry.actor.clone());
+        actors.extend(actor::load_persisted_actor(&entry.path).ok().flatten());
+    }
+    actors.extend(actor::load_persisted_actor(root).ok().flatten());
+    let config = Config::load_from_repo(root);
+    if config.actor.default != "unknown" {
+        actors.insert(config.actor.default);
+    }
+    Ok(actors)
+}
+
+/// Open lease storage for the repository at `repo` (or the current directory)
+fn open_lease_storage(repo: Option<PathBuf>) -> Result<Storage> {
+    let start =
+        repo.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    let repository =
+        git2::Repository::discover(&start).map_err(|_| Error::RepoNotFound(start.clone()))?;
+    let workdir = repository
+        .workdir()
+        .ok_or_else(|| Error::NotARepo(start.clone()))?
+        .to_path_buf();
+    let common_dir = resolve_common_dir(&repository)?;
+    let storage = Storage::new(workdir.clone(), common_dir, workdir);
+
+    if !storage.is_initialized() {
+        return Err(Error::OperationFailed(
+            "sv not initialized. Run 'sv init' first.".to_string(),
+        ));
+    }
+    Ok(storage)
+}
+
 /// Find a lease by full UUID or prefix
 fn find_lease_by_id<'a>(store: &'a LeaseStore, id_str: &str) -> Option<&'a Lease> {
     // Try exact UUID match first
@@ -960,25 +2207,3 @@ fn find_lease_by_id<'a>(store: &'a LeaseStore, id_str: &str) -> Option<&'a Lease
         .active()
         .find(|lease| lease.id.to_string().to_lowercase().starts_with(&normalized))
 }
-
-/// Write leases to file in JSONL format (atomic write via temp + rename)
-fn write_leases_jsonl(path: &std::path::Path, leases: &[Lease]) -> Result<()> {
-    use std::fs::File;
-    use std::io::Write;
-
-    // Write to temp file first
-    let temp_path = path.with_extension("tmp");
-    let mut file = File::create(&temp_path)?;
-
-    for lease in leases {
-        let json = serde_json::to_string(lease)?;
-        writeln!(file, "{}", json)?;
-    }
-
-    file.sync_all()?;
-
-    // Atomic rename
-    std::fs::rename(&temp_path, path)?;
-
-    Ok(())
-}
@@ -0,0 +1,87 @@
+//! sv man command implementation
+//!
+//! Renders roff man pages from the clap command tree, either the top-level
+//! page to stdout or one page per subcommand into a directory.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use clap::CommandFactory;
+use clap_mangen::Man;
+
+use super::Cli;
+use crate::error::{Error, Result};
+use crate::output::{emit_success, result_writer, HumanOutput, OutputOptions};
+
+/// Options for `sv man`
+pub struct ManOptions {
+    pub section: String,
+    pub out: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+#[derive(serde::Serialize)]
+struct ManReport {
+    section: String,
+    dir: PathBuf,
+    files: Vec<PathBuf>,
+}
+
+pub fn run(options: ManOptions) -> Result<()> {
+    let section = options.section.trim().to_string();
+    if section.is_empty() || !section.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+        return Err(Error::InvalidArgument(format!(
+            "invalid man section '{}': expected e.g. 1 or 8",
+            options.section
+        )));
+    }
+
+    let mut command = Cli::command().disable_help_subcommand(true);
+    // Building assigns subcommands their `sv-ws-new` style display names
+    command.build();
+
+    let Some(dir) = options.out else {
+        let mut page = Vec::new();
+        Man::new(command).section(section).render(&mut page)?;
+        let mut writer = result_writer();
+        writer.write_all(&page)?;
+        writer.flush()?;
+        return Ok(());
+    };
+
+    std::fs::create_dir_all(&dir)?;
+    let mut files = Vec::new();
+    generate(command, &section, &dir, &mut files)?;
+    files.sort();
+
+    let mut human = HumanOutput::new(format!("sv man: wrote {} page(s)", files.len()));
+    human.push_summary("section", section.clone());
+    human.push_summary("dir", dir.display().to_string());
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "man",
+        &ManReport {
+            section,
+            dir,
+            files,
+        },
+        Some(&human),
+    )
+}
+
+fn generate(
+    command: clap::Command,
+    section: &str,
+    dir: &std::path::Path,
+    files: &mut Vec<PathBuf>,
+) -> Result<()> {
+    for sub in command.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        generate(sub.clone(), section, dir, files)?;
+    }
+    files.push(Man::new(command).section(section).generate_to(dir)?);
+    Ok(())
+}
@@ -3,24 +3,40 @@
 //! This module defines the CLI structure using clap derive macros.
 //! Each subcommand is defined in its own submodule.
 
-use clap::{CommandFactory, Parser, Subcommand};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 
 use crate::error::{Error, Result};
+use crate::output::outln;
 
 mod actor;
+mod change_id;
 mod commit;
+mod completions;
+mod config;
+mod env;
+mod events;
 mod forge;
+mod hoist;
 mod init;
 mod lease;
+mod man;
+mod multi;
 mod onto;
 mod op;
 mod project;
 mod protect;
+mod refs;
 mod release;
 mod status;
 mod switch;
 mod take;
 mod task;
+mod undo;
+mod version;
 mod ws;
 
 const ROBOT_HELP: &str = r#"sv --robot-help
@@ -42,12 +58,16 @@ Quickstart (typical agent flow)
   sv release <paths...>
 
 Environment
-  SV_REPO   -> default repo path (otherwise current directory)
+  SV_REPO   -> default repo path (otherwise current directory); for status/risk/
+               lease ls a path list runs across each repo, like repeated --repo
+               (other commands always read it as a single path)
   SV_ACTOR  -> default actor name for leases/ops
   SV_ACTOR_FILTER -> default actor filter for task list/ready/count
+  NO_COLOR  -> disable ANSI colors in human output (same as --no-color)
 
 Storage layout
   .sv.toml           Config (tracked)
+  .sv.local.toml     Worktree-local config overlay (ignored)
   .sv/               Workspace-local state (ignored)
   .sv/worktrees/     Default root for new workspaces (unless --dir is used)
   .git/sv/           Shared local state (leases, registry, oplog, hoist state)
@@ -57,6 +77,9 @@ Output contracts
   --json   Machine-readable output with envelope:
            { schema_version, command, status, data, warnings, next_steps }
   --events Emit JSONL events to file or stdout ("-"). Use --events <path> with --json.
+  --output Write the command result (human or JSON) to a file; diagnostics stay on stderr.
+           Combine with --events <other-path> to keep result, events, and logs separate.
+  --result-line  Print one summary line to stderr, e.g. "SV_RESULT take ok conflicts=0 created=2 updated=0"
 
 Exit codes
   0 success
@@ -67,21 +90,30 @@ Exit codes
 Commands (high level)
   sv init                   Initialize repo state
   sv actor set|show          Configure actor identity
+  sv config show [--effective] Show repo or effective config
   sv ws new|list|info|rm|clean|here|switch Workspace management
   sv switch                 Resolve workspace path for fast switching
   sv take                   Create leases on paths/globs
   sv release                Release leases
-  sv lease ls|who|renew|break|wait Inspect/manage leases
+  sv lease ls|who|blame|renew|break|wait Inspect/manage leases
   sv protect status|add|off|rm Protected paths
   sv commit                 Commit with sv checks + Change-Id
+  sv change-id [verify]     Generate, read, or verify Change-Ids
   sv task new|list|ready|count|stats|show|start|status|priority|edit|close|delete|comment|parent|epic|project|block|unblock|relate|unrelate|relations|sync|doctor|repair|compact|prefix  Tasks
   sv project new|list|show|edit|archive|unarchive|sync|migrate-legacy  Projects
   sv forge hooks install     Configure Forge task hooks
+  sv refs prune|migrate     Delete stale sv refs or move them to [refs] namespace
+  sv events validate        Check a captured JSONL event stream
   sv risk                   Overlap/conflict analysis
   sv onto                   Rebase/merge current workspace onto another
   sv hoist                  Bulk integrate workspaces into an integration branch
   sv op log                 Operation history
   sv undo                   Undo recent ops (limited)
+  sv redo                   Re-apply the most recently undone op
+  sv completions <shell>    Print a bash/zsh/fish/elvish/powershell completion script
+  sv man [--section] [--out] Generate roff man pages (stdout or one file per subcommand)
+  sv version [--json]       Version, git sha, build date, libgit2 version, features
+  sv env [--json]           Resolved repo, state dirs, actor (and source), config files, env vars
 
 Tasks (notes)
   list/ready sorted: status -> priority -> readiness -> updated_at -> id
@@ -102,12 +134,13 @@ Leases
   TTL: default 2h, configurable in .sv.toml
 
 Protected paths
-  Modes: guard (block), warn (allow with warning)
+  Modes: guard (block; --allow-protected bypasses), readonly (block; only
+  --override-readonly bypasses), warn (allow with warning)
   Per-workspace overrides stored in .sv/overrides/protect.json
 
 Events (JSONL)
-  lease_created, lease_released, workspace_created, workspace_removed,
-  commit_blocked, commit_created, task_created, task_started,
+  lease_created, lease_released, lease_renewed, lease_expired, workspace_created, workspace_removed,
+  commit_blocked, commit_created, protection_bypassed, protection_warned, task_created, task_started,
   task_status_changed, task_priority_changed, task_edited, task_closed, task_deleted,
   task_epic_auto_close_set, task_epic_auto_close_cleared,
   task_commented, task_epic_set, task_epic_cleared, task_project_set, task_project_cleared, task_parent_set, task_parent_cleared, task_blocked,
@@ -127,9 +160,11 @@ Purpose
 Commands
   sv ws new <name> [--base] [--dir] [--branch] [--sparse ...]
   sv ws here [--name]
-  sv ws list [--selector]
+  sv ws list [--selector] [--stale <dur>] [--all]
   sv ws info <name>
   sv ws rm <name> [--force]
+  sv ws archive <name> [--force]
+  sv ws restore <name>
   sv ws clean [--selector] [--dest] [--force] [--dry-run]
   sv ws switch [name] [--path]
 
@@ -142,11 +177,15 @@ Purpose
   Acquire leases on paths or globs.
 
 Usage
-  sv take <paths...> [--strength] [--intent] [--scope] [--ttl] [--note]
+  sv take <paths...> [--strength] [--intent] [--scope] [--ttl] [--note] [--force] [--dry-run]
 
 Notes
   strength: observe|cooperative|strong|exclusive
   note required for strong/exclusive
+  omitted --strength/--intent use leases.default_strength/default_intent (cooperative/other)
+  paths already covered by your own active lease (same or stronger strength,
+  same or wider scope) are skipped and reported under "covered"; a covering lease
+  that would expire before --ttl is renewed to it; --force takes them anyway
 "#;
 const RELEASE_ROBOT_HELP: &str = r#"sv release --robot-help
 
@@ -154,7 +193,15 @@ Purpose
   Release leases by id or pathspec.
 
 Usage
-  sv release <ids|pathspecs...> [--force]
+  sv release <ids|pathspecs...> [--force] [--dry-run]
+  sv release --expired [--dry-run]
+  sv release --selector '<expr>' [--force] [--dry-run]
+
+Notes
+  --expired marks your already-expired leases released with reason expired; the records
+  stay in the lease log and sv undo restores them (emits lease_expired)
+  --selector predicates: active, stale, held("<actor>"|"me"), expiring("<ttl>"),
+  touching("<path>"), overlaps("<pathspec>"), name~"<text>"; others' leases need --force
 "#;
 const LEASE_ROBOT_HELP: &str = r#"sv lease --robot-help
 
@@ -162,11 +209,22 @@ Purpose
   Inspect/manage leases.
 
 Commands
-  sv lease ls [--selector] [--actor]
-  sv lease who <path>
+  sv lease ls [--selector] [--actor] [--path <pathspec>] [--format text|json|jsonl] [--by-actor]
+  sv lease who <path|glob> [--at <rfc3339>]
+  sv lease blame <path|glob>
   sv lease renew <ids...> [--ttl]
   sv lease break <ids...> --reason "<text>"
   sv lease wait <targets...> [--timeout] [--poll]
+  sv lease keepalive [--interval 5m] [--renew-to <ttl>] [--count <n>]
+  sv lease export [--output <file>]
+  sv lease import <file|->
+  sv lease doctor [--repair]
+
+Notes
+  keepalive runs in the foreground, renewing the current actor's leases before
+  they expire; Ctrl-C stops it and leaves leases in place. Emits lease_renewed events.
+  blame replays the oplog into a chronological take/renew/keepalive/release/break
+  list for leases on a path (who held, who acted, break reasons); who is current state.
 "#;
 const PROTECT_ROBOT_HELP: &str = r#"sv protect --robot-help
 
@@ -178,6 +236,15 @@ Commands
   sv protect add <patterns...> [--mode guard|readonly|warn]
   sv protect off <patterns...>
   sv protect rm <patterns...> [--force]
+
+Notes
+  protect rm removes the configured pattern equal to an argument; an argument
+  equal to no configured pattern is a glob over the configured pattern
+  strings. Each removed pattern is listed; an argument matching nothing is an
+  error unless --force.
+  protect add validates every pattern as a glob first; any invalid pattern
+  fails the command (exit 2) without touching .sv.toml. Patterns already
+  present are reported under already_exists rather than added twice.
 "#;
 const COMMIT_ROBOT_HELP: &str = r#"sv commit --robot-help
 
@@ -185,7 +252,25 @@ Purpose
   Commit with protected/lease checks + Change-Id injection.
 
 Usage
-  sv commit -m "<msg>" [-a] [--amend] [--no-edit] [--allow-protected] [--force-lease]
+  sv commit -m "<msg>" [-a] [--amend] [--no-edit] [--allow-protected] [--override-readonly] [--reason <text>] [--force-lease] [--dry-run]
+
+Notes
+  A guard-mode protect block exits 3; with --json, error.details carries
+  protected_violations: [{ path, pattern, mode }] for every blocked path.
+  Modes: guard blocks unless --allow-protected; readonly blocks even then and
+  only yields to --override-readonly (sv protect off does not lift it); warn
+  commits with a warning and a protection_warned event. A bypass records
+  --reason in the oplog and a protection_bypassed event;
+  [protect] require_bypass_reason = true makes --reason mandatory.
+"#;
+const CHANGE_ID_ROBOT_HELP: &str = r#"sv change-id --robot-help
+
+Purpose
+  Generate a Change-Id, or read the Change-Id trailer of a commit.
+
+Usage
+  sv change-id [--of <rev>]
+  sv change-id verify [--base <ref>]
 "#;
 const TASK_ROBOT_HELP: &str = r#"sv task --robot-help
 
@@ -203,16 +288,17 @@ Quickstart
 Commands
   sv task [--epic <id>] [--project <id>]  Open task TUI
   sv task new "<title>" [--status] [--priority P0-P4] [--body]
-  sv task list [--status] [--priority] [--epic] [--project] [--workspace] [--updated-by] [--updated-since] [--limit]
-  sv task ready [--priority] [--epic] [--project] [--workspace] [--updated-by] [--updated-since] [--limit]
-  sv task count [--ready] [--status] [--priority] [--epic] [--project] [--workspace] [--updated-by] [--updated-since] [--limit]
+  sv task list [--status ...] [--priority] [--epic] [--project] [--workspace] [--updated-by] [--updated-since] [--limit] [--format text|json|tsv]
+  sv task ready [--priority] [--epic] [--project] [--workspace] [--updated-by] [--updated-since] [--limit] [--format text|json|tsv]
+  sv task count [--ready] [--status ...] [--priority] [--epic] [--project] [--workspace] [--updated-by] [--updated-since] [--limit] [--fail-over <n>]
   sv task stats
-  sv task show <id>
-  sv task start <id>
+  sv task timeline [--since <dur|rfc3339>] [--actor] [--limit]
+  sv task show <id> [--events-file <path>]
+  sv task start <id> [--takeover] [--lease <path>...] [--create-workspace [--base <ref>]]
   sv task status <id> <status>
   sv task priority <id> <P0-P4>
   sv task edit <id> [--title] [--body]
-  sv task close <id> [--status]
+  sv task close <id> [--status] [--merge [--into <base>]]
   sv task delete <id>
   sv task comment <id> "<text>"
   sv task parent set <child> <parent>
@@ -227,7 +313,9 @@ Commands
   sv task relate <left> <right> --desc "<text>"
   sv task unrelate <left> <right>
   sv task relations <id>
-  sv task sync
+  sv task sync [--pull] [--push] [--remote <name>]
+  sv task install-merge-driver
+  sv task merge-driver <base> <ours> <theirs>
   sv task doctor
   sv task repair --dedupe-creates [--dry-run]
   sv task compact [--older-than] [--max-log-mb] [--dry-run]
@@ -247,10 +335,10 @@ Purpose
 
 Commands
   sv project new "<name>" [--description]
-  sv project list [--all]
+  sv project list [--all] [--with-progress]
   sv project show <id>
   sv project edit <id> [--name] [--description]
-  sv project archive <id>
+  sv project archive <id> [--force]
   sv project unarchive <id>
   sv project sync
   sv project migrate-legacy [--dry-run]
@@ -258,6 +346,39 @@ Commands
 Notes
   Legacy task-backed project ids remain readable.
   Use migrate-legacy to create standalone project entities from legacy ids.
+  archive refuses while member tasks (including ones inheriting the project from
+  their epic) are not closed; --force clears the project from those tasks and from
+  the epics they inherit it from (reported as cleared_tasks / detached_tasks).
+"#;
+const REFS_ROBOT_HELP: &str = r#"sv refs --robot-help
+
+Purpose
+  Maintain the sv-managed ref namespace (sv/ws/*, sv/hoist/*).
+
+Commands
+  sv refs prune [--dry-run] [--force]
+  sv refs migrate [--dry-run]
+
+Notes
+  prune deletes workspace branches with no registered workspace and
+  integration branches of completed hoists. Refs with commits not merged into
+  their base are skipped unless --force. Old ref targets are kept in the op log.
+  [refs] namespace = "refs/sv" in .sv.toml creates new workspace and hoist refs
+  under refs/sv (hidden from git branch; sv/ws/<name> still resolves). migrate
+  moves existing refs into the configured namespace and re-attaches worktrees.
+"#;
+const EVENTS_ROBOT_HELP: &str = r#"sv events --robot-help
+
+Purpose
+  Check captured JSONL event streams (written by --events <path>).
+
+Commands
+  sv events validate <file>
+
+Notes
+  Each non-blank line must be an event envelope with the current
+  schema_version (sv.event.v1), a known event kind, and an RFC3339 timestamp.
+  Problems are reported with their line numbers; JSON: data.invalid[].
 "#;
 const FORGE_ROBOT_HELP: &str = r#"sv forge --robot-help
 
@@ -276,7 +397,26 @@ Purpose
   Overlap/conflict analysis across workspaces.
 
 Usage
-  sv risk [--selector] [--base] [--simulate]
+  sv risk [--selector] [--base] [--since <ref>] [--simulate] [--mine] [--fail-on <severity>]
+
+Notes
+  --since <ref> scopes each workspace's touched files to <ref>..branch (from
+  their merge base) instead of full divergence from base; the report carries
+  since_ref. Not combinable with --simulate.
+  Overlaps on paths matching [risk] ignore globs (default: target/**,
+  **/node_modules/**, vendor/**, dist/**, **/*.min.js) are dropped and counted
+  in suppressed_overlaps; set ignore = [] to keep them.
+  Renames count as touching both paths, so editing a file another workspace
+  renamed is an overlap; such overlaps list the rename under renames.
+  --simulate reports files changed on both sides where either blob is binary
+  as kind "binary" (binary conflict, resolve manually) instead of content.
+  On a terminal, progress ("analyzing workspace X of N", "simulating merge X
+  of N") is shown on stderr; it is off with --json or --quiet.
+  Ctrl-C during --simulate stops between workspace pairs, prints the pairs
+  finished so far with cancelled: true, then exits 130. A second Ctrl-C, or
+  one after the simulation finished, terminates immediately.
+  --fail-on exits 3 after printing the report when any overlap is at or above
+  the severity; it does not apply to --simulate.
 "#;
 const OP_ROBOT_HELP: &str = r#"sv op --robot-help
 
@@ -284,7 +424,18 @@ Purpose
   Operation history.
 
 Commands
-  sv op log [--limit] [--actor] [--operation] [--since] [--until] [--json]
+  sv op log [--limit] [--actor] [--operation <kind|family>] [--touching <pathspec>] [--since] [--until] [--graph] [--json]
+
+Notes
+  operation kinds: init, actor_set, ws_new, ws_rm, ws_clean, ws_here, ws_register,
+  ws_archive, ws_restore, take, release, lease_renew, lease_break, lease_keepalive, commit,
+  onto, hoist, refs_prune, refs_migrate, undo, redo, protect, task, other; a family prefix
+  such as ws or lease selects all of its kinds
+  --touching matches recorded paths (leased pathspecs, committed files, files moved by onto
+  or hoist);
+  entries written before paths were recorded are skipped and counted in skipped_untagged
+  each JSON entry has commits: oids the operation created (commit, onto, hoist,
+  task close --merge); --graph prints them under each operation
 "#;
 const UNDO_ROBOT_HELP: &str = r#"sv undo --robot-help
 
@@ -292,7 +443,32 @@ Purpose
   Undo last operation.
 
 Usage
-  sv undo [--op <id>]
+  sv undo [--op <id>] [--dry-run] [--keep-worktree]
+
+Notes
+  Reverts the most recent op log entry with undo data (or --op <id>): resets
+  refs it moved, removes paths it created, unregisters workspaces it created,
+  and releases or restores the leases it changed.
+  A created worktree with uncommitted changes is not removed: the undo fails
+  (exit 4) unless --keep-worktree, which leaves created worktrees in place.
+  --dry-run prints the same changes without applying them; with --json,
+  data has ref_updates [{name,current,to}], remove_paths, keep_paths,
+  unregister_workspaces and lease_changes [{lease_id,pathspec,action remove|restore}].
+"#;
+const REDO_ROBOT_HELP: &str = r#"sv redo --robot-help
+
+Purpose
+  Re-apply the most recently undone operation.
+
+Usage
+  sv redo
+
+Notes
+  sv undo and sv redo are recorded in the op log (kinds undo and redo); the
+  redo stack is replayed from those records. sv redo re-applies the most
+  recently undone operation, putting refs and leases back. Any other new
+  operation clears the stack; the undone operation stays undone. Operations whose undo removed paths (e.g. sv ws new)
+  cannot be redone.
 "#;
 const ACTOR_ROBOT_HELP: &str = r#"sv actor --robot-help
 
@@ -303,6 +479,20 @@ Commands
   sv actor set <name>
   sv actor show
 "#;
+const CONFIG_ROBOT_HELP: &str = r#"sv config --robot-help
+
+Purpose
+  Show sv configuration.
+
+Commands
+  sv config show               Config from .sv.toml (or defaults)
+  sv config show --effective   Merged config with .sv.local.toml applied
+
+Notes
+  Precedence is .sv.local.toml > .sv.toml > built-in defaults. The local
+  file lives in the worktree root and is git-ignored by sv init. With
+  --json, data.origins maps each key set by a file to "repo" or "local".
+"#;
 const INIT_ROBOT_HELP: &str = r#"sv init --robot-help
 
 Purpose
@@ -311,13 +501,76 @@ Purpose
 Usage
   sv init
 "#;
+const COMPLETIONS_ROBOT_HELP: &str = r#"sv completions --robot-help
+
+Purpose
+  Print a static shell completion script generated from the CLI definition.
+
+Usage
+  sv completions <bash|zsh|fish|elvish|powershell>
+
+Notes
+  Writes the script to stdout (or --output); needs no repository.
+"#;
+const MAN_ROBOT_HELP: &str = r#"sv man --robot-help
+
+Purpose
+  Generate roff man pages from the CLI definition for packaging.
+
+Usage
+  sv man [--section <n>] [--out <dir>]
+
+Notes
+  Without --out, prints the top-level sv page to stdout.
+  With --out, writes sv.<n>, sv-ws.<n>, sv-ws-new.<n>, ... into <dir>
+  (created if missing) and reports { section, dir, files }.
+  Needs no repository.
+"#;
+const VERSION_ROBOT_HELP: &str = r#"sv version --robot-help
+
+Purpose
+  Show version and build metadata; needs no repository.
+
+Usage
+  sv version [--json]
+
+Notes
+  JSON data: { version, git_sha, build_date, libgit2_version, features }.
+  git_sha is "unknown" for builds outside a git checkout; build_date honors
+  SOURCE_DATE_EPOCH.
+"#;
+const ENV_ROBOT_HELP: &str = r#"sv env --robot-help
+
+Purpose
+  Diagnose the environment sv resolves; works outside a repository.
+
+Usage
+  sv env [--json]
+
+Notes
+  JSON data: { cwd, repo, git_common_dir, state { local, shared, initialized },
+  actor { name, source }, config { repo, local, error? }, libgit2_version,
+  sv_version, env { SV_REPO, SV_ACTOR, ... } }.
+  actor.source is one of cli, env, persisted, config, default.
+"#;
 const STATUS_ROBOT_HELP: &str = r#"sv status --robot-help
 
 Purpose
   Show current workspace status.
 
 Usage
-  sv status [--json]
+  sv status [--short] [--json]
+
+Short format
+  One space-separated line per item; the tag and field order are stable:
+    W <workspace> <+ahead-behind|?> <*|->     W agent1 +3-1 *
+    L <pathspec> <holder> <obs|coop|strong|excl> <ttl>
+                                               L src/auth/** alice excl 1h
+    T <task-id> <status>
+    P <staged protected path>
+  Fields containing spaces, quotes, backslashes, control or non-ASCII characters
+  are C-quoted like git status --porcelain ("my file.rs", "caf\303\251.rs").
+  --json takes precedence over --short.
 "#;
 const SWITCH_ROBOT_HELP: &str = r#"sv switch --robot-help
 
@@ -334,10 +587,11 @@ Notes
 const ONTO_ROBOT_HELP: &str = r#"sv onto --robot-help
 
 Purpose
-  Rebase/merge current workspace onto target workspace.
+  Rebase/merge current workspace onto target workspace (or any ref with --into).
 
 Usage
-  sv onto <target> [--strategy rebase|merge|cherry-pick] [--base] [--preflight]
+  sv onto <target> [--strategy rebase|merge|cherry-pick] [--base] [--commits <a..b>] [--preflight] [--dry-run]
+  sv onto --into <ref> [--strategy ...] [--base] [--preflight] [--dry-run]
 "#;
 const HOIST_ROBOT_HELP: &str = r#"sv hoist --robot-help
 
@@ -345,7 +599,14 @@ Purpose
   Integrate multiple workspaces into an integration branch.
 
 Usage
-  sv hoist -s <selector> [--dest] [--strategy] [--order] [--dry-run] [--close-tasks] [--rm] [--rm-force]
+  sv hoist -s <selector> [--legacy-selector] [--dest] [--strategy] [--order] [--squash] [--name <branch>] [--force] [--dry-run] [--close-tasks] [--rm] [--rm-force]
+  sv hoist resolve <commit-id> [--dest <ref>]
+
+Notes
+  The integration branch is refused while another hoist on it is in progress
+  or has conflicts awaiting sv hoist resolve (exit 4); --force resets it.
+  On a terminal, "replaying commit X of N" progress is shown on stderr; it is
+  off with --json or --quiet.
 "#;
 
 /// sv - Simultaneous Versioning
@@ -365,14 +626,18 @@ Usage
   sv commit -m "Fix refresh edge case"
   sv risk --json
   sv take src/auth/** --json --events /tmp/sv.events.jsonl
+  sv take src/auth/** --json --output /tmp/sv.result.json --events /tmp/sv.events.jsonl
 
 Notes:
   Use --events <path> when combining with --json.
 "#)]
 pub struct Cli {
-    /// Path to the repository (defaults to current directory)
-    #[arg(long, global = true, env = "SV_REPO")]
-    pub repo: Option<std::path::PathBuf>,
+    /// Path to the repository (defaults to $SV_REPO, then the current directory).
+    /// Repeat to run status, risk, or lease ls across several repositories;
+    /// For those commands SV_REPO may likewise hold a path list (`:`-separated,
+    /// `;` on Windows).
+    #[arg(long, global = true, value_name = "path")]
+    pub repo: Vec<std::path::PathBuf>,
 
     /// Actor identity for leases and operations
     #[arg(long, global = true, env = "SV_ACTOR")]
@@ -382,10 +647,14 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub json: bool,
 
-    /// Emit JSONL events to stdout or a file (use "-" for stdout). Use --events <path> with --json.
+    /// Emit JSONL events to stdout, a file, or a Unix socket (use "-" for stdout, "unix:<path>" for a socket). Use --events <path> with --json.
     #[arg(long, global = true, value_name = "path", num_args = 0..=1, default_missing_value = "-")]
     pub events: Option<String>,
 
+    /// Write the command result (human or JSON) to a file instead of stdout
+    #[arg(long, global = true, value_name = "path")]
+    pub output: Option<std::path::PathBuf>,
+
     /// Print detailed robot-oriented help and exit
     #[arg(long, global = true)]
     pub robot_help: bool,
@@ -398,6 +667,19 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub verbose: bool,
 
+    /// Disable ANSI colors in human output (also honors NO_COLOR)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Print a grep-able "SV_RESULT <command> <status> key=count" line to stderr
+    #[arg(long, global = true)]
+    pub result_line: bool,
+
+    /// Whether --actor was given on the command line rather than filled from
+    /// SV_ACTOR (set by [`Cli::parse_args`])
+    #[arg(skip)]
+    pub actor_flag: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -432,13 +714,13 @@ Examples:
         #[arg(required = true)]
         paths: Vec<String>,
 
-        /// Lease strength: observe, cooperative, strong, exclusive
-        #[arg(long, default_value = "cooperative")]
-        strength: String,
+        /// Lease strength: observe, cooperative, strong, exclusive [default: leases.default_strength]
+        #[arg(long)]
+        strength: Option<String>,
 
-        /// Intent: bugfix, feature, docs, refactor, rename, format, mechanical, investigation, other
-        #[arg(long, default_value = "other")]
-        intent: String,
+        /// Intent: bugfix, feature, docs, refactor, rename, format, mechanical, investigation, other [default: leases.default_intent]
+        #[arg(long)]
+        intent: Option<String>,
 
         /// Scope: repo, branch:<name>, ws:<workspace>
         #[arg(long, default_value = "repo")]
@@ -451,6 +733,14 @@ Examples:
         /// Note explaining the lease (required for strong/exclusive)
         #[arg(long)]
         note: Option<String>,
+
+        /// Create leases even for paths your existing leases already cover
+        #[arg(long)]
+        force: bool,
+
+        /// Validate and show the leases that would be taken without writing
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Release a lease
@@ -460,15 +750,29 @@ Examples:
   sv release 01HZXJ6ZP9QK3A5T
   sv release src/auth/**
   sv release src/auth/** --events /tmp/sv.events.jsonl
+  sv release --expired
+  sv release --selector 'held("me") & expiring("1h")'
 "#)]
     Release {
         /// Lease IDs or pathspecs to release
-        #[arg(required = true)]
+        #[arg(required_unless_present_any = ["expired", "selector"])]
         targets: Vec<String>,
 
+        /// Release the leases a selector matches (e.g. 'held("me") & expiring("1h")')
+        #[arg(short, long, conflicts_with = "expired")]
+        selector: Option<String>,
+
         /// Force release even if owned by another actor
         #[arg(long)]
         force: bool,
+
+        /// Release all of your leases that have already expired
+        #[arg(long, conflicts_with = "targets")]
+        expired: bool,
+
+        /// Validate and show the leases that would be released without writing
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Lease management commands
@@ -505,7 +809,7 @@ Examples:
 Examples:
   sv commit -m "Fix refresh edge case"
   sv commit --amend --no-edit
-  sv commit --allow-protected
+  sv commit --allow-protected --reason "regenerate lockfile"
   sv commit --force-lease
 "#
     )]
@@ -530,13 +834,48 @@ Examples:
         #[arg(long)]
         no_edit: bool,
 
-        /// Allow committing protected paths
+        /// Allow committing guard-mode protected paths
         #[arg(long)]
         allow_protected: bool,
 
+        /// Allow committing readonly paths (not covered by --allow-protected)
+        #[arg(long)]
+        override_readonly: bool,
+
+        /// Why protection is bypassed (recorded in the oplog and events)
+        #[arg(long)]
+        reason: Option<String>,
+
         /// Force commit despite lease conflicts
         #[arg(long)]
         force_lease: bool,
+
+        /// Run all checks and show the files that would be committed without committing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Generate a Change-Id or read one from a commit
+    #[command(long_about = r#"Print a Change-Id without committing.
+
+Without arguments a fresh Change-Id is generated. With --of, the Change-Id
+trailer of that commit is printed; nothing is printed on stdout if the commit
+has none.
+
+Examples:
+  sv change-id
+  sv change-id --of HEAD
+  sv change-id --of HEAD~2 --json
+  sv change-id verify --base main
+"#)]
+    #[command(args_conflicts_with_subcommands = true)]
+    ChangeId {
+        #[command(subcommand)]
+        command: Option<ChangeIdCommands>,
+
+        /// Read the Change-Id trailer of this revision
+        #[arg(long, value_name = "rev")]
+        of: Option<String>,
     },
 
     /// Task management
@@ -587,6 +926,32 @@ Examples:
         command: Option<ForgeCommands>,
     },
 
+    /// Inspect captured event streams
+    #[command(long_about = r#"Work with JSONL event streams written by --events.
+
+Examples:
+  sv take src/** --events /tmp/sv.jsonl
+  sv events validate /tmp/sv.jsonl
+"#)]
+    Events {
+        #[command(subcommand)]
+        command: Option<EventsCommands>,
+    },
+
+    /// Maintain sv-managed refs
+    #[command(long_about = r#"Maintain the sv/ws/* and sv/hoist/* ref namespace.
+
+Examples:
+  sv refs prune --dry-run
+  sv refs prune
+  sv refs prune --force --json
+  sv refs migrate --dry-run
+"#)]
+    Refs {
+        #[command(subcommand)]
+        command: Option<RefsCommands>,
+    },
+
     /// Risk assessment and conflict prediction
     #[command(long_about = r#"Show overlap risk across workspaces.
 
@@ -594,6 +959,13 @@ Examples:
   sv risk
   sv risk --simulate
   sv risk --selector "agent*"
+  sv risk --mine --json
+  sv risk --fail-on high --json
+
+--mine keeps only overlaps that involve the current actor: a workspace owned by
+the actor or a path covered by one of the actor's active leases.
+--fail-on exits with code 3 after reporting when any overlap is at or above the
+given severity.
 "#)]
     Risk {
         /// Selector for workspaces to analyze
@@ -604,17 +976,37 @@ Examples:
         #[arg(long)]
         base: Option<String>,
 
+        /// Only count each workspace's changes in <ref>..branch (e.g. HEAD~20)
+        #[arg(long, value_name = "ref", conflicts_with = "simulate")]
+        since: Option<String>,
+
         /// Simulate actual merge conflicts
         #[arg(long)]
         simulate: bool,
+
+        /// Only show overlaps involving the current actor's workspaces or leases
+        #[arg(long)]
+        mine: bool,
+
+        /// Exit nonzero when an overlap reaches this severity (low|medium|high|critical)
+        #[arg(long, value_name = "SEVERITY", conflicts_with = "simulate")]
+        fail_on: Option<String>,
     },
 
     /// Operation log and undo
     #[command(long_about = r#"Inspect operation history.
 
+Operation kinds: init, actor_set, ws_new, ws_rm, ws_clean, ws_here, ws_register,
+ws_archive, ws_restore, take, release, lease_renew, lease_break, lease_keepalive,
+commit, onto, hoist, refs_prune, refs_migrate, undo, redo, protect, task, other.
+A family prefix such as "ws" matches every ws_* kind.
+--touching keeps entries whose recorded paths overlap a pathspec.
+
 Examples:
   sv op log --limit 20
   sv op log --actor alice
+  sv op log --operation ws
+  sv op log --touching src/auth --since 2025-01-01T00:00:00Z
 "#)]
     Op {
         #[command(subcommand)]
@@ -624,16 +1016,48 @@ Examples:
     /// Undo the last operation
     #[command(long_about = r#"Undo a recent sv operation.
 
+Reverts the most recent operation that recorded undo data, or the one
+given with --op (ids are shown by sv op log --json). --dry-run shows the
+refs, paths, workspaces and leases the undo would change without touching
+them.
+
+Undoing sv ws new removes the worktree it created. sv undo refuses when that
+worktree has uncommitted changes; --keep-worktree leaves it on disk instead.
+
 Examples:
+  sv undo --dry-run
   sv undo
-  sv undo --op 01HZXJ6ZP9QK3A5T
+  sv undo --keep-worktree
+  sv undo --op 3f2a9c4e-8d1b-4c6a-9e7f-2b5d8a1c0e93 --json
 "#)]
     Undo {
         /// Specific operation ID to undo
         #[arg(long)]
         op: Option<String>,
+
+        /// Show the changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Leave worktrees the operation created in place
+        #[arg(long)]
+        keep_worktree: bool,
     },
 
+    /// Re-apply the most recently undone operation
+    #[command(long_about = r#"Re-apply the most recently undone operation.
+
+Each sv undo is recorded in the op log; sv redo re-applies the most recently
+undone operation, restoring the refs and leases the undo changed, and records
+that too. Recording any other operation clears the redo stack. Undos that removed paths (such as a workspace created by
+sv ws new) cannot be redone.
+
+Examples:
+  sv undo && sv redo
+  sv redo --json
+"#)]
+    Redo,
+
     /// Set or show actor identity
     #[command(long_about = r#"Manage the actor identity used for leases and ops.
 
@@ -646,6 +1070,21 @@ Examples:
         command: Option<ActorCommands>,
     },
 
+    /// Show sv configuration
+    #[command(long_about = r#"Show the sv configuration for this repo.
+
+A worktree-local .sv.local.toml overlays .sv.toml; keys it sets win over the
+repo file, which wins over built-in defaults.
+
+Examples:
+  sv config show
+  sv config show --effective
+"#)]
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommands>,
+    },
+
     /// Initialize sv in a repository
     #[command(long_about = r#"Initialize sv state in the repo.
 
@@ -659,9 +1098,14 @@ Examples:
 
 Examples:
   sv status
+  sv status --short
   sv status --json
 "#)]
-    Status,
+    Status {
+        /// One line per item in a stable, grep/awk-friendly format
+        #[arg(long)]
+        short: bool,
+    },
 
     /// Resolve workspace path for fast switching
     #[command(long_about = r#"Resolve workspace path for fast switching.
@@ -689,11 +1133,17 @@ Examples:
   sv onto agent5 --strategy merge
   sv onto agent5 --base main
   sv onto agent5 --preflight
+  sv onto agent5 --strategy cherry-pick --commits abc123..def456
 "#
     )]
     Onto {
         /// Target workspace name to rebase onto
-        target: String,
+        #[arg(required_unless_present = "into")]
+        target: Option<String>,
+
+        /// Reposition onto an arbitrary ref (e.g., origin/main) instead of a workspace
+        #[arg(long, value_name = "ref", conflicts_with = "target")]
+        into: Option<String>,
 
         /// Strategy: rebase (default), merge, or cherry-pick
         #[arg(long, default_value = "rebase")]
@@ -703,9 +1153,17 @@ Examples:
         #[arg(long)]
         base: Option<String>,
 
+        /// Commit range to cherry-pick from the target workspace (e.g., abc123..def456)
+        #[arg(long, value_name = "rev-range")]
+        commits: Option<String>,
+
         /// Preview conflicts before rebasing (dry run with merge simulation)
         #[arg(long)]
         preflight: bool,
+
+        /// Validate and show the commits that would be replayed without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Hoist workspace branches into an integration branch
@@ -714,11 +1172,42 @@ Examples:
 Examples:
   sv hoist -s 'ws(active) & ahead("main")' --strategy stack --order workspace
   sv hoist -s "agent*" -d main --dry-run
+  sv hoist -s "actor:agent*" --legacy-selector --dry-run
+
+Selectors are parsed with the full selector grammar; a selector that does not
+parse is an error (with its location) unless it is an obvious legacy form
+(prefix*, actor:<name>, a bare workspace name) or --legacy-selector is given.
+
+--squash collapses each workspace into one commit on the integration branch
+(strategy stack or rebase). The commit keeps the first commit's author and
+lists the squashed subjects in its message body. Conflicts in a squashed
+hoist cannot be continued with sv hoist resolve; keep the default
+conflict propagation, or re-run without --squash.
+
+The integration branch defaults to sv/hoist/<dest>. A hoist refuses to reset
+it while another hoist on that branch is in progress or has conflicts waiting
+for sv hoist resolve; use --name <branch> to run it on a separate integration
+branch, or --force to take the branch over.
+
+--order time replays commits oldest first. Commits with the same timestamp
+are ordered by workspace name, and each workspace keeps its own commit order,
+so dry-run output is reproducible.
+
+Conflicts recorded with --continue-on-conflict --no-propagate-conflicts are
+resolved one at a time with `sv hoist resolve <commit-id>`.
 "#)]
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Hoist {
+        #[command(subcommand)]
+        command: Option<HoistCommands>,
+
         /// Selector for workspaces to include (e.g., ws(active) & ahead("main") or legacy actor:agent*)
         #[arg(short, long, required = true)]
-        selector: String,
+        selector: Option<String>,
+
+        /// Fall back to legacy prefix/actor matching when the selector does not parse
+        #[arg(long)]
+        legacy_selector: bool,
 
         /// Destination ref to integrate onto (e.g., "main") (default: current branch)
         #[arg(short, long)]
@@ -732,6 +1221,18 @@ Examples:
         #[arg(long, default_value = "workspace")]
         order: String,
 
+        /// Squash each workspace's commits into one commit (stack/rebase only)
+        #[arg(long)]
+        squash: bool,
+
+        /// Integration branch to build on (default: sv/hoist/<dest>)
+        #[arg(long, value_name = "branch")]
+        name: Option<String>,
+
+        /// Reset the integration branch even if another hoist is using it
+        #[arg(long)]
+        force: bool,
+
         /// Dry run: show what would be done without making changes
         #[arg(long)]
         dry_run: bool,
@@ -760,6 +1261,188 @@ Examples:
         #[arg(long, requires = "rm")]
         rm_force: bool,
     },
+
+    /// Print a shell completion script
+    #[command(long_about = r#"Print a shell completion script to stdout.
+
+Examples:
+  sv completions bash > ~/.local/share/bash-completion/completions/sv
+  sv completions zsh > "${fpath[1]}/_sv"
+  sv completions fish > ~/.config/fish/completions/sv.fish
+"#)]
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
+
+    /// Generate roff man pages
+    #[command(long_about = r#"Generate roff man pages from the CLI definition.
+
+Without --out, prints the top-level sv page to stdout. With --out, writes one
+page per subcommand (sv.1, sv-ws.1, sv-ws-new.1, ...) into the directory.
+
+Examples:
+  sv man | man -l -
+  sv man --out target/man
+  sv man --section 8 --out /tmp/man8
+"#)]
+    Man {
+        /// Manual section for the pages
+        #[arg(long, default_value = "1")]
+        section: String,
+
+        /// Directory to write one page per subcommand into
+        #[arg(long, value_name = "dir")]
+        out: Option<std::path::PathBuf>,
+    },
+
+    /// Show version and build metadata
+    #[command(long_about = r#"Show the sv version with build metadata.
+
+Reports the git sha and date sv was built from, the linked libgit2 version,
+and enabled cargo features. Include `sv version --json` in support tickets.
+
+Examples:
+  sv version
+  sv version --json
+"#)]
+    Version,
+
+    /// Show the resolved environment sv operates in
+    #[command(long_about = r#"Show the environment sv resolves for commands.
+
+Reports the repository and git common dir, sv state directories, the actor
+and where it came from, config files loaded, the libgit2 version, and the
+environment variables sv reads. Works outside a repository. Run this first
+when a command misbehaves.
+
+Examples:
+  sv env
+  sv env --json
+"#)]
+    Env,
+}
+
+/// Change-Id subcommands
+#[derive(Subcommand, Debug)]
+pub enum ChangeIdCommands {
+    /// Report missing or duplicate Change-Id trailers between base and HEAD
+    #[command(long_about = r#"Check Change-Id trailers on the current branch.
+
+Walks the commits from the base ref to HEAD and lists commits without a
+Change-Id trailer and commits that share one. Two commits with the same
+Change-Id usually mean a rebase kept both the old and the rewritten commit.
+Merge commits are not checked.
+
+Examples:
+  sv change-id verify
+  sv change-id verify --base origin/main --json
+"#)]
+    Verify {
+        /// Base ref to walk from (default: config base)
+        #[arg(long)]
+        base: Option<String>,
+    },
+}
+
+/// Refs subcommands
+#[derive(Subcommand, Debug)]
+pub enum RefsCommands {
+    /// Delete refs left behind by removed workspaces and finished hoists
+    #[command(long_about = r#"Delete stale sv-managed refs.
+
+Prunes sv/ws/* branches that no registered workspace uses and sv/hoist/*
+branches whose hoist completed (or left no state). Refs checked out in a
+worktree are kept, and refs with commits not merged into their base (the
+hoist destination, or the configured base) are kept unless --force.
+
+The deleted refs and their targets are recorded in the operation log.
+
+Examples:
+  sv refs prune --dry-run
+  sv refs prune --force
+"#)]
+    Prune {
+        /// Show what would be deleted without deleting
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also delete refs with unmerged commits
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Move sv-managed refs into the configured namespace
+    #[command(long_about = r#"Move sv-managed refs into the configured namespace.
+
+By default sv creates workspace and hoist refs as ordinary branches
+(refs/heads/sv/ws/<name>). With this in .sv.toml:
+
+  [refs]
+  namespace = "refs/sv"
+
+new refs are created as refs/sv/ws/<name> instead, which keeps them out of
+git branch while the short name sv/ws/<name> still resolves. migrate moves
+existing refs to the configured namespace (in either direction) and keeps
+worktrees that have them checked out attached to the moved ref.
+
+Examples:
+  sv refs migrate --dry-run
+  sv refs migrate
+"#)]
+    Migrate {
+        /// Show what would be moved without moving
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Events subcommands
+#[derive(Subcommand, Debug)]
+pub enum EventsCommands {
+    /// Validate a JSONL event stream against the event schema
+    #[command(long_about = r#"Validate a captured JSONL event stream.
+
+Reads each line of <file>, checks it is an event envelope with a known
+schema_version and event kind and a valid timestamp, and reports malformed
+lines with their line numbers. Blank lines are ignored.
+
+Examples:
+  sv events validate /tmp/sv.jsonl
+  sv events validate /tmp/sv.jsonl --json
+"#)]
+    Validate {
+        /// JSONL file to validate
+        file: std::path::PathBuf,
+    },
+}
+
+/// Hoist subcommands
+#[derive(Subcommand, Debug)]
+pub enum HoistCommands {
+    /// Mark a hoist conflict resolved and continue the replay
+    #[command(long_about = r#"Resolve a hoist conflict and continue.
+
+Check out the integration branch, apply the conflicting commit and fix it in
+the working tree, then run resolve: the working tree is staged and committed
+as that commit, and the commits skipped after it are replayed. If the replay
+hits another conflict, it is left in the working tree to fix and resolve next.
+A --squash hoist cannot be resolved this way; re-run it with --force.
+
+Examples:
+  git checkout sv/hoist/main && git cherry-pick -n 1a2b3c4d
+  # fix the conflicts
+  sv hoist resolve 1a2b3c4d
+  sv hoist resolve 1a2b3c4d --dest main --json
+"#)]
+    Resolve {
+        /// Conflicting commit (full or abbreviated id)
+        commit: String,
+
+        /// Destination ref of the hoist (default: the hoist whose integration branch is checked out)
+        #[arg(short, long)]
+        dest: Option<String>,
+    },
 }
 
 /// Workspace subcommands
@@ -813,12 +1496,22 @@ Examples:
 Examples:
   sv ws list
   sv ws list -s "agent*"
+  sv ws list --stale 7d
+  sv ws list --all
 "#
     )]
     List {
         /// Selector to filter workspaces
         #[arg(short, long)]
         selector: Option<String>,
+
+        /// Only workspaces with no commits on their branch and no bound-task activity within this window (e.g. 7d)
+        #[arg(long, value_name = "dur")]
+        stale: Option<String>,
+
+        /// Include archived workspaces
+        #[arg(long)]
+        all: bool,
     },
 
     /// Show detailed workspace info
@@ -847,6 +1540,41 @@ Examples:
         force: bool,
     },
 
+    /// Archive a workspace: remove its worktree, keep its branch and record
+    #[command(long_about = r#"Archive a finished workspace.
+
+Removes the worktree directory but keeps the branch and the registry record
+(marked archived), so history is preserved and the workspace can be restored
+later. Leases scoped to the workspace (--scope ws:<name>) are released.
+Archived workspaces are hidden from sv ws list unless --all.
+
+Examples:
+  sv ws archive agent1
+  sv ws restore agent1
+"#)]
+    Archive {
+        /// Workspace name
+        name: String,
+
+        /// Archive even with uncommitted changes (they are lost)
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Restore an archived workspace
+    #[command(long_about = r#"Recreate the worktree of an archived workspace.
+
+The worktree is added back at its recorded path, checked out on the kept
+branch.
+
+Examples:
+  sv ws restore agent1
+"#)]
+    Restore {
+        /// Workspace name
+        name: String,
+    },
+
     /// Remove merged workspaces
     #[command(long_about = r#"Remove merged workspaces in bulk.
 
@@ -902,6 +1630,9 @@ pub enum LeaseCommands {
 Examples:
   sv lease ls
   sv lease ls --actor alice
+  sv lease ls --path 'src/auth/**' --actor alice
+  sv lease ls --format jsonl | jq .pathspec
+  sv lease ls --by-actor
 "#)]
     Ls {
         /// Selector to filter leases
@@ -911,16 +1642,60 @@ Examples:
         /// Filter by actor
         #[arg(long)]
         actor: Option<String>,
+
+        /// Only leases whose pathspec intersects this path or glob
+        #[arg(long, value_name = "pathspec")]
+        path: Option<String>,
+
+        /// Output format: text, json, or jsonl (one lease per line)
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Summarize active leases per actor, by strength and soonest expiry
+        #[arg(long)]
+        by_actor: bool,
     },
 
     /// Show who holds leases on a path
-    #[command(long_about = r#"Show leases that overlap a path.
+    #[command(long_about = r#"Show leases that overlap a path or glob.
+
+Glob arguments list every lease intersecting the pattern, grouped by lease path.
 
 Examples:
   sv lease who src/auth/token.rs
+  sv lease who 'src/auth/**'
+  sv lease who src/auth/token.rs --at 2025-01-15T10:00:00Z
+
+--at reconstructs past holders by replaying the oplog; leases taken before
+sv recorded lease snapshots in the oplog are not visible.
 "#)]
     Who {
-        /// Path to check
+        /// Path or glob to check
+        path: String,
+
+        /// Show holders at this RFC3339 timestamp instead of now
+        #[arg(long)]
+        at: Option<String>,
+    },
+
+    /// Show the coordination history of a path
+    #[command(
+        long_about = r#"List every take, renew, keepalive, release, and break of
+leases on a path, oldest first, replayed from the oplog.
+
+Unlike `sv lease who`, which shows current holders, blame is an audit trail:
+who held the path, who acted (e.g. broke a lease), and the reasons given.
+Glob arguments include every lease intersecting the pattern.
+
+Examples:
+  sv lease blame src/auth/token.rs
+  sv lease blame 'src/auth/**' --json
+
+Leases taken before sv recorded lease snapshots in the oplog are not visible.
+"#
+    )]
+    Blame {
+        /// Path or glob to audit
         path: String,
     },
 
@@ -976,6 +1751,95 @@ Examples:
         #[arg(long, default_value = "1s")]
         poll: String,
     },
+
+    /// Export leases as JSONL
+    #[command(
+        long_about = r#"Export all lease records (active and history) as JSONL.
+
+Writes to stdout, or to a file with the global --output. Pair with
+`sv lease import` to back up leases or share them between clones.
+
+Examples:
+  sv lease export > leases.jsonl
+  sv lease export --output /tmp/leases.jsonl
+"#
+    )]
+    Export,
+
+    /// Import leases from a JSONL export
+    #[command(
+        long_about = r#"Merge leases from a `sv lease export` file by lease ID.
+
+New active leases are added. For an ID already present, a released/broken
+record wins over an active one, and otherwise the later expires_at wins.
+Expired leases are skipped. If the same ID is owned by different actors, the
+local lease is kept and a warning is printed. Active leases are checked like
+sv take: one that conflicts with another actor's local lease is skipped with a
+warning, and the import fails if it would push an actor past the lease quota.
+The import is recorded in the op log, so sv undo releases the added leases.
+Use "-" to read stdin.
+
+Examples:
+  sv lease import leases.jsonl
+  ssh host 'cd repo && sv lease export' | sv lease import -
+"#
+    )]
+    Import {
+        /// JSONL file from `sv lease export` ("-" for stdin)
+        file: std::path::PathBuf,
+    },
+
+    /// Check the lease log for problems
+    #[command(long_about = r#"Check the lease log for problems.
+
+Reports malformed lines (partial writes, manual edits; other lease commands
+ignore these with a warning and keep them when rewriting the log), duplicate lease IDs, active leases scoped to
+workspaces that are no longer registered, active leases held by actors sv has
+no record of (never recorded an operation, not tied to a workspace, not
+persisted with sv actor set), and active leases past their expiry.
+
+With --repair, malformed lines are moved to .git/sv/leases.malformed.jsonl,
+duplicate IDs keep only their newest record, expired leases are marked
+expired, and the log is rewritten. Leases orphaned by a workspace or actor are
+reported only; release or break them explicitly.
+
+Examples:
+  sv lease doctor
+  sv lease doctor --json
+  sv lease doctor --repair
+"#)]
+    Doctor {
+        /// Rewrite a clean log (drop malformed lines, dedupe, mark expired)
+        #[arg(long)]
+        repair: bool,
+    },
+
+    /// Keep the current actor's leases alive until interrupted
+    #[command(
+        long_about = r#"Renew the current actor's leases before they expire, until Ctrl-C.
+
+Each round renews active leases that would expire before the round after next.
+Interrupting stops the loop and leaves leases as they are.
+
+Examples:
+  sv lease keepalive
+  sv lease keepalive --interval 10m --renew-to 1h
+  sv lease keepalive --events /tmp/sv.events.jsonl
+"#
+    )]
+    Keepalive {
+        /// Time between renewal rounds
+        #[arg(long, default_value = "5m")]
+        interval: String,
+
+        /// TTL to renew leases to (defaults to leases.default_ttl)
+        #[arg(long, value_name = "ttl")]
+        renew_to: Option<String>,
+
+        /// Stop after this many rounds
+        #[arg(long, value_name = "n")]
+        count: Option<u64>,
+    },
 }
 
 /// Protect subcommands
@@ -1020,11 +1884,17 @@ Examples:
     /// Remove protected patterns from config
     #[command(long_about = r#"Remove protected patterns from .sv.toml.
 
+Each argument removes the configured pattern with the same text. An argument
+equal to no configured pattern is a glob instead, removing every configured
+pattern it matches (matched against the pattern strings, not file paths).
+Every removed pattern is listed.
+
 Examples:
   sv protect rm Cargo.lock
+  sv protect rm 'src/*'
 "#)]
     Rm {
-        /// Patterns to remove
+        /// Patterns (or globs over configured patterns) to remove
         #[arg(required = true)]
         patterns: Vec<String>,
 
@@ -1043,11 +1913,16 @@ pub enum TaskCommands {
 Examples:
   sv task new "Ship CLI help"
   sv task new "Ship CLI help" --priority P1
+  sv task new "Imported" --id sv-1042
 "#)]
     New {
         /// Task title
         title: String,
 
+        /// Use this ID (or suffix, prefixed with tasks.id_prefix) instead of generating one
+        #[arg(long)]
+        id: Option<String>,
+
         /// Initial status (defaults to tasks.default_status)
         #[arg(long)]
         status: Option<String>,
@@ -1067,18 +1942,20 @@ Examples:
 Examples:
   sv task list
   sv task list --status open
+  sv task list --status open --status in_progress
   sv task list --priority P2
   sv task list --epic sv-abc
   sv task list --project sv-proj
   sv task list --workspace agent1
   sv task list --updated-by alice --updated-since 2025-01-01T00:00:00Z
   sv task list --limit 20
+  sv task list --format tsv > tasks.tsv
 "#)]
     #[command(visible_alias = "ls")]
     List {
-        /// Filter by status
+        /// Filter by status (repeatable; matches any)
         #[arg(long)]
-        status: Option<String>,
+        status: Vec<String>,
 
         /// Filter by priority (P0-P4)
         #[arg(long)]
@@ -1111,6 +1988,10 @@ Examples:
         /// Limit number of tasks returned
         #[arg(long)]
         limit: Option<usize>,
+
+        /// Output format: text, json, or tsv (id, title, status, priority, epic, project, workspace, updated_at)
+        #[arg(long)]
+        format: Option<String>,
     },
 
     /// List ready tasks (open and unblocked)
@@ -1124,6 +2005,7 @@ Examples:
   sv task ready --workspace agent1
   sv task ready --updated-by alice --updated-since 2025-01-01T00:00:00Z
   sv task ready --limit 20
+  sv task ready --format tsv
 "#)]
     Ready {
         /// Filter by priority (P0-P4)
@@ -1157,6 +2039,10 @@ Examples:
         /// Limit number of tasks returned
         #[arg(long)]
         limit: Option<usize>,
+
+        /// Output format: text, json, or tsv (id, title, status, priority, epic, project, workspace, updated_at)
+        #[arg(long)]
+        format: Option<String>,
     },
 
     /// Count tasks
@@ -1165,21 +2051,25 @@ Examples:
 Examples:
   sv task count
   sv task count --status open
+  sv task count --status open --status in_progress
   sv task count --priority P2
   sv task count --ready
   sv task count --epic sv-abc
   sv task count --project sv-proj
   sv task count --workspace agent1
   sv task count --updated-by alice --updated-since 2025-01-01T00:00:00Z
+  sv task count --status blocked --fail-over 5
+
+--fail-over exits with code 3 after printing the count when it exceeds <n>.
 "#)]
     Count {
         /// Count ready tasks only (open and unblocked)
         #[arg(long)]
         ready: bool,
 
-        /// Filter by status (not allowed with --ready)
+        /// Filter by status (repeatable; matches any; not allowed with --ready)
         #[arg(long)]
-        status: Option<String>,
+        status: Vec<String>,
 
         /// Filter by priority (P0-P4)
         #[arg(long)]
@@ -1212,6 +2102,10 @@ Examples:
         /// Limit number of tasks considered (after filtering)
         #[arg(long)]
         limit: Option<usize>,
+
+        /// Exit nonzero when the count exceeds this number
+        #[arg(long, value_name = "n")]
+        fail_over: Option<usize>,
     },
 
     /// Show repo task/project/event stats
@@ -1223,15 +2117,46 @@ Examples:
 "#)]
     Stats,
 
+    /// Show task activity across all tasks
+    #[command(
+        long_about = r#"Show a chronological feed of task events across all tasks.
+
+Unlike `sv task show`, this covers every task; useful for standups.
+
+Examples:
+  sv task timeline --since 24h
+  sv task timeline --actor alice --limit 20
+  sv task timeline --since 2025-01-01T00:00:00Z --json
+"#
+    )]
+    Timeline {
+        /// Only events within this duration (e.g. 24h) or since an RFC3339 timestamp
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only events by this actor
+        #[arg(long)]
+        actor: Option<String>,
+
+        /// Maximum events to show (most recent)
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+
     /// Show task details
     #[command(long_about = r#"Show a task by ID.
 
 Examples:
   sv task show 01HZ...
+  sv task show 01HZ... --events-file /tmp/01HZ.events.jsonl
 "#)]
     Show {
         /// Task ID
         id: String,
+
+        /// Write the task's full event history (including relation events) as JSONL
+        #[arg(long, value_name = "path")]
+        events_file: Option<std::path::PathBuf>,
     },
 
     /// Start a task in the current workspace
@@ -1240,6 +2165,16 @@ Examples:
 Examples:
   sv task start 01HZ...
   sv task start 01HZ... --takeover
+  sv task start 01HZ... --lease src/auth/** --lease docs/auth.md
+  sv task start 01HZ... --create-workspace --base main
+
+--lease takes cooperative leases scoped to the current workspace, with an
+intent guessed from the task title. If a lease conflicts the task is not
+started; if the start fails the leases are released again.
+
+--create-workspace creates a new workspace named after the task (branch
+sv/ws/<task-id>) and starts the task there instead of in the current
+workspace. If any later step fails the workspace is removed again.
 "#)]
     Start {
         /// Task ID
@@ -1248,6 +2183,18 @@ Examples:
         /// Take over an in-progress task owned by another actor
         #[arg(long)]
         takeover: bool,
+
+        /// Also lease these paths for the task (repeatable)
+        #[arg(long, value_name = "path", num_args = 1..)]
+        lease: Vec<String>,
+
+        /// Create a new workspace for the task (sv/ws/<task-id>) and start it there
+        #[arg(long)]
+        create_workspace: bool,
+
+        /// Base ref for the created workspace (default: repo base)
+        #[arg(long, value_name = "ref", requires = "create_workspace")]
+        base: Option<String>,
     },
 
     /// Change task status
@@ -1303,6 +2250,12 @@ Examples:
 
 Examples:
   sv task close 01HZ...
+  sv task close 01HZ... --merge
+  sv task close 01HZ... --merge --into release
+
+--merge merges (or fast-forwards) the task's workspace branch into the
+workspace base after closing. If the merge would conflict, the conflicts are
+reported and nothing is committed.
 "#)]
     Close {
         /// Task ID
@@ -1311,6 +2264,14 @@ Examples:
         /// Closed status override
         #[arg(long)]
         status: Option<String>,
+
+        /// Merge the task's workspace branch into its base after closing
+        #[arg(long)]
+        merge: bool,
+
+        /// Branch to merge into (default: the workspace base)
+        #[arg(long, value_name = "base", requires = "merge")]
+        into: Option<String>,
     },
 
     /// Delete a task
@@ -1450,14 +2411,73 @@ Examples:
     /// Sync tracked + shared task logs and snapshots
     #[command(long_about = r#"Merge tracked and shared logs, rebuild snapshot.
 
+With --pull, fetch the current branch from the remote and merge its
+.tasks/tasks.jsonl by event id (union, never a textual merge). With --push,
+commit the tracked task log and snapshot (only those paths) and push the
+current branch. With both, the sync commit builds on the fetched tip (or
+merges it when the branches diverged) so the push fast-forwards; a push
+without --pull is rejected if the remote moved.
+
 Examples:
   sv task sync
+  sv task sync --pull
+  sv task sync --pull --push --remote origin
 "#)]
-    Sync,
+    Sync {
+        /// Commit the tracked task log + snapshot and push the current branch
+        #[arg(long)]
+        push: bool,
+
+        /// Fetch the current branch and merge remote task events by event id
+        #[arg(long)]
+        pull: bool,
+
+        /// Remote used by --push/--pull
+        #[arg(long, default_value = "origin")]
+        remote: String,
+    },
+
+    /// Git merge driver for .tasks/tasks.jsonl (union by event id)
+    #[command(long_about = r#"Git merge driver for the task event log.
+
+Invoked by git as `sv task merge-driver %O %A %B`. Unions the events of both
+sides by event id (events dropped on either side since the merge base stay
+dropped), writes the result in stable order to %A, and exits 0. Configure it
+with `sv task install-merge-driver`.
+
+Examples:
+  sv task merge-driver base.jsonl ours.jsonl theirs.jsonl
+"#)]
+    MergeDriver {
+        /// Merge base version (%O)
+        base: std::path::PathBuf,
+
+        /// Current branch version (%A); receives the merged result
+        ours: std::path::PathBuf,
+
+        /// Other branch version (%B)
+        theirs: std::path::PathBuf,
+    },
+
+    /// Configure git to merge .tasks/tasks.jsonl with sv
+    #[command(long_about = r#"Configure the task log merge driver.
+
+Sets merge.sv-tasks.driver in the repository git config and adds
+`.tasks/tasks.jsonl merge=sv-tasks` to .gitattributes (idempotent). Commit
+.gitattributes; each clone still needs to run this once for the git config.
+
+Examples:
+  sv task install-merge-driver
+"#)]
+    InstallMergeDriver,
 
     /// Validate task logs for replay hazards
     #[command(long_about = r#"Validate task logs for replay hazards.
 
+Reports duplicate task_created events, malformed log lines, and epic cycles
+that leave member tasks without a resolvable project (with the epic link to
+clear).
+
 Examples:
   sv task doctor
   sv task doctor --json
@@ -1635,6 +2655,10 @@ pub enum ProjectEntityCommands {
         /// Include archived projects
         #[arg(long)]
         all: bool,
+
+        /// Show closed/open member task counts per project
+        #[arg(long)]
+        with_progress: bool,
     },
 
     /// Show a project
@@ -1658,9 +2682,22 @@ pub enum ProjectEntityCommands {
     },
 
     /// Archive a project
+    #[command(long_about = r#"Archive a project.
+
+Refuses while the project still has non-closed member tasks and lists them.
+With --force, those tasks have their project cleared before archiving.
+
+Examples:
+  sv project archive 01HZ...
+  sv project archive 01HZ... --force
+"#)]
     Archive {
         /// Project ID
         id: String,
+
+        /// Clear the project from non-closed member tasks and archive anyway
+        #[arg(long)]
+        force: bool,
     },
 
     /// Unarchive a project
@@ -1719,6 +2756,15 @@ pub enum OpCommands {
 Examples:
   sv op log --limit 20
   sv op log --actor alice
+  sv op log --touching 'src/auth/**' --actor alice
+  sv op log --graph --operation commit
+
+--touching only matches entries that recorded their affected paths; older
+entries are skipped and counted in a note.
+
+--graph lists each operation with the commits it created (commit, onto,
+hoist, task close --merge), so the log can be matched against git history.
+--json always includes a commits array per entry.
 "#)]
     Log {
         /// Maximum entries to show
@@ -1729,10 +2775,14 @@ Examples:
         #[arg(long)]
         actor: Option<String>,
 
-        /// Filter by operation type (e.g., "init", "ws", "commit")
+        /// Filter by operation kind (e.g., "take", "ws_new") or family ("ws")
         #[arg(long)]
         operation: Option<String>,
 
+        /// Only show entries whose recorded paths overlap this pathspec
+        #[arg(long)]
+        touching: Option<String>,
+
         /// Only show entries on/after this RFC3339 timestamp
         #[arg(long)]
         since: Option<String>,
@@ -1740,6 +2790,10 @@ Examples:
         /// Only show entries on/before this RFC3339 timestamp
         #[arg(long)]
         until: Option<String>,
+
+        /// Show the commits each operation created
+        #[arg(long)]
+        graph: bool,
     },
 }
 
@@ -1766,11 +2820,36 @@ Examples:
     Show,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Show repo or effective config
+    #[command(
+        long_about = r#"Show the config from .sv.toml, or the effective config.
+
+--effective applies the worktree-local .sv.local.toml overlay and reports
+which file each key came from.
+
+Examples:
+  sv config show
+  sv config show --effective --json
+"#
+    )]
+    Show {
+        /// Apply the .sv.local.toml overlay and report key sources
+        #[arg(long)]
+        effective: bool,
+    },
+}
+
 /// Options for risk command
 pub struct RiskOptions {
     pub selector: Option<String>,
     pub base: Option<String>,
+    pub since: Option<String>,
     pub simulate: bool,
+    pub mine: bool,
+    pub fail_on: Option<String>,
+    pub actor: Option<String>,
     pub repo: Option<std::path::PathBuf>,
     pub json: bool,
     pub quiet: bool,
@@ -1781,61 +2860,183 @@ fn run_risk(opts: RiskOptions) -> Result<()> {
     use crate::config::Config;
     use crate::git;
     use crate::risk;
+    use std::collections::HashSet;
 
+    let fail_on = opts
+        .fail_on
+        .as_deref()
+        .map(str::parse::<risk::RiskSeverity>)
+        .transpose()?;
     let repo = git::open_repo(opts.repo.as_deref())?;
     let workdir = git::workdir(&repo)?;
     let config = Config::load_from_repo(&workdir);
 
-    // Determine base ref
-    let base_ref = opts.base.unwrap_or_else(|| config.base.clone());
+    // Determine base ref: the configured base for the checked-out branch
+    let base_ref = opts.base.unwrap_or_else(|| {
+        let branch = git::head_info(&repo).ok().and_then(|info| info.shorthand);
+        match branch {
+            Some(branch) => config.base_for(&branch).to_string(),
+            None => config.base.clone(),
+        }
+    });
+
+    // With --mine, the full report is computed and then narrowed to the
+    // actor's workspaces and the paths the actor holds leases on.
+    let mine = if opts.mine {
+        let actor = crate::actor::resolve_actor_optional(Some(&workdir), opts.actor.as_deref())?
+            .ok_or_else(|| {
+                Error::InvalidArgument(
+                    "--mine requires an actor (use --actor, SV_ACTOR, or sv actor set)".to_string(),
+                )
+            })?;
+        let storage =
+            crate::storage::Storage::new(workdir.clone(), git::common_dir(&repo), workdir.clone());
+        let workspaces: HashSet<String> = storage
+            .read_workspaces()?
+            .workspaces
+            .into_iter()
+            .filter(|entry| entry.actor.as_deref() == Some(actor.as_str()))
+            .map(|entry| entry.name)
+            .collect();
+        let leases: Vec<crate::lease::Lease> = storage
+            .load_leases()?
+            .by_actor(&actor)
+            .filter(|lease| lease.is_active())
+            .cloned()
+            .collect();
+        Some((workspaces, leases))
+    } else {
+        None
+    };
+    let leased = |leases: &[crate::lease::Lease], path: &str| {
+        leases.iter().any(|lease| lease.matches_path(path))
+    };
 
     if opts.simulate {
         // Run virtual merge simulation
-        let report = risk::simulate_conflicts(&repo, &base_ref)?;
+        let mut progress = crate::output::Progress::new(crate::output::OutputOptions {
+            json: opts.json,
+            quiet: opts.quiet,
+        });
+        let interrupted = interrupt_flag();
+        let mut report = risk::simulate_conflicts_with_progress(
+            &repo,
+            &base_ref,
+            &mut |n, total, pair| {
+                progress.update(format_args!("simulating merge {n} of {total} ({pair})"))
+            },
+            &interrupted,
+        )?;
+        // Ctrl-C from here on terminates as usual
+        drop(interrupted);
+        progress.finish();
+        if let Some((workspaces, leases)) = &mine {
+            report.workspace_pairs.retain_mut(|pair| {
+                if workspaces.contains(&pair.workspace_a) || workspaces.contains(&pair.workspace_b)
+                {
+                    return true;
+                }
+                pair.conflicts
+                    .retain(|conflict| leased(leases, &conflict.path));
+                !pair.conflicts.is_empty()
+            });
+        }
 
         if opts.json {
-            println!("{}", serde_json::to_string_pretty(&report)?);
+            outln!("{}", serde_json::to_string_pretty(&report)?);
         } else if !opts.quiet {
             print_simulation_report(&report);
         }
+        if report.cancelled {
+            return Err(Error::Interrupted(format!(
+                "simulated {} workspace pair(s) before Ctrl-C",
+                report.workspace_pairs.len()
+            )));
+        }
     } else {
         // Run basic overlap detection
-        let report = risk::compute_risk(&repo, &base_ref)?;
+        let mut progress = crate::output::Progress::new(crate::output::OutputOptions {
+            json: opts.json,
+            quiet: opts.quiet,
+        });
+        let mut report = risk::compute_risk_with_progress(
+            &repo,
+            &base_ref,
+            opts.since.as_deref(),
+            &config.risk,
+            &mut |n, total, workspace| {
+                progress.update(format_args!(
+                    "analyzing workspace {n} of {total} ({workspace})"
+                ))
+            },
+        )?;
+        progress.finish();
+        if let Some((workspaces, leases)) = &mine {
+            report.overlaps.retain(|overlap| {
+                overlap
+                    .workspaces
+                    .iter()
+                    .any(|name| workspaces.contains(name))
+                    || leased(leases, &overlap.path)
+            });
+        }
 
         if opts.json {
-            println!("{}", serde_json::to_string_pretty(&report)?);
+            outln!("{}", serde_json::to_string_pretty(&report)?);
         } else if !opts.quiet {
             print_risk_report(&report);
         }
+
+        if let Some(threshold) = fail_on {
+            let failing = report
+                .overlaps
+                .iter()
+                .filter(|overlap| overlap.severity >= threshold)
+                .count();
+            if failing > 0 {
+                return Err(Error::GateFailed(format!(
+                    "{failing} overlap(s) at or above {threshold} severity"
+                )));
+            }
+        }
     }
 
     Ok(())
 }
 
 fn print_risk_report(report: &crate::risk::RiskReport) {
-    println!("Risk Report (base: {})", report.base_ref);
-    println!();
+    match &report.since_ref {
+        Some(since) => outln!("Risk Report (base: {}, since: {since})", report.base_ref),
+        None => outln!("Risk Report (base: {})", report.base_ref),
+    }
+    outln!();
 
     if report.workspaces.is_empty() {
-        println!("No workspaces registered.");
+        outln!("No workspaces registered.");
         return;
     }
 
-    println!("Workspaces analyzed: {}", report.workspaces.len());
+    outln!("Workspaces analyzed: {}", report.workspaces.len());
     for ws in &report.workspaces {
-        println!(
+        outln!(
             "  {} ({}) - {} files touched",
             ws.name,
             ws.branch,
             ws.files.len()
         );
     }
-    println!();
+    outln!();
 
+    if report.suppressed_overlaps > 0 {
+        outln!(
+            "Suppressed overlaps (risk.ignore): {}",
+            report.suppressed_overlaps
+        );
+    }
     if report.overlaps.is_empty() {
-        println!("No overlapping files detected.");
+        outln!("No overlapping files detected.");
     } else {
-        println!("Overlapping files: {}", report.overlaps.len());
+        outln!("Overlapping files: {}", report.overlaps.len());
         for overlap in &report.overlaps {
             let severity_str = match overlap.severity {
                 crate::risk::RiskSeverity::Low => "LOW",
@@ -1843,21 +3044,34 @@ fn print_risk_report(report: &crate::risk::RiskReport) {
                 crate::risk::RiskSeverity::High => "HIGH",
                 crate::risk::RiskSeverity::Critical => "CRITICAL",
             };
-            println!(
+            outln!(
                 "  [{}] {} (workspaces: {})",
                 severity_str,
                 overlap.path,
                 overlap.workspaces.join(", ")
             );
+            for rename in &overlap.renames {
+                outln!(
+                    "    renamed in {}: {} -> {}",
+                    rename.workspace,
+                    rename.from,
+                    rename.to
+                );
+            }
+            if let Some(coordination) = &overlap.coordination {
+                outln!("    {}", coordination);
+            }
             if !overlap.suggestions.is_empty() {
                 for suggestion in &overlap.suggestions {
                     if let Some(command) = &suggestion.command {
-                        println!(
+                        outln!(
                             "    - {}: {} ({})",
-                            suggestion.action, suggestion.reason, command
+                            suggestion.action,
+                            suggestion.reason,
+                            command
                         );
                     } else {
-                        println!("    - {}: {}", suggestion.action, suggestion.reason);
+                        outln!("    - {}: {}", suggestion.action, suggestion.reason);
                     }
                 }
             }
@@ -1865,52 +3079,92 @@ fn print_risk_report(report: &crate::risk::RiskReport) {
     }
 }
 
+/// Set a flag on Ctrl-C instead of terminating, so loops can stop cleanly.
+///
+/// Only the first Ctrl-C is caught: a second one, or any once every clone of
+/// the flag has been dropped, exits with status 130 like an unhandled SIGINT.
+fn interrupt_flag() -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    let handler_flag = Arc::clone(&flag);
+    std::thread::spawn(move || {
+        let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        else {
+            return;
+        };
+        runtime.block_on(async {
+            while tokio::signal::ctrl_c().await.is_ok() {
+                if Arc::strong_count(&handler_flag) == 1
+                    || handler_flag.swap(true, Ordering::SeqCst)
+                {
+                    std::process::exit(crate::error::exit_codes::INTERRUPTED);
+                }
+            }
+        });
+    });
+    flag
+}
+
 fn print_simulation_report(report: &crate::risk::SimulationReport) {
-    println!("Merge Simulation Report (base: {})", report.base_ref);
-    println!();
+    outln!("Merge Simulation Report (base: {})", report.base_ref);
+    outln!();
+
+    if report.cancelled {
+        outln!("Interrupted: showing partial results.");
+        outln!();
+    }
 
     if report.workspace_pairs.is_empty() {
-        println!("No workspace pairs to simulate.");
+        if !report.cancelled {
+            outln!("No workspace pairs to simulate.");
+        }
         return;
     }
 
-    println!("Workspace pairs analyzed: {}", report.workspace_pairs.len());
-    println!();
+    outln!("Workspace pairs analyzed: {}", report.workspace_pairs.len());
+    outln!();
 
     let mut has_conflicts = false;
     for pair in &report.workspace_pairs {
         if pair.conflicts.is_empty() {
-            println!(
+            outln!(
                 "  {} vs {} - no conflicts",
-                pair.workspace_a, pair.workspace_b
+                pair.workspace_a,
+                pair.workspace_b
             );
         } else {
             has_conflicts = true;
-            println!(
+            outln!(
                 "  {} vs {} - {} conflict(s):",
                 pair.workspace_a,
                 pair.workspace_b,
                 pair.conflicts.len()
             );
             for conflict in &pair.conflicts {
+                // Keep the established lowercase kind names (addadd, binary, ...)
                 let kind_str = format!("{:?}", conflict.kind).to_lowercase();
-                println!("    [{}] {}", kind_str, conflict.path);
+                outln!("    [{}] {}", kind_str, conflict.path);
             }
         }
     }
 
-    if !has_conflicts {
-        println!();
-        println!("All workspace pairs can merge cleanly.");
+    if !has_conflicts && !report.cancelled {
+        outln!();
+        outln!("All workspace pairs can merge cleanly.");
     }
 }
 
 /// Options for hoist command
 pub struct HoistOptions {
     pub selector: String,
+    pub legacy_selector: bool,
     pub dest: Option<String>,
     pub strategy: String,
     pub order: String,
+    pub squash: bool,
+    pub name: Option<String>,
+    pub force: bool,
     pub dry_run: bool,
     pub continue_on_conflict: bool,
     pub no_propagate_conflicts: bool,
@@ -1986,6 +3240,8 @@ pub struct HoistOutput {
     pub status: String,
     pub applied: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub squash: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub continue_on_conflict: Option<bool>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub task_warnings: Vec<HoistTaskWarning>,
@@ -2012,60 +3268,103 @@ pub struct HoistTaskWarning {
     pub workspace: Option<String>,
 }
 
+/// Resolve a workspace selector for hoist and `sv ws list/clean`.
+///
+/// Selectors use the full selector grammar. Legacy matching (`prefix*`,
+/// `actor:<name>`, exact names) is used only when `legacy` is set or the
+/// selector is an obvious legacy form; otherwise a parse error is reported
+/// with its location instead of silently falling back.
 fn resolve_hoist_workspaces(
     repo: &git2::Repository,
     registry: &crate::storage::WorkspacesRegistry,
     selector: &str,
+    legacy: bool,
 ) -> Result<Vec<crate::storage::WorkspaceEntry>> {
+    use crate::selector::{EntityKind, Predicate, SelectorContext, SelectorItem};
+    use std::collections::HashMap;
+
     if selector == "all" {
         return Ok(registry.workspaces.clone());
     }
 
-    let parsed = crate::selector::parse_selector(selector);
-    if let Ok(expr) = parsed {
-        use crate::selector::{EntityKind, Predicate, SelectorContext, SelectorItem};
-        use std::collections::HashMap;
-
-        let mut workspace_items = Vec::with_capacity(registry.workspaces.len());
-        let mut workspace_lookup = HashMap::new();
-        for entry in &registry.workspaces {
-            workspace_items.push(SelectorItem::new(entry.name.clone(), entry.name.clone()));
-            workspace_lookup.insert(entry.name.clone(), entry);
+    let expr = match crate::selector::parse_selector(selector) {
+        Ok(expr) => expr,
+        Err(_) if legacy || is_legacy_selector(selector) => {
+            return Ok(legacy_match_workspaces(registry, selector));
+        }
+        Err(err) => {
+            return Err(Error::InvalidArgument(format!(
+                "invalid selector: {}\n  {}\n  {}^\n(use --legacy-selector for prefix/actor matching)",
+                err,
+                selector,
+                " ".repeat(err.position)
+            )));
         }
+    };
 
-        let ctx = SelectorContext::new(&workspace_items, &[], &[], |kind, item, predicate| {
-            if kind != EntityKind::Workspace {
-                return false;
-            }
-            let entry = match workspace_lookup.get(&item.id) {
-                Some(entry) => *entry,
-                None => return false,
-            };
-            match predicate {
-                Predicate::Active => entry.path.exists(),
-                Predicate::Stale => !entry.path.exists(),
-                Predicate::Blocked => false,
-                Predicate::Ahead(ref_spec) => workspace_is_ahead(repo, entry, ref_spec),
-                Predicate::Touching(pathspec) => workspace_touches(repo, entry, pathspec),
-                Predicate::Overlaps(_) => false,
-                Predicate::NameMatches(_) => false,
-            }
-        });
+    let mut workspace_items = Vec::with_capacity(registry.workspaces.len());
+    let mut workspace_lookup = HashMap::new();
+    for entry in &registry.workspaces {
+        workspace_items.push(SelectorItem::new(entry.name.clone(), entry.name.clone()));
+        workspace_lookup.insert(entry.name.clone(), entry);
+    }
 
-        let matches = crate::selector::evaluate_selector(&expr, &ctx);
-        let mut selected = Vec::new();
-        for hit in matches {
-            if hit.kind != crate::selector::EntityKind::Workspace {
-                continue;
-            }
-            if let Some(entry) = workspace_lookup.get(&hit.item.id) {
-                selected.push((*entry).clone());
-            }
+    let ctx = SelectorContext::new(&workspace_items, &[], &[], |kind, item, predicate| {
+        if kind != EntityKind::Workspace {
+            return false;
+        }
+        let entry = match workspace_lookup.get(&item.id) {
+            Some(entry) => *entry,
+            None => return false,
+        };
+        match predicate {
+            Predicate::Active => entry.path.exists(),
+            Predicate::Stale => !entry.path.exists(),
+            Predicate::Blocked => false,
+            Predicate::Ahead(ref_spec) => workspace_is_ahead(repo, entry, ref_spec),
+            Predicate::Touching(pathspec) => workspace_touches(repo, entry, pathspec),
+            Predicate::Overlaps(_) => false,
+            Predicate::NameMatches(_) => false,
+            Predicate::Held(_) | Predicate::Expiring(_) => false,
+        }
+    });
+
+    let matches = crate::selector::evaluate_selector(&expr, &ctx);
+    let mut selected = Vec::new();
+    for hit in matches {
+        if hit.kind != crate::selector::EntityKind::Workspace {
+            continue;
+        }
+        if let Some(entry) = workspace_lookup.get(&hit.item.id) {
+            selected.push((*entry).clone());
         }
-        return Ok(selected);
     }
+    Ok(selected)
+}
+
+/// Whether a recorded hoist still owns its integration branch: it has not
+/// completed, or it has conflicts waiting for `sv hoist resolve`.
+fn hoist_in_use(state: &crate::storage::HoistState) -> bool {
+    use crate::storage::{HoistCommitStatus, HoistStatus};
+
+    state.status != HoistStatus::Completed
+        || state
+            .commits
+            .iter()
+            .any(|commit| commit.status == HoistCommitStatus::Conflict)
+}
 
-    Ok(legacy_match_workspaces(registry, selector))
+/// Whether a selector that failed to parse is plainly meant as a legacy one:
+/// `actor:<name>`, a `<prefix>*` glob or a bare workspace name.
+fn is_legacy_selector(selector: &str) -> bool {
+    let selector = selector.trim();
+    if selector.starts_with("actor:") {
+        return true;
+    }
+    !selector.is_empty()
+        && !selector
+            .chars()
+            .any(|ch| ch.is_whitespace() || matches!(ch, '(' | ')' | '&' | '|' | '~' | '"'))
 }
 
 fn legacy_match_workspaces(
@@ -2127,7 +3426,7 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
     use crate::actor;
     use crate::config::Config;
     use crate::git;
-    use crate::storage::{HoistCommit, HoistState, HoistStatus, Storage};
+    use crate::storage::{HoistCommit, HoistCommitStatus, HoistState, HoistStatus, Storage};
     use crate::task::{TaskEvent, TaskEventType, TaskStore};
     use chrono::Utc;
     use uuid::Uuid;
@@ -2135,6 +3434,11 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
     // Parse and validate strategy
     let strategy: HoistStrategy = opts.strategy.parse()?;
     let order: HoistOrder = opts.order.parse()?;
+    if opts.squash && strategy == HoistStrategy::Merge {
+        return Err(crate::error::Error::InvalidArgument(
+            "--squash requires --strategy stack or rebase".to_string(),
+        ));
+    }
 
     // Open repository
     let repo = git::open_repo(opts.repo.as_deref())?;
@@ -2161,7 +3465,8 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
     // Get workspaces matching selector
     // For now, we support simple selectors: "all" or prefix matching
     let registry = storage.read_workspaces()?;
-    let matching_workspaces = resolve_hoist_workspaces(&repo, &registry, &opts.selector)?;
+    let matching_workspaces =
+        resolve_hoist_workspaces(&repo, &registry, &opts.selector, opts.legacy_selector)?;
 
     if matching_workspaces.is_empty() {
         return Err(crate::error::Error::InvalidArgument(format!(
@@ -2235,15 +3540,19 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
                     .cloned()
                     .unwrap_or_else(|| "closed".to_string());
                 task_store.validate_status(&close_status)?;
-                for task in active_tasks {
-                    let mut event = TaskEvent::new(TaskEventType::TaskClosed, task.id.clone());
-                    event.actor = actor.clone();
-                    event.status = Some(close_status.clone());
-                    event.workspace_id = task.workspace_id.clone();
-                    event.workspace = task.workspace.clone();
-                    event.branch = task.branch.clone();
-                    task_store.append_event(event)?;
-                }
+                let events: Vec<TaskEvent> = active_tasks
+                    .into_iter()
+                    .map(|task| {
+                        let mut event = TaskEvent::new(TaskEventType::TaskClosed, task.id);
+                        event.actor = actor.clone();
+                        event.status = Some(close_status.clone());
+                        event.workspace_id = task.workspace_id;
+                        event.workspace = task.workspace;
+                        event.branch = task.branch;
+                        event
+                    })
+                    .collect();
+                task_store.append_events(&events)?;
             }
         }
     }
@@ -2255,16 +3564,16 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
             } else {
                 "Closed"
             };
-            println!("{verb} {} task(s):", task_warnings.len());
+            outln!("{verb} {} task(s):", task_warnings.len());
         } else {
-            println!("Active tasks for selected workspaces:");
+            outln!("Active tasks for selected workspaces:");
         }
         for task in &task_warnings {
             let workspace = task.workspace.as_deref().unwrap_or("unknown");
-            println!("  - {} ({}, ws: {})", task.task_id, task.status, workspace);
+            outln!("  - {} ({}, ws: {})", task.task_id, task.status, workspace);
         }
         if !opts.close_tasks {
-            println!("  hint: sv task close <id> or sv hoist --close-tasks");
+            outln!("  hint: sv task close <id> or sv hoist --close-tasks");
         }
     }
 
@@ -2291,7 +3600,65 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
 
     // Generate hoist ID and integration branch name
     let hoist_id = Uuid::new_v4().to_string();
-    let integration_ref = format!("sv/hoist/{}", dest);
+    let integration_ref = match opts.name.as_deref() {
+        Some(name) => {
+            if !git2::Branch::name_is_valid(name)? {
+                return Err(crate::error::Error::InvalidArgument(format!(
+                    "invalid integration branch name '{}'",
+                    name
+                )));
+            }
+            if name == dest.strip_prefix("refs/heads/").unwrap_or(&dest) {
+                return Err(crate::error::Error::InvalidArgument(format!(
+                    "integration branch '{}' cannot be the destination itself",
+                    name
+                )));
+            }
+            name.to_string()
+        }
+        None => format!("sv/hoist/{}", dest),
+    };
+
+    // The integration branch is reset below; refuse to clobber one that a
+    // hoist in progress (or awaiting `sv hoist resolve`) still owns.
+    if !opts.force {
+        let states = storage.list_hoist_states()?;
+        if let Some(existing) = states
+            .iter()
+            .find(|state| state.integration_ref == integration_ref && hoist_in_use(state))
+        {
+            let unresolved = existing
+                .commits
+                .iter()
+                .filter(|commit| commit.status == HoistCommitStatus::Conflict)
+                .count();
+            let detail = if existing.status == HoistStatus::InProgress && unresolved == 0 {
+                format!("in progress since {}", existing.started_at.to_rfc3339())
+            } else {
+                format!(
+                    "{} unresolved conflict(s) since {}; continue with sv hoist resolve",
+                    unresolved,
+                    existing.updated_at.to_rfc3339()
+                )
+            };
+            return Err(crate::error::Error::OperationFailed(format!(
+                "integration branch {} is in use by hoist {} ({}); use --name <branch> for a separate integration branch or --force to reset it",
+                integration_ref, existing.hoist_id, detail
+            )));
+        }
+        let known = states
+            .iter()
+            .any(|state| state.integration_ref == integration_ref);
+        if opts.name.is_some()
+            && !known
+            && crate::refs::find_refname(&repo, &integration_ref).is_some()
+        {
+            return Err(crate::error::Error::OperationFailed(format!(
+                "branch {} already exists and is not a hoist integration branch; use --force to reset it",
+                integration_ref
+            )));
+        }
+    }
 
     if opts.dry_run {
         // Dry run output
@@ -2315,6 +3682,7 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
             workspaces: matching_workspaces.iter().map(|w| w.name.clone()).collect(),
             status: "dry_run".to_string(),
             applied: false,
+            squash: opts.squash.then_some(true),
             continue_on_conflict: if opts.continue_on_conflict {
                 Some(true)
             } else {
@@ -2326,28 +3694,31 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
         };
 
         if opts.json {
-            println!("{}", serde_json::to_string_pretty(&output)?);
+            outln!("{}", serde_json::to_string_pretty(&output)?);
         } else if !opts.quiet {
-            println!("Hoist (dry run)");
-            println!("  ID: {}", hoist_id);
-            println!("  Dest: {} -> {}", dest, integration_ref);
-            println!("  Strategy: {:?}", strategy);
-            println!("  Order: {:?}", order);
-            println!("  Workspaces: {}", matching_workspaces.len());
+            outln!("Hoist (dry run)");
+            outln!("  ID: {}", hoist_id);
+            outln!("  Dest: {} -> {}", dest, integration_ref);
+            outln!("  Strategy: {:?}", strategy);
+            outln!("  Order: {:?}", order);
+            if opts.squash {
+                outln!("  Squash: one commit per workspace");
+            }
+            outln!("  Workspaces: {}", matching_workspaces.len());
             for ws in &matching_workspaces {
-                println!("    - {} ({})", ws.name, ws.branch);
+                outln!("    - {} ({})", ws.name, ws.branch);
             }
             if let Some(cleanup) = &output.workspace_cleanup {
-                println!();
-                println!("Workspace cleanup (dry run)");
+                outln!();
+                outln!("Workspace cleanup (dry run)");
                 if !cleanup.removed.is_empty() {
-                    println!("  Would remove: {}", cleanup.removed.join(", "));
+                    outln!("  Would remove: {}", cleanup.removed.join(", "));
                 }
                 if !cleanup.skipped.is_empty() {
-                    println!("  Skipped: {}", cleanup.skipped.len());
+                    outln!("  Skipped: {}", cleanup.skipped.len());
                 }
                 if !cleanup.failed.is_empty() {
-                    println!("  Failed: {}", cleanup.failed.len());
+                    outln!("  Failed: {}", cleanup.failed.len());
                 }
             }
         }
@@ -2358,21 +3729,31 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
     // Create or reset integration branch to dest ref
     let dest_commit = repo.revparse_single(&dest)?.peel_to_commit()?;
 
-    // Check if integration branch exists
-    let branch_exists = repo
-        .find_branch(&integration_ref, git2::BranchType::Local)
-        .is_ok();
-
-    if branch_exists {
-        // Reset existing branch to dest
-        let mut branch = repo.find_branch(&integration_ref, git2::BranchType::Local)?;
-        branch
-            .get_mut()
-            .set_target(dest_commit.id(), &format!("sv hoist: reset to {}", dest))?;
-    } else {
-        // Create new branch at dest
-        repo.branch(&integration_ref, &dest_commit, false)?;
-    }
+    // Reset the integration branch to dest, creating it under the configured
+    // namespace if it does not exist yet
+    let integration_refname = crate::refs::find_refname(&repo, &integration_ref)
+        .unwrap_or_else(|| crate::refs::managed_refname(&config.refs.namespace, &integration_ref));
+    repo.reference(
+        &integration_refname,
+        dest_commit.id(),
+        true,
+        &format!("sv hoist: reset to {}", dest),
+    )?;
+
+    // Claim the integration branch before replaying so a concurrent hoist
+    // sees it as in use.
+    let started_at = Utc::now();
+    let claim = HoistState {
+        hoist_id: hoist_id.clone(),
+        dest_ref: dest.clone(),
+        integration_ref: integration_ref.clone(),
+        status: HoistStatus::InProgress,
+        started_at,
+        updated_at: started_at,
+        commits: hoist_commits.clone(),
+        squash: opts.squash,
+    };
+    storage.write_hoist_state(&claim)?;
 
     // Extract commit OIDs for replay
     let commit_oids: Vec<git2::Oid> = candidates.iter().map(|c| c.oid).collect();
@@ -2384,8 +3765,31 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
         continue_on_conflict: opts.continue_on_conflict,
         propagate_conflicts,
     };
-    let replay_outcome =
-        crate::hoist::replay_commits(&repo, &integration_ref, &commit_oids, &replay_options)?;
+    let replayed = if opts.squash {
+        crate::hoist::squash_commits(&repo, &integration_ref, &candidates, &replay_options)
+    } else {
+        let mut progress = crate::output::Progress::new(crate::output::OutputOptions {
+            json: opts.json,
+            quiet: opts.quiet,
+        });
+        crate::hoist::replay_commits_with_progress(
+            &repo,
+            &integration_ref,
+            &commit_oids,
+            &replay_options,
+            &mut |n, total, summary| {
+                progress.update(format_args!("replaying commit {n} of {total}: {summary}"))
+            },
+        )
+    };
+    let replay_outcome = match replayed {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            // Release the claim so the branch is not reported as in use
+            let _ = storage.clear_hoist_state(claim.storage_key());
+            return Err(err);
+        }
+    };
 
     // Build final hoist commits from replay outcome
     let final_commits: Vec<HoistCommit> = replay_outcome
@@ -2448,9 +3852,10 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
         dest_ref: dest.clone(),
         integration_ref: integration_ref.clone(),
         status: final_status.clone(),
-        started_at: now,
+        started_at,
         updated_at: now,
         commits: final_commits,
+        squash: opts.squash,
     };
     storage.write_hoist_state(&state)?;
 
@@ -2504,6 +3909,51 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
         }
     }
 
+    // Record the hoist with the commits it created on the integration branch
+    let mut record = crate::oplog::OpRecord::new(
+        format!("sv hoist {} --dest {}", opts.selector, dest),
+        actor.clone(),
+    );
+    record.affected_refs.push(integration_ref.clone());
+    if applied {
+        record.affected_refs.push(dest.clone());
+    }
+    record.affected_workspaces = matching_workspaces.iter().map(|w| w.name.clone()).collect();
+    record.commits = replay_outcome
+        .entries
+        .iter()
+        .filter_map(|entry| entry.applied_id.map(|oid| oid.to_string()))
+        .collect();
+    let mut hoisted_paths = std::collections::BTreeSet::new();
+    for oid in replay_outcome
+        .entries
+        .iter()
+        .filter_map(|entry| entry.applied_id)
+    {
+        let Ok(commit) = repo.find_commit(oid) else {
+            continue;
+        };
+        let (Ok(tree), Ok(parent_tree)) = (
+            commit.tree(),
+            commit.parent(0).and_then(|parent| parent.tree()),
+        ) else {
+            continue;
+        };
+        if let Ok(changes) = git::diff_trees(&repo, &parent_tree, &tree) {
+            hoisted_paths.extend(
+                git::changed_paths(&changes)
+                    .iter()
+                    .map(|path| path.to_string_lossy().replace('\\', "/")),
+            );
+        }
+    }
+    record.affected_paths = hoisted_paths.into_iter().collect();
+    if replay_summary.conflicts > 0 {
+        record.outcome =
+            crate::oplog::OpOutcome::failed(format!("{} conflict(s)", replay_summary.conflicts));
+    }
+    let _ = crate::oplog::OpLog::for_storage(&storage).append(&record);
+
     // Build conflict output
     let conflict_output: Vec<HoistConflictSummary> = replay_outcome
         .conflicts
@@ -2580,6 +4030,7 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
         workspaces: matching_workspaces.iter().map(|w| w.name.clone()).collect(),
         status: status_str.to_string(),
         applied,
+        squash: opts.squash.then_some(true),
         continue_on_conflict: if opts.continue_on_conflict {
             Some(true)
         } else {
@@ -2591,108 +4042,114 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
     };
 
     if opts.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
+        outln!("{}", serde_json::to_string_pretty(&output)?);
     } else if !opts.quiet {
-        println!("Hoist complete");
-        println!("  ID: {}", hoist_id);
-        println!("  Integration branch: {}", integration_ref);
-        println!("  Base: {} ({})", dest, &dest_commit.id().to_string()[..8]);
-        println!("  Strategy: {:?}", strategy);
-        println!("  Order: {:?}", order);
+        outln!("Hoist complete");
+        outln!("  ID: {}", hoist_id);
+        outln!("  Integration branch: {}", integration_ref);
+        outln!("  Base: {} ({})", dest, &dest_commit.id().to_string()[..8]);
+        outln!("  Strategy: {:?}", strategy);
+        outln!("  Order: {:?}", order);
+        if opts.squash {
+            outln!("  Squash: one commit per workspace");
+        }
         if opts.continue_on_conflict {
-            println!("  Continue on conflict: yes");
+            outln!("  Continue on conflict: yes");
         }
-        println!("  Workspaces: {}", matching_workspaces.len());
+        outln!("  Workspaces: {}", matching_workspaces.len());
         for ws in &matching_workspaces {
-            println!("    - {} ({})", ws.name, ws.branch);
+            outln!("    - {} ({})", ws.name, ws.branch);
         }
-        println!();
-        println!("Replay summary:");
-        println!("  Applied: {}", replay_summary.applied);
+        outln!();
+        outln!("Replay summary:");
+        outln!("  Applied: {}", replay_summary.applied);
         if replay_summary.in_conflict > 0 {
-            println!(
+            outln!(
                 "  In-conflict: {} (committed with markers)",
                 replay_summary.in_conflict
             );
         }
         if replay_summary.conflicts > 0 {
-            println!("  Conflicts: {} (stopped)", replay_summary.conflicts);
+            outln!("  Conflicts: {} (stopped)", replay_summary.conflicts);
         }
         if replay_summary.skipped > 0 {
-            println!("  Skipped: {}", replay_summary.skipped);
+            outln!("  Skipped: {}", replay_summary.skipped);
         }
         if !conflict_output.is_empty() {
-            println!();
+            outln!();
             if replay_summary.in_conflict > 0 {
-                println!("In-conflict commits (resolve with 'sv resolve'):");
+                outln!("In-conflict commits (resolve with 'sv resolve'):");
             } else {
-                println!("Conflicts:");
+                outln!("Conflicts:");
             }
             for conflict in &conflict_output {
-                println!(
+                outln!(
                     "  {} - files: {}",
                     &conflict.commit_id[..8],
                     conflict.files.join(", ")
                 );
             }
         }
-        println!();
+        outln!();
         if applied {
             let commit_count = replay_summary.applied + replay_summary.in_conflict;
             if replay_summary.in_conflict > 0 {
-                println!(
+                outln!(
                     "{} updated to include {} commit(s) ({} with conflicts)",
-                    dest, commit_count, replay_summary.in_conflict
+                    dest,
+                    commit_count,
+                    replay_summary.in_conflict
                 );
             } else {
-                println!("{} updated to include {} commit(s)", dest, commit_count);
+                outln!("{} updated to include {} commit(s)", dest, commit_count);
             }
             if dest_is_head {
                 if worktree_updated {
-                    println!("  Worktree: updated");
+                    outln!("  Worktree: updated");
                 } else if let Some(warning) = &worktree_update_warning {
-                    println!("  Worktree: not updated ({warning})");
+                    outln!("  Worktree: not updated ({warning})");
                 }
             }
         } else if opts.no_apply {
-            println!(
+            outln!(
                 "Skipped apply (--no-apply). To apply: git checkout {} && git merge --ff-only {}",
-                dest, integration_ref
+                dest,
+                integration_ref
             );
         } else if replay_summary.conflicts > 0 {
-            println!("Apply skipped due to conflicts. Resolve conflicts and retry.");
+            outln!("Apply skipped due to conflicts. Resolve conflicts and retry.");
         } else if total_applied == 0 {
-            println!("Nothing to apply (no commits replayed).");
+            outln!("Nothing to apply (no commits replayed).");
         }
         if let Some(cleanup) = &workspace_cleanup {
-            println!();
+            outln!();
             let header = if cleanup.dry_run {
                 "Workspace cleanup (dry run)"
             } else {
                 "Workspace cleanup"
             };
-            println!("{header}");
-            println!("  Removed: {}", cleanup.removed.len());
-            println!("  Skipped: {}", cleanup.skipped.len());
-            println!("  Failed: {}", cleanup.failed.len());
+            outln!("{header}");
+            outln!("  Removed: {}", cleanup.removed.len());
+            outln!("  Skipped: {}", cleanup.skipped.len());
+            outln!("  Failed: {}", cleanup.failed.len());
             if !cleanup.removed.is_empty() {
                 let label = if cleanup.dry_run {
                     "Would remove"
                 } else {
                     "Removed"
                 };
-                println!("{label}: {}", cleanup.removed.join(", "));
+                outln!("{label}: {}", cleanup.removed.join(", "));
             }
             if !cleanup.skipped.is_empty() {
-                println!("Skipped:");
+                outln!("Skipped:");
                 for skip in &cleanup.skipped {
-                    println!("  - {} ({})", skip.name, skip.reason);
+                    outln!("  - {} ({})", skip.name, skip.reason);
                 }
             }
             if !cleanup.failed.is_empty() {
-                println!("Failed:");
+                outln!("Failed:");
                 for failure in &cleanup.failed {
-                    println!("  - {} ({})", failure.name, failure.error);
+                    outln!("  - {} ({})", failure.name, failure.error);
                 }
             }
         }
@@ -2702,6 +4159,14 @@ fn run_hoist(opts: HoistOptions) -> Result<()> {
 }
 
 impl Cli {
+    /// Parse the process arguments, recording whether --actor was explicit.
+    pub fn parse_args() -> Self {
+        let matches = Self::command().get_matches();
+        let mut cli = Self::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+        cli.actor_flag = matches.value_source("actor") == Some(ValueSource::CommandLine);
+        cli
+    }
+
     /// Execute the CLI command
     pub fn run(self) -> Result<()> {
         let Cli {
@@ -2709,12 +4174,20 @@ impl Cli {
             actor,
             json,
             events,
+            output,
             robot_help,
             quiet,
             verbose: _,
+            no_color,
+            result_line,
+            actor_flag,
             command,
         } = self;
 
+        crate::output::set_no_color(no_color);
+        crate::output::set_result_line(result_line);
+        crate::output::set_output_path(output.as_deref())?;
+
         if robot_help {
             let help = match &command {
                 Some(Commands::Ws { .. }) => WS_ROBOT_HELP,
@@ -2723,28 +4196,39 @@ impl Cli {
                 Some(Commands::Lease { .. }) => LEASE_ROBOT_HELP,
                 Some(Commands::Protect { .. }) => PROTECT_ROBOT_HELP,
                 Some(Commands::Commit { .. }) => COMMIT_ROBOT_HELP,
+                Some(Commands::ChangeId { .. }) => CHANGE_ID_ROBOT_HELP,
                 Some(Commands::Task { .. }) => TASK_ROBOT_HELP,
                 Some(Commands::Project { .. }) => PROJECT_ROBOT_HELP,
                 Some(Commands::Forge { .. }) => FORGE_ROBOT_HELP,
+                Some(Commands::Events { .. }) => EVENTS_ROBOT_HELP,
+                Some(Commands::Refs { .. }) => REFS_ROBOT_HELP,
                 Some(Commands::Risk { .. }) => RISK_ROBOT_HELP,
                 Some(Commands::Op { .. }) => OP_ROBOT_HELP,
                 Some(Commands::Undo { .. }) => UNDO_ROBOT_HELP,
+                Some(Commands::Redo) => REDO_ROBOT_HELP,
                 Some(Commands::Actor { .. }) => ACTOR_ROBOT_HELP,
+                Some(Commands::Config { .. }) => CONFIG_ROBOT_HELP,
                 Some(Commands::Init) => INIT_ROBOT_HELP,
-                Some(Commands::Status) => STATUS_ROBOT_HELP,
+                Some(Commands::Status { .. }) => STATUS_ROBOT_HELP,
                 Some(Commands::Switch { .. }) => SWITCH_ROBOT_HELP,
                 Some(Commands::Onto { .. }) => ONTO_ROBOT_HELP,
                 Some(Commands::Hoist { .. }) => HOIST_ROBOT_HELP,
+                Some(Commands::Completions { .. }) => COMPLETIONS_ROBOT_HELP,
+                Some(Commands::Man { .. }) => MAN_ROBOT_HELP,
+                Some(Commands::Version) => VERSION_ROBOT_HELP,
+                Some(Commands::Env) => ENV_ROBOT_HELP,
                 None => ROBOT_HELP,
             };
-            println!("{help}");
+            outln!("{help}");
             return Ok(());
         }
 
+        // Only an explicit request for both JSON and events on stdout is an
+        // error; --output moves the JSON result elsewhere.
         let events_to_stdout = matches!(events.as_deref(), Some("-"));
-        if events_to_stdout && json {
+        if events_to_stdout && json && !crate::output::result_redirected() {
             return Err(Error::InvalidArgument(
-                "--json requires --events <path> to avoid mixing JSON output with JSONL events"
+                "--json requires --events <path> (or --output <path>) to avoid mixing JSON output with JSONL events"
                     .to_string(),
             ));
         }
@@ -2753,16 +4237,64 @@ impl Cli {
             None => {
                 let mut cli = Cli::command();
                 cli.print_help()?;
-                println!();
+                outln!();
                 return Err(Error::InvalidArgument("missing command".to_string()));
             }
         };
 
+        let mut repos = repo;
+        if repos.is_empty() {
+            if let Some(paths) = std::env::var_os("SV_REPO") {
+                // SV_REPO is a path list only for commands that run across
+                // repositories, and only when it is not itself an existing
+                // path (which may contain the separator)
+                let single = std::path::PathBuf::from(&paths);
+                repos = if multi::supports(&command) && !single.exists() {
+                    std::env::split_paths(&paths)
+                        .filter(|path| !path.as_os_str().is_empty())
+                        .collect()
+                } else {
+                    Some(single)
+                        .filter(|path| !path.as_os_str().is_empty())
+                        .into_iter()
+                        .collect()
+                };
+            }
+        }
+        if repos.len() > 1 {
+            return multi::run(
+                command,
+                multi::MultiOptions {
+                    repos,
+                    actor,
+                    json,
+                    quiet,
+                },
+            );
+        }
+        let repo = repos.pop();
+
         match command {
             Commands::Init => init::run(repo, json, quiet),
-            Commands::Status => status::run(status::StatusOptions {
+            Commands::Completions { shell } => completions::run(shell),
+            Commands::Version => version::run(version::VersionOptions { json, quiet }),
+            Commands::Env => env::run(env::EnvOptions {
+                repo,
+                actor,
+                actor_flag,
+                json,
+                quiet,
+            }),
+            Commands::Man { section, out } => man::run(man::ManOptions {
+                section,
+                out,
+                json,
+                quiet,
+            }),
+            Commands::Status { short } => status::run(status::StatusOptions {
                 repo,
                 actor,
+                short,
                 json,
                 quiet,
             }),
@@ -2775,14 +4307,20 @@ impl Cli {
             }),
             Commands::Onto {
                 target,
+                into,
                 strategy,
                 base,
+                commits,
                 preflight,
+                dry_run,
             } => onto::run(onto::OntoOptions {
                 target_workspace: target,
+                into,
                 strategy,
                 base,
+                commits,
                 preflight,
+                dry_run,
                 actor,
                 repo,
                 json,
@@ -2814,8 +4352,14 @@ impl Cli {
                         json,
                         quiet,
                     }),
-                    WsCommands::List { selector } => ws::run_list(ws::ListOptions {
+                    WsCommands::List {
                         selector,
+                        stale,
+                        all,
+                    } => ws::run_list(ws::ListOptions {
+                        selector,
+                        stale,
+                        all,
                         repo,
                         json,
                         quiet,
@@ -2833,6 +4377,21 @@ impl Cli {
                         json,
                         quiet,
                     }),
+                    WsCommands::Archive { name, force } => ws::run_archive(ws::ArchiveOptions {
+                        name,
+                        force,
+                        actor,
+                        repo,
+                        json,
+                        quiet,
+                    }),
+                    WsCommands::Restore { name } => ws::run_restore(ws::RestoreOptions {
+                        name,
+                        actor,
+                        repo,
+                        json,
+                        quiet,
+                    }),
                     WsCommands::Clean {
                         selector,
                         dest,
@@ -2867,6 +4426,8 @@ impl Cli {
                 scope,
                 ttl,
                 note,
+                force,
+                dry_run,
             } => take::run(take::TakeOptions {
                 paths,
                 strength,
@@ -2874,31 +4435,58 @@ impl Cli {
                 scope,
                 ttl,
                 note,
+                force,
+                dry_run,
                 actor,
                 events: events.clone(),
                 repo,
                 json,
                 quiet,
             }),
-            Commands::Release { targets, force } => release::run(release::ReleaseOptions {
+            Commands::Release {
+                targets,
+                selector,
+                force,
+                expired,
+                dry_run,
+            } => release::run(release::ReleaseOptions {
                 targets,
+                selector,
                 actor,
                 events: events.clone(),
                 repo,
                 force,
+                expired,
+                dry_run,
                 json,
                 quiet,
             }),
             Commands::Lease { command } => match command {
                 Some(cmd) => match cmd {
-                    LeaseCommands::Ls { selector, actor } => lease::run_ls(lease::LsOptions {
+                    LeaseCommands::Ls {
                         selector,
                         actor,
+                        path,
+                        format,
+                        by_actor,
+                    } => lease::run_ls(lease::LsOptions {
+                        selector,
+                        actor,
+                        path,
+                        format,
+                        by_actor,
                         repo,
                         json,
                         quiet,
                     }),
-                    LeaseCommands::Who { path } => lease::run_who(lease::WhoOptions {
+                    LeaseCommands::Who { path, at } => lease::run_who(lease::WhoOptions {
+                        path,
+                        at,
+                        repo,
+                        json,
+                        quiet,
+                    }),
+                    LeaseCommands::Blame { path } => lease::run_blame(lease::BlameOptions {
                         path,
                         repo,
                         json,
@@ -2932,6 +4520,34 @@ impl Cli {
                         json,
                         quiet,
                     }),
+                    LeaseCommands::Export => lease::run_export(lease::ExportOptions { repo }),
+                    LeaseCommands::Import { file } => lease::run_import(lease::ImportOptions {
+                        file,
+                        actor,
+                        repo,
+                        json,
+                        quiet,
+                    }),
+                    LeaseCommands::Doctor { repair } => lease::run_doctor(lease::DoctorOptions {
+                        repair,
+                        repo,
+                        json,
+                        quiet,
+                    }),
+                    LeaseCommands::Keepalive {
+                        interval,
+                        renew_to,
+                        count,
+                    } => lease::run_keepalive(lease::KeepaliveOptions {
+                        interval,
+                        renew_to,
+                        count,
+                        actor,
+                        events: events.clone(),
+                        repo,
+                        json,
+                        quiet,
+                    }),
                 },
                 None => {
                     print_subcommand_help("lease")?;
@@ -2975,6 +4591,23 @@ impl Cli {
                     ))
                 }
             },
+            Commands::ChangeId {
+                command: Some(ChangeIdCommands::Verify { base }),
+                ..
+            } => change_id::run_verify(change_id::VerifyOptions {
+                base,
+                repo,
+                json,
+                quiet,
+            }),
+            Commands::ChangeId { command: None, of } => {
+                change_id::run(change_id::ChangeIdOptions {
+                    of,
+                    repo,
+                    json,
+                    quiet,
+                })
+            }
             Commands::Commit {
                 message,
                 file,
@@ -2982,7 +4615,10 @@ impl Cli {
                 all,
                 no_edit,
                 allow_protected,
+                override_readonly,
+                reason,
                 force_lease,
+                dry_run,
             } => commit::run(commit::CommitOptions {
                 message,
                 file,
@@ -2990,8 +4626,12 @@ impl Cli {
                 all,
                 no_edit,
                 allow_protected,
+                override_readonly,
+                reason,
                 force_lease,
+                dry_run,
                 actor,
+                events: events.clone(),
                 repo,
                 json,
                 quiet,
@@ -3004,11 +4644,13 @@ impl Cli {
                 Some(cmd) => match cmd {
                     TaskCommands::New {
                         title,
+                        id,
                         status,
                         priority,
                         body,
                     } => task::run_new(task::NewOptions {
                         title,
+                        id,
                         status,
                         priority,
                         body,
@@ -3027,6 +4669,7 @@ impl Cli {
                         actor: list_actor,
                         updated_since,
                         limit,
+                        format,
                     } => task::run_list(task::ListOptions {
                         status,
                         priority,
@@ -3036,6 +4679,7 @@ impl Cli {
                         actor: list_actor,
                         updated_since,
                         limit,
+                        format,
                         repo,
                         json,
                         quiet,
@@ -3048,6 +4692,7 @@ impl Cli {
                         actor: list_actor,
                         updated_since,
                         limit,
+                        format,
                     } => task::run_ready(task::ReadyOptions {
                         priority,
                         epic: list_epic.or_else(|| epic.clone()),
@@ -3056,6 +4701,7 @@ impl Cli {
                         actor: list_actor,
                         updated_since,
                         limit,
+                        format,
                         repo,
                         json,
                         quiet,
@@ -3070,6 +4716,7 @@ impl Cli {
                         actor: list_actor,
                         updated_since,
                         limit,
+                        fail_over,
                     } => task::run_count(task::CountOptions {
                         ready,
                         status,
@@ -3080,6 +4727,7 @@ impl Cli {
                         actor: list_actor,
                         updated_since,
                         limit,
+                        fail_over,
                         repo,
                         json,
                         quiet,
@@ -3087,15 +4735,37 @@ impl Cli {
                     TaskCommands::Stats => {
                         task::run_stats(task::StatsOptions { repo, json, quiet })
                     }
-                    TaskCommands::Show { id } => task::run_show(task::ShowOptions {
+                    TaskCommands::Timeline {
+                        since,
+                        actor: timeline_actor,
+                        limit,
+                    } => task::run_timeline(task::TimelineOptions {
+                        since,
+                        actor: timeline_actor,
+                        limit,
+                        repo,
+                        json,
+                        quiet,
+                    }),
+                    TaskCommands::Show { id, events_file } => task::run_show(task::ShowOptions {
                         id,
+                        events_file,
                         repo,
                         json,
                         quiet,
                     }),
-                    TaskCommands::Start { id, takeover } => task::run_start(task::StartOptions {
+                    TaskCommands::Start {
+                        id,
+                        takeover,
+                        lease,
+                        create_workspace,
+                        base,
+                    } => task::run_start(task::StartOptions {
                         id,
                         takeover,
+                        lease,
+                        create_workspace,
+                        base,
                         actor,
                         events: events.clone(),
                         repo,
@@ -3132,9 +4802,16 @@ impl Cli {
                         json,
                         quiet,
                     }),
-                    TaskCommands::Close { id, status } => task::run_close(task::CloseOptions {
+                    TaskCommands::Close {
+                        id,
+                        status,
+                        merge,
+                        into,
+                    } => task::run_close(task::CloseOptions {
                         id,
                         status,
+                        merge,
+                        into,
                         actor,
                         events: events.clone(),
                         repo,
@@ -3291,7 +4968,31 @@ impl Cli {
                         json,
                         quiet,
                     }),
-                    TaskCommands::Sync => task::run_sync(task::SyncOptions { repo, json, quiet }),
+                    TaskCommands::Sync { push, pull, remote } => {
+                        task::run_sync(task::SyncOptions {
+                            push,
+                            pull,
+                            remote,
+                            repo,
+                            json,
+                            quiet,
+                        })
+                    }
+                    TaskCommands::MergeDriver { base, ours, theirs } => {
+                        task::run_merge_driver(task::MergeDriverOptions {
+                            base,
+                            ours,
+                            theirs,
+                            json,
+                        })
+                    }
+                    TaskCommands::InstallMergeDriver => {
+                        task::run_install_merge_driver(task::InstallMergeDriverOptions {
+                            repo,
+                            json,
+                            quiet,
+                        })
+                    }
                     TaskCommands::Doctor => {
                         task::run_doctor(task::DoctorOptions { repo, json, quiet })
                     }
@@ -3344,9 +5045,10 @@ impl Cli {
                             quiet,
                         })
                     }
-                    ProjectEntityCommands::List { all } => {
+                    ProjectEntityCommands::List { all, with_progress } => {
                         project::run_list(project::ListOptions {
                             all,
+                            with_progress,
                             repo,
                             json,
                             quiet,
@@ -3371,9 +5073,10 @@ impl Cli {
                         json,
                         quiet,
                     }),
-                    ProjectEntityCommands::Archive { id } => {
+                    ProjectEntityCommands::Archive { id, force } => {
                         project::run_archive(project::ArchiveOptions {
                             id,
+                            force,
                             actor,
                             repo,
                             json,
@@ -3409,6 +5112,40 @@ impl Cli {
                     ))
                 }
             },
+            Commands::Refs { command } => match command {
+                Some(RefsCommands::Prune { dry_run, force }) => {
+                    refs::run_prune(refs::PruneOptions {
+                        dry_run,
+                        force,
+                        actor,
+                        repo,
+                        json,
+                        quiet,
+                    })
+                }
+                Some(RefsCommands::Migrate { dry_run }) => {
+                    refs::run_migrate(refs::MigrateOptions {
+                        dry_run,
+                        actor,
+                        repo,
+                        json,
+                        quiet,
+                    })
+                }
+                None => {
+                    print_subcommand_help("refs")?;
+                    Err(Error::InvalidArgument("missing refs command".to_string()))
+                }
+            },
+            Commands::Events { command } => match command {
+                Some(EventsCommands::Validate { file }) => {
+                    events::run_validate(events::ValidateOptions { file, json, quiet })
+                }
+                None => {
+                    print_subcommand_help("events")?;
+                    Err(Error::InvalidArgument("missing events command".to_string()))
+                }
+            },
             Commands::Forge { command } => match command {
                 Some(cmd) => match cmd {
                     ForgeCommands::Hooks { command } => match command {
@@ -3438,11 +5175,18 @@ impl Cli {
             Commands::Risk {
                 selector,
                 base,
+                since,
                 simulate,
+                mine,
+                fail_on,
             } => run_risk(RiskOptions {
                 selector,
                 base,
+                since,
                 simulate,
+                mine,
+                fail_on,
+                actor,
                 repo,
                 json,
                 quiet,
@@ -3453,14 +5197,18 @@ impl Cli {
                         limit,
                         actor,
                         operation,
+                        touching,
                         since,
                         until,
+                        graph,
                     } => op::run_log(op::LogOptions {
                         limit,
                         actor,
                         operation,
+                        touching,
                         since,
                         until,
+                        graph,
                         repo,
                         json,
                         quiet,
@@ -3471,12 +5219,19 @@ impl Cli {
                     Err(Error::InvalidArgument("missing op command".to_string()))
                 }
             },
-            Commands::Undo { op } => {
-                if !quiet {
-                    println!("sv undo {:?} - not yet implemented", op);
-                }
-                Ok(())
-            }
+            Commands::Undo {
+                op,
+                dry_run,
+                keep_worktree,
+            } => undo::run(undo::UndoOptions {
+                op,
+                dry_run,
+                keep_worktree,
+                repo,
+                json,
+                quiet,
+            }),
+            Commands::Redo => undo::run_redo(undo::RedoOptions { repo, json, quiet }),
             Commands::Actor { command } => match command {
                 Some(cmd) => match cmd {
                     ActorCommands::Set { name } => actor::run_set(actor::SetOptions {
@@ -3497,11 +5252,38 @@ impl Cli {
                     Err(Error::InvalidArgument("missing actor command".to_string()))
                 }
             },
+            Commands::Config { command } => match command {
+                Some(ConfigCommands::Show { effective }) => config::run_show(config::ShowOptions {
+                    effective,
+                    repo,
+                    json,
+                    quiet,
+                }),
+                None => {
+                    print_subcommand_help("config")?;
+                    Err(Error::InvalidArgument("missing config command".to_string()))
+                }
+            },
+            Commands::Hoist {
+                command: Some(HoistCommands::Resolve { commit, dest }),
+                ..
+            } => hoist::run_resolve(hoist::ResolveOptions {
+                commit,
+                dest,
+                repo,
+                json,
+                quiet,
+            }),
             Commands::Hoist {
+                command: None,
                 selector,
+                legacy_selector,
                 dest,
                 strategy,
                 order,
+                squash,
+                name,
+                force,
                 dry_run,
                 continue_on_conflict,
                 no_propagate_conflicts,
@@ -3510,10 +5292,15 @@ impl Cli {
                 rm,
                 rm_force,
             } => run_hoist(HoistOptions {
-                selector,
+                selector: selector
+                    .ok_or_else(|| Error::InvalidArgument("--selector is required".to_string()))?,
+                legacy_selector,
                 dest,
                 strategy,
                 order,
+                squash,
+                name,
+                force,
                 dry_run,
                 continue_on_conflict,
                 no_propagate_conflicts,
@@ -3534,7 +5321,7 @@ fn print_subcommand_help(name: &str) -> Result<()> {
     let mut cli = Cli::command();
     if let Some(subcommand) = cli.find_subcommand_mut(name) {
         subcommand.print_help()?;
-        println!();
+        outln!();
     }
     Ok(())
 }
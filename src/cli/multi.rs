@@ -0,0 +1,165 @@
+//! Multi-repo runs of read-only summary commands
+//!
+//! `sv --repo a --repo b <cmd>` (or a path-list `SV_REPO`) runs `status`,
+//! `risk`, or `lease ls` once per repository and aggregates the results:
+//! human output gets a header per repo, JSON is keyed by repo path.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use super::{lease, run_risk, status, Commands, LeaseCommands, RiskOptions};
+use crate::error::{Error, Result};
+use crate::output::{capture_result, emit_error, emit_success, outln, OutputOptions};
+
+/// Options shared by every per-repo run
+pub struct MultiOptions {
+    pub repos: Vec<PathBuf>,
+    pub actor: Option<String>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+type RunOne = Box<dyn Fn(PathBuf) -> Result<()>>;
+
+/// Whether `command` can run across several repositories
+pub fn supports(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::Status { .. }
+            | Commands::Risk { .. }
+            | Commands::Lease {
+                command: Some(LeaseCommands::Ls { .. })
+            }
+    )
+}
+
+pub fn run(command: Commands, options: MultiOptions) -> Result<()> {
+    let MultiOptions {
+        repos,
+        actor,
+        json,
+        quiet,
+    } = options;
+
+    let (name, run_one): (&str, RunOne) = match command {
+        Commands::Status { short } => (
+            "status",
+            Box::new(move |repo| {
+                status::run(status::StatusOptions {
+                    repo: Some(repo),
+                    actor: actor.clone(),
+                    short,
+                    json,
+                    quiet,
+                })
+            }),
+        ),
+        Commands::Risk {
+            selector,
+            base,
+            since,
+            simulate,
+            mine,
+            fail_on,
+        } => (
+            "risk",
+            Box::new(move |repo| {
+                run_risk(RiskOptions {
+                    selector: selector.clone(),
+                    base: base.clone(),
+                    since: since.clone(),
+                    simulate,
+                    mine,
+                    fail_on: fail_on.clone(),
+                    actor: actor.clone(),
+                    repo: Some(repo),
+                    json,
+                    quiet,
+                })
+            }),
+        ),
+        Commands::Lease {
+            command:
+                Some(LeaseCommands::Ls {
+                    selector,
+                    actor: actor_filter,
+                    path,
+                    format,
+                    by_actor,
+                }),
+        } => (
+            "lease ls",
+            Box::new(move |repo| {
+                lease::run_ls(lease::LsOptions {
+                    selector: selector.clone(),
+                    actor: actor_filter.clone(),
+                    path: path.clone(),
+                    format: format.clone(),
+                    by_actor,
+                    repo: Some(repo),
+                    json,
+                    quiet,
+                })
+            }),
+        ),
+        _ => {
+            return Err(Error::InvalidArgument(
+                "multiple repositories (--repo/SV_REPO) are only supported for status, risk, and lease ls"
+                    .to_string(),
+            ))
+        }
+    };
+
+    let total = repos.len();
+    let mut results = BTreeMap::new();
+    let mut failed = Vec::new();
+    let mut first_code = None;
+    for repo in repos {
+        let key = repo.display().to_string();
+        let (result, mut captured) = capture_result(|| run_one(repo));
+        if let Err(err) = &result {
+            first_code.get_or_insert(err.exit_code());
+            failed.push(key.clone());
+            if json {
+                // Gate failures already wrote their report; others get the
+                // usual error envelope in this repo's slot
+                let (_, envelope) = capture_result(|| emit_error(name, err, true));
+                captured.push_str(&envelope);
+            }
+        }
+
+        if json {
+            // Non-JSON output (e.g. `--format jsonl`) is kept as a string
+            let value = match serde_json::from_str::<serde_json::Value>(&captured) {
+                Ok(value) => value,
+                Err(_) => serde_json::Value::String(captured),
+            };
+            results.insert(key, value);
+        } else if !quiet {
+            outln!("==> {key} <==");
+            outln!("{}", captured.trim_end());
+            if let Err(err) = &result {
+                outln!("error: {err}");
+            }
+            outln!();
+        }
+    }
+
+    if let Some(code) = first_code {
+        return Err(Error::ReposFailed {
+            failed,
+            total,
+            code,
+            results: if json {
+                serde_json::to_value(&results)?
+            } else {
+                serde_json::Value::Null
+            },
+        });
+    }
+
+    if json {
+        emit_success(OutputOptions { json: true, quiet }, name, &results, None)?;
+    }
+    Ok(())
+}
@@ -1,6 +1,7 @@
 //! sv onto command implementation
 //!
-//! Repositions the current workspace on top of another workspace's tip.
+//! Repositions the current workspace on top of another workspace's tip, or
+//! onto an arbitrary ref with `--into`.
 
 use std::path::PathBuf;
 use std::process::Command;
@@ -10,19 +11,23 @@ use serde::Serialize;
 
 use crate::actor;
 use crate::cli::ws;
-use crate::error::{Error, Result};
+use crate::config::Config;
+use crate::error::{ConflictedFile, Error, Result};
 use crate::git;
 use crate::merge;
 use crate::oplog::{OpLog, OpRecord, RefUpdate, UndoData};
-use crate::output::{emit_success, HumanOutput, OutputOptions};
+use crate::output::{emit_success, outln, HumanOutput, OutputOptions};
 use crate::storage::Storage;
 
 /// Options for the onto command
 pub struct OntoOptions {
-    pub target_workspace: String,
+    pub target_workspace: Option<String>,
+    pub into: Option<String>,
     pub strategy: String,
     pub base: Option<String>,
+    pub commits: Option<String>,
     pub preflight: bool,
+    pub dry_run: bool,
     pub actor: Option<String>,
     pub repo: Option<PathBuf>,
     pub json: bool,
@@ -55,20 +60,30 @@ impl FromStr for OntoStrategy {
 
 #[derive(Debug, Serialize)]
 struct OntoReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    would: Option<&'static str>,
     current_workspace: String,
-    target_workspace: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_workspace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_ref: Option<String>,
     current_branch: String,
     target_branch: String,
     base: String,
     strategy: OntoStrategy,
     head_before: Option<String>,
     head_after: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    commits: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
 struct PreflightReport {
     current_workspace: String,
-    target_workspace: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_workspace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_ref: Option<String>,
     current_branch: String,
     target_branch: String,
     base: String,
@@ -77,6 +92,29 @@ struct PreflightReport {
     conflicts: Vec<PreflightConflict>,
 }
 
+/// What `sv onto` repositions onto: a workspace's branch or a ref (`--into`)
+struct OntoTarget {
+    workspace: Option<String>,
+    /// Workspace branch, or the ref as given to `--into`
+    ref_name: String,
+    /// Revision handed to git: the workspace branch, or the resolved commit
+    rev: String,
+}
+
+impl OntoTarget {
+    fn label(&self) -> &str {
+        self.workspace.as_deref().unwrap_or(&self.ref_name)
+    }
+
+    /// The `sv onto` invocation that targets this again
+    fn command(&self) -> String {
+        match &self.workspace {
+            Some(name) => format!("sv onto {name}"),
+            None => format!("sv onto --into {}", self.ref_name),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct PreflightConflict {
     path: String,
@@ -99,29 +137,61 @@ pub fn run(options: OntoOptions) -> Result<()> {
     let current_entry =
         ws::ensure_current_workspace(&storage, &repo, &workdir, options.actor.as_deref())?;
 
-    let registry = storage.read_workspaces()?;
-    let target_entry = registry
-        .find(&options.target_workspace)
-        .cloned()
-        .ok_or_else(|| Error::WorkspaceNotFound(options.target_workspace.clone()))?;
-
-    if current_entry.name == target_entry.name {
-        return Err(Error::InvalidArgument(
-            "target workspace must be different from current workspace".to_string(),
-        ));
-    }
+    let target = match (&options.target_workspace, &options.into) {
+        (_, Some(into)) => {
+            let commit = repo
+                .revparse_single(into)
+                .and_then(|object| object.peel_to_commit())
+                .map_err(|_| Error::InvalidArgument(format!("cannot resolve ref '{into}'")))?;
+            OntoTarget {
+                workspace: None,
+                ref_name: into.clone(),
+                rev: commit.id().to_string(),
+            }
+        }
+        (Some(name), None) => {
+            let registry = storage.read_workspaces()?;
+            let entry = registry
+                .find(name)
+                .cloned()
+                .ok_or_else(|| Error::WorkspaceNotFound(name.clone()))?;
+            if current_entry.name == entry.name {
+                return Err(Error::InvalidArgument(
+                    "target workspace must be different from current workspace".to_string(),
+                ));
+            }
+            OntoTarget {
+                workspace: Some(entry.name),
+                ref_name: entry.branch.clone(),
+                rev: entry.branch,
+            }
+        }
+        (None, None) => {
+            return Err(Error::InvalidArgument(
+                "a target workspace or --into <ref> is required".to_string(),
+            ));
+        }
+    };
 
-    let base_ref = options
-        .base
-        .clone()
-        .unwrap_or_else(|| current_entry.base.clone());
+    // Without --base, use the base the workspace was created from; a
+    // workspace registered in place records its own branch, so fall back to
+    // the configured base for that branch.
+    let base_ref = options.base.clone().unwrap_or_else(|| {
+        if current_entry.base != current_entry.branch {
+            current_entry.base.clone()
+        } else {
+            Config::load_from_repo(&workdir)
+                .base_for(&current_entry.branch)
+                .to_string()
+        }
+    });
     let actor_name = actor::resolve_actor(Some(&workdir), options.actor.as_deref())?;
 
     let strategy = OntoStrategy::from_str(&options.strategy)?;
 
-    if matches!(strategy, OntoStrategy::CherryPick) {
+    if options.commits.is_some() && !matches!(strategy, OntoStrategy::CherryPick) {
         return Err(Error::InvalidArgument(
-            "cherry-pick strategy not yet supported; use rebase or merge".to_string(),
+            "--commits requires --strategy cherry-pick".to_string(),
         ));
     }
 
@@ -132,12 +202,8 @@ pub fn run(options: OntoOptions) -> Result<()> {
             OntoStrategy::Merge => None,
             OntoStrategy::CherryPick => Some(base_ref.as_str()),
         };
-        let simulation = merge::simulate_merge(
-            &repo,
-            &current_entry.branch,
-            &target_entry.branch,
-            preflight_base,
-        )?;
+        let simulation =
+            merge::simulate_merge(&repo, &current_entry.branch, &target.rev, preflight_base)?;
         let base_display = if preflight_base.is_some() {
             base_ref.clone()
         } else {
@@ -153,7 +219,7 @@ pub fn run(options: OntoOptions) -> Result<()> {
             })
             .collect();
 
-        let mut continue_cmd = format!("sv onto {}", target_entry.name);
+        let mut continue_cmd = target.command();
         if options.strategy != "rebase" {
             continue_cmd.push_str(&format!(" --strategy {}", options.strategy));
         }
@@ -163,9 +229,10 @@ pub fn run(options: OntoOptions) -> Result<()> {
 
         let report = PreflightReport {
             current_workspace: current_entry.name.clone(),
-            target_workspace: target_entry.name.clone(),
+            target_workspace: target.workspace.clone(),
+            target_ref: options.into.clone(),
             current_branch: current_entry.branch.clone(),
-            target_branch: target_entry.branch.clone(),
+            target_branch: target.ref_name.clone(),
             base: base_display.clone(),
             strategy,
             has_conflicts: !conflicts.is_empty(),
@@ -173,22 +240,24 @@ pub fn run(options: OntoOptions) -> Result<()> {
         };
 
         if options.json {
-            println!("{}", serde_json::to_string_pretty(&report)?);
+            outln!("{}", serde_json::to_string_pretty(&report)?);
         } else if !options.quiet {
-            println!(
+            outln!(
                 "sv onto preflight: {} -> {}",
-                current_entry.name, target_entry.name
+                current_entry.name,
+                target.label()
             );
-            println!();
-            println!("Summary:");
-            println!(
+            outln!();
+            outln!("Summary:");
+            outln!(
                 "  Current: {} ({})",
-                current_entry.name, current_entry.branch
+                current_entry.name,
+                current_entry.branch
             );
-            println!("  Target: {} ({})", target_entry.name, target_entry.branch);
-            println!("  Base: {}", base_display);
-            println!("  Strategy: {:?}", strategy);
-            println!();
+            outln!("  Target: {} ({})", target.label(), target.rev);
+            outln!("  Base: {}", base_display);
+            outln!("  Strategy: {:?}", strategy);
+            outln!();
 
             let op_label = match strategy {
                 OntoStrategy::Rebase => "rebase",
@@ -197,21 +266,21 @@ pub fn run(options: OntoOptions) -> Result<()> {
             };
 
             if conflicts.is_empty() {
-                println!("No conflicts detected - {op_label} should succeed cleanly.");
-                println!();
-                println!("Run without --preflight to execute:");
-                println!("  {}", continue_cmd);
+                outln!("No conflicts detected - {op_label} should succeed cleanly.");
+                outln!();
+                outln!("Run without --preflight to execute:");
+                outln!("  {}", continue_cmd);
             } else {
-                println!("Predicted conflicts ({}):", conflicts.len());
+                outln!("Predicted conflicts ({}):", conflicts.len());
                 for conflict in &conflicts {
-                    println!("  [{}] {}", conflict.kind, conflict.path);
+                    outln!("  [{}] {}", conflict.kind, conflict.path);
                 }
-                println!();
-                println!("Warning: {op_label} may require manual conflict resolution.");
-                println!("Consider coordinating with workspace owner before proceeding.");
-                println!();
-                println!("To continue anyway, run:");
-                println!("  {}", continue_cmd);
+                outln!();
+                outln!("Warning: {op_label} may require manual conflict resolution.");
+                outln!("Consider coordinating with workspace owner before proceeding.");
+                outln!();
+                outln!("To continue anyway, run:");
+                outln!("  {}", continue_cmd);
             }
         }
 
@@ -225,25 +294,105 @@ pub fn run(options: OntoOptions) -> Result<()> {
         .and_then(|h| h.target())
         .map(|oid| oid.to_string());
 
+    let picked = if matches!(strategy, OntoStrategy::CherryPick) {
+        resolve_cherry_pick_commits(&repo, &base_ref, &target.rev, options.commits.as_deref())?
+    } else {
+        Vec::new()
+    };
+
+    if options.dry_run {
+        // Report the commits that would be replayed, oldest first.
+        let replay = match strategy {
+            OntoStrategy::Rebase => {
+                let mut commits = git::commits_ahead(&repo, &base_ref, &current_entry.branch)?;
+                commits.reverse();
+                commits
+            }
+            OntoStrategy::Merge => {
+                let mut commits = git::commits_ahead(&repo, &current_entry.branch, &target.rev)?;
+                commits.reverse();
+                commits
+            }
+            OntoStrategy::CherryPick => picked,
+        };
+
+        let report = OntoReport {
+            would: Some("onto"),
+            current_workspace: current_entry.name.clone(),
+            target_workspace: target.workspace.clone(),
+            target_ref: options.into.clone(),
+            current_branch: current_entry.branch.clone(),
+            target_branch: target.ref_name.clone(),
+            base: base_ref.clone(),
+            strategy,
+            head_before: head_before.clone(),
+            head_after: None,
+            commits: replay.iter().map(|oid| oid.to_string()).collect(),
+        };
+
+        let mut human = HumanOutput::new(format!(
+            "sv onto (dry run): would {} {} commit(s) from {} -> {}",
+            options.strategy,
+            replay.len(),
+            current_entry.name,
+            target.label()
+        ));
+        human.push_summary("strategy", options.strategy.clone());
+        human.push_summary("base", base_ref.clone());
+        human.push_summary("commits", replay.len().to_string());
+        for oid in &replay {
+            let summary = repo
+                .find_commit(*oid)
+                .ok()
+                .and_then(|commit| commit.summary().map(str::to_string))
+                .unwrap_or_default();
+            human.push_detail(format!("{} {}", &oid.to_string()[..8], summary));
+        }
+        human.push_next_step(target.command());
+
+        emit_success(
+            OutputOptions {
+                json: options.json,
+                quiet: options.quiet,
+            },
+            "onto",
+            &report,
+            Some(&human),
+        )?;
+        return Ok(());
+    }
+
     let mut cmd = Command::new("git");
     cmd.current_dir(&workdir);
 
     match strategy {
         OntoStrategy::Rebase => {
-            cmd.args(["rebase", "--onto", &target_entry.branch, &base_ref]);
+            cmd.args(["rebase", "--onto", &target.rev, &base_ref]);
         }
         OntoStrategy::Merge => {
-            cmd.args(["merge", &target_entry.branch]);
+            cmd.args(["merge", &target.rev]);
         }
         OntoStrategy::CherryPick => {
-            return Err(Error::InvalidArgument(
-                "cherry-pick strategy not yet supported; use rebase or merge".to_string(),
-            ));
+            cmd.arg("cherry-pick");
+            cmd.args(picked.iter().map(|oid| oid.to_string()));
         }
     }
 
     let output = cmd.output()?;
     if !output.status.success() {
+        let files = conflicted_files(&repo, &workdir)?;
+        if !files.is_empty() {
+            let (operation, head) = match strategy {
+                OntoStrategy::Rebase => ("rebase", "REBASE_HEAD"),
+                OntoStrategy::Merge => ("merge", "MERGE_HEAD"),
+                OntoStrategy::CherryPick => ("cherry-pick", "CHERRY_PICK_HEAD"),
+            };
+            return Err(Error::Conflicted {
+                operation: operation.to_string(),
+                commit: repo.refname_to_id(head).ok().map(|oid| oid.to_string()),
+                files,
+            });
+        }
         let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
         let message = if stderr.is_empty() {
             "git operation failed".to_string()
@@ -260,27 +409,32 @@ pub fn run(options: OntoOptions) -> Result<()> {
         .map(|oid| oid.to_string());
 
     let report = OntoReport {
+        would: None,
         current_workspace: current_entry.name.clone(),
-        target_workspace: target_entry.name.clone(),
+        target_workspace: target.workspace.clone(),
+        target_ref: options.into.clone(),
         current_branch: current_entry.branch.clone(),
-        target_branch: target_entry.branch.clone(),
+        target_branch: target.ref_name.clone(),
         base: base_ref.clone(),
         strategy,
         head_before: head_before.clone(),
         head_after: head_after.clone(),
+        commits: picked.iter().map(|oid| oid.to_string()).collect(),
     };
 
     let mut human = HumanOutput::new(format!(
         "sv onto: {} -> {} ({})",
-        current_entry.name, target_entry.name, options.strategy
+        current_entry.name,
+        target.label(),
+        options.strategy
     ));
     human.push_summary("strategy", options.strategy.clone());
     human.push_summary("current", current_entry.name.clone());
-    human.push_summary("target", target_entry.name.clone());
+    human.push_summary("target", target.label().to_string());
     human.push_summary("base", base_ref.clone());
     human.push_detail(format!(
         "branch: {} -> {}",
-        current_entry.branch, target_entry.branch
+        current_entry.branch, target.ref_name
     ));
     if let Some(head_before) = head_before.as_deref() {
         human.push_detail(format!(
@@ -294,6 +448,13 @@ pub fn run(options: OntoOptions) -> Result<()> {
             &head_after[..8.min(head_after.len())]
         ));
     }
+    if !picked.is_empty() {
+        human.push_summary("commits", picked.len().to_string());
+        for oid in &picked {
+            let oid = oid.to_string();
+            human.push_detail(format!("picked: {}", &oid[..8]));
+        }
+    }
     human.push_next_step("sv risk".to_string());
 
     emit_success(
@@ -308,14 +469,26 @@ pub fn run(options: OntoOptions) -> Result<()> {
 
     let oplog = OpLog::for_storage(&storage);
     let mut record = OpRecord::new(
-        format!(
-            "sv onto {} --strategy {}",
-            target_entry.name, options.strategy
-        ),
+        format!("{} --strategy {}", target.command(), options.strategy),
         Some(actor_name),
     );
     record.affected_workspaces.push(current_entry.name.clone());
-    record.affected_workspaces.push(target_entry.name.clone());
+    if let Some(name) = &target.workspace {
+        record.affected_workspaces.push(name.clone());
+    }
+    record.affected_refs.push(target.ref_name.clone());
+    if let (Some(before), Some(after)) = (head_before.as_deref(), head_after.as_deref()) {
+        if let Ok(changes) = git::diff_files(&repo, before, Some(after)) {
+            record.affected_paths = git::changed_paths(&changes)
+                .iter()
+                .map(|path| path.to_string_lossy().replace('\\', "/"))
+                .collect();
+        }
+    }
+    if let Some(after) = head_after.as_deref() {
+        record.commits =
+            created_commits(&repo, after, &[head_before.as_deref(), Some(&target.rev)]);
+    }
     if let Some(ref_name) = head_ref {
         record.affected_refs.push(ref_name.clone());
         record.undo_data = Some(UndoData {
@@ -331,3 +504,121 @@ pub fn run(options: OntoOptions) -> Result<()> {
 
     Ok(())
 }
+
+/// Files the stopped git operation left conflicted, with conflict hunk counts.
+///
+/// Hunks are counted from the `<<<<<<<` markers git wrote into the worktree
+/// file; delete/modify and binary conflicts have none.
+fn conflicted_files(
+    repo: &git2::Repository,
+    workdir: &std::path::Path,
+) -> Result<Vec<ConflictedFile>> {
+    let mut index = repo.index()?;
+    index.read(true)?;
+    let files = merge::collect_conflicts(repo, &index)?
+        .into_iter()
+        .map(|conflict| {
+            let hunks = std::fs::read(workdir.join(&conflict.path))
+                .map(|content| {
+                    content
+                        .split(|byte| *byte == b'\n')
+                        .filter(|line| line.starts_with(b"<<<<<<<"))
+                        .count()
+                })
+                .unwrap_or(0);
+            ConflictedFile {
+                path: conflict.path,
+                hunks,
+            }
+        })
+        .collect();
+    Ok(files)
+}
+
+/// Resolve the commits to cherry-pick from the target workspace, oldest first.
+///
+/// Without a range, every commit in `base..target_branch` is picked. A range
+/// (`a..b`) or single revision narrows the selection, but every selected
+/// commit must belong to the target workspace's branch.
+fn resolve_cherry_pick_commits(
+    repo: &git2::Repository,
+    base_ref: &str,
+    target_branch: &str,
+    range: Option<&str>,
+) -> Result<Vec<git2::Oid>> {
+    let workspace_commits = git::commits_ahead(repo, base_ref, target_branch)?;
+
+    let selected = match range.map(str::trim) {
+        None => workspace_commits.clone(),
+        Some("") => {
+            return Err(Error::InvalidArgument(
+                "--commits cannot be empty".to_string(),
+            ))
+        }
+        Some(spec) => walk_commit_range(repo, spec)?,
+    };
+
+    if let Some(outside) = selected.iter().find(|oid| !workspace_commits.contains(oid)) {
+        return Err(Error::InvalidArgument(format!(
+            "commit {} is not on workspace branch '{}' (range {}..{})",
+            &outside.to_string()[..8],
+            target_branch,
+            base_ref,
+            target_branch
+        )));
+    }
+
+    if selected.is_empty() {
+        return Err(Error::InvalidArgument(format!(
+            "no commits to cherry-pick from '{}'",
+            target_branch
+        )));
+    }
+
+    // commits_ahead/revwalk yield newest first; cherry-pick wants oldest first.
+    let mut ordered: Vec<git2::Oid> = workspace_commits
+        .into_iter()
+        .filter(|oid| selected.contains(oid))
+        .collect();
+    ordered.reverse();
+    Ok(ordered)
+}
+
+fn walk_commit_range(repo: &git2::Repository, spec: &str) -> Result<Vec<git2::Oid>> {
+    if !spec.contains("..") {
+        let commit = repo
+            .revparse_single(spec)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|_| Error::InvalidArgument(format!("unknown revision '{}'", spec)))?;
+        return Ok(vec![commit.id()]);
+    }
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk
+        .push_range(spec)
+        .map_err(|err| Error::InvalidArgument(format!("invalid range '{}': {}", spec, err)))?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+    let mut commits = Vec::new();
+    for oid in revwalk {
+        commits.push(oid?);
+    }
+    Ok(commits)
+}
+
+/// Commits reachable from `tip` but from none of `excluded`, oldest first:
+/// the commits an onto created (rebased or picked copies, merge commits).
+fn created_commits(repo: &git2::Repository, tip: &str, excluded: &[Option<&str>]) -> Vec<String> {
+    let walk = || -> Result<Vec<String>> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(repo.revparse_single(tip)?.peel_to_commit()?.id())?;
+        for spec in excluded.iter().flatten() {
+            revwalk.hide(repo.revparse_single(spec)?.peel_to_commit()?.id())?;
+        }
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        revwalk
+            .map(|oid| oid.map(|oid| oid.to_string()).map_err(Error::from))
+            .collect()
+    };
+    walk().unwrap_or_default()
+}
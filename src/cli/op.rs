@@ -1,6 +1,7 @@
 //! sv op subcommand implementations.
 //!
-//! Provides operation log display with filtering.
+//! Provides operation log display with filtering, and a `--graph` view that
+//! lists the commits each operation created.
 
 use std::path::PathBuf;
 
@@ -9,7 +10,11 @@ use serde::Serialize;
 
 use crate::error::{Error, Result};
 use crate::git;
-use crate::oplog::{format_records, OpDetails, OpLog, OpLogFilter, OpOutcome};
+use crate::lease;
+use crate::oplog::{
+    format_records, OpDetails, OpLog, OpLogFilter, OpOutcome, OpRecord, OperationKind,
+};
+use crate::output::outln;
 use crate::storage::Storage;
 
 /// Options for the op log command.
@@ -19,6 +24,8 @@ pub struct LogOptions {
     pub since: Option<String>,
     pub until: Option<String>,
     pub operation: Option<String>,
+    pub touching: Option<String>,
+    pub graph: bool,
     pub repo: Option<PathBuf>,
     pub json: bool,
     pub quiet: bool,
@@ -30,8 +37,11 @@ struct LogEntry {
     timestamp: String,
     actor: Option<String>,
     command: String,
+    kind: OperationKind,
     affected_refs: Vec<String>,
     affected_workspaces: Vec<String>,
+    affected_paths: Vec<String>,
+    commits: Vec<String>,
     outcome: OpOutcome,
     details: Option<OpDetails>,
 }
@@ -40,6 +50,8 @@ struct LogEntry {
 struct LogReport {
     records: Vec<LogEntry>,
     total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skipped_untagged: Option<usize>,
 }
 
 /// Run the op log command.
@@ -48,26 +60,56 @@ pub fn run_log(options: LogOptions) -> Result<()> {
     let workdir = git::workdir(&repo)?;
     let common_dir = resolve_common_dir(&repo)?;
 
-    let storage = Storage::new(workdir.clone(), common_dir, workdir);
+    let storage = Storage::new(workdir.clone(), common_dir, workdir.clone());
     if !storage.is_initialized() {
         return Err(Error::OperationFailed(
             "sv not initialized. Run 'sv init' first.".to_string(),
         ));
     }
 
+    if let Some(operation) = options.operation.as_deref() {
+        OperationKind::validate_filter(operation)?;
+    }
+
     let since = parse_timestamp("since", options.since.as_deref())?;
     let until = parse_timestamp("until", options.until.as_deref())?;
 
+    let touching = match options.touching.as_deref() {
+        Some(raw) => {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| workdir.clone());
+            Some(lease::resolve_pathspec(&workdir, &cwd, raw)?)
+        }
+        None => None,
+    };
+
     let filter = OpLogFilter {
         actor: options.actor.clone(),
         since,
         until,
         operation: options.operation.clone(),
+        touching: touching.clone(),
     };
 
     let log = OpLog::for_storage(&storage);
     let records = log.read_filtered(&filter, Some(options.limit))?;
 
+    // Entries written before paths were recorded can never match --touching;
+    // count the ones the other filters would have kept so we can say so.
+    let skipped_untagged = if touching.is_some() {
+        let untouched = OpLogFilter {
+            touching: None,
+            ..filter.clone()
+        };
+        Some(
+            log.read_filtered(&untouched, None)?
+                .iter()
+                .filter(|record| record.affected_paths.is_empty())
+                .count(),
+        )
+    } else {
+        None
+    };
+
     if options.json {
         let entries: Vec<LogEntry> = records
             .iter()
@@ -76,8 +118,11 @@ pub fn run_log(options: LogOptions) -> Result<()> {
                 timestamp: record.timestamp.to_rfc3339(),
                 actor: record.actor.clone(),
                 command: record.command.clone(),
+                kind: record.kind(),
                 affected_refs: record.affected_refs.clone(),
                 affected_workspaces: record.affected_workspaces.clone(),
+                affected_paths: record.affected_paths.clone(),
+                commits: record.commits.clone(),
                 outcome: record.outcome.clone(),
                 details: record.details.clone(),
             })
@@ -86,8 +131,9 @@ pub fn run_log(options: LogOptions) -> Result<()> {
         let report = LogReport {
             total: entries.len(),
             records: entries,
+            skipped_untagged,
         };
-        println!("{}", serde_json::to_string_pretty(&report)?);
+        outln!("{}", serde_json::to_string_pretty(&report)?);
         return Ok(());
     }
 
@@ -96,14 +142,54 @@ pub fn run_log(options: LogOptions) -> Result<()> {
     }
 
     if records.is_empty() {
-        println!("No operations recorded.");
+        outln!("No operations recorded.");
+    } else if options.graph {
+        outln!("{}", format_graph(&repo, &records));
     } else {
-        println!("{}", format_records(&records));
+        outln!("{}", format_records(&records));
+    }
+    if let Some(skipped) = skipped_untagged.filter(|count| *count > 0) {
+        outln!(
+            "Note: skipped {skipped} entr{} without recorded paths (written before path tagging).",
+            if skipped == 1 { "y" } else { "ies" }
+        );
     }
 
     Ok(())
 }
 
+/// Render records newest first, each followed by the commits it created.
+fn format_graph(repo: &git2::Repository, records: &[OpRecord]) -> String {
+    let mut lines = Vec::new();
+    for (idx, record) in records.iter().enumerate() {
+        if idx > 0 {
+            lines.push("|".to_string());
+        }
+        let op_id = record.op_id.to_string();
+        lines.push(format!(
+            "* {} {} {} {} \"{}\"",
+            &op_id[..8],
+            record.timestamp.to_rfc3339(),
+            record.kind(),
+            record.actor.as_deref().unwrap_or("-"),
+            record.command
+        ));
+        for commit in &record.commits {
+            let summary = git2::Oid::from_str(commit)
+                .ok()
+                .and_then(|oid| repo.find_commit(oid).ok())
+                .map(|found| found.summary().unwrap_or_default().to_string())
+                .unwrap_or_else(|| "(not in repository)".to_string());
+            lines.push(format!(
+                "|   {} {}",
+                &commit[..8.min(commit.len())],
+                summary
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
 fn parse_timestamp(label: &str, value: Option<&str>) -> Result<Option<DateTime<Utc>>> {
     let Some(value) = value else {
         return Ok(None);
@@ -10,7 +10,7 @@ use crate::git;
 use crate::output::{emit_success, HumanOutput, OutputOptions};
 use crate::project::{ProjectRecord, ProjectStore, ProjectSyncReport};
 use crate::storage::Storage;
-use crate::task::TaskStore;
+use crate::task::{TaskEvent, TaskEventType, TaskRecord, TaskStore};
 
 pub struct NewOptions {
     pub name: String,
@@ -23,6 +23,7 @@ pub struct NewOptions {
 
 pub struct ListOptions {
     pub all: bool,
+    pub with_progress: bool,
     pub repo: Option<PathBuf>,
     pub json: bool,
     pub quiet: bool,
@@ -47,6 +48,7 @@ pub struct EditOptions {
 
 pub struct ArchiveOptions {
     pub id: String,
+    pub force: bool,
     pub actor: Option<String>,
     pub repo: Option<PathBuf>,
     pub json: bool,
@@ -86,12 +88,27 @@ struct ProjectCreateOutput {
 struct ProjectListOutput {
     total: usize,
     projects: Vec<ProjectRecord>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    progress: Option<Vec<ProjectProgress>>,
+}
+
+#[derive(serde::Serialize)]
+struct ProjectProgress {
+    project: String,
+    open: usize,
+    closed: usize,
+    percent: u32,
 }
 
 #[derive(serde::Serialize)]
 struct ProjectChangeOutput {
     id: String,
     changed: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cleared_tasks: Vec<String>,
+    /// Active members detached because their epic's project was cleared
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    detached_tasks: Vec<String>,
 }
 
 #[derive(serde::Serialize)]
@@ -152,17 +169,31 @@ pub fn run_new(options: NewOptions) -> Result<()> {
 pub fn run_list(options: ListOptions) -> Result<()> {
     let ctx = load_context(options.repo, None, false)?;
     let projects = ctx.project_store.list(options.all)?;
+    let progress = if options.with_progress {
+        Some(project_progress(&ctx.task_store, &projects)?)
+    } else {
+        None
+    };
     let output = ProjectListOutput {
         total: projects.len(),
         projects,
+        progress,
     };
     let mut human = HumanOutput::new("Projects");
     human.push_summary("Total", output.total.to_string());
-    for project in &output.projects {
+    for (idx, project) in output.projects.iter().enumerate() {
         let mut line = format!("{} {}", project.id, project.name);
         if project.archived {
             line.push_str(" [archived]");
         }
+        if let Some(progress) = output.progress.as_ref().and_then(|all| all.get(idx)) {
+            line.push_str(&format!(
+                " {}/{} closed ({}%)",
+                progress.closed,
+                progress.open + progress.closed,
+                progress.percent
+            ));
+        }
         human.push_detail(line);
     }
     emit_success(
@@ -210,6 +241,8 @@ pub fn run_edit(options: EditOptions) -> Result<()> {
     let output = ProjectChangeOutput {
         id: resolved.clone(),
         changed,
+        cleared_tasks: Vec::new(),
+        detached_tasks: Vec::new(),
     };
     let mut human = HumanOutput::new(if changed {
         "Project updated".to_string()
@@ -229,24 +262,95 @@ pub fn run_edit(options: EditOptions) -> Result<()> {
 }
 
 pub fn run_archive(options: ArchiveOptions) -> Result<()> {
-    set_archived(
-        options.id,
-        true,
-        options.actor,
-        options.repo,
-        options.json,
-        options.quiet,
+    let ctx = load_context(options.repo, options.actor, true)?;
+    let resolved = ctx.project_store.resolve_project_id(&options.id)?;
+    let members = active_members(&ctx.task_store, &resolved)?;
+    if !members.active.is_empty() && !options.force {
+        return Err(Error::InvalidArgument(format!(
+            "project {resolved} has {} active task(s): {} (close them or use --force to clear their project)",
+            members.active.len(),
+            members.active.join(", ")
+        )));
+    }
+
+    let mut events = Vec::with_capacity(members.carriers.len());
+    for task_id in &members.carriers {
+        let mut event = TaskEvent::new(TaskEventType::TaskProjectCleared, task_id.clone());
+        event.actor = ctx.actor.clone();
+        event.related_task_id = Some(resolved.clone());
+        events.push(event);
+    }
+    ctx.task_store.append_events(&events)?;
+
+    let changed = ctx
+        .project_store
+        .set_archived(&resolved, true, ctx.actor.clone())?;
+    let detached_tasks = members
+        .active
+        .into_iter()
+        .filter(|task_id| !members.carriers.contains(task_id))
+        .collect();
+    let output = ProjectChangeOutput {
+        id: resolved.clone(),
+        changed,
+        cleared_tasks: members.carriers,
+        detached_tasks,
+    };
+    let mut human = HumanOutput::new(if changed {
+        "Project archived"
+    } else {
+        "Project already archived"
+    });
+    human.push_summary("ID", resolved);
+    if !output.cleared_tasks.is_empty() {
+        human.push_summary("Cleared tasks", output.cleared_tasks.len().to_string());
+        for task_id in &output.cleared_tasks {
+            human.push_detail(task_id.clone());
+        }
+    }
+    if !output.detached_tasks.is_empty() {
+        human.push_summary("Detached via epic", output.detached_tasks.len().to_string());
+        for task_id in &output.detached_tasks {
+            human.push_detail(format!("{task_id} (epic project cleared)"));
+        }
+    }
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "project archive",
+        &output,
+        Some(&human),
     )
 }
 
 pub fn run_unarchive(options: UnarchiveOptions) -> Result<()> {
-    set_archived(
-        options.id,
-        false,
-        options.actor,
-        options.repo,
-        options.json,
-        options.quiet,
+    let ctx = load_context(options.repo, options.actor, false)?;
+    let changed = ctx
+        .project_store
+        .set_archived(&options.id, false, ctx.actor.clone())?;
+    let resolved = ctx.project_store.resolve_project_id(&options.id)?;
+    let output = ProjectChangeOutput {
+        id: resolved.clone(),
+        changed,
+        cleared_tasks: Vec::new(),
+        detached_tasks: Vec::new(),
+    };
+    let mut human = HumanOutput::new(if changed {
+        "Project unarchived"
+    } else {
+        "Project already active"
+    });
+    human.push_summary("ID", resolved);
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "project unarchive",
+        &output,
+        Some(&human),
     )
 }
 
@@ -275,7 +379,7 @@ pub fn run_migrate_legacy(options: MigrateLegacyOptions) -> Result<()> {
         .into_iter()
         .map(|project| project.id)
         .collect();
-    let tasks = ctx.task_store.list(None)?;
+    let tasks = ctx.task_store.list(&[])?;
     let title_by_task_id: BTreeMap<String, String> = tasks
         .iter()
         .map(|task| (task.id.clone(), task.title.clone()))
@@ -374,44 +478,105 @@ fn load_context(
     })
 }
 
-fn set_archived(
-    id: String,
-    archived: bool,
-    actor_name: Option<String>,
-    repo: Option<PathBuf>,
-    json: bool,
-    quiet: bool,
-) -> Result<()> {
-    let ctx = load_context(repo, actor_name, false)?;
-    let changed = ctx
-        .project_store
-        .set_archived(&id, archived, ctx.actor.clone())?;
-    let resolved = ctx.project_store.resolve_project_id(&id)?;
-    let output = ProjectChangeOutput {
-        id: resolved.clone(),
-        changed,
-    };
-    let title = if archived {
-        if changed {
-            "Project archived"
-        } else {
-            "Project already archived"
+/// Closed/open member counts per project, in `projects` order.
+///
+/// Membership matches `sv task list --project`: a task belongs to a project
+/// when its effective project (its own, else its epic's) is the project, and a
+/// legacy task-backed project counts its anchor task too.
+fn project_progress(
+    task_store: &TaskStore,
+    projects: &[ProjectRecord],
+) -> Result<Vec<ProjectProgress>> {
+    let tasks = task_store.list(&[])?;
+    let effective = crate::cli::task::build_effective_project_map(&tasks);
+    let closed_statuses = &task_store.config().closed_statuses;
+
+    let mut counts: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for task in &tasks {
+        let is_closed = closed_statuses
+            .iter()
+            .any(|status| status.eq_ignore_ascii_case(task.status.trim()));
+        let mut members = BTreeSet::new();
+        if let Some(project) = effective.get(&task.id).and_then(|value| value.as_deref()) {
+            members.insert(project);
         }
-    } else if changed {
-        "Project unarchived"
-    } else {
-        "Project already active"
-    };
-    let mut human = HumanOutput::new(title);
-    human.push_summary("ID", resolved);
-    emit_success(
-        OutputOptions { json, quiet },
-        if archived {
-            "project archive"
-        } else {
-            "project unarchive"
-        },
-        &output,
-        Some(&human),
-    )
+        members.insert(task.id.as_str());
+        for project in members {
+            let entry = counts.entry(project).or_default();
+            if is_closed {
+                entry.1 += 1;
+            } else {
+                entry.0 += 1;
+            }
+        }
+    }
+
+    Ok(projects
+        .iter()
+        .map(|project| {
+            let (open, closed) = counts.get(project.id.as_str()).copied().unwrap_or_default();
+            let total = open + closed;
+            let percent = (closed * 100).checked_div(total).unwrap_or(0) as u32;
+            ProjectProgress {
+                project: project.id.clone(),
+                open,
+                closed,
+                percent,
+            }
+        })
+        .collect())
+}
+
+/// Non-closed members of a project and the tasks they get it from.
+struct ActiveMembers {
+    /// Non-closed tasks whose effective project is the project
+    active: Vec<String>,
+    /// Tasks that set the project themselves and carry it to an active
+    /// member (the member itself, or an epic up its chain)
+    carriers: Vec<String>,
+}
+
+/// Active members of `project_id`, with membership as in `sv task list
+/// --project`: a task's own project, else the one inherited from its epic.
+///
+/// Archiving with `--force` clears the project from the carriers, which
+/// detaches members that inherit it through their epic too.
+fn active_members(task_store: &TaskStore, project_id: &str) -> Result<ActiveMembers> {
+    let closed = &task_store.config().closed_statuses;
+    let tasks = task_store.list(&[])?;
+    let effective = crate::cli::task::build_effective_project_map(&tasks);
+    let by_id: BTreeMap<&str, &TaskRecord> =
+        tasks.iter().map(|task| (task.id.as_str(), task)).collect();
+
+    let mut active = BTreeSet::new();
+    let mut carriers = BTreeSet::new();
+    for task in &tasks {
+        let is_member =
+            effective.get(&task.id).and_then(|value| value.as_deref()) == Some(project_id);
+        let is_closed = closed
+            .iter()
+            .any(|status| status.eq_ignore_ascii_case(task.status.trim()));
+        if !is_member || is_closed {
+            continue;
+        }
+        active.insert(task.id.clone());
+
+        let mut current = task;
+        let mut seen = BTreeSet::new();
+        while seen.insert(current.id.as_str()) {
+            if current.project.as_deref() == Some(project_id) {
+                carriers.insert(current.id.clone());
+                break;
+            }
+            match current.epic.as_deref().and_then(|epic| by_id.get(epic)) {
+                Some(epic) => current = epic,
+                None => break,
+            }
+        }
+    }
+
+    Ok(ActiveMembers {
+        active: active.into_iter().collect(),
+        carriers: carriers.into_iter().collect(),
+    })
 }
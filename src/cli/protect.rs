@@ -8,7 +8,7 @@ use crate::config::{Config, ProtectPath};
 use crate::error::{Error, Result};
 use crate::git;
 use crate::output::{emit_success, HumanOutput, OutputOptions};
-use crate::protect::{compute_status, load_override};
+use crate::protect::{compile_pattern, compute_status, load_override};
 use crate::storage::Storage;
 
 /// Options for the protect status command
@@ -49,7 +49,6 @@ pub struct RmOptions {
 struct AddReport {
     added: Vec<String>,
     already_exists: Vec<String>,
-    invalid: Vec<InvalidPattern>,
 }
 
 #[derive(Clone, serde::Serialize)]
@@ -241,9 +240,25 @@ pub fn run_add(options: AddOptions) -> Result<()> {
         )));
     }
 
+    // Reject malformed patterns before touching .sv.toml
+    let invalid: Vec<String> = options
+        .patterns
+        .iter()
+        .filter_map(|pattern| {
+            compile_pattern(pattern)
+                .err()
+                .map(|err| format!("'{pattern}': {err}"))
+        })
+        .collect();
+    if !invalid.is_empty() {
+        return Err(Error::InvalidArgument(format!(
+            "invalid protect pattern {}",
+            invalid.join("; ")
+        )));
+    }
+
     let mut added = Vec::new();
     let mut already_exists = Vec::new();
-    let mut invalid = Vec::new();
 
     // Get existing patterns
     let existing_patterns: Vec<String> = config
@@ -258,18 +273,14 @@ pub fn run_add(options: AddOptions) -> Result<()> {
 
     // Process each pattern
     for pattern in &options.patterns {
-        // Validate pattern syntax
-        if let Err(e) = glob::Pattern::new(pattern) {
-            invalid.push(InvalidPattern {
-                pattern: pattern.clone(),
-                error: e.to_string(),
-            });
+        // Check for duplicates, including repeats within this invocation
+        if existing_patterns.contains(pattern) {
+            if !already_exists.contains(pattern) {
+                already_exists.push(pattern.clone());
+            }
             continue;
         }
-
-        // Check for duplicates
-        if existing_patterns.contains(pattern) {
-            already_exists.push(pattern.clone());
+        if added.contains(pattern) {
             continue;
         }
 
@@ -294,26 +305,15 @@ pub fn run_add(options: AddOptions) -> Result<()> {
         config.save(&config_path)?;
     }
 
-    // Return error if all patterns were invalid
-    if added.is_empty() && !options.patterns.is_empty() && !invalid.is_empty() {
-        return Err(Error::InvalidArgument(format!(
-            "Invalid pattern: {}",
-            invalid[0].error
-        )));
-    }
-
     let report = AddReport {
         added: added.clone(),
         already_exists: already_exists.clone(),
-        invalid: invalid.clone(),
     };
 
     let header = if !added.is_empty() {
         format!("sv protect add: added {} pattern(s)", added.len())
     } else if !already_exists.is_empty() {
         "sv protect add: patterns already protected".to_string()
-    } else if !invalid.is_empty() {
-        "sv protect add: invalid patterns".to_string()
     } else {
         "sv protect add: no changes".to_string()
     };
@@ -321,7 +321,6 @@ pub fn run_add(options: AddOptions) -> Result<()> {
     let mut human = HumanOutput::new(header);
     human.push_summary("added", added.len().to_string());
     human.push_summary("already_exists", already_exists.len().to_string());
-    human.push_summary("invalid", invalid.len().to_string());
 
     for pattern in &added {
         human.push_detail(format!("added: {pattern} [{}]", options.mode));
@@ -329,9 +328,6 @@ pub fn run_add(options: AddOptions) -> Result<()> {
     for pattern in &already_exists {
         human.push_warning(format!("already protected: {pattern}"));
     }
-    for inv in &invalid {
-        human.push_warning(format!("invalid pattern: {} ({})", inv.pattern, inv.error));
-    }
 
     human.push_next_step("sv protect status");
 
@@ -390,10 +386,10 @@ pub fn run_off(options: OffOptions) -> Result<()> {
     let mut invalid = Vec::new();
 
     for pattern in &options.patterns {
-        if let Err(e) = glob::Pattern::new(pattern) {
+        if let Err(e) = compile_pattern(pattern) {
             invalid.push(InvalidPattern {
                 pattern: pattern.clone(),
-                error: e.to_string(),
+                error: e,
             });
             continue;
         }
@@ -537,19 +533,38 @@ pub fn run_rm(options: RmOptions) -> Result<()> {
     let mut not_found = Vec::new();
 
     for pattern in &options.patterns {
+        // Arguments match configured patterns (not paths): an exact string
+        // removes only that pattern; otherwise the argument is a glob over
+        // the pattern text.
+        let configured = |p: &ProtectPath| match p {
+            ProtectPath::Simple(s) => s.clone(),
+            ProtectPath::WithMode { pattern, .. } => pattern.clone(),
+        };
+        let exact = config
+            .protect
+            .paths
+            .iter()
+            .any(|p| configured(p) == *pattern);
+        let matcher = if exact {
+            None
+        } else {
+            compile_pattern(pattern).ok()
+        };
         let initial_len = config.protect.paths.len();
 
         config.protect.paths.retain(|p| {
-            let p_pattern = match p {
-                ProtectPath::Simple(s) => s,
-                ProtectPath::WithMode { pattern, .. } => pattern,
-            };
-            p_pattern != pattern
+            let p_pattern = configured(p);
+            let matched = p_pattern == *pattern
+                || matcher
+                    .as_ref()
+                    .is_some_and(|matcher| matcher.matches(&p_pattern));
+            if matched && !removed.contains(&p_pattern) {
+                removed.push(p_pattern);
+            }
+            !matched
         });
 
-        if config.protect.paths.len() < initial_len {
-            removed.push(pattern.clone());
-        } else {
+        if config.protect.paths.len() == initial_len {
             not_found.push(pattern.clone());
         }
     }
@@ -0,0 +1,333 @@
+//! sv refs command implementation
+//!
+//! Maintains the sv-managed ref namespace: prunes workspace branches left
+//! behind by workspaces that are no longer registered and integration
+//! branches of finished hoists, and migrates refs between `refs/heads` and
+//! the dedicated `refs/sv` namespace.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::actor;
+use crate::config::Config;
+use crate::error::Result;
+use crate::git;
+use crate::oplog::{OpLog, OpRecord, RefUpdate, UndoData};
+use crate::output::{emit_success, HumanOutput, OutputOptions};
+use crate::refs::{self, SvRefKind};
+use crate::storage::{HoistState, HoistStatus, Storage};
+
+/// Options for `sv refs prune`
+pub struct PruneOptions {
+    pub dry_run: bool,
+    pub force: bool,
+    pub actor: Option<String>,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+/// Options for `sv refs migrate`
+pub struct MigrateOptions {
+    pub dry_run: bool,
+    pub actor: Option<String>,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct PruneReport {
+    dry_run: bool,
+    pruned: Vec<PrunedRef>,
+    skipped: Vec<SkippedRef>,
+}
+
+#[derive(Debug, Serialize)]
+struct PrunedRef {
+    name: String,
+    refname: String,
+    kind: SvRefKind,
+    target: String,
+    reason: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SkippedRef {
+    name: String,
+    kind: SvRefKind,
+    reason: String,
+}
+
+/// Run `sv refs prune`.
+///
+/// Only refs nothing uses any more are candidates: `sv/ws/*` branches no
+/// registered workspace points at, and `sv/hoist/*` branches whose hoist
+/// completed or left no state. Candidates that are checked out, or that hold
+/// commits not merged into their base, are skipped (the latter unless
+/// `--force`).
+pub fn run_prune(options: PruneOptions) -> Result<()> {
+    let repo = git::open_repo(options.repo.as_deref())?;
+    let workdir = git::workdir(&repo)?;
+    let storage = Storage::new(workdir.clone(), git::common_dir(&repo), workdir.clone());
+    let config = Config::load_from_repo(&workdir);
+    let registry = storage.read_workspaces()?;
+    let hoist_states = storage.list_hoist_states()?;
+    let checked_out: Vec<String> = git::list_worktrees(&repo)?
+        .into_iter()
+        .filter_map(|worktree| worktree.branch)
+        .collect();
+
+    let mut pruned = Vec::new();
+    let mut skipped = Vec::new();
+    let mut finished_states: Vec<&HoistState> = Vec::new();
+    for sv_ref in refs::list_sv_refs(&repo)? {
+        let (reason, base, state) = match sv_ref.kind {
+            SvRefKind::Workspace => {
+                if registry
+                    .workspaces
+                    .iter()
+                    .any(|entry| entry.branch == sv_ref.name)
+                {
+                    continue;
+                }
+                ("workspace not registered", config.base.clone(), None)
+            }
+            SvRefKind::Hoist => {
+                match hoist_states
+                    .iter()
+                    .find(|state| state.integration_ref == sv_ref.name)
+                {
+                    None => ("no hoist state", config.base.clone(), None),
+                    Some(state) if state.status == HoistStatus::Completed => {
+                        ("hoist completed", state.dest_ref.clone(), Some(state))
+                    }
+                    Some(_) => continue,
+                }
+            }
+        };
+
+        let skip = |reason: String| SkippedRef {
+            name: sv_ref.name.clone(),
+            kind: sv_ref.kind,
+            reason,
+        };
+        if checked_out.contains(&sv_ref.name) {
+            skipped.push(skip("checked out in a worktree".to_string()));
+            continue;
+        }
+        if !options.force {
+            let unmerged = match git::commits_ahead(&repo, &base, &sv_ref.name) {
+                Ok(commits) => commits.len(),
+                Err(_) => {
+                    skipped.push(skip(format!("cannot compare with base '{base}'")));
+                    continue;
+                }
+            };
+            if unmerged > 0 {
+                skipped.push(skip(format!(
+                    "{unmerged} commit(s) not merged into {base} (use --force)"
+                )));
+                continue;
+            }
+        }
+
+        pruned.push(PrunedRef {
+            name: sv_ref.name.clone(),
+            refname: sv_ref.refname.clone(),
+            kind: sv_ref.kind,
+            target: sv_ref.target.to_string(),
+            reason: reason.to_string(),
+        });
+        finished_states.extend(state);
+    }
+
+    if !options.dry_run && !pruned.is_empty() {
+        for entry in &pruned {
+            refs::delete_branch(&repo, &entry.name)?;
+        }
+        for state in finished_states {
+            storage.clear_hoist_state(state.storage_key())?;
+        }
+
+        let actor = actor::resolve_actor_optional(Some(&workdir), options.actor.as_deref())?;
+        let mut record = OpRecord::new("sv refs prune", actor);
+        record.affected_refs = pruned.iter().map(|entry| entry.name.clone()).collect();
+        record.undo_data = Some(UndoData {
+            ref_updates: pruned
+                .iter()
+                .map(|entry| RefUpdate {
+                    name: entry.refname.clone(),
+                    old: Some(entry.target.clone()),
+                    new: None,
+                })
+                .collect(),
+            ..UndoData::default()
+        });
+        OpLog::for_storage(&storage).append(&record)?;
+    }
+
+    let header = if options.dry_run {
+        "sv refs prune (dry run)"
+    } else {
+        "sv refs prune"
+    };
+    let mut human = HumanOutput::new(header);
+    human.push_summary(
+        if options.dry_run {
+            "Would prune"
+        } else {
+            "Pruned"
+        },
+        pruned.len().to_string(),
+    );
+    human.push_summary("Skipped", skipped.len().to_string());
+    for entry in &pruned {
+        human.push_detail(format!("{} ({})", entry.name, entry.reason));
+    }
+    for entry in &skipped {
+        human.push_detail(format!("skipped {}: {}", entry.name, entry.reason));
+    }
+    if options.dry_run && !pruned.is_empty() {
+        human.push_next_step("sv refs prune");
+    }
+
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "refs prune",
+        &PruneReport {
+            dry_run: options.dry_run,
+            pruned,
+            skipped,
+        },
+        Some(&human),
+    )
+}
+
+#[derive(Debug, Serialize)]
+struct MigrateReport {
+    namespace: String,
+    dry_run: bool,
+    migrated: Vec<MigratedRef>,
+    skipped: Vec<SkippedRef>,
+}
+
+#[derive(Debug, Serialize)]
+struct MigratedRef {
+    name: String,
+    kind: SvRefKind,
+    from: String,
+    to: String,
+}
+
+/// Run `sv refs migrate`.
+///
+/// Moves every sv-managed ref that is not yet in the configured
+/// `[refs] namespace` into it, keeping worktrees attached to their refs.
+pub fn run_migrate(options: MigrateOptions) -> Result<()> {
+    let repo = git::open_repo(options.repo.as_deref())?;
+    let workdir = git::workdir(&repo)?;
+    let storage = Storage::new(workdir.clone(), git::common_dir(&repo), workdir.clone());
+    let namespace = Config::load_from_repo(&workdir).refs.namespace;
+
+    let mut migrated = Vec::new();
+    let mut skipped = Vec::new();
+    let mut targets = Vec::new();
+    for sv_ref in refs::list_sv_refs(&repo)? {
+        let to = refs::managed_refname(&namespace, &sv_ref.name);
+        if to == sv_ref.refname {
+            continue;
+        }
+        if repo.find_reference(&to).is_ok() {
+            skipped.push(SkippedRef {
+                name: sv_ref.name.clone(),
+                kind: sv_ref.kind,
+                reason: format!("{to} already exists"),
+            });
+            continue;
+        }
+        targets.push(sv_ref.target.to_string());
+        migrated.push(MigratedRef {
+            name: sv_ref.name,
+            kind: sv_ref.kind,
+            from: sv_ref.refname,
+            to,
+        });
+    }
+
+    if !options.dry_run && !migrated.is_empty() {
+        for entry in &migrated {
+            refs::move_sv_ref(&repo, &entry.from, &entry.to)?;
+        }
+
+        let actor = actor::resolve_actor_optional(Some(&workdir), options.actor.as_deref())?;
+        let mut record = OpRecord::new("sv refs migrate", actor);
+        record.affected_refs = migrated.iter().map(|entry| entry.name.clone()).collect();
+        record.undo_data = Some(UndoData {
+            ref_updates: migrated
+                .iter()
+                .zip(&targets)
+                .flat_map(|(entry, target)| {
+                    [
+                        RefUpdate {
+                            name: entry.from.clone(),
+                            old: Some(target.clone()),
+                            new: None,
+                        },
+                        RefUpdate {
+                            name: entry.to.clone(),
+                            old: None,
+                            new: Some(target.clone()),
+                        },
+                    ]
+                })
+                .collect(),
+            ..UndoData::default()
+        });
+        OpLog::for_storage(&storage).append(&record)?;
+    }
+
+    let header = if options.dry_run {
+        format!("sv refs migrate to {namespace} (dry run)")
+    } else {
+        format!("sv refs migrate to {namespace}")
+    };
+    let mut human = HumanOutput::new(header);
+    human.push_summary(
+        if options.dry_run {
+            "Would migrate"
+        } else {
+            "Migrated"
+        },
+        migrated.len().to_string(),
+    );
+    human.push_summary("Skipped", skipped.len().to_string());
+    for entry in &migrated {
+        human.push_detail(format!("{} -> {}", entry.from, entry.to));
+    }
+    for entry in &skipped {
+        human.push_detail(format!("skipped {}: {}", entry.name, entry.reason));
+    }
+    if options.dry_run && !migrated.is_empty() {
+        human.push_next_step("sv refs migrate");
+    }
+
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "refs migrate",
+        &MigrateReport {
+            namespace,
+            dry_run: options.dry_run,
+            migrated,
+            skipped,
+        },
+        Some(&human),
+    )
+}
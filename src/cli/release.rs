@@ -6,22 +6,26 @@ use std::path::PathBuf;
 
 use uuid::Uuid;
 
+use crate::cli::lease::select_leases;
 use crate::config::Config;
 use crate::error::{Error, Result};
 use crate::events::{Event, EventDestination, EventKind};
-use crate::lease::{Lease, LeaseStatus, LeaseStore};
+use crate::lease::{self, Lease, LeaseStatus, LeaseStore};
 use crate::lock::{FileLock, DEFAULT_LOCK_TIMEOUT_MS};
-use crate::oplog::{LeaseChange, OpLog, OpRecord, UndoData};
+use crate::oplog::{LeaseChange, OpDetails, OpLog, OpRecord, UndoData};
 use crate::output::{emit_success, HumanOutput, OutputOptions};
 use crate::storage::Storage;
 
 /// Options for the release command
 pub struct ReleaseOptions {
     pub targets: Vec<String>,
+    pub selector: Option<String>,
     pub actor: Option<String>,
     pub events: Option<String>,
     pub repo: Option<PathBuf>,
     pub force: bool,
+    pub expired: bool,
+    pub dry_run: bool,
     pub json: bool,
     pub quiet: bool,
 }
@@ -29,6 +33,8 @@ pub struct ReleaseOptions {
 /// Result of releasing leases
 #[derive(serde::Serialize)]
 struct ReleaseReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    would: Option<&'static str>,
     released: Vec<ReleasedLease>,
     not_found: Vec<String>,
     not_owned: Vec<NotOwnedInfo>,
@@ -93,10 +99,15 @@ pub fn run(options: ReleaseOptions) -> Result<()> {
     // Load config
     let config = Config::load_from_repo(&workdir);
 
-    let event_destination = EventDestination::parse(options.events.as_deref());
+    // Dry runs never emit events.
+    let event_destination = if options.dry_run {
+        None
+    } else {
+        EventDestination::parse(options.events.as_deref())
+    };
     let mut event_sink = event_destination
         .as_ref()
-        .map(|dest| dest.open())
+        .map(|dest| dest.open_with(&config.events))
         .transpose()?;
 
     // Determine current actor
@@ -114,17 +125,64 @@ pub fn run(options: ReleaseOptions) -> Result<()> {
     let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
 
     // Load existing leases
-    let mut leases: Vec<Lease> = storage.read_jsonl(&leases_file)?;
+    let mut leases: Vec<Lease> = storage.read_leases()?;
     let mut store = LeaseStore::from_vec(leases.clone());
 
     // Expire stale leases first
     store.expire_stale();
 
+    let cwd = std::env::current_dir().unwrap_or_else(|_| workdir.clone());
     let mut released = Vec::new();
     let mut released_details = Vec::new();
     let mut not_found = Vec::new();
     let mut not_owned = Vec::new();
 
+    // --expired releases the actor's expired leases instead of processing targets
+    let expired_details = if options.expired {
+        release_expired(&mut leases, current_actor.as_deref())
+    } else {
+        Vec::new()
+    };
+    released.extend(expired_details.iter().map(|lease| ReleasedLease {
+        id: lease.id.to_string(),
+        pathspec: lease.pathspec.clone(),
+        actor: lease.actor.clone(),
+    }));
+
+    // Release every active lease the selector picks, subject to ownership
+    if let Some(selector) = options.selector.as_deref() {
+        let selected = select_leases(&leases, selector, current_actor.as_deref())?;
+        let ids: Vec<Uuid> = leases
+            .iter()
+            .filter(|lease| lease.status == LeaseStatus::Active && selected.contains(&lease.id))
+            .map(|lease| lease.id)
+            .collect();
+        if ids.is_empty() {
+            not_found.push(selector.to_string());
+        }
+        for id in ids {
+            match find_and_release_by_id(&mut leases, &id, current_actor.as_deref(), options.force)
+            {
+                ReleaseResult::Released(lease) => {
+                    released.push(ReleasedLease {
+                        id: lease.id.to_string(),
+                        pathspec: lease.pathspec.clone(),
+                        actor: lease.actor.clone(),
+                    });
+                    released_details.push(lease);
+                }
+                ReleaseResult::NotOwned(lease) => {
+                    not_owned.push(NotOwnedInfo {
+                        target: selector.to_string(),
+                        lease_id: lease.id.to_string(),
+                        owner: lease.actor.clone(),
+                    });
+                }
+                ReleaseResult::NotFound => {}
+            }
+        }
+    }
+
     // Process each target
     for target in &options.targets {
         // Try to parse as UUID first
@@ -155,10 +213,14 @@ pub fn run(options: ReleaseOptions) -> Result<()> {
                 }
             }
         } else {
-            // Treat as pathspec - release all matching leases owned by current actor
+            // Treat as pathspec - release all matching leases owned by current actor.
+            // Fall back to the raw text so leases stored before normalization
+            // can still be released.
+            let pathspec =
+                lease::resolve_pathspec(&workdir, &cwd, target).unwrap_or_else(|_| target.clone());
             let matching = find_and_release_by_pathspec(
                 &mut leases,
-                target,
+                &pathspec,
                 current_actor.as_deref(),
                 options.force,
             );
@@ -191,16 +253,27 @@ pub fn run(options: ReleaseOptions) -> Result<()> {
     }
 
     // Write updated leases back
-    if !released.is_empty() {
-        write_leases(&leases_file, &leases)?;
+    if !options.dry_run && !released.is_empty() {
+        crate::lease::write_lease_log(&leases_file, &leases)?;
 
         // Record operation in oplog for undo support
         let oplog = OpLog::for_storage(&storage);
         let pathspecs: Vec<_> = released.iter().map(|l| l.pathspec.clone()).collect();
-        let mut record = OpRecord::new(
-            format!("sv release {}", pathspecs.join(" ")),
-            current_actor.clone(),
-        );
+        let command = if options.expired {
+            "sv release --expired".to_string()
+        } else {
+            format!("sv release {}", pathspecs.join(" "))
+        };
+        let mut record = OpRecord::new(command, current_actor.clone());
+        record.affected_paths = pathspecs;
+        record.details = Some(OpDetails {
+            leases: released_details
+                .iter()
+                .chain(expired_details.iter())
+                .cloned()
+                .collect(),
+            ..OpDetails::default()
+        });
         record.undo_data = Some(UndoData {
             lease_changes: released
                 .iter()
@@ -217,21 +290,16 @@ pub fn run(options: ReleaseOptions) -> Result<()> {
 
     let mut event_warning: Option<String> = None;
     if let Some(sink) = event_sink.as_mut() {
-        for lease in &released_details {
-            let event = match Event::new(EventKind::LeaseReleased, lease.actor.clone()).with_data(
-                LeaseReleaseEventData {
-                    id: lease.id.to_string(),
-                    pathspec: lease.pathspec.clone(),
-                    strength: lease.strength.to_string(),
-                    intent: lease.intent.to_string(),
-                    scope: lease.scope.to_string(),
-                    actor: lease.actor.clone(),
-                    ttl: lease.ttl.clone(),
-                    expires_at: lease.expires_at.to_rfc3339(),
-                    released_at: lease.status_changed_at.as_ref().map(|ts| ts.to_rfc3339()),
-                    note: lease.note.clone(),
-                },
-            ) {
+        let events = released_details
+            .iter()
+            .map(|lease| (EventKind::LeaseReleased, lease))
+            .chain(
+                expired_details
+                    .iter()
+                    .map(|lease| (EventKind::LeaseExpired, lease)),
+            );
+        for (kind, lease) in events {
+            let event = match lease_event(kind, lease) {
                 Ok(event) => event,
                 Err(err) => {
                     event_warning = Some(format!("event output failed: {err}"));
@@ -259,12 +327,25 @@ pub fn run(options: ReleaseOptions) -> Result<()> {
 
     // Output results
     let report = ReleaseReport {
+        would: options.dry_run.then_some("release"),
         released: released.clone(),
         not_found: not_found.clone(),
         not_owned: not_owned.clone(),
     };
 
-    let header = if !released.is_empty() {
+    let header = if options.expired {
+        let verb = if options.dry_run {
+            "(dry run): would release"
+        } else {
+            "released"
+        };
+        format!("sv release {verb} {} expired lease(s)", released.len())
+    } else if options.dry_run {
+        format!(
+            "sv release (dry run): would release {} lease(s)",
+            released.len()
+        )
+    } else if !released.is_empty() {
         format!("sv release: released {} lease(s)", released.len())
     } else if !not_found.is_empty() || !not_owned.is_empty() {
         "sv release: partial matches".to_string()
@@ -313,6 +394,46 @@ pub fn run(options: ReleaseOptions) -> Result<()> {
     Ok(())
 }
 
+/// Build a lease event carrying the release payload.
+fn lease_event(kind: EventKind, lease: &Lease) -> Result<Event> {
+    let released_at = match lease.status {
+        LeaseStatus::Released => lease.status_changed_at.as_ref().map(|ts| ts.to_rfc3339()),
+        _ => None,
+    };
+    Event::new(kind, lease.actor.clone()).with_data(LeaseReleaseEventData {
+        id: lease.id.to_string(),
+        pathspec: lease.pathspec.clone(),
+        strength: lease.strength.to_string(),
+        intent: lease.intent.to_string(),
+        scope: lease.scope.to_string(),
+        actor: lease.actor.clone(),
+        ttl: lease.ttl.clone(),
+        expires_at: lease.expires_at.to_rfc3339(),
+        released_at,
+        note: lease.note.clone(),
+    })
+}
+
+/// Mark the actor's expired leases released (reason: expired) and return them.
+///
+/// Covers leases already marked expired and active ones past their expiry.
+/// Without an actor only ownerless leases are released. The records stay in
+/// the log, so the release can be undone.
+fn release_expired(leases: &mut [Lease], current_actor: Option<&str>) -> Vec<Lease> {
+    let now = chrono::Utc::now();
+    let mut released = Vec::new();
+    for lease in leases.iter_mut() {
+        let expired = lease.status == LeaseStatus::Expired
+            || (lease.status == LeaseStatus::Active && lease.expires_at <= now);
+        if expired && lease.actor.as_deref() == current_actor {
+            lease.release();
+            lease.status_reason = Some("expired".to_string());
+            released.push(lease.clone());
+        }
+    }
+    released
+}
+
 enum ReleaseResult {
     Released(Lease),
     NotFound,
@@ -393,27 +514,6 @@ fn find_and_release_by_pathspec(
     results
 }
 
-fn write_leases(path: &PathBuf, leases: &[Lease]) -> Result<()> {
-    use std::fs::File;
-    use std::io::Write;
-
-    // Write to temp file first
-    let temp_path = path.with_extension("tmp");
-    let mut file = File::create(&temp_path)?;
-
-    for lease in leases {
-        let json = serde_json::to_string(lease)?;
-        writeln!(file, "{}", json)?;
-    }
-
-    file.sync_all()?;
-
-    // Atomic rename
-    std::fs::rename(&temp_path, path)?;
-
-    Ok(())
-}
-
 fn resolve_common_dir(repository: &git2::Repository) -> Result<PathBuf> {
     let git_dir = repository.path();
     let commondir_path = git_dir.join("commondir");
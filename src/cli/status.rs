@@ -1,7 +1,9 @@
 //! sv status command implementation
 //!
-//! Provides a single-pane summary of the current workspace state.
+//! Provides a single-pane summary of the current workspace state, or a
+//! stable one-line-per-item format with `--short`.
 
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::path::PathBuf;
 
@@ -10,8 +12,8 @@ use crate::cli::ws;
 use crate::config::Config;
 use crate::error::{Error, Result};
 use crate::git;
-use crate::lease::{Lease, LeaseStatus, LeaseStore};
-use crate::output::{emit_success, HumanOutput, OutputOptions};
+use crate::lease::{Lease, LeaseStatus, LeaseStore, LeaseStrength, Policy};
+use crate::output::{emit_success, outln, HumanOutput, OutputOptions};
 use crate::protect::{compute_status, load_override};
 use crate::storage::Storage;
 use crate::task::TaskStore;
@@ -20,6 +22,7 @@ use crate::task::TaskStore;
 pub struct StatusOptions {
     pub repo: Option<PathBuf>,
     pub actor: Option<String>,
+    pub short: bool,
     pub json: bool,
     pub quiet: bool,
 }
@@ -52,6 +55,15 @@ struct WorkspaceSummary {
     base: String,
     branch: String,
     repo_root: PathBuf,
+    /// Whether this checkout is in the workspace registry (false only when
+    /// sv is not initialized)
+    registered: bool,
+    /// Whether this status call registered the checkout (it was not in the
+    /// registry before)
+    auto_registered: bool,
+    detached: bool,
+    /// Commit HEAD points at (unset on an unborn branch)
+    head: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     ahead_behind: Option<AheadBehind>,
 }
@@ -99,6 +111,26 @@ struct AheadBehind {
     behind: usize,
 }
 
+/// Run `sv status`.
+///
+/// With `short` (and without `json`), prints one space-separated line per
+/// item instead of the summary. The leading tag and field order are a
+/// contract for scripts; new item kinds may be added, existing ones keep
+/// their fields:
+///
+/// ```text
+/// W <workspace> <ahead/behind> <dirty>     W agent1 +3-1 *
+/// L <pathspec> <holder> <strength> <ttl>   L src/auth/** alice excl 1h
+/// T <task-id> <status>                     T sv-abc in_progress
+/// P <path>                                 P Cargo.lock
+/// ```
+///
+/// `<ahead/behind>` is `+<ahead>-<behind>` against the base, or `?` when
+/// unknown; `<dirty>` is `*` with uncommitted changes, else `-`. Leases
+/// are the actor's active ones (`<holder>` is `-` when ownerless),
+/// `<strength>` is one of `obs`, `coop`, `strong`, `excl`, and `<ttl>` is
+/// the remaining time in its largest unit (`2d`, `1h`, `45m`, `30s`).
+/// `P` lists staged files under guard/readonly protection.
 pub fn run(options: StatusOptions) -> Result<()> {
     let start = options
         .repo
@@ -117,6 +149,12 @@ pub fn run(options: StatusOptions) -> Result<()> {
     let actor_name = actor::resolve_actor(Some(&workdir), options.actor.as_deref())?;
 
     // Ensure current workspace is registered (auto-registers if needed when sv is initialized)
+    let was_registered = storage.is_initialized()
+        && storage
+            .read_workspaces()?
+            .workspaces
+            .iter()
+            .any(|entry| entry.path == workdir);
     let workspace_entry = if storage.is_initialized() {
         Some(ws::ensure_current_workspace(
             &storage,
@@ -144,13 +182,20 @@ pub fn run(options: StatusOptions) -> Result<()> {
         .unwrap_or_else(|| workdir.clone());
 
     let head_info = git::head_info(&repository).ok();
+    let detached = head_info.as_ref().is_some_and(|info| info.is_detached);
+    let head_oid = head_info.as_ref().map(|info| info.oid.to_string());
     let workspace_branch = workspace_entry
         .as_ref()
         .map(|entry| entry.branch.clone())
         .or_else(|| head_info.as_ref().and_then(|info| info.shorthand.clone()))
         .unwrap_or_else(|| "HEAD".to_string());
 
-    let ahead_behind = compute_ahead_behind(&repository, &workspace_branch, &workspace_base);
+    // A detached HEAD is compared by commit, not by the registered branch
+    let compared = match (&head_oid, detached) {
+        (Some(oid), true) => oid.clone(),
+        _ => workspace_branch.clone(),
+    };
+    let ahead_behind = compute_ahead_behind(&repository, &compared, &workspace_base);
 
     let workspace_summary = WorkspaceSummary {
         name: workspace_name.clone(),
@@ -158,6 +203,10 @@ pub fn run(options: StatusOptions) -> Result<()> {
         base: workspace_base.clone(),
         branch: workspace_branch.clone(),
         repo_root: repo_root.clone(),
+        registered: workspace_entry.is_some(),
+        auto_registered: workspace_entry.is_some() && !was_registered,
+        detached,
+        head: head_oid.clone(),
         ahead_behind: ahead_behind.clone(),
     };
 
@@ -176,7 +225,7 @@ pub fn run(options: StatusOptions) -> Result<()> {
         task_store.active_tasks_for_workspaces(&workspace_ids, &workspace_names)?
     };
 
-    let leases: Vec<Lease> = storage.read_jsonl(&storage.leases_file())?;
+    let leases: Vec<Lease> = storage.read_leases()?;
     let mut store = LeaseStore::from_vec(leases);
     store.expire_stale();
 
@@ -197,13 +246,7 @@ pub fn run(options: StatusOptions) -> Result<()> {
 
     let mut conflict_ids = HashSet::new();
     for lease in &active_leases {
-        let conflicts = store.check_conflicts(
-            &lease.pathspec,
-            lease.strength,
-            Some(actor_name.as_str()),
-            false,
-        );
-        for conflict in conflicts {
+        for (conflict, _) in store.conflicts_with(lease, &Policy::default()) {
             conflict_ids.insert(conflict.id);
         }
     }
@@ -239,6 +282,39 @@ pub fn run(options: StatusOptions) -> Result<()> {
     let mut protected_files: Vec<String> = blocked_files.into_iter().collect();
     protected_files.sort();
 
+    if options.short && !options.json {
+        if options.quiet {
+            return Ok(());
+        }
+        let ahead_behind = ahead_behind
+            .as_ref()
+            .map_or("?".to_string(), |ab| format!("+{}-{}", ab.ahead, ab.behind));
+        let dirty = if git::has_uncommitted_changes(&repository).unwrap_or(false) {
+            "*"
+        } else {
+            "-"
+        };
+        outln!("W {} {ahead_behind} {dirty}", short_quote(&workspace_name));
+        let mut short_leases = active_leases.clone();
+        short_leases.sort_by(|a, b| a.pathspec.cmp(&b.pathspec));
+        for lease in short_leases {
+            outln!(
+                "L {} {} {} {}",
+                short_quote(&lease.pathspec),
+                lease.actor.as_deref().map_or("-".into(), short_quote),
+                short_strength(lease.strength),
+                short_remaining(lease.expires_at)
+            );
+        }
+        for task in &active_tasks {
+            outln!("T {} {}", short_quote(&task.id), short_quote(&task.status));
+        }
+        for path in &protected_files {
+            outln!("P {}", short_quote(path));
+        }
+        return Ok(());
+    }
+
     let mut warnings = Vec::new();
     let mut next_steps = Vec::new();
 
@@ -247,6 +323,16 @@ pub fn run(options: StatusOptions) -> Result<()> {
         next_steps.push("sv init".to_string());
     }
 
+    if detached {
+        let short = head_oid
+            .as_deref()
+            .map_or("", |oid| &oid[..8.min(oid.len())]);
+        warnings.push(format!("HEAD is detached at {short}"));
+        if let Some(entry) = workspace_entry.as_ref().filter(|e| e.branch != "HEAD") {
+            next_steps.push(format!("git switch {}", entry.branch));
+        }
+    }
+
     let config_path = workdir.join(".sv.toml");
     if !config_path.exists() {
         warnings.push("missing .sv.toml; using defaults".to_string());
@@ -298,6 +384,8 @@ pub fn run(options: StatusOptions) -> Result<()> {
 
     let header = if !storage.is_initialized() {
         "sv status: sv not initialized".to_string()
+    } else if detached {
+        "sv status: detached HEAD".to_string()
     } else {
         "sv status: workspace ready".to_string()
     };
@@ -308,9 +396,17 @@ pub fn run(options: StatusOptions) -> Result<()> {
         "workspace",
         format!("{} ({})", workspace_name, workdir.display()),
     );
-    human.push_summary("branch", workspace_branch.clone());
+    match head_oid.as_deref().filter(|_| detached) {
+        Some(oid) => human.push_summary("branch", format!("HEAD (detached at {oid})")),
+        None => human.push_summary("branch", workspace_branch.clone()),
+    }
     human.push_summary("base", workspace_base);
     human.push_summary("repo", repo_root.display().to_string());
+    if workspace_summary.auto_registered {
+        human.push_detail(format!(
+            "registered this checkout as workspace '{workspace_name}'"
+        ));
+    }
 
     if let Some(status) = &ahead_behind {
         human.push_detail(format!(
@@ -403,6 +499,53 @@ pub fn run(options: StatusOptions) -> Result<()> {
     Ok(())
 }
 
+/// Quote a `--short` field the way git porcelain quotes paths: fields with
+/// whitespace, quotes, backslashes, control or non-ASCII characters become a
+/// C-style string literal (non-ASCII bytes as octal escapes).
+fn short_quote(field: &str) -> Cow<'_, str> {
+    let plain = !field.is_empty()
+        && field
+            .bytes()
+            .all(|byte| byte.is_ascii_graphic() && byte != b'"' && byte != b'\\');
+    if plain {
+        return Cow::Borrowed(field);
+    }
+    let mut quoted = String::with_capacity(field.len() + 2);
+    quoted.push('"');
+    for byte in field.bytes() {
+        match byte {
+            b'"' => quoted.push_str("\\\""),
+            b'\\' => quoted.push_str("\\\\"),
+            b'\t' => quoted.push_str("\\t"),
+            b'\n' => quoted.push_str("\\n"),
+            b'\r' => quoted.push_str("\\r"),
+            byte if byte == b' ' || byte.is_ascii_graphic() => quoted.push(byte as char),
+            byte => quoted.push_str(&format!("\\{byte:03o}")),
+        }
+    }
+    quoted.push('"');
+    Cow::Owned(quoted)
+}
+
+fn short_strength(strength: LeaseStrength) -> &'static str {
+    match strength {
+        LeaseStrength::Observe => "obs",
+        LeaseStrength::Cooperative => "coop",
+        LeaseStrength::Strong => "strong",
+        LeaseStrength::Exclusive => "excl",
+    }
+}
+
+fn short_remaining(expires_at: chrono::DateTime<chrono::Utc>) -> String {
+    let remaining = (expires_at - chrono::Utc::now()).num_seconds().max(0);
+    match remaining {
+        s if s >= 86_400 => format!("{}d", s / 86_400),
+        s if s >= 3_600 => format!("{}h", s / 3_600),
+        s if s >= 60 => format!("{}m", s / 60),
+        s => format!("{s}s"),
+    }
+}
+
 fn compute_ahead_behind(repo: &git2::Repository, branch: &str, base: &str) -> Option<AheadBehind> {
     let branch_oid = resolve_oid(repo, branch)?;
     let base_oid = resolve_oid(repo, base)?;
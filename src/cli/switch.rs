@@ -10,7 +10,7 @@ use serde::Serialize;
 
 use crate::error::{Error, Result};
 use crate::git;
-use crate::output::{emit_success, HumanOutput, OutputOptions};
+use crate::output::{emit_success, outln, HumanOutput, OutputOptions};
 use crate::storage::Storage;
 
 /// Options for the switch command
@@ -47,7 +47,7 @@ pub fn run(options: SwitchOptions) -> Result<()> {
     let output = switch_workspace(&storage, &workspace)?;
 
     if !options.json {
-        println!("{}", output.path.display());
+        outln!("{}", output.path.display());
         return Ok(());
     }
 
@@ -76,7 +76,7 @@ fn resolve_workspace_name(
     json: bool,
 ) -> Result<String> {
     match requested.map(str::trim) {
-        Some(name) if name.is_empty() => Err(Error::InvalidArgument(
+        Some("") => Err(Error::InvalidArgument(
             "workspace name cannot be empty".to_string(),
         )),
         Some(name) => Ok(name.to_string()),
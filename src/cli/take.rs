@@ -8,20 +8,25 @@ use crate::actor;
 use crate::config::Config;
 use crate::error::{Error, Result};
 use crate::events::{Event, EventDestination, EventKind};
-use crate::lease::{parse_duration, Lease, LeaseIntent, LeaseScope, LeaseStore, LeaseStrength};
+use crate::lease::{
+    self as lease, parse_duration, ActorQuota, ConflictReason, Lease, LeaseIntent, LeaseScope,
+    LeaseStatus, LeaseStore, LeaseStrength, Policy,
+};
 use crate::lock::{FileLock, DEFAULT_LOCK_TIMEOUT_MS};
-use crate::oplog::{LeaseChange, OpLog, OpRecord, UndoData};
-use crate::output::{emit_success, HumanOutput, OutputOptions};
+use crate::oplog::{LeaseChange, OpDetails, OpLog, OpRecord, UndoData};
+use crate::output::{self, emit_success, HumanOutput, OutputOptions};
 use crate::storage::Storage;
 
 /// Options for the take command
 pub struct TakeOptions {
     pub paths: Vec<String>,
-    pub strength: String,
-    pub intent: String,
+    pub strength: Option<String>,
+    pub intent: Option<String>,
     pub scope: String,
     pub ttl: String,
     pub note: Option<String>,
+    pub force: bool,
+    pub dry_run: bool,
     pub actor: Option<String>,
     pub events: Option<String>,
     pub repo: Option<PathBuf>,
@@ -32,10 +37,14 @@ pub struct TakeOptions {
 /// Result of taking leases
 #[derive(serde::Serialize)]
 struct TakeReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    would: Option<&'static str>,
     actor: String,
     created: Vec<LeaseInfo>,
     updated: Vec<LeaseInfo>,
     conflicts: Vec<ConflictInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    covered: Vec<CoveredInfo>,
     summary: TakeSummary,
 }
 
@@ -63,6 +72,16 @@ struct ConflictInfo {
     holder: Option<String>,
     strength: String,
     lease_id: String,
+    reason: ConflictReason,
+}
+
+/// A requested path skipped because one of the actor's leases already covers it
+#[derive(Clone, serde::Serialize)]
+struct CoveredInfo {
+    path: String,
+    lease_id: String,
+    covered_by: String,
+    strength: String,
 }
 
 #[derive(serde::Serialize)]
@@ -110,17 +129,24 @@ pub fn run(options: TakeOptions) -> Result<()> {
     // Load config
     let config = Config::load_from_repo(&workdir);
 
-    let event_destination = EventDestination::parse(options.events.as_deref());
+    // Dry runs never emit events. With --json, events may only share stdout
+    // when the JSON result was redirected by --output; otherwise they are
+    // suppressed so the JSON document stays parseable.
+    let event_destination = match EventDestination::parse(options.events.as_deref()) {
+        _ if options.dry_run => None,
+        Some(EventDestination::Stdout) if options.json && !output::result_redirected() => None,
+        destination => destination,
+    };
     let mut event_sink = event_destination
         .as_ref()
-        .map(|dest| dest.open())
+        .map(|dest| dest.open_with(&config.events))
         .transpose()?;
 
-    // Parse strength
-    let strength: LeaseStrength = options.strength.parse()?;
-
-    // Parse intent
-    let intent: LeaseIntent = options.intent.parse()?;
+    // Parse strength and intent, defaulting from [leases] config
+    let strength: LeaseStrength = config
+        .leases
+        .resolve_strength(options.strength.as_deref())?;
+    let intent: LeaseIntent = config.leases.resolve_intent(options.intent.as_deref())?;
 
     // Parse scope
     let scope: LeaseScope = options.scope.parse()?;
@@ -134,7 +160,7 @@ pub fn run(options: TakeOptions) -> Result<()> {
     let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
 
     // Load existing leases
-    let existing_leases: Vec<Lease> = storage.read_jsonl(&leases_file)?;
+    let existing_leases: Vec<Lease> = storage.read_leases()?;
     let mut store = LeaseStore::from_vec(existing_leases);
 
     // Expire stale leases
@@ -149,168 +175,56 @@ pub fn run(options: TakeOptions) -> Result<()> {
         return Err(Error::NoteRequired(strength.to_string()));
     }
 
-    let mut created_leases = Vec::new();
-    let mut updated_leases = Vec::new();
-    let mut conflicts = Vec::new();
-
-    // Create or update leases for each path
-    for pathspec in &options.paths {
-        // Check for conflicts with OTHER actors
-        let path_conflicts = store.check_conflicts(
-            pathspec,
-            strength,
-            actor.as_deref(),
-            false, // TODO: support --allow-overlap flag
-        );
-
-        if !path_conflicts.is_empty() {
-            for conflict in path_conflicts {
-                conflicts.push(ConflictInfo {
-                    path: pathspec.clone(),
-                    holder: conflict.actor.clone(),
-                    strength: conflict.strength.to_string(),
-                    lease_id: conflict.id.to_string(),
-                });
-            }
-            continue;
-        }
-
-        // Check if this actor already has a lease on this exact path (upsert)
-        if let Some(actor_name) = actor.as_deref() {
-            if let Some(existing) = store.find_by_actor_and_path_mut(actor_name, pathspec) {
-                // Update existing lease instead of creating new one
-                existing.update(
-                    strength,
-                    intent,
-                    scope.clone(),
-                    &options.ttl,
-                    options.note.clone(),
-                )?;
-                updated_leases.push(existing.clone());
-                continue;
-            }
-        }
-
-        // Build a new lease
-        let mut builder = Lease::builder(pathspec)
-            .strength(strength)
-            .intent(intent)
-            .scope(scope.clone())
-            .ttl(&options.ttl);
-
-        if let Some(ref actor_name) = actor {
-            builder = builder.actor(actor_name);
-        }
-
-        if let Some(ref note) = options.note {
-            builder = builder.note(note);
-        }
-
-        let lease = builder.build()?;
-
-        // Add to store for conflict checking of subsequent paths
-        store.add(lease.clone());
-
-        created_leases.push(lease);
-    }
-
-    // Write leases to storage
-    if !updated_leases.is_empty() {
-        // If we updated any leases, we need to rewrite the entire file
-        storage.save_leases(&store)?;
-    } else {
-        // Only new leases - can just append
-        for lease in &created_leases {
-            storage.append_jsonl(&leases_file, lease)?;
-        }
-    }
-
-    // Record operation in oplog for undo support
-    if !created_leases.is_empty() || !updated_leases.is_empty() {
-        let oplog = OpLog::for_storage(&storage);
-        let all_pathspecs: Vec<_> = created_leases
-            .iter()
-            .chain(updated_leases.iter())
-            .map(|l| l.pathspec.clone())
-            .collect();
-        let mut record = OpRecord::new(
-            format!("sv take {}", all_pathspecs.join(" ")),
-            actor.clone(),
-        );
-
-        let mut lease_changes: Vec<LeaseChange> = created_leases
-            .iter()
-            .map(|l| LeaseChange {
-                lease_id: l.id.to_string(),
-                action: "create".to_string(),
-            })
-            .collect();
-
-        lease_changes.extend(updated_leases.iter().map(|l| LeaseChange {
-            lease_id: l.id.to_string(),
-            action: "update".to_string(),
-        }));
-
-        record.undo_data = Some(UndoData {
-            lease_changes,
-            ..UndoData::default()
-        });
-        // Best effort - don't fail the command if oplog write fails
-        let _ = oplog.append(&record);
+    // Apply leases.max_ttl before creating or updating anything
+    let limited_ttl = config.leases.limit_ttl(&options.ttl)?;
+    let ttl = limited_ttl.ttl.clone();
+
+    // TODO: support --allow-overlap flag
+    let policy = Policy::from_compat(&config.leases.compat, false);
+
+    // Validate and resolve every path (relative to cwd) before touching the store
+    let cwd = std::env::current_dir().unwrap_or_else(|_| workdir.clone());
+    let pathspecs = options
+        .paths
+        .iter()
+        .map(|raw| lease::resolve_pathspec(&workdir, &cwd, raw))
+        .collect::<Result<Vec<_>>>()?;
+
+    let spec = LeaseSpec {
+        strength,
+        intent,
+        scope,
+        ttl: ttl.clone(),
+        note: options.note.clone(),
+        actor: actor.clone(),
+        allow_redundant: options.force,
+    };
+    let LeasePlan {
+        created: created_leases,
+        updated: updated_leases,
+        conflicts,
+        covered,
+        ..
+    } = plan_leases(
+        &mut store,
+        &pathspecs,
+        &spec,
+        &policy,
+        config.leases.actor_quota(),
+    )?;
+
+    // Write leases to storage and record the operation for undo support.
+    // Dry runs leave storage, oplog, and events untouched.
+    if !options.dry_run {
+        persist_leases(&storage, &store, &created_leases, &updated_leases)?;
+        record_take(&storage, actor.clone(), &created_leases, &updated_leases);
     }
 
     let mut event_warning: Option<String> = None;
     if let Some(sink) = event_sink.as_mut() {
-        // Emit events for created leases
-        for lease in &created_leases {
-            let event = match Event::new(EventKind::LeaseCreated, lease.actor.clone()).with_data(
-                LeaseEventData {
-                    id: lease.id.to_string(),
-                    pathspec: lease.pathspec.clone(),
-                    strength: lease.strength.to_string(),
-                    intent: lease.intent.to_string(),
-                    scope: lease.scope.to_string(),
-                    actor: lease.actor.clone(),
-                    ttl: lease.ttl.clone(),
-                    expires_at: lease.expires_at.to_rfc3339(),
-                    created_at: lease.created_at.to_rfc3339(),
-                    note: lease.note.clone(),
-                },
-            ) {
-                Ok(event) => event,
-                Err(err) => {
-                    event_warning = Some(format!("event output failed: {err}"));
-                    break;
-                }
-            };
-            if let Err(err) = sink.emit(&event) {
-                event_warning = Some(format!("event output failed: {err}"));
-                break;
-            }
-        }
-        // Emit events for updated leases (using LeaseCreated for now, could add LeaseUpdated)
-        for lease in &updated_leases {
-            let event = match Event::new(EventKind::LeaseCreated, lease.actor.clone()).with_data(
-                LeaseEventData {
-                    id: lease.id.to_string(),
-                    pathspec: lease.pathspec.clone(),
-                    strength: lease.strength.to_string(),
-                    intent: lease.intent.to_string(),
-                    scope: lease.scope.to_string(),
-                    actor: lease.actor.clone(),
-                    ttl: lease.ttl.clone(),
-                    expires_at: lease.expires_at.to_rfc3339(),
-                    created_at: lease.created_at.to_rfc3339(),
-                    note: lease.note.clone(),
-                },
-            ) {
-                Ok(event) => event,
-                Err(err) => {
-                    event_warning = Some(format!("event output failed: {err}"));
-                    break;
-                }
-            };
-            if let Err(err) = sink.emit(&event) {
+        // Updated leases are reported as LeaseCreated for now (could add LeaseUpdated)
+        for lease in created_leases.iter().chain(updated_leases.iter()) {
+            if let Err(err) = lease_created_event(lease).and_then(|event| sink.emit(&event)) {
                 event_warning = Some(format!("event output failed: {err}"));
                 break;
             }
@@ -330,10 +244,12 @@ pub fn run(options: TakeOptions) -> Result<()> {
     };
 
     let report = TakeReport {
+        would: options.dry_run.then_some("take"),
         actor: actor_label.clone(),
         created: created_leases.iter().map(lease_to_info).collect(),
         updated: updated_leases.iter().map(lease_to_info).collect(),
         conflicts: conflicts.clone(),
+        covered: covered.clone(),
         summary: TakeSummary {
             created: created_leases.len(),
             updated: updated_leases.len(),
@@ -341,9 +257,17 @@ pub fn run(options: TakeOptions) -> Result<()> {
         },
     };
 
-    let events_to_stdout = matches!(event_destination, Some(EventDestination::Stdout));
+    let events_to_stdout =
+        matches!(event_destination, Some(EventDestination::Stdout)) && !output::result_redirected();
     let total_leases = created_leases.len() + updated_leases.len();
-    let header = if total_leases > 0 && !conflicts.is_empty() {
+    let header = if options.dry_run {
+        format!(
+            "sv take (dry run): would create {}, update {} lease(s) ({} conflict(s))",
+            created_leases.len(),
+            updated_leases.len(),
+            conflicts.len()
+        )
+    } else if total_leases > 0 && !conflicts.is_empty() {
         if updated_leases.is_empty() {
             format!(
                 "sv take: created {} lease(s) ({} conflict(s))",
@@ -378,11 +302,19 @@ pub fn run(options: TakeOptions) -> Result<()> {
         }
     } else if !conflicts.is_empty() {
         format!("sv take: {} conflict(s)", conflicts.len())
+    } else if !covered.is_empty() {
+        format!(
+            "sv take: {} path(s) already covered by your leases",
+            covered.len()
+        )
     } else {
         "sv take: no leases created".to_string()
     };
 
     let mut human = HumanOutput::new(header);
+    if let Some(warning) = limited_ttl.warning() {
+        human.push_warning(warning);
+    }
     if let Some(warning) = event_warning {
         human.push_warning(warning);
     }
@@ -413,19 +345,24 @@ pub fn run(options: TakeOptions) -> Result<()> {
         ));
     }
 
-    for conflict in &conflicts {
-        human.push_warning(format!(
-            "conflict: {} held by {} ({})",
-            conflict.path,
-            conflict.holder.as_deref().unwrap_or("(ownerless)"),
-            conflict.strength
+    for info in &covered {
+        human.push_detail(format!(
+            "{} (already covered by {} lease {} on {})",
+            info.path, info.strength, info.lease_id, info.covered_by
         ));
     }
 
+    for conflict in &conflicts {
+        human.push_warning(format!("conflict: {}: {}", conflict.path, conflict.reason));
+    }
+
     if let Some(conflict) = conflicts.first() {
         human.push_next_step(format!("sv lease who {}", conflict.path));
         human.push_next_step("retry with --allow-overlap if intentional");
     }
+    if !covered.is_empty() && total_leases == 0 {
+        human.push_next_step("retry with --force to take separate leases anyway");
+    }
 
     let conflicts_only =
         created_leases.is_empty() && updated_leases.is_empty() && !conflicts.is_empty();
@@ -456,6 +393,343 @@ pub fn run(options: TakeOptions) -> Result<()> {
     Ok(())
 }
 
+/// Lease attributes shared by every path in one take
+pub(crate) struct LeaseSpec {
+    pub strength: LeaseStrength,
+    pub intent: LeaseIntent,
+    pub scope: LeaseScope,
+    pub ttl: String,
+    pub note: Option<String>,
+    pub actor: Option<String>,
+    /// Take paths the actor's existing leases already cover
+    pub allow_redundant: bool,
+}
+
+/// Leases a take would create or update, and the paths that conflict
+struct LeasePlan {
+    created: Vec<Lease>,
+    updated: Vec<Lease>,
+    /// Updated leases as they were before this take
+    previous: Vec<Lease>,
+    conflicts: Vec<ConflictInfo>,
+    /// Paths skipped because an existing lease of the actor covers them
+    covered: Vec<CoveredInfo>,
+}
+
+/// Plan leases for each pathspec against `store`, applying them in memory.
+///
+/// Fails when the planned leases would push the actor past `quota`.
+fn plan_leases(
+    store: &mut LeaseStore,
+    pathspecs: &[String],
+    spec: &LeaseSpec,
+    policy: &Policy,
+    quota: ActorQuota,
+) -> Result<LeasePlan> {
+    let held: Vec<Lease> = match spec.actor.as_deref() {
+        Some(actor_name) => store
+            .by_actor(actor_name)
+            .filter(|lease| lease.is_active())
+            .cloned()
+            .collect(),
+        None => Vec::new(),
+    };
+    let mut plan = LeasePlan {
+        created: Vec::new(),
+        updated: Vec::new(),
+        previous: Vec::new(),
+        conflicts: Vec::new(),
+        covered: Vec::new(),
+    };
+    let until = chrono::Utc::now() + parse_duration(&spec.ttl)?;
+
+    for pathspec in pathspecs {
+        // Check for conflicts with OTHER actors
+        let mut requested = Lease::probe(pathspec, spec.strength, spec.actor.as_deref());
+        requested.scope = spec.scope.clone();
+        let path_conflicts = store.conflicts_with(&requested, policy);
+
+        if !path_conflicts.is_empty() {
+            for (conflict, reason) in path_conflicts {
+                plan.conflicts.push(ConflictInfo {
+                    path: pathspec.clone(),
+                    holder: conflict.actor.clone(),
+                    strength: conflict.strength.to_string(),
+                    lease_id: conflict.id.to_string(),
+                    reason,
+                });
+            }
+            continue;
+        }
+
+        // Check if this actor already has a lease on this exact path (upsert)
+        if let Some(actor_name) = spec.actor.as_deref() {
+            if let Some(existing) = store.find_by_actor_and_path_mut(actor_name, pathspec) {
+                // Update existing lease instead of creating new one
+                plan.previous.push(existing.clone());
+                existing.update(
+                    spec.strength,
+                    spec.intent,
+                    spec.scope.clone(),
+                    &spec.ttl,
+                    spec.note.clone(),
+                )?;
+                plan.updated.push(existing.clone());
+                continue;
+            }
+
+            // Skip paths already held at equal-or-greater strength
+            if !spec.allow_redundant {
+                if let Some(covering) = find_covering_lease(store, actor_name, pathspec, spec) {
+                    // Renew a covering lease that would expire before the
+                    // requested TTL, so the path stays held as long as asked
+                    if let Some(lease) = store.find_mut(&covering) {
+                        if lease.expires_at < until {
+                            plan.previous.push(lease.clone());
+                            lease.renew(&spec.ttl)?;
+                            plan.updated.push(lease.clone());
+                        }
+                        plan.covered.push(CoveredInfo {
+                            path: pathspec.clone(),
+                            lease_id: lease.id.to_string(),
+                            covered_by: lease.pathspec.clone(),
+                            strength: lease.strength.to_string(),
+                        });
+                    }
+                    continue;
+                }
+            }
+        }
+
+        // Build a new lease
+        let mut builder = Lease::builder(pathspec)
+            .id(store.next_id())
+            .strength(spec.strength)
+            .intent(spec.intent)
+            .scope(spec.scope.clone())
+            .ttl(&spec.ttl);
+
+        if let Some(ref actor_name) = spec.actor {
+            builder = builder.actor(actor_name);
+        }
+
+        if let Some(ref note) = spec.note {
+            builder = builder.note(note);
+        }
+
+        let lease = builder.build()?;
+
+        // Add to store for conflict checking of subsequent paths
+        store.add(lease.clone());
+
+        plan.created.push(lease);
+    }
+
+    if let Some(actor_name) = spec.actor.as_deref() {
+        quota.check(actor_name, &held, store)?;
+    }
+
+    Ok(plan)
+}
+
+/// Find an active lease of `actor` that already covers `pathspec` for `spec`.
+///
+/// A lease covers the request when its pathspec contains the requested one,
+/// its strength is at least the requested strength, and its scope is repo-wide
+/// or the same as the requested scope. Its expiry is not considered; the
+/// caller renews it to the requested TTL.
+fn find_covering_lease(
+    store: &LeaseStore,
+    actor: &str,
+    pathspec: &str,
+    spec: &LeaseSpec,
+) -> Option<uuid::Uuid> {
+    store
+        .by_actor(actor)
+        .find(|lease| {
+            lease.is_active()
+                && lease.strength.priority() >= spec.strength.priority()
+                && (lease.scope == LeaseScope::Repo || lease.scope == spec.scope)
+                && lease::pathspec_covers(&lease.pathspec, pathspec)
+        })
+        .map(|lease| lease.id)
+}
+
+/// Write planned leases; the caller holds the leases lock.
+fn persist_leases(
+    storage: &Storage,
+    store: &LeaseStore,
+    created: &[Lease],
+    updated: &[Lease],
+) -> Result<()> {
+    if !updated.is_empty() {
+        // If we updated any leases, we need to rewrite the entire file
+        storage.save_leases(store)
+    } else {
+        // Only new leases - can just append
+        let leases_file = storage.leases_file();
+        for lease in created {
+            storage.append_jsonl(&leases_file, lease)?;
+        }
+        Ok(())
+    }
+}
+
+/// Record a take in the oplog (best effort).
+fn record_take(storage: &Storage, actor: Option<String>, created: &[Lease], updated: &[Lease]) {
+    if created.is_empty() && updated.is_empty() {
+        return;
+    }
+    let oplog = OpLog::for_storage(storage);
+    let all_pathspecs: Vec<_> = created
+        .iter()
+        .chain(updated.iter())
+        .map(|l| l.pathspec.clone())
+        .collect();
+    let mut record = OpRecord::new(format!("sv take {}", all_pathspecs.join(" ")), actor);
+    record.affected_paths = all_pathspecs;
+    record.details = Some(OpDetails {
+        leases: created.iter().chain(updated.iter()).cloned().collect(),
+        ..OpDetails::default()
+    });
+
+    let mut lease_changes: Vec<LeaseChange> = created
+        .iter()
+        .map(|l| LeaseChange {
+            lease_id: l.id.to_string(),
+            action: "create".to_string(),
+        })
+        .collect();
+
+    lease_changes.extend(updated.iter().map(|l| LeaseChange {
+        lease_id: l.id.to_string(),
+        action: "update".to_string(),
+    }));
+
+    record.undo_data = Some(UndoData {
+        lease_changes,
+        ..UndoData::default()
+    });
+    // Best effort - don't fail the command if oplog write fails
+    let _ = oplog.append(&record);
+}
+
+/// Build the `lease_created` event for a lease.
+pub(crate) fn lease_created_event(lease: &Lease) -> Result<Event> {
+    Event::new(EventKind::LeaseCreated, lease.actor.clone()).with_data(LeaseEventData {
+        id: lease.id.to_string(),
+        pathspec: lease.pathspec.clone(),
+        strength: lease.strength.to_string(),
+        intent: lease.intent.to_string(),
+        scope: lease.scope.to_string(),
+        actor: lease.actor.clone(),
+        ttl: lease.ttl.clone(),
+        expires_at: lease.expires_at.to_rfc3339(),
+        created_at: lease.created_at.to_rfc3339(),
+        note: lease.note.clone(),
+    })
+}
+
+/// Leases taken by [`take_all`], with enough state to roll them back
+pub(crate) struct TakenLeases {
+    pub created: Vec<Lease>,
+    pub updated: Vec<Lease>,
+    previous: Vec<Lease>,
+}
+
+/// Take leases on every pathspec or none of them.
+///
+/// Used by commands that take leases as a side effect (`sv task start
+/// --lease`): any conflict fails the whole request before anything is
+/// written, so the caller never ends up holding only some of the paths.
+pub(crate) fn take_all(
+    storage: &Storage,
+    config: &Config,
+    pathspecs: &[String],
+    spec: &LeaseSpec,
+) -> Result<TakenLeases> {
+    if config.leases.require_note && spec.strength.requires_note() && spec.note.is_none() {
+        return Err(Error::NoteRequired(spec.strength.to_string()));
+    }
+
+    let leases_file = storage.leases_file();
+    let lock_path = leases_file.with_extension("lock");
+    let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
+
+    let existing_leases: Vec<Lease> = storage.read_leases()?;
+    let mut store = LeaseStore::from_vec(existing_leases);
+    store.expire_stale();
+    let grace = parse_duration(&config.leases.expiration_grace)?;
+    let _expired = store.cleanup_expired(grace);
+
+    let policy = Policy::from_compat(&config.leases.compat, false);
+    let plan = plan_leases(
+        &mut store,
+        pathspecs,
+        spec,
+        &policy,
+        config.leases.actor_quota(),
+    )?;
+    if let Some(conflict) = plan.conflicts.first() {
+        return Err(Error::LeaseConflict {
+            path: conflict.path.clone().into(),
+            holder: conflict
+                .holder
+                .clone()
+                .unwrap_or_else(|| "(ownerless)".to_string()),
+            strength: conflict.strength.clone(),
+        });
+    }
+
+    persist_leases(storage, &store, &plan.created, &plan.updated)?;
+    record_take(storage, spec.actor.clone(), &plan.created, &plan.updated);
+
+    Ok(TakenLeases {
+        created: plan.created,
+        updated: plan.updated,
+        previous: plan.previous,
+    })
+}
+
+/// Undo a [`take_all`]: release the leases it created and restore updated ones.
+pub(crate) fn rollback(storage: &Storage, taken: &TakenLeases, reason: &str) -> Result<()> {
+    let leases_file = storage.leases_file();
+    let lock_path = leases_file.with_extension("lock");
+    let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
+
+    let mut leases: Vec<Lease> = storage.read_leases()?;
+    for lease in &mut leases {
+        if taken.created.iter().any(|created| created.id == lease.id) {
+            lease.status = LeaseStatus::Released;
+            lease.status_changed_at = Some(chrono::Utc::now());
+            lease.status_reason = Some(reason.to_string());
+        } else if let Some(previous) = taken.previous.iter().find(|p| p.id == lease.id) {
+            *lease = previous.clone();
+        }
+    }
+    storage.save_leases(&LeaseStore::from_vec(leases))?;
+
+    if !taken.created.is_empty() {
+        let pathspecs: Vec<_> = taken.created.iter().map(|l| l.pathspec.clone()).collect();
+        let actor = taken.created[0].actor.clone();
+        let mut record = OpRecord::new(format!("sv release {}", pathspecs.join(" ")), actor);
+        record.affected_paths = pathspecs;
+        record.undo_data = Some(UndoData {
+            lease_changes: taken
+                .created
+                .iter()
+                .map(|l| LeaseChange {
+                    lease_id: l.id.to_string(),
+                    action: "release".to_string(),
+                })
+                .collect(),
+            ..UndoData::default()
+        });
+        let _ = OpLog::for_storage(storage).append(&record);
+    }
+    Ok(())
+}
+
 fn resolve_common_dir(repository: &git2::Repository) -> Result<PathBuf> {
     let git_dir = repository.path();
     let commondir_path = git_dir.join("commondir");
@@ -6,14 +6,18 @@ use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
 
 use crate::actor;
-use crate::cli::ws;
+use crate::cli::{take, ws};
 use crate::config::Config;
 use crate::error::{Error, Result};
 use crate::events::{Event, EventDestination, EventKind};
 use crate::git;
 use crate::integrations::forge as forge_integration;
-use crate::output::{emit_success, HumanOutput, OutputOptions};
+use crate::lease::{self, Lease, LeaseIntent, LeaseScope, LeaseStrength};
+use crate::merge;
+use crate::oplog::{OpLog, OpRecord, RefUpdate, UndoData};
+use crate::output::{emit_success, outln, HumanOutput, OutputOptions};
 use crate::project::ProjectStore;
+use crate::refs;
 use crate::repo_stats;
 use crate::storage::{Storage, WorkspaceEntry};
 use crate::task::{
@@ -23,6 +27,7 @@ use crate::task::{
 
 pub struct NewOptions {
     pub title: String,
+    pub id: Option<String>,
     pub status: Option<String>,
     pub priority: Option<String>,
     pub body: Option<String>,
@@ -34,7 +39,7 @@ pub struct NewOptions {
 }
 
 pub struct ListOptions {
-    pub status: Option<String>,
+    pub status: Vec<String>,
     pub priority: Option<String>,
     pub epic: Option<String>,
     pub project: Option<String>,
@@ -42,6 +47,7 @@ pub struct ListOptions {
     pub actor: Option<String>,
     pub updated_since: Option<String>,
     pub limit: Option<usize>,
+    pub format: Option<String>,
     pub repo: Option<PathBuf>,
     pub json: bool,
     pub quiet: bool,
@@ -55,6 +61,7 @@ pub struct ReadyOptions {
     pub actor: Option<String>,
     pub updated_since: Option<String>,
     pub limit: Option<usize>,
+    pub format: Option<String>,
     pub repo: Option<PathBuf>,
     pub json: bool,
     pub quiet: bool,
@@ -62,7 +69,7 @@ pub struct ReadyOptions {
 
 pub struct CountOptions {
     pub ready: bool,
-    pub status: Option<String>,
+    pub status: Vec<String>,
     pub priority: Option<String>,
     pub epic: Option<String>,
     pub project: Option<String>,
@@ -70,6 +77,7 @@ pub struct CountOptions {
     pub actor: Option<String>,
     pub updated_since: Option<String>,
     pub limit: Option<usize>,
+    pub fail_over: Option<usize>,
     pub repo: Option<PathBuf>,
     pub json: bool,
     pub quiet: bool,
@@ -81,8 +89,18 @@ pub struct StatsOptions {
     pub quiet: bool,
 }
 
+pub struct TimelineOptions {
+    pub since: Option<String>,
+    pub actor: Option<String>,
+    pub limit: usize,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
 pub struct ShowOptions {
     pub id: String,
+    pub events_file: Option<PathBuf>,
     pub repo: Option<PathBuf>,
     pub json: bool,
     pub quiet: bool,
@@ -91,6 +109,12 @@ pub struct ShowOptions {
 pub struct StartOptions {
     pub id: String,
     pub takeover: bool,
+    /// Paths to lease (cooperative, workspace-scoped) as part of the start
+    pub lease: Vec<String>,
+    /// Create a `sv/ws/<task-id>` workspace and start the task there
+    pub create_workspace: bool,
+    /// Base ref for the created workspace
+    pub base: Option<String>,
     pub actor: Option<String>,
     pub events: Option<String>,
     pub repo: Option<PathBuf>,
@@ -111,6 +135,10 @@ pub struct StatusOptions {
 pub struct CloseOptions {
     pub id: String,
     pub status: Option<String>,
+    /// Merge the task's workspace branch into its base after closing
+    pub merge: bool,
+    /// Merge target (default: the workspace base)
+    pub into: Option<String>,
     pub actor: Option<String>,
     pub events: Option<String>,
     pub repo: Option<PathBuf>,
@@ -244,6 +272,22 @@ pub struct RelationsOptions {
 }
 
 pub struct SyncOptions {
+    pub push: bool,
+    pub pull: bool,
+    pub remote: String,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+pub struct MergeDriverOptions {
+    pub base: PathBuf,
+    pub ours: PathBuf,
+    pub theirs: PathBuf,
+    pub json: bool,
+}
+
+pub struct InstallMergeDriverOptions {
     pub repo: Option<PathBuf>,
     pub json: bool,
     pub quiet: bool,
@@ -326,7 +370,8 @@ struct TaskContext {
 
 pub fn run_new(options: NewOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, false)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let title = options.title.trim();
     if title.is_empty() {
         return Err(Error::InvalidArgument("title cannot be empty".to_string()));
@@ -341,13 +386,28 @@ pub fn run_new(options: NewOptions) -> Result<()> {
         None => ctx.store.default_priority(),
     };
 
-    let task_id = ctx.store.generate_task_id()?;
+    let task_id = match options.id.as_deref() {
+        Some(id) => ctx.store.explicit_task_id(id)?,
+        None => ctx.store.generate_task_id()?,
+    };
     let mut event = TaskEvent::new(TaskEventType::TaskCreated, task_id.clone());
     event.actor = ctx.actor.clone();
     event.title = Some(title.to_string());
     event.body = options.body;
     event.status = Some(status.clone());
     event.priority = Some(priority.clone());
+    let hook_warning = match forge_integration::create_issue_best_effort(
+        &ctx.repo_root,
+        &task_id,
+        ctx.actor.as_deref().unwrap_or("unknown"),
+        title,
+    ) {
+        Ok(forge_ref) => {
+            event.forge_ref = forge_ref;
+            None
+        }
+        Err(warning) => Some(warning),
+    };
     ctx.store.append_event(event.clone())?;
 
     let event_warning = emit_task_event(&mut event_sink, EventKind::TaskCreated, &event);
@@ -356,15 +416,23 @@ pub fn run_new(options: NewOptions) -> Result<()> {
         id: task_id.clone(),
         status: status.clone(),
         priority: priority.clone(),
+        forge_ref: event.forge_ref.clone(),
+        hook_warnings: hook_warning.iter().cloned().collect(),
     };
 
     let mut human = HumanOutput::new("Task created");
     if let Some(warning) = event_warning {
         human.push_warning(warning);
     }
+    if let Some(warning) = hook_warning {
+        human.push_warning(warning);
+    }
     human.push_summary("ID", task_id);
     human.push_summary("Status", status);
     human.push_summary("Priority", priority);
+    if let Some(forge_ref) = output.forge_ref.as_ref() {
+        human.push_summary("Forge", forge_ref.clone());
+    }
 
     emit_success(
         OutputOptions {
@@ -377,10 +445,73 @@ pub fn run_new(options: NewOptions) -> Result<()> {
     )
 }
 
+/// Output format for task list/ready
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListFormat {
+    Text,
+    Json,
+    /// Tab-separated columns with a header row
+    Tsv,
+}
+
+impl ListFormat {
+    fn resolve(format: Option<&str>, json: bool) -> Result<Self> {
+        match format
+            .map(|value| value.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("text") => Ok(ListFormat::Text),
+            Some("json") => Ok(ListFormat::Json),
+            Some("tsv") => Ok(ListFormat::Tsv),
+            Some(other) => Err(Error::InvalidArgument(format!(
+                "invalid format '{other}' (expected text, json, or tsv)"
+            ))),
+            None if json => Ok(ListFormat::Json),
+            None => Ok(ListFormat::Text),
+        }
+    }
+}
+
+const TSV_COLUMNS: [&str; 8] = [
+    "id",
+    "title",
+    "status",
+    "priority",
+    "epic",
+    "project",
+    "workspace",
+    "updated_at",
+];
+
+/// Render tasks as TSV: a header row, then one row per task. Tabs and line
+/// breaks inside values become spaces so every task stays on one row.
+fn tasks_tsv(tasks: &[TaskRecord]) -> String {
+    fn cell(value: &str) -> String {
+        value.replace(['\t', '\n', '\r'], " ")
+    }
+
+    let mut lines = vec![TSV_COLUMNS.join("\t")];
+    for task in tasks {
+        let row = [
+            cell(&task.id),
+            cell(&task.title),
+            cell(&task.status),
+            cell(&task.priority),
+            cell(task.epic.as_deref().unwrap_or_default()),
+            cell(task.project.as_deref().unwrap_or_default()),
+            cell(task.workspace.as_deref().unwrap_or_default()),
+            task.updated_at.to_rfc3339(),
+        ];
+        lines.push(row.join("\t"));
+    }
+    lines.join("\n")
+}
+
 pub fn run_list(options: ListOptions) -> Result<()> {
+    let format = ListFormat::resolve(options.format.as_deref(), options.json)?;
     let ctx = load_context(options.repo, None, false)?;
     let updated_since = parse_timestamp("updated-since", options.updated_since.as_deref())?;
-    let mut tasks = ctx.store.list(options.status.as_deref())?;
+    let mut tasks = ctx.store.list(&options.status)?;
     let epic_filter = resolve_epic_filter(&ctx.store, options.epic.as_deref())?;
     let project_filter = resolve_project_filter(&ctx.store, options.project.as_deref())?;
 
@@ -404,10 +535,14 @@ pub fn run_list(options: ListOptions) -> Result<()> {
     };
     crate::task::sort_tasks(&mut tasks, ctx.store.config(), &blocked_ids);
     apply_limit(&mut tasks, options.limit)?;
+    if format == ListFormat::Tsv {
+        outln!("{}", tasks_tsv(&tasks));
+        return Ok(());
+    }
 
     let output = TaskListOutput {
         total: tasks.len(),
-        tasks: tasks.clone(),
+        tasks: MergedProbe::new(&ctx).list_items(&tasks),
     };
 
     let mut human = HumanOutput::new("Tasks");
@@ -421,7 +556,8 @@ pub fn run_list(options: ListOptions) -> Result<()> {
     if let Some(error) = blocked_error {
         human.push_warning(error);
     }
-    for task in tasks {
+    for item in &output.tasks {
+        let task = &item.task;
         let mut line = format!(
             "[{}][{}] {} {}",
             task.status, task.priority, task.id, task.title
@@ -435,12 +571,15 @@ pub fn run_list(options: ListOptions) -> Result<()> {
         if let Some(workspace) = task.workspace.as_ref() {
             line.push_str(&format!(" (ws: {})", workspace));
         }
+        if item.merged == Some(true) {
+            line.push_str(" (merged)");
+        }
         human.push_detail(line);
     }
 
     emit_success(
         OutputOptions {
-            json: options.json,
+            json: format == ListFormat::Json,
             quiet: options.quiet,
         },
         "task list",
@@ -450,6 +589,7 @@ pub fn run_list(options: ListOptions) -> Result<()> {
 }
 
 pub fn run_ready(options: ReadyOptions) -> Result<()> {
+    let format = ListFormat::resolve(options.format.as_deref(), options.json)?;
     let ctx = load_context(options.repo, None, false)?;
     let updated_since = parse_timestamp("updated-since", options.updated_since.as_deref())?;
     let mut tasks = ctx.store.list_ready()?;
@@ -470,10 +610,14 @@ pub fn run_ready(options: ReadyOptions) -> Result<()> {
     let blocked_ids = std::collections::HashSet::new();
     crate::task::sort_tasks(&mut tasks, ctx.store.config(), &blocked_ids);
     apply_limit(&mut tasks, options.limit)?;
+    if format == ListFormat::Tsv {
+        outln!("{}", tasks_tsv(&tasks));
+        return Ok(());
+    }
 
     let output = TaskListOutput {
         total: tasks.len(),
-        tasks: tasks.clone(),
+        tasks: MergedProbe::new(&ctx).list_items(&tasks),
     };
 
     let mut human = HumanOutput::new("Ready tasks");
@@ -484,7 +628,8 @@ pub fn run_ready(options: ReadyOptions) -> Result<()> {
     if let Some(project_id) = project_filter {
         human.push_summary("Project", project_id);
     }
-    for task in tasks {
+    for item in &output.tasks {
+        let task = &item.task;
         let mut line = format!(
             "[{}][{}] {} {}",
             task.status, task.priority, task.id, task.title
@@ -498,12 +643,15 @@ pub fn run_ready(options: ReadyOptions) -> Result<()> {
         if let Some(workspace) = task.workspace.as_ref() {
             line.push_str(&format!(" (ws: {})", workspace));
         }
+        if item.merged == Some(true) {
+            line.push_str(" (merged)");
+        }
         human.push_detail(line);
     }
 
     emit_success(
         OutputOptions {
-            json: options.json,
+            json: format == ListFormat::Json,
             quiet: options.quiet,
         },
         "task ready",
@@ -520,7 +668,7 @@ struct TaskCountOutput {
 pub fn run_count(options: CountOptions) -> Result<()> {
     let ctx = load_context(options.repo, None, false)?;
 
-    if options.ready && options.status.is_some() {
+    if options.ready && !options.status.is_empty() {
         return Err(Error::InvalidArgument(
             "cannot use --status with --ready".to_string(),
         ));
@@ -530,25 +678,43 @@ pub fn run_count(options: CountOptions) -> Result<()> {
     let epic_filter = resolve_epic_filter(&ctx.store, options.epic.as_deref())?;
     let project_filter = resolve_project_filter(&ctx.store, options.project.as_deref())?;
 
-    let mut tasks = if options.ready {
-        ctx.store.list_ready()?
+    let status_only = !options.ready
+        && options.priority.is_none()
+        && epic_filter.is_none()
+        && project_filter.is_none()
+        && options.workspace.is_none()
+        && options.actor.is_none()
+        && updated_since.is_none();
+    let total = if status_only {
+        // Plain status counts never need full task records.
+        let total = ctx.store.count(&options.status)?;
+        match options.limit {
+            Some(0) => return Err(Error::InvalidArgument("limit must be >= 1".to_string())),
+            Some(limit) => total.min(limit),
+            None => total,
+        }
     } else {
-        ctx.store.list(options.status.as_deref())?
-    };
+        let mut tasks = if options.ready {
+            ctx.store.list_ready()?
+        } else {
+            ctx.store.list(&options.status)?
+        };
 
-    apply_task_filters(
-        &ctx.store,
-        &mut tasks,
-        options.priority.as_deref(),
-        epic_filter.as_deref(),
-        project_filter.as_deref(),
-        options.workspace.as_deref(),
-        options.actor.as_deref(),
-        updated_since,
-    )?;
+        apply_task_filters(
+            &ctx.store,
+            &mut tasks,
+            options.priority.as_deref(),
+            epic_filter.as_deref(),
+            project_filter.as_deref(),
+            options.workspace.as_deref(),
+            options.actor.as_deref(),
+            updated_since,
+        )?;
 
-    apply_limit(&mut tasks, options.limit)?;
-    let output = TaskCountOutput { total: tasks.len() };
+        apply_limit(&mut tasks, options.limit)?;
+        tasks.len()
+    };
+    let output = TaskCountOutput { total };
 
     let human = HumanOutput::new(output.total.to_string());
     emit_success(
@@ -559,9 +725,169 @@ pub fn run_count(options: CountOptions) -> Result<()> {
         "task count",
         &output,
         Some(&human),
+    )?;
+
+    match options.fail_over {
+        Some(max) if output.total > max => Err(Error::GateFailed(format!(
+            "task count {} exceeds {max}",
+            output.total
+        ))),
+        _ => Ok(()),
+    }
+}
+
+#[derive(serde::Serialize)]
+struct TimelineEntry {
+    #[serde(flatten)]
+    event: TaskEvent,
+    description: String,
+}
+
+#[derive(serde::Serialize)]
+struct TaskTimelineOutput {
+    events: Vec<TimelineEntry>,
+}
+
+pub fn run_timeline(options: TimelineOptions) -> Result<()> {
+    let ctx = load_context(options.repo, None, false)?;
+    if options.limit == 0 {
+        return Err(Error::InvalidArgument("limit must be >= 1".to_string()));
+    }
+    let since = parse_since(options.since.as_deref())?;
+
+    let mut events: Vec<TaskEvent> = ctx
+        .store
+        .load_merged_events()?
+        .into_iter()
+        .filter(|event| since.is_none_or(|since| event.timestamp >= since))
+        .filter(|event| {
+            options
+                .actor
+                .as_deref()
+                .is_none_or(|actor| event.actor.as_deref() == Some(actor))
+        })
+        .collect();
+    // Keep the most recent events, still printed oldest first
+    if events.len() > options.limit {
+        events.drain(..events.len() - options.limit);
+    }
+
+    let output = TaskTimelineOutput {
+        events: events
+            .into_iter()
+            .map(|event| TimelineEntry {
+                description: describe_event(&event),
+                event,
+            })
+            .collect(),
+    };
+
+    let mut human = HumanOutput::new(format!("Task timeline ({} events)", output.events.len()));
+    human.push_table(
+        ["TIME", "TYPE", "TASK", "ACTOR", "DESCRIPTION"],
+        output.events.iter().map(|entry| {
+            let event = &entry.event;
+            [
+                event.timestamp.format("%Y-%m-%d %H:%M").to_string(),
+                event_type_label(event.event_type),
+                event.task_id.clone(),
+                event.actor.clone().unwrap_or_else(|| "-".to_string()),
+                entry.description.clone(),
+            ]
+        }),
+    );
+
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "task timeline",
+        &output,
+        Some(&human),
     )
 }
 
+/// Parse `--since` as a duration back from now ("24h") or an RFC3339 timestamp.
+fn parse_since(value: Option<&str>) -> Result<Option<DateTime<Utc>>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    if let Ok(duration) = crate::lease::parse_duration(value) {
+        return Ok(Some(Utc::now() - duration));
+    }
+    parse_timestamp("since", Some(value)).map_err(|_| {
+        Error::InvalidArgument(format!(
+            "invalid --since '{value}': expected a duration (e.g. 24h) or RFC3339 timestamp"
+        ))
+    })
+}
+
+fn event_type_label(event_type: TaskEventType) -> String {
+    serde_json::to_value(event_type)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+        .trim_start_matches("task_")
+        .to_string()
+}
+
+/// One-line summary of a task event for timeline output.
+fn describe_event(event: &TaskEvent) -> String {
+    let related = event.related_task_id.as_deref().unwrap_or("-");
+    match event.event_type {
+        TaskEventType::TaskCreated => match event.title.as_deref() {
+            Some(title) => format!("created \"{title}\""),
+            None => "created".to_string(),
+        },
+        TaskEventType::TaskStarted => match event.workspace.as_deref() {
+            Some(workspace) => format!("started in {workspace}"),
+            None => "started".to_string(),
+        },
+        TaskEventType::TaskStatusChanged => {
+            format!("status -> {}", event.status.as_deref().unwrap_or("-"))
+        }
+        TaskEventType::TaskPriorityChanged => {
+            format!("priority -> {}", event.priority.as_deref().unwrap_or("-"))
+        }
+        TaskEventType::TaskEdited => match event.title.as_deref() {
+            Some(title) => format!("edited \"{title}\""),
+            None => "edited".to_string(),
+        },
+        TaskEventType::TaskClosed => match event.status.as_deref() {
+            Some(status) => format!("closed ({status})"),
+            None => "closed".to_string(),
+        },
+        TaskEventType::TaskDeleted => "deleted".to_string(),
+        TaskEventType::TaskCommented => format!(
+            "commented: {}",
+            event
+                .comment
+                .as_deref()
+                .and_then(|comment| comment.lines().next())
+                .unwrap_or("")
+        ),
+        TaskEventType::TaskEpicSet => format!("epic -> {related}"),
+        TaskEventType::TaskEpicCleared => "epic cleared".to_string(),
+        TaskEventType::TaskEpicAutoCloseSet => match event.epic_auto_close {
+            Some(false) => "epic auto-close off".to_string(),
+            _ => "epic auto-close on".to_string(),
+        },
+        TaskEventType::TaskEpicAutoCloseCleared => "epic auto-close cleared".to_string(),
+        TaskEventType::TaskProjectSet => format!("project -> {related}"),
+        TaskEventType::TaskProjectCleared => "project cleared".to_string(),
+        TaskEventType::TaskParentSet => format!("parent -> {related}"),
+        TaskEventType::TaskParentCleared => "parent cleared".to_string(),
+        TaskEventType::TaskBlocked => format!("blocks {related}"),
+        TaskEventType::TaskUnblocked => format!("no longer blocks {related}"),
+        TaskEventType::TaskRelated => match event.relation_description.as_deref() {
+            Some(description) => format!("related to {related} ({description})"),
+            None => format!("related to {related}"),
+        },
+        TaskEventType::TaskUnrelated => format!("unrelated from {related}"),
+    }
+}
+
 pub fn run_stats(options: StatsOptions) -> Result<()> {
     let ctx = load_context(options.repo, None, false)?;
     let project_store = ProjectStore::new(ctx.store.storage().clone());
@@ -726,7 +1052,7 @@ fn apply_task_filters(
     Ok(())
 }
 
-fn build_effective_project_map(tasks: &[TaskRecord]) -> HashMap<String, Option<String>> {
+pub(crate) fn build_effective_project_map(tasks: &[TaskRecord]) -> HashMap<String, Option<String>> {
     let mut index_by_id = HashMap::new();
     for (idx, task) in tasks.iter().enumerate() {
         index_by_id.insert(task.id.as_str(), idx);
@@ -742,6 +1068,107 @@ fn build_effective_project_map(tasks: &[TaskRecord]) -> HashMap<String, Option<S
     out
 }
 
+/// An epic chain that loops back on itself before reaching a project, so the
+/// members' effective project cannot be resolved.
+#[derive(Debug, Clone, serde::Serialize)]
+struct ProjectCycle {
+    /// Task ids in epic order, starting from the smallest id.
+    chain: Vec<String>,
+    /// Task whose epic link to clear to break the cycle (the link set last).
+    clear_epic: String,
+}
+
+/// Find epic cycles among tasks that would inherit their project from an epic.
+/// A task with its own project ends the chain, so only project-less links count.
+/// Each cycle is returned in epic order, starting from its smallest task id.
+fn find_project_cycles(tasks: &[TaskRecord]) -> Vec<Vec<String>> {
+    let index_by_id: HashMap<&str, usize> = tasks
+        .iter()
+        .enumerate()
+        .map(|(idx, task)| (task.id.as_str(), idx))
+        .collect();
+    let next = |idx: usize| -> Option<usize> {
+        let task = &tasks[idx];
+        if task.project.is_some() {
+            return None;
+        }
+        task.epic
+            .as_deref()
+            .and_then(|epic| index_by_id.get(epic))
+            .copied()
+    };
+
+    // 0 = unvisited, 1 = on the current walk, 2 = done
+    let mut state = vec![0u8; tasks.len()];
+    let mut cycles = Vec::new();
+    for start in 0..tasks.len() {
+        let mut walk = Vec::new();
+        let mut current = Some(start);
+        while let Some(idx) = current {
+            match state[idx] {
+                0 => {
+                    state[idx] = 1;
+                    walk.push(idx);
+                    current = next(idx);
+                }
+                1 => {
+                    let pos = walk.iter().position(|entry| *entry == idx).unwrap_or(0);
+                    let mut members: Vec<usize> = walk[pos..].to_vec();
+                    let min = members
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, member)| tasks[**member].id.as_str())
+                        .map(|(offset, _)| offset)
+                        .unwrap_or(0);
+                    members.rotate_left(min);
+                    cycles.push(
+                        members
+                            .into_iter()
+                            .map(|member| tasks[member].id.clone())
+                            .collect(),
+                    );
+                    break;
+                }
+                _ => break,
+            }
+        }
+        for idx in walk {
+            state[idx] = 2;
+        }
+    }
+    cycles.sort();
+    cycles
+}
+
+/// Pair each cycle with the member whose epic link was set most recently,
+/// which is usually the link that closed the loop.
+fn describe_project_cycles(
+    store: &TaskStore,
+    cycles: Vec<Vec<String>>,
+) -> Result<Vec<ProjectCycle>> {
+    let mut described = Vec::with_capacity(cycles.len());
+    for chain in cycles {
+        let mut clear_epic = chain.first().cloned().unwrap_or_default();
+        let mut latest = None;
+        for task_id in &chain {
+            let set_at = store
+                .events_for(task_id)?
+                .into_iter()
+                .filter(|event| {
+                    event.event_type == TaskEventType::TaskEpicSet && event.task_id == *task_id
+                })
+                .map(|event| event.timestamp)
+                .max();
+            if set_at > latest {
+                latest = set_at;
+                clear_epic = task_id.clone();
+            }
+        }
+        described.push(ProjectCycle { chain, clear_epic });
+    }
+    Ok(described)
+}
+
 fn resolve_effective_project<'a>(
     idx: usize,
     tasks: &'a [TaskRecord],
@@ -779,9 +1206,42 @@ pub fn run_show(options: ShowOptions) -> Result<()> {
     let ctx = load_context(options.repo, None, false)?;
     let resolved = ctx.store.resolve_task_id(&options.id)?;
     let details = ctx.store.details(&resolved)?;
+    let workspace_status = task_workspace_status(&ctx, &details.task);
+    let merged = MergedProbe::new(&ctx).merged(&details.task);
 
     let mut human = HumanOutput::new(format!("Task {}", resolved));
     push_task_summary(&mut human, &details);
+    if let Some(merged) = merged {
+        human.push_summary("Merged", if merged { "yes" } else { "no" }.to_string());
+    }
+    if let Some(status) = workspace_status.as_ref() {
+        match status.ahead_behind.as_ref() {
+            Some(divergence) => human.push_summary(
+                "Divergence",
+                format!(
+                    "{} ahead / {} behind {}",
+                    divergence.ahead, divergence.behind, divergence.base
+                ),
+            ),
+            None if !status.exists => {
+                human.push_summary("Divergence", "workspace missing".to_string())
+            }
+            None => {}
+        }
+    }
+    if let Some(path) = options.events_file.as_deref() {
+        let events = ctx.store.events_for(&resolved)?;
+        let mut buffer = Vec::new();
+        for event in &events {
+            serde_json::to_writer(&mut buffer, event)?;
+            buffer.push(b'\n');
+        }
+        std::fs::write(path, buffer)?;
+        human.push_summary(
+            "events file",
+            format!("{} ({} event(s))", path.display(), events.len()),
+        );
+    }
     for comment in &details.comments {
         let actor = comment.actor.as_deref().unwrap_or("unknown");
         human.push_detail(format!(
@@ -798,36 +1258,160 @@ pub fn run_show(options: ShowOptions) -> Result<()> {
             quiet: options.quiet,
         },
         "task show",
-        &details,
+        &TaskShowOutput {
+            details,
+            workspace_status,
+            merged,
+        },
         Some(&human),
     )
 }
 
+#[derive(serde::Serialize)]
+struct TaskShowOutput {
+    #[serde(flatten)]
+    details: TaskDetails,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace_status: Option<TaskWorkspaceStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merged: Option<bool>,
+}
+
+/// Registered workspace a task is bound to, with its branch divergence
+#[derive(serde::Serialize)]
+struct TaskWorkspaceStatus {
+    name: String,
+    branch: String,
+    base: String,
+    exists: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ahead_behind: Option<ws::AheadBehind>,
+}
+
+fn task_workspace_status(ctx: &TaskContext, task: &TaskRecord) -> Option<TaskWorkspaceStatus> {
+    if task.workspace_id.is_none() && task.workspace.is_none() {
+        return None;
+    }
+    let registry = ctx.store.storage().read_workspaces().ok()?;
+    let entry = registry.workspaces.into_iter().find(|entry| {
+        task.workspace_id.as_deref() == Some(entry.id.as_str())
+            || task.workspace.as_deref() == Some(entry.name.as_str())
+    })?;
+    let ahead_behind = git::open_repo(Some(&ctx.repo_root))
+        .ok()
+        .and_then(|repo| ws::compute_ahead_behind(&repo, &entry.branch, &entry.base));
+    Some(TaskWorkspaceStatus {
+        exists: entry.path.exists(),
+        name: entry.name,
+        branch: entry.branch,
+        base: entry.base,
+        ahead_behind,
+    })
+}
+
 pub fn run_start(options: StartOptions) -> Result<()> {
-    let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let ctx = load_context(options.repo, options.actor, !options.create_workspace)?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let resolved = ctx.store.resolve_task_id(&options.id)?;
 
-    let workspace = ctx
-        .workspace
+    let repo = git::open_repo(Some(&ctx.repo_root))?;
+    let created_workspace = if options.create_workspace {
+        Some(ws::create_workspace(
+            &repo,
+            ctx.store.storage(),
+            &resolved,
+            options.base,
+            None,
+            None,
+            ctx.actor.clone(),
+        )?)
+    } else {
+        None
+    };
+    let workspace = created_workspace
+        .clone()
+        .or_else(|| ctx.workspace.clone())
         .ok_or_else(|| Error::OperationFailed("workspace not found for task start".to_string()))?;
 
+    // Take the leases first (all or nothing) so a conflict leaves the task
+    // untouched, then release them again if the start itself fails.
+    let taken = if options.lease.is_empty() {
+        None
+    } else {
+        match take_task_leases(&ctx, &resolved, &workspace, &options.lease) {
+            Ok(taken) => Some(taken),
+            Err(err) => {
+                abandon_start(&ctx, &repo, None, created_workspace.as_ref())?;
+                return Err(err);
+            }
+        }
+    };
+
     let in_progress = ctx.store.config().in_progress_status.clone();
-    let start_outcome = ctx.store.start_task(StartTaskRequest {
+    let start_outcome = match ctx.store.start_task(StartTaskRequest {
         task_id: resolved.clone(),
         actor: ctx.actor.clone(),
         workspace_id: Some(workspace.id.clone()),
         workspace: Some(workspace.name.clone()),
         branch: Some(workspace.branch.clone()),
         takeover: options.takeover,
-    })?;
+    }) {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            abandon_start(&ctx, &repo, taken.as_ref(), created_workspace.as_ref())?;
+            return Err(err);
+        }
+    };
+    if let Some(entry) = created_workspace.as_ref() {
+        ws::record_workspace_created(ctx.store.storage(), entry);
+    }
 
-    let output = TaskStatusOutput {
+    let taken_leases: Vec<&Lease> = taken
+        .iter()
+        .flat_map(|taken| taken.created.iter().chain(taken.updated.iter()))
+        .collect();
+    let mut output = TaskStartOutput {
         id: resolved.clone(),
         status: in_progress,
+        leases: taken_leases
+            .iter()
+            .map(|lease| TaskLeaseOutput {
+                id: lease.id.to_string(),
+                path: lease.pathspec.clone(),
+                intent: lease.intent.to_string(),
+            })
+            .collect(),
+        workspace: created_workspace.as_ref().map(|entry| ws::NewOutput {
+            name: entry.name.clone(),
+            path: entry.path.clone(),
+            branch: entry.branch.clone(),
+            base: entry.base.clone(),
+        }),
+        hook_warnings: Vec::new(),
     };
 
     let mut human = HumanOutput::new("Task started");
+    if let Some(created) = output.workspace.as_ref() {
+        human.push_detail(format!(
+            "created workspace {} at {} (branch {}, base {})",
+            created.name,
+            created.path.display(),
+            created.branch,
+            created.base
+        ));
+    }
+    for lease in &taken_leases {
+        if let Some(sink) = event_sink.as_mut() {
+            if let Err(err) = take::lease_created_event(lease).and_then(|event| sink.emit(&event)) {
+                human.push_warning(format!("event output failed: {err}"));
+            }
+        }
+        human.push_detail(format!(
+            "leased {} ({}, intent: {})",
+            lease.pathspec, lease.strength, lease.intent
+        ));
+    }
     match start_outcome {
         StartTaskOutcome::Started {
             event,
@@ -847,7 +1431,8 @@ pub fn run_start(options: StartOptions) -> Result<()> {
                 &resolved,
                 ctx.actor.as_deref().unwrap_or("unknown"),
             ) {
-                human.push_warning(warning);
+                human.push_warning(warning.clone());
+                output.hook_warnings.push(warning);
             }
         }
         StartTaskOutcome::AlreadyInProgressByActor => {
@@ -869,9 +1454,83 @@ pub fn run_start(options: StartOptions) -> Result<()> {
     )
 }
 
+#[derive(serde::Serialize)]
+struct TaskStartOutput {
+    id: String,
+    status: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    leases: Vec<TaskLeaseOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace: Option<ws::NewOutput>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hook_warnings: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+struct TaskLeaseOutput {
+    id: String,
+    path: String,
+    intent: String,
+}
+
+/// Undo the side effects of a failed `sv task start`: release leases taken
+/// for it and remove a workspace created by `--create-workspace`.
+fn abandon_start(
+    ctx: &TaskContext,
+    repo: &git2::Repository,
+    taken: Option<&take::TakenLeases>,
+    created_workspace: Option<&WorkspaceEntry>,
+) -> Result<()> {
+    if let Some(taken) = taken {
+        take::rollback(ctx.store.storage(), taken, "task start failed")?;
+    }
+    if let Some(entry) = created_workspace {
+        ws::discard_workspace(repo, ctx.store.storage(), entry)?;
+    }
+    Ok(())
+}
+
+/// Take cooperative, workspace-scoped leases for `sv task start --lease`.
+fn take_task_leases(
+    ctx: &TaskContext,
+    task_id: &str,
+    workspace: &WorkspaceEntry,
+    paths: &[String],
+) -> Result<take::TakenLeases> {
+    let config = Config::load_from_repo(&ctx.repo_root);
+    let cwd = std::env::current_dir().unwrap_or_else(|_| ctx.repo_root.clone());
+    let pathspecs = paths
+        .iter()
+        .map(|raw| lease::resolve_pathspec(&ctx.repo_root, &cwd, raw))
+        .collect::<Result<Vec<_>>>()?;
+
+    let title = ctx.store.details(task_id)?.task.title;
+    let default_intent = config.leases.resolve_intent(None)?;
+    let ttl = config.leases.limit_ttl(&config.leases.default_ttl)?.ttl;
+    let spec = take::LeaseSpec {
+        strength: LeaseStrength::Cooperative,
+        intent: intent_for_task(&title).unwrap_or(default_intent),
+        scope: LeaseScope::Workspace(workspace.name.clone()),
+        ttl,
+        note: Some(format!("task {task_id}: {title}")),
+        actor: ctx.actor.clone(),
+        allow_redundant: false,
+    };
+    take::take_all(ctx.store.storage(), &config, &pathspecs, &spec)
+}
+
+/// Lease intent suggested by a task title ("Fix login redirect" -> bugfix).
+fn intent_for_task(title: &str) -> Option<LeaseIntent> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter_map(|word| word.parse::<LeaseIntent>().ok())
+        .find(|intent| *intent != LeaseIntent::Other)
+}
+
 pub fn run_status(options: StatusOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let resolved = ctx.store.resolve_task_id(&options.id)?;
     ctx.store.validate_status(&options.status)?;
     ensure_project_group_not_closed(&ctx.store, &resolved, &options.status)?;
@@ -932,7 +1591,8 @@ pub fn run_status(options: StatusOptions) -> Result<()> {
 
 pub fn run_priority(options: PriorityOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let resolved = ctx.store.resolve_task_id(&options.id)?;
     let priority = ctx.store.normalize_priority(&options.priority)?;
 
@@ -972,7 +1632,8 @@ pub fn run_priority(options: PriorityOptions) -> Result<()> {
 
 pub fn run_edit(options: EditOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let resolved = ctx.store.resolve_task_id(&options.id)?;
 
     if options.title.is_none() && options.body.is_none() {
@@ -1037,7 +1698,8 @@ pub fn run_edit(options: EditOptions) -> Result<()> {
 
 pub fn run_close(options: CloseOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let resolved = ctx.store.resolve_task_id(&options.id)?;
 
     let status = options.status.unwrap_or_else(|| {
@@ -1050,6 +1712,13 @@ pub fn run_close(options: CloseOptions) -> Result<()> {
     });
     ctx.store.validate_status(&status)?;
     ensure_project_group_not_closed(&ctx.store, &resolved, &status)?;
+    // Capture the bound workspace before the close event rebinds the task to
+    // the closing workspace.
+    let bound = if options.merge {
+        Some(ctx.store.details(&resolved)?.task)
+    } else {
+        None
+    };
 
     let mut event = TaskEvent::new(TaskEventType::TaskClosed, resolved.clone());
     event.actor = ctx.actor.clone();
@@ -1080,12 +1749,74 @@ pub fn run_close(options: CloseOptions) -> Result<()> {
         ctx.actor.as_deref().unwrap_or("unknown"),
     );
 
-    let output = TaskStatusOutput {
+    let mut merge_warnings = Vec::new();
+    let merge = match bound.as_ref() {
+        Some(task) => match task.branch.as_deref() {
+            Some(branch) => {
+                let into = options
+                    .into
+                    .clone()
+                    .or_else(|| task_workspace_status(&ctx, task).map(|status| status.base))
+                    .unwrap_or_else(|| Config::load_from_repo(&ctx.repo_root).base);
+                match merge_task_branch(&ctx, &resolved, branch, &into) {
+                    Ok(merge) => Some(merge),
+                    Err(err) => {
+                        merge_warnings.push(format!("merge of {branch} into {into} failed: {err}"));
+                        Some(TaskMergeOutput {
+                            branch: branch.to_string(),
+                            into,
+                            status: "failed",
+                            commit: None,
+                            conflicts: Vec::new(),
+                            error: Some(err.to_string()),
+                        })
+                    }
+                }
+            }
+            None => {
+                merge_warnings.push("task has no bound workspace; skipping merge".to_string());
+                None
+            }
+        },
+        None => None,
+    };
+
+    let output = TaskCloseOutput {
         id: resolved.clone(),
         status: status.clone(),
+        merge,
+        hook_warnings: hook_warning.iter().cloned().collect(),
     };
 
     let mut human = HumanOutput::new("Task closed");
+    for warning in merge_warnings {
+        human.push_warning(warning);
+    }
+    if let Some(merge) = output.merge.as_ref() {
+        match merge.status {
+            "up_to_date" => human.push_detail(format!(
+                "{} is already merged into {}",
+                merge.branch, merge.into
+            )),
+            "fast_forward" | "merged" => human.push_detail(format!(
+                "merged {} into {} ({})",
+                merge.branch,
+                merge.into,
+                merge.status.replace('_', "-")
+            )),
+            "conflicts" => {
+                human.push_warning(format!(
+                    "merge of {} into {} would conflict; nothing was committed",
+                    merge.branch, merge.into
+                ));
+                for conflict in merge::summarize_conflicts(&merge.conflicts) {
+                    human.push_detail(format!("conflict: {conflict}"));
+                }
+                human.push_next_step(format!("sv onto {} --preflight", merge.into));
+            }
+            _ => {}
+        }
+    }
     if let Some(warning) = event_warning {
         human.push_warning(warning);
     }
@@ -1115,9 +1846,152 @@ pub fn run_close(options: CloseOptions) -> Result<()> {
     )
 }
 
+#[derive(serde::Serialize)]
+struct TaskCloseOutput {
+    id: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merge: Option<TaskMergeOutput>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hook_warnings: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+struct TaskMergeOutput {
+    branch: String,
+    into: String,
+    /// One of `up_to_date`, `fast_forward`, `merged`, `conflicts`, `failed`
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    conflicts: Vec<merge::MergeConflict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Merge a closed task's workspace branch into `into` for `sv task close --merge`.
+///
+/// Conflicts are predicted with a virtual merge first, so nothing is written
+/// when the merge would not be clean. When `into` is checked out in a
+/// worktree the merge runs there (like `sv onto --strategy merge`); otherwise
+/// the branch ref is fast-forwarded or given a merge commit directly.
+fn merge_task_branch(
+    ctx: &TaskContext,
+    task_id: &str,
+    branch: &str,
+    into: &str,
+) -> Result<TaskMergeOutput> {
+    let repo = git::open_repo(Some(&ctx.repo_root))?;
+    let mut output = TaskMergeOutput {
+        branch: branch.to_string(),
+        into: into.to_string(),
+        status: "up_to_date",
+        commit: None,
+        conflicts: Vec::new(),
+        error: None,
+    };
+
+    let into_ref = format!("refs/heads/{into}");
+    if repo.find_reference(&into_ref).is_err() {
+        return Err(Error::InvalidArgument(format!(
+            "merge target '{into}' is not a local branch"
+        )));
+    }
+    if git::is_ancestor(&repo, branch, into)? {
+        return Ok(output);
+    }
+
+    let simulation = merge::simulate_merge(&repo, into, branch, None)?;
+    if !simulation.conflicts.is_empty() {
+        output.status = "conflicts";
+        output.conflicts = simulation.conflicts;
+        return Ok(output);
+    }
+
+    let before = refs::resolve_ref_oid(&repo, into)?;
+    let theirs = refs::resolve_ref_oid(&repo, branch)?;
+    let fast_forward = git::is_ancestor(&repo, into, branch)?;
+    let checked_out = git::list_worktrees(&repo)?
+        .into_iter()
+        .find(|worktree| worktree.branch.as_deref() == Some(into));
+
+    if let Some(worktree) = checked_out {
+        let output_git = std::process::Command::new("git")
+            .args(["merge", "--no-edit", branch])
+            .current_dir(&worktree.path)
+            .output()?;
+        if !output_git.status.success() {
+            let _ = std::process::Command::new("git")
+                .args(["merge", "--abort"])
+                .current_dir(&worktree.path)
+                .output();
+            let stderr = String::from_utf8_lossy(&output_git.stderr)
+                .trim()
+                .to_string();
+            return Err(Error::OperationFailed(format!(
+                "git merge failed in {}: {stderr}",
+                worktree.path.display()
+            )));
+        }
+    } else if fast_forward {
+        refs::move_branch_ref(&repo, into, theirs)?;
+    } else {
+        let ours = repo.find_commit(before)?;
+        let theirs = repo.find_commit(theirs)?;
+        let mut index = repo.merge_commits(&ours, &theirs, None)?;
+        let tree = repo.find_tree(index.write_tree_to(&repo)?)?;
+        let signature = repo.signature()?;
+        repo.commit(
+            Some(&into_ref),
+            &signature,
+            &signature,
+            &format!("Merge branch '{branch}' into {into}"),
+            &tree,
+            &[&ours, &theirs],
+        )?;
+    }
+
+    let after = refs::resolve_ref_oid(&repo, into)?;
+    output.status = if fast_forward {
+        "fast_forward"
+    } else {
+        "merged"
+    };
+    output.commit = Some(after.to_string());
+
+    let oplog = OpLog::for_storage(ctx.store.storage());
+    let mut record = OpRecord::new(
+        format!("sv task close {task_id} --merge --into {into}"),
+        ctx.actor.clone(),
+    );
+    record.affected_refs.push(into_ref.clone());
+    if !fast_forward {
+        record.commits.push(after.to_string());
+    }
+    if let Ok(changes) = git::diff_files(&repo, &before.to_string(), Some(&after.to_string())) {
+        record.affected_paths = git::changed_paths(&changes)
+            .iter()
+            .map(|path| path.to_string_lossy().replace('\\', "/"))
+            .collect();
+    }
+    record.undo_data = Some(UndoData {
+        ref_updates: vec![RefUpdate {
+            name: into_ref,
+            old: Some(before.to_string()),
+            new: Some(after.to_string()),
+        }],
+        ..UndoData::default()
+    });
+    let _ = oplog.append(&record);
+
+    Ok(output)
+}
+
 pub fn run_delete(options: DeleteOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let resolved = ctx.store.resolve_task_id(&options.id)?;
 
     let details = ctx.store.details(&resolved)?;
@@ -1171,7 +2045,8 @@ pub fn run_delete(options: DeleteOptions) -> Result<()> {
 
 pub fn run_comment(options: CommentOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let resolved = ctx.store.resolve_task_id(&options.id)?;
     let text = options.text.trim();
     if text.is_empty() {
@@ -1211,7 +2086,8 @@ pub fn run_comment(options: CommentOptions) -> Result<()> {
 
 pub fn run_parent_set(options: ParentSetOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let child = ctx.store.resolve_task_id(&options.child)?;
     let parent = ctx.store.resolve_task_id(&options.parent)?;
     if child == parent {
@@ -1264,7 +2140,8 @@ pub fn run_parent_set(options: ParentSetOptions) -> Result<()> {
 
 pub fn run_parent_clear(options: ParentClearOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let child = ctx.store.resolve_task_id(&options.child)?;
     let relations = ctx.store.relations(&child)?;
     let parent = relations
@@ -1307,7 +2184,8 @@ pub fn run_parent_clear(options: ParentClearOptions) -> Result<()> {
 
 pub fn run_epic_set(options: EpicSetOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let task = ctx.store.resolve_task_id(&options.task)?;
     let epic = ctx.store.resolve_task_id(&options.epic)?;
     if task == epic {
@@ -1357,7 +2235,8 @@ pub fn run_epic_set(options: EpicSetOptions) -> Result<()> {
 
 pub fn run_epic_clear(options: EpicClearOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let task = ctx.store.resolve_task_id(&options.task)?;
     let details = ctx.store.details(&task)?;
     let epic = details
@@ -1401,7 +2280,8 @@ pub fn run_epic_clear(options: EpicClearOptions) -> Result<()> {
 
 pub fn run_epic_auto_close(options: EpicAutoCloseOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let epic = ctx.store.resolve_task_id(&options.epic)?;
     let mode = parse_epic_auto_close_mode(&options.mode)?;
 
@@ -1462,7 +2342,8 @@ pub fn run_epic_auto_close(options: EpicAutoCloseOptions) -> Result<()> {
 
 pub fn run_project_set(options: ProjectSetOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let task = ctx.store.resolve_task_id(&options.task)?;
     let project_target = resolve_project_target(&ctx.store, &options.project)?;
     let project = project_target.id().to_string();
@@ -1548,7 +2429,8 @@ fn resolve_project_target(store: &TaskStore, input: &str) -> Result<ProjectTarge
 
 pub fn run_project_clear(options: ProjectClearOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let task = ctx.store.resolve_task_id(&options.task)?;
     let details = ctx.store.details(&task)?;
     let project = details
@@ -1592,7 +2474,8 @@ pub fn run_project_clear(options: ProjectClearOptions) -> Result<()> {
 
 pub fn run_block(options: BlockOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let blocker = ctx.store.resolve_task_id(&options.blocker)?;
     let blocked = ctx.store.resolve_task_id(&options.blocked)?;
     if blocker == blocked {
@@ -1628,6 +2511,7 @@ pub fn run_block(options: BlockOptions) -> Result<()> {
     let output = TaskBlockOutput {
         blocker: blocker.clone(),
         blocked: blocked.clone(),
+        hook_warnings: hook_warning.iter().cloned().collect(),
     };
 
     let mut human = HumanOutput::new("Task blocked");
@@ -1653,7 +2537,8 @@ pub fn run_block(options: BlockOptions) -> Result<()> {
 
 pub fn run_unblock(options: UnblockOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let blocker = ctx.store.resolve_task_id(&options.blocker)?;
     let blocked = ctx.store.resolve_task_id(&options.blocked)?;
     if blocker == blocked {
@@ -1683,6 +2568,7 @@ pub fn run_unblock(options: UnblockOptions) -> Result<()> {
     let output = TaskBlockOutput {
         blocker: blocker.clone(),
         blocked: blocked.clone(),
+        hook_warnings: Vec::new(),
     };
 
     let mut human = HumanOutput::new("Task unblocked");
@@ -1705,7 +2591,8 @@ pub fn run_unblock(options: UnblockOptions) -> Result<()> {
 
 pub fn run_relate(options: RelateOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let left = ctx.store.resolve_task_id(&options.left)?;
     let right = ctx.store.resolve_task_id(&options.right)?;
     if left == right {
@@ -1768,7 +2655,8 @@ pub fn run_relate(options: RelateOptions) -> Result<()> {
 
 pub fn run_unrelate(options: UnrelateOptions) -> Result<()> {
     let ctx = load_context(options.repo, options.actor, true)?;
-    let (mut event_sink, events_to_stdout) = open_task_event_sink(options.events.as_deref())?;
+    let (mut event_sink, events_to_stdout) =
+        open_task_event_sink(options.events.as_deref(), &ctx.repo_root)?;
     let left = ctx.store.resolve_task_id(&options.left)?;
     let right = ctx.store.resolve_task_id(&options.right)?;
     if left == right {
@@ -1861,10 +2749,110 @@ pub fn run_relations(options: RelationsOptions) -> Result<()> {
     )
 }
 
+#[derive(serde::Serialize)]
+struct TaskSyncOutput {
+    #[serde(flatten)]
+    report: crate::task::TaskSyncReport,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pulled: Option<TaskSyncRemoteOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pushed: Option<TaskSyncRemoteOutput>,
+}
+
+#[derive(serde::Serialize)]
+struct TaskSyncRemoteOutput {
+    remote: String,
+    branch: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<String>,
+}
+
 pub fn run_sync(options: SyncOptions) -> Result<()> {
     let ctx = load_context(options.repo, None, false)?;
     let policy = ctx.store.auto_compaction_policy()?;
-    let report = ctx.store.sync(policy)?;
+
+    let remote_branch = if options.push || options.pull {
+        let repo = git::open_repo(Some(&ctx.repo_root))?;
+        let head = git::head_info(&repo)?;
+        match head.shorthand.filter(|_| !head.is_detached) {
+            Some(branch) => Some((repo, branch)),
+            None => {
+                return Err(Error::InvalidArgument(
+                    "task sync --push/--pull requires a checked-out branch".to_string(),
+                ))
+            }
+        }
+    } else {
+        None
+    };
+    let tracked_paths = [
+        ctx.store.tracked_log_path(),
+        ctx.store.tracked_snapshot_path(),
+    ]
+    .iter()
+    .map(|path| {
+        path.strip_prefix(&ctx.repo_root)
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|_| path.clone())
+    })
+    .collect::<Vec<_>>();
+
+    // Remote events are unioned by event_id; the remote JSONL is never
+    // merged textually.
+    let mut pulled = None;
+    let mut pulled_tip = None;
+    let mut incoming = Vec::new();
+    if options.pull {
+        if let Some((repo, branch)) = &remote_branch {
+            if let Some(tip) = git::fetch_branch(repo, &options.remote, branch)? {
+                pulled_tip = Some(tip);
+                if let Some(content) = git::read_blob_at(repo, tip, &tracked_paths[0])? {
+                    incoming = crate::task::parse_events_jsonl(&String::from_utf8_lossy(&content))
+                        .map_err(|err| {
+                            Error::OperationFailed(format!(
+                                "remote task log on {}/{} is unreadable: {}",
+                                options.remote, branch, err
+                            ))
+                        })?;
+                }
+            }
+            pulled = Some(TaskSyncRemoteOutput {
+                remote: options.remote.clone(),
+                branch: branch.clone(),
+                commit: None,
+            });
+        }
+    }
+
+    let report = ctx.store.sync_with(incoming, policy)?;
+
+    let mut pushed = None;
+    if options.push {
+        if let Some((repo, branch)) = &remote_branch {
+            // A pulled tip that HEAD doesn't contain yet is integrated into
+            // the sync commit so the push fast-forwards the remote.
+            let upstream = match pulled_tip {
+                Some(tip) if !git::is_ancestor(repo, &tip.to_string(), "HEAD")? => Some(tip),
+                _ => None,
+            };
+            // The snapshot carries a generation timestamp, so only the log
+            // decides whether there is anything new to commit.
+            let head = git::head_info(repo)?;
+            let committed_log = git::read_blob_at(repo, head.oid, &tracked_paths[0])?;
+            let current_log = std::fs::read(ctx.store.tracked_log_path())?;
+            let commit = match upstream {
+                Some(tip) => git::commit_paths_onto(repo, &tracked_paths, "sv: sync tasks", tip)?,
+                None if committed_log.as_deref() == Some(current_log.as_slice()) => None,
+                None => git::commit_paths(repo, &tracked_paths, "sv: sync tasks")?,
+            };
+            git::push_branch(repo, &options.remote, branch)?;
+            pushed = Some(TaskSyncRemoteOutput {
+                remote: options.remote.clone(),
+                branch: branch.clone(),
+                commit: commit.map(|oid| oid.to_string()),
+            });
+        }
+    }
     let duplicate_creates = ctx.store.duplicate_creates().unwrap_or_default();
 
     let mut human = HumanOutput::new("Task sync complete");
@@ -1873,6 +2861,25 @@ pub fn run_sync(options: SyncOptions) -> Result<()> {
     if report.compacted {
         human.push_summary("Compacted", report.removed_events.to_string());
     }
+    if let Some(pulled) = &pulled {
+        human.push_summary(
+            "Pulled",
+            format!(
+                "{} new event(s) from {}/{}",
+                report.imported_events, pulled.remote, pulled.branch
+            ),
+        );
+    }
+    if let Some(pushed) = &pushed {
+        let commit = match &pushed.commit {
+            Some(oid) => format!("committed {}", &oid[..oid.len().min(12)]),
+            None => "no task changes to commit".to_string(),
+        };
+        human.push_summary(
+            "Pushed",
+            format!("{}/{} ({})", pushed.remote, pushed.branch, commit),
+        );
+    }
     if !duplicate_creates.is_empty() {
         let task_ids = duplicate_creates
             .iter()
@@ -1893,7 +2900,126 @@ pub fn run_sync(options: SyncOptions) -> Result<()> {
             quiet: options.quiet,
         },
         "task sync",
-        &report,
+        &TaskSyncOutput {
+            report,
+            pulled,
+            pushed,
+        },
+        Some(&human),
+    )
+}
+
+const MERGE_DRIVER_NAME: &str = "sv-tasks";
+const MERGE_DRIVER_COMMAND: &str = "sv task merge-driver %O %A %B";
+const MERGE_DRIVER_ATTRIBUTE: &str = ".tasks/tasks.jsonl merge=sv-tasks";
+
+#[derive(serde::Serialize)]
+struct MergeDriverOutput {
+    path: String,
+    events: usize,
+}
+
+pub fn run_merge_driver(options: MergeDriverOptions) -> Result<()> {
+    let read = |path: &Path| {
+        std::fs::read_to_string(path).map_err(|err| {
+            Error::OperationFailed(format!("failed to read {}: {}", path.display(), err))
+        })
+    };
+    let merged = crate::task::merge_event_logs(
+        &read(&options.base)?,
+        &read(&options.ours)?,
+        &read(&options.theirs)?,
+    )?;
+    std::fs::write(&options.ours, &merged)?;
+
+    // Git shows driver output inline with the merge, so stay silent unless
+    // JSON was requested.
+    let output = MergeDriverOutput {
+        path: options.ours.display().to_string(),
+        events: merged.lines().count(),
+    };
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: !options.json,
+        },
+        "task merge-driver",
+        &output,
+        None,
+    )
+}
+
+#[derive(serde::Serialize)]
+struct InstallMergeDriverOutput {
+    driver: String,
+    command: String,
+    attributes_path: String,
+    attributes_updated: bool,
+}
+
+pub fn run_install_merge_driver(options: InstallMergeDriverOptions) -> Result<()> {
+    let repo = git::open_repo(options.repo.as_deref())?;
+    let workdir = git::workdir(&repo)?;
+
+    let mut config = repo.config()?;
+    config.set_str(
+        &format!("merge.{MERGE_DRIVER_NAME}.name"),
+        "sv task event log union",
+    )?;
+    config.set_str(
+        &format!("merge.{MERGE_DRIVER_NAME}.driver"),
+        MERGE_DRIVER_COMMAND,
+    )?;
+
+    let attributes_path = workdir.join(".gitattributes");
+    let existing = match std::fs::read_to_string(&attributes_path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let attributes_updated = !existing
+        .lines()
+        .any(|line| line.trim() == MERGE_DRIVER_ATTRIBUTE);
+    if attributes_updated {
+        let mut content = existing;
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(MERGE_DRIVER_ATTRIBUTE);
+        content.push('\n');
+        std::fs::write(&attributes_path, content)?;
+    }
+
+    let output = InstallMergeDriverOutput {
+        driver: MERGE_DRIVER_NAME.to_string(),
+        command: MERGE_DRIVER_COMMAND.to_string(),
+        attributes_path: attributes_path.display().to_string(),
+        attributes_updated,
+    };
+    let mut human = HumanOutput::new("Task merge driver installed");
+    human.push_summary(
+        "Driver",
+        format!("{MERGE_DRIVER_NAME} ({MERGE_DRIVER_COMMAND})"),
+    );
+    human.push_summary(
+        "Attributes",
+        if attributes_updated {
+            format!("added to {}", attributes_path.display())
+        } else {
+            format!("already in {}", attributes_path.display())
+        },
+    );
+    if attributes_updated {
+        human.push_next_step("Commit .gitattributes so other clones use the driver".to_string());
+    }
+
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "task install-merge-driver",
+        &output,
         Some(&human),
     )
 }
@@ -1901,10 +3027,22 @@ pub fn run_sync(options: SyncOptions) -> Result<()> {
 pub fn run_doctor(options: DoctorOptions) -> Result<()> {
     let ctx = load_context(options.repo, None, false)?;
     let report = ctx.store.doctor()?;
+    let (project_cycles, cycle_error) = match ctx
+        .store
+        .list(&[])
+        .and_then(|tasks| describe_project_cycles(&ctx.store, find_project_cycles(&tasks)))
+    {
+        Ok(cycles) => (cycles, None),
+        Err(err) => (
+            Vec::new(),
+            Some(format!("project cycle check skipped: {err}")),
+        ),
+    };
 
     let output = TaskDoctorOutput {
         duplicate_creates: report.duplicate_creates.clone(),
         malformed_events: report.malformed_events.clone(),
+        project_cycles,
     };
 
     let mut human = HumanOutput::new("Task doctor report");
@@ -1916,6 +3054,10 @@ pub fn run_doctor(options: DoctorOptions) -> Result<()> {
         "Malformed events",
         report.malformed_events.len().to_string(),
     );
+    human.push_summary("Project cycles", output.project_cycles.len().to_string());
+    if let Some(warning) = cycle_error {
+        human.push_warning(warning);
+    }
 
     for entry in &report.duplicate_creates {
         human.push_detail(format!(
@@ -1928,9 +3070,17 @@ pub fn run_doctor(options: DoctorOptions) -> Result<()> {
     for entry in &report.malformed_events {
         human.push_detail(format!("{}:{} {}", entry.log_path, entry.line, entry.error));
     }
+    for cycle in &output.project_cycles {
+        let mut chain = cycle.chain.clone();
+        chain.extend(cycle.chain.first().cloned());
+        human.push_detail(format!("project cycle {}", chain.join(" -> ")));
+    }
     if !report.duplicate_creates.is_empty() {
         human.push_next_step("Run: sv task repair --dedupe-creates --dry-run".to_string());
     }
+    for cycle in &output.project_cycles {
+        human.push_next_step(format!("Run: sv task epic clear {}", cycle.clear_epic));
+    }
 
     emit_success(
         OutputOptions {
@@ -2135,12 +3285,76 @@ struct TaskCreatedOutput {
     id: String,
     status: String,
     priority: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    forge_ref: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hook_warnings: Vec<String>,
 }
 
 #[derive(serde::Serialize)]
 struct TaskListOutput {
     total: usize,
-    tasks: Vec<TaskRecord>,
+    tasks: Vec<TaskListItem>,
+}
+
+#[derive(serde::Serialize)]
+struct TaskListItem {
+    #[serde(flatten)]
+    task: TaskRecord,
+    /// Whether the task's branch is already merged into its base
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merged: Option<bool>,
+}
+
+/// Answers "is this task's branch already merged?" for task output.
+///
+/// The base is the bound workspace's base when it is still registered, or the
+/// repo base otherwise. Tasks without a branch, or whose branch no longer
+/// resolves, have no answer.
+struct MergedProbe {
+    repo: Option<git2::Repository>,
+    workspaces: Vec<WorkspaceEntry>,
+    default_base: String,
+}
+
+impl MergedProbe {
+    fn new(ctx: &TaskContext) -> Self {
+        Self {
+            repo: git::open_repo(Some(&ctx.repo_root)).ok(),
+            workspaces: ctx
+                .store
+                .storage()
+                .read_workspaces()
+                .map(|registry| registry.workspaces)
+                .unwrap_or_default(),
+            default_base: Config::load_from_repo(&ctx.repo_root).base,
+        }
+    }
+
+    fn merged(&self, task: &TaskRecord) -> Option<bool> {
+        let repo = self.repo.as_ref()?;
+        let branch = task.branch.as_deref()?;
+        let base = self
+            .workspaces
+            .iter()
+            .find(|entry| {
+                task.workspace_id.as_deref() == Some(entry.id.as_str())
+                    || task.workspace.as_deref() == Some(entry.name.as_str())
+            })
+            .map(|entry| entry.base.as_str())
+            .unwrap_or(&self.default_base);
+        git::is_merged(repo, branch, base).ok()
+    }
+
+    fn list_items(&self, tasks: &[TaskRecord]) -> Vec<TaskListItem> {
+        tasks
+            .iter()
+            .map(|task| TaskListItem {
+                merged: self.merged(task),
+                task: task.clone(),
+            })
+            .collect()
+    }
 }
 
 #[derive(serde::Serialize)]
@@ -2198,6 +3412,7 @@ mod tests {
             closed_by: None,
             comments_count: 0,
             last_comment_at: None,
+            forge_ref: None,
         }
     }
 
@@ -2213,6 +3428,26 @@ mod tests {
         let mut tasks = vec![task("a")];
         assert!(apply_limit(&mut tasks, Some(0)).is_err());
     }
+
+    #[test]
+    fn find_project_cycles_reports_epic_loops() {
+        let mut tasks = vec![task("c"), task("a"), task("b"), task("d"), task("e")];
+        // a -> b -> c -> a; e -> d leads into the loop.
+        tasks[1].epic = Some("b".to_string());
+        tasks[2].epic = Some("c".to_string());
+        tasks[0].epic = Some("a".to_string());
+        tasks[3].epic = Some("a".to_string());
+        tasks[4].epic = Some("d".to_string());
+
+        assert_eq!(
+            find_project_cycles(&tasks),
+            vec![vec!["a".to_string(), "b".to_string(), "c".to_string()]]
+        );
+
+        // A task with its own project ends the chain.
+        tasks[2].project = Some("proj".to_string());
+        assert!(find_project_cycles(&tasks).is_empty());
+    }
 }
 
 #[derive(serde::Serialize)]
@@ -2244,6 +3479,8 @@ struct TaskProjectOutput {
 struct TaskBlockOutput {
     blocker: String,
     blocked: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hook_warnings: Vec<String>,
 }
 
 #[derive(serde::Serialize)]
@@ -2282,6 +3519,7 @@ struct TaskCompactOutput {
 struct TaskDoctorOutput {
     duplicate_creates: Vec<crate::task::TaskDuplicateCreate>,
     malformed_events: Vec<crate::task::TaskMalformedEvent>,
+    project_cycles: Vec<ProjectCycle>,
 }
 
 #[derive(serde::Serialize)]
@@ -2428,7 +3666,7 @@ fn maybe_auto_close_epic_chain(
     event_sink: &mut Option<crate::events::EventSink>,
 ) -> Result<AutoCloseResult> {
     let mut result = AutoCloseResult::default();
-    let tasks = store.list(None)?;
+    let tasks = store.list(&[])?;
     let mut status_by_id: HashMap<String, String> = HashMap::new();
     let mut epic_by_task: HashMap<String, Option<String>> = HashMap::new();
     for task in tasks {
@@ -2602,9 +3840,16 @@ fn resolve_project_filter(store: &TaskStore, value: Option<&str>) -> Result<Opti
     Ok(Some(store.resolve_task_id(trimmed)?))
 }
 
-fn open_task_event_sink(events: Option<&str>) -> Result<(Option<crate::events::EventSink>, bool)> {
+fn open_task_event_sink(
+    events: Option<&str>,
+    repo_root: &Path,
+) -> Result<(Option<crate::events::EventSink>, bool)> {
     let destination = EventDestination::parse(events);
-    let sink = destination.as_ref().map(|dest| dest.open()).transpose()?;
+    let config = Config::load_from_repo(repo_root);
+    let sink = destination
+        .as_ref()
+        .map(|dest| dest.open_with(&config.events))
+        .transpose()?;
     let events_to_stdout = matches!(destination, Some(EventDestination::Stdout));
     Ok((sink, events_to_stdout))
 }
@@ -2670,6 +3915,9 @@ fn push_task_summary(human: &mut HumanOutput, details: &TaskDetails) {
     if let Some(branch) = task.branch.as_ref() {
         human.push_summary("Branch", branch.clone());
     }
+    if let Some(forge_ref) = task.forge_ref.as_ref() {
+        human.push_summary("Forge", forge_ref.clone());
+    }
     if let Some(body) = task.body.as_ref() {
         human.push_detail(body.clone());
     }
@@ -0,0 +1,201 @@
+//! sv undo and sv redo command implementations
+//!
+//! Reverts the last undoable operation in the op log (or a specific one), or
+//! with `--dry-run` only reports what reverting it would change; `sv redo`
+//! re-applies the most recently undone operation.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::git;
+use crate::output::{emit_success, HumanOutput, OutputOptions};
+use crate::storage::Storage;
+use crate::undo::{self, PlannedLeaseAction, UndoOptions as LibUndoOptions, UndoPlan};
+
+/// Options for `sv undo`
+pub struct UndoOptions {
+    pub op: Option<String>,
+    pub dry_run: bool,
+    pub keep_worktree: bool,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+/// Options for `sv redo`
+pub struct RedoOptions {
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct UndoReport {
+    dry_run: bool,
+    #[serde(flatten)]
+    plan: UndoPlan,
+}
+
+/// Run `sv undo [--op <id>] [--dry-run] [--keep-worktree]`.
+///
+/// The plan is computed first either way, so a dry run reports exactly the
+/// changes a real run would apply.
+pub fn run(options: UndoOptions) -> Result<()> {
+    let storage = open_storage(options.repo.as_deref())?;
+
+    let op_id = options
+        .op
+        .as_deref()
+        .map(|raw| {
+            Uuid::parse_str(raw)
+                .map_err(|_| Error::InvalidArgument(format!("invalid operation id '{raw}'")))
+        })
+        .transpose()?;
+    let lib_options = LibUndoOptions {
+        op_id,
+        keep_worktree: options.keep_worktree,
+    };
+
+    let plan = undo::plan(&storage, &lib_options)?;
+    if !options.dry_run {
+        undo::undo(&storage, lib_options)?;
+    }
+
+    let header = if options.dry_run {
+        "sv undo (dry run)"
+    } else {
+        "sv undo"
+    };
+    let mut human = HumanOutput::new(header);
+    human.push_summary("Operation", format!("{} ({})", plan.command, plan.op_id));
+    let verb = |planned: &'static str, done: &'static str| {
+        if options.dry_run {
+            planned
+        } else {
+            done
+        }
+    };
+    for update in &plan.ref_updates {
+        human.push_detail(match (&update.current, &update.to) {
+            (_, None) => format!("{} {}", verb("delete", "deleted"), update.name),
+            (Some(current), Some(to)) => format!(
+                "{} {}: {} -> {}",
+                verb("reset", "reset"),
+                update.name,
+                short(current),
+                short(to)
+            ),
+            (None, Some(to)) => {
+                format!(
+                    "{} {} at {}",
+                    verb("recreate", "recreated"),
+                    update.name,
+                    short(to)
+                )
+            }
+        });
+    }
+    for path in &plan.remove_paths {
+        human.push_detail(format!("{} {}", verb("remove", "removed"), path.display()));
+    }
+    for path in &plan.keep_paths {
+        human.push_detail(format!("{} {}", verb("keep", "kept"), path.display()));
+    }
+    for name in &plan.unregister_workspaces {
+        human.push_detail(format!(
+            "{} workspace {name}",
+            verb("unregister", "unregistered")
+        ));
+    }
+    for change in &plan.lease_changes {
+        let action = match change.action {
+            PlannedLeaseAction::Remove => verb("release", "released"),
+            PlannedLeaseAction::Restore => verb("restore", "restored"),
+        };
+        human.push_detail(format!(
+            "{action} lease {} ({})",
+            change.lease_id, change.pathspec
+        ));
+    }
+    if plan.is_empty() {
+        human.push_warning(
+            "nothing to change; the operation's effects are already gone".to_string(),
+        );
+    }
+    if options.dry_run {
+        human.push_next_step(format!("sv undo --op {}", plan.op_id));
+    }
+
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "undo",
+        &UndoReport {
+            dry_run: options.dry_run,
+            plan,
+        },
+        Some(&human),
+    )
+}
+
+#[derive(Debug, Serialize)]
+struct RedoReport {
+    op_id: String,
+    command: String,
+    restored_refs: Vec<String>,
+    reverted_leases: Vec<String>,
+}
+
+/// Run `sv redo`.
+pub fn run_redo(options: RedoOptions) -> Result<()> {
+    let storage = open_storage(options.repo.as_deref())?;
+    let summary = undo::redo(&storage)?;
+
+    let mut human = HumanOutput::new("sv redo");
+    human.push_summary(
+        "Operation",
+        format!("{} ({})", summary.command, summary.op_id),
+    );
+    for name in &summary.restored_refs {
+        human.push_detail(format!("restored {name}"));
+    }
+    for lease_id in &summary.reverted_leases {
+        human.push_detail(format!("reapplied lease change {lease_id}"));
+    }
+
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "redo",
+        &RedoReport {
+            op_id: summary.op_id.to_string(),
+            command: summary.command,
+            restored_refs: summary.restored_refs,
+            reverted_leases: summary.reverted_leases,
+        },
+        Some(&human),
+    )
+}
+
+fn open_storage(repo: Option<&std::path::Path>) -> Result<Storage> {
+    let repo = git::open_repo(repo)?;
+    let workdir = git::workdir(&repo)?;
+    let storage = Storage::new(workdir.clone(), git::common_dir(&repo), workdir);
+    if !storage.is_initialized() {
+        return Err(Error::OperationFailed(
+            "sv not initialized. Run 'sv init' first.".to_string(),
+        ));
+    }
+    Ok(storage)
+}
+
+fn short(oid: &str) -> &str {
+    &oid[..8.min(oid.len())]
+}
@@ -0,0 +1,64 @@
+//! sv version command implementation
+//!
+//! Reports build metadata without opening a repository.
+
+use crate::error::Result;
+use crate::output::{emit_success, HumanOutput, OutputOptions};
+
+/// Options for `sv version`
+pub struct VersionOptions {
+    pub json: bool,
+    pub quiet: bool,
+}
+
+#[derive(serde::Serialize)]
+struct VersionReport {
+    version: &'static str,
+    git_sha: &'static str,
+    build_date: &'static str,
+    libgit2_version: String,
+    /// Enabled cargo features
+    features: Vec<&'static str>,
+}
+
+pub fn run(options: VersionOptions) -> Result<()> {
+    let (major, minor, rev) = git2::Version::get().libgit2_version();
+    let report = VersionReport {
+        version: env!("CARGO_PKG_VERSION"),
+        git_sha: env!("SV_GIT_SHA"),
+        build_date: env!("SV_BUILD_DATE"),
+        libgit2_version: format!("{major}.{minor}.{rev}"),
+        features: enabled_features(),
+    };
+
+    let mut human = HumanOutput::new(format!("sv {}", report.version));
+    human.push_summary("git sha", report.git_sha);
+    human.push_summary("build date", report.build_date);
+    human.push_summary("libgit2", report.libgit2_version.clone());
+    human.push_summary(
+        "features",
+        if report.features.is_empty() {
+            "(none)".to_string()
+        } else {
+            report.features.join(", ")
+        },
+    );
+
+    emit_success(
+        OutputOptions {
+            json: options.json,
+            quiet: options.quiet,
+        },
+        "version",
+        &report,
+        Some(&human),
+    )
+}
+
+fn enabled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(feature = "webhook") {
+        features.push("webhook");
+    }
+    features
+}
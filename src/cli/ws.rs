@@ -1,20 +1,27 @@
 //! Workspace (worktree) management commands
 //!
-//! Implements `sv ws new`, `sv ws list`, `sv ws info`, `sv ws rm`, `sv ws clean`, `sv ws here`.
+//! Implements `sv ws new`, `sv ws list`, `sv ws info`, `sv ws rm`, `sv ws clean`, `sv ws here`,
+//! `sv ws archive` and `sv ws restore`.
 
 use std::path::{Path, PathBuf};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use git2::Repository;
 use serde::Serialize;
 use std::collections::HashSet;
 
+use crate::actor;
 use crate::change_id::find_change_id;
 use crate::config::Config;
 use crate::error::{Error, Result};
 use crate::git;
-use crate::oplog::{OpLog, OpRecord, UndoData, WorkspaceChange};
+use crate::lease::LeaseScope;
+use crate::lock::{FileLock, DEFAULT_LOCK_TIMEOUT_MS};
+use crate::oplog::{LeaseChange, OpDetails, OpLog, OpRecord, UndoData, WorkspaceChange};
+use crate::output::{format_human, outln, HumanOutput};
+use crate::refs;
 use crate::storage::{Storage, WorkspaceEntry};
+use crate::task::TaskStore;
 
 /// Options for `sv ws new`
 pub struct NewOptions {
@@ -50,20 +57,72 @@ pub fn run_new(opts: NewOptions) -> Result<()> {
     let repo = git::open_repo(opts.repo.as_deref())?;
     let workdir = git::workdir(&repo)?;
     let common_dir = resolve_common_dir(&repo)?;
+    let storage = Storage::new(workdir.clone(), common_dir, workdir);
+
+    let entry = create_workspace(
+        &repo,
+        &storage,
+        &opts.name,
+        opts.base,
+        opts.dir,
+        opts.branch,
+        opts.actor,
+    )?;
+    record_workspace_created(&storage, &entry);
+
+    // Output result
+    let output = NewOutput {
+        name: entry.name,
+        path: entry.path,
+        branch: entry.branch,
+        base: entry.base,
+    };
+
+    if opts.json {
+        outln!("{}", serde_json::to_string_pretty(&output)?);
+    } else if !opts.quiet {
+        outln!(
+            "Created workspace '{}' at {}",
+            output.name,
+            output.path.display()
+        );
+        outln!("  Branch: {}", output.branch);
+        outln!("  Base: {}", output.base);
+    }
+
+    Ok(())
+}
+
+/// Create a worktree and register it as a workspace.
+///
+/// The branch defaults to `sv/ws/<name>`, the base to the configured base
+/// for that branch (see [`Config::base_for`]) and the directory to
+/// `.sv/worktrees/<name>`. Nothing is written to the oplog; callers do that
+/// once the surrounding operation has succeeded (see
+/// [`record_workspace_created`]).
+pub(crate) fn create_workspace(
+    repo: &Repository,
+    storage: &Storage,
+    name: &str,
+    base: Option<String>,
+    dir: Option<PathBuf>,
+    branch: Option<String>,
+    actor: Option<String>,
+) -> Result<WorkspaceEntry> {
+    let workdir = git::workdir(repo)?;
+    let common_dir = resolve_common_dir(repo)?;
 
     // Load config for defaults
     let config = Config::load_from_repo(&workdir);
 
-    // Determine the base ref (what to branch from)
-    let base_ref = opts.base.unwrap_or_else(|| config.base.clone());
-
     // Determine the branch name
-    let branch_name = opts
-        .branch
-        .unwrap_or_else(|| format!("sv/ws/{}", opts.name));
+    let branch_name = branch.unwrap_or_else(|| format!("sv/ws/{}", name));
+
+    // Determine the base ref (what to branch from)
+    let base_ref = base.unwrap_or_else(|| config.base_for(&branch_name).to_string());
 
     // Determine the worktree directory path
-    let worktree_path = if let Some(dir) = opts.dir {
+    let worktree_path = if let Some(dir) = dir {
         if dir.is_absolute() {
             dir
         } else {
@@ -71,90 +130,96 @@ pub fn run_new(opts: NewOptions) -> Result<()> {
         }
     } else {
         // Default: .sv/worktrees/<name>
-        workdir.join(".sv").join("worktrees").join(&opts.name)
+        workdir.join(".sv").join("worktrees").join(name)
     };
 
     // Check if workspace name already exists in registry
-    let storage = Storage::new(workdir.clone(), common_dir.clone(), workdir.clone());
-    if storage.find_workspace(&opts.name)?.is_some() {
+    if storage.find_workspace(name)?.is_some() {
         return Err(Error::InvalidArgument(format!(
             "workspace '{}' already exists in registry",
-            opts.name
+            name
         )));
     }
 
     // Create the worktree using git module
-    git::create_worktree(
-        &repo,
-        &opts.name,
+    git::create_worktree_in(
+        repo,
+        name,
         &worktree_path,
         &base_ref,
         Some(&branch_name),
+        &config.refs.namespace,
     )?;
 
     // Register in the workspaces registry
     let now = Utc::now().to_rfc3339();
-    let actor = opts.actor.clone();
     let entry = WorkspaceEntry::new(
-        opts.name.clone(),
+        name.to_string(),
         worktree_path.clone(),
-        branch_name.clone(),
-        base_ref.clone(),
-        actor.clone(),
+        branch_name,
+        base_ref,
+        actor,
         now,
         None,
     );
-    storage.add_workspace(entry)?;
+    let registered = storage.add_workspace(entry.clone()).and_then(|()| {
+        // Initialize workspace-local .sv/ directory
+        Storage::new(workdir, common_dir, worktree_path).init_local()
+    });
+    if let Err(err) = registered {
+        let _ = discard_workspace(repo, storage, &entry);
+        return Err(err);
+    }
 
-    // Initialize workspace-local .sv/ directory
-    let ws_storage = Storage::new(workdir.clone(), common_dir, worktree_path.clone());
-    ws_storage.init_local()?;
+    Ok(entry)
+}
 
-    // Record operation in oplog
-    let oplog = OpLog::for_storage(&storage);
-    let mut record = OpRecord::new(format!("sv ws new {}", opts.name), actor.clone());
-    record.affected_workspaces.push(opts.name.clone());
-    record.affected_refs.push(branch_name.clone());
+/// Record a `sv ws new` entry in the oplog for a freshly created workspace.
+pub(crate) fn record_workspace_created(storage: &Storage, entry: &WorkspaceEntry) {
+    let oplog = OpLog::for_storage(storage);
+    let mut record = OpRecord::new(format!("sv ws new {}", entry.name), entry.actor.clone());
+    record.affected_workspaces.push(entry.name.clone());
+    record.affected_refs.push(entry.branch.clone());
     record.undo_data = Some(UndoData {
         workspace_changes: vec![WorkspaceChange {
-            name: opts.name.clone(),
+            name: entry.name.clone(),
             action: "create".to_string(),
-            path: Some(worktree_path.display().to_string()),
-            branch: Some(branch_name.clone()),
-            base: Some(base_ref.clone()),
+            path: Some(entry.path.display().to_string()),
+            branch: Some(entry.branch.clone()),
+            base: Some(entry.base.clone()),
         }],
-        created_paths: vec![worktree_path.display().to_string()],
+        created_paths: vec![entry.path.display().to_string()],
         ..Default::default()
     });
     // Best-effort oplog write - don't fail the command if oplog fails
     let _ = oplog.append(&record);
+}
 
-    // Output result
-    let output = NewOutput {
-        name: opts.name,
-        path: worktree_path,
-        branch: branch_name,
-        base: base_ref,
-    };
-
-    if opts.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
-    } else if !opts.quiet {
-        println!(
-            "Created workspace '{}' at {}",
-            output.name,
-            output.path.display()
-        );
-        println!("  Branch: {}", output.branch);
-        println!("  Base: {}", output.base);
+/// Tear down a workspace created by [`create_workspace`]: the worktree, its
+/// branch and the registry entry.
+pub(crate) fn discard_workspace(
+    repo: &Repository,
+    storage: &Storage,
+    entry: &WorkspaceEntry,
+) -> Result<()> {
+    if git::remove_worktree(repo, &entry.name, true).is_err() && entry.path.exists() {
+        std::fs::remove_dir_all(&entry.path)?;
     }
-
+    git::prune_worktrees(repo).ok();
+    if refs::find_refname(repo, &entry.branch).is_some() {
+        refs::delete_branch(repo, &entry.branch)?;
+    }
+    storage.remove_workspace(&entry.name)?;
     Ok(())
 }
 
 /// Options for `sv ws list`
 pub struct ListOptions {
     pub selector: Option<String>,
+    /// Only list workspaces with no commits or task activity within this window
+    pub stale: Option<String>,
+    /// Include archived workspaces
+    pub all: bool,
     pub repo: Option<PathBuf>,
     pub json: bool,
     pub quiet: bool,
@@ -169,7 +234,13 @@ pub struct WorkspaceListItem {
     pub base: String,
     pub actor: Option<String>,
     pub last_active: Option<String>,
+    /// Commit time of the branch tip
+    pub last_commit_at: Option<DateTime<Utc>>,
+    /// Latest update of an open task bound to the workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_task_activity_at: Option<DateTime<Utc>>,
     pub exists: bool,
+    pub archived: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ahead_behind: Option<AheadBehind>,
 }
@@ -188,63 +259,133 @@ pub fn run_list(opts: ListOptions) -> Result<()> {
     let workdir = git::workdir(&repo)?;
     let common_dir = resolve_common_dir(&repo)?;
 
-    let storage = Storage::new(workdir.clone(), common_dir, workdir);
+    let stale_cutoff = opts
+        .stale
+        .as_deref()
+        .map(|window| crate::lease::parse_duration(window).map(|window| Utc::now() - window))
+        .transpose()?;
+
+    let storage = Storage::new(workdir.clone(), common_dir, workdir.clone());
     let registry = storage.read_workspaces()?;
     let entries = match opts.selector.as_deref() {
-        Some(selector) => super::resolve_hoist_workspaces(&repo, &registry, selector)?,
+        Some(selector) => super::resolve_hoist_workspaces(&repo, &registry, selector, false)?,
         None => registry.workspaces.clone(),
     };
 
+    let ids: Vec<String> = entries.iter().map(|entry| entry.id.clone()).collect();
+    let names: Vec<String> = entries.iter().map(|entry| entry.name.clone()).collect();
+    let bound_tasks = TaskStore::new(storage, Config::load_from_repo(&workdir).tasks)
+        .active_tasks_for_workspaces(&ids, &names)
+        .unwrap_or_default();
+
     // Convert to list items
-    let items: Vec<WorkspaceListItem> = entries
-        .iter()
-        .map(|entry| {
-            let ahead_behind = compute_ahead_behind(&repo, &entry.branch, &entry.base);
-            WorkspaceListItem {
-                name: entry.name.clone(),
-                path: entry.path.clone(),
-                branch: entry.branch.clone(),
-                base: entry.base.clone(),
-                actor: entry.actor.clone(),
-                last_active: entry.last_active.clone(),
-                exists: entry.path.exists(),
-                ahead_behind,
+    let mut items = Vec::new();
+    for entry in &entries {
+        if entry.archived && !opts.all {
+            continue;
+        }
+        let last_commit_at = git::last_commit_time(&repo, &entry.branch);
+        let last_task_activity_at = bound_tasks
+            .iter()
+            .filter(|task| {
+                task.workspace_id.as_deref() == Some(entry.id.as_str())
+                    || task.workspace.as_deref() == Some(entry.name.as_str())
+            })
+            .map(|task| task.updated_at)
+            .max();
+        if let Some(cutoff) = stale_cutoff {
+            let last_activity = last_commit_at.max(last_task_activity_at);
+            if last_activity.is_some_and(|at| at >= cutoff) {
+                continue;
             }
-        })
-        .collect();
+        }
+
+        let ahead_behind = compute_ahead_behind(&repo, &entry.branch, &entry.base);
+        items.push(WorkspaceListItem {
+            name: entry.name.clone(),
+            path: entry.path.clone(),
+            branch: entry.branch.clone(),
+            base: entry.base.clone(),
+            actor: entry.actor.clone(),
+            last_active: entry.last_active.clone(),
+            last_commit_at,
+            last_task_activity_at,
+            exists: entry.path.exists(),
+            archived: entry.archived,
+            ahead_behind,
+        });
+    }
 
     if opts.json {
-        println!("{}", serde_json::to_string_pretty(&items)?);
+        outln!("{}", serde_json::to_string_pretty(&items)?);
     } else if !opts.quiet {
-        if items.is_empty() {
-            println!("No workspaces registered");
+        if items.is_empty() && opts.stale.is_some() {
+            outln!("No stale workspaces");
+        } else if items.is_empty() {
+            outln!("No workspaces registered");
         } else {
-            for item in &items {
-                let missing = if item.exists { "" } else { " (missing)" };
-                println!("{}{}", item.name, missing);
-                println!("  path: {}", item.path.display());
-                println!("  branch: {}", item.branch);
-                println!("  base: {}", item.base);
-                if let Some(actor) = &item.actor {
-                    println!("  actor: {}", actor);
-                }
-                if let Some(last_active) = &item.last_active {
-                    println!("  last active: {}", last_active);
-                }
-                if let Some(status) = &item.ahead_behind {
-                    println!(
-                        "  status: {} ahead / {} behind vs {}",
-                        status.ahead, status.behind, status.base
-                    );
+            let header = match opts.stale.as_deref() {
+                Some(window) => {
+                    format!("Stale workspaces, inactive for {window} ({}):", items.len())
                 }
-            }
+                None => format!("Workspaces ({}):", items.len()),
+            };
+            let mut human = HumanOutput::new(header);
+            human.push_table(
+                [
+                    "NAME",
+                    "BRANCH",
+                    "BASE",
+                    "ACTOR",
+                    "STATUS",
+                    "LAST ACTIVE",
+                    "PATH",
+                ],
+                items.iter().map(|item| {
+                    let missing = if item.archived {
+                        " (archived)"
+                    } else if item.exists {
+                        ""
+                    } else {
+                        " (missing)"
+                    };
+                    let status = item
+                        .ahead_behind
+                        .as_ref()
+                        .map(|status| format!("+{} -{}", status.ahead, status.behind))
+                        .unwrap_or_else(|| "-".to_string());
+                    vec![
+                        format!("{}{}", item.name, missing),
+                        item.branch.clone(),
+                        item.base.clone(),
+                        item.actor.clone().unwrap_or_else(|| "-".to_string()),
+                        status,
+                        last_active_label(item),
+                        item.path.display().to_string(),
+                    ]
+                }),
+            );
+            outln!("{}", format_human(&human));
         }
     }
 
     Ok(())
 }
 
-fn compute_ahead_behind(repo: &Repository, branch: &str, base: &str) -> Option<AheadBehind> {
+/// Latest of the branch tip's commit time and bound-task activity, falling
+/// back to the registry's last-active stamp.
+fn last_active_label(item: &WorkspaceListItem) -> String {
+    match item.last_commit_at.max(item.last_task_activity_at) {
+        Some(at) => at.format("%Y-%m-%d %H:%M").to_string(),
+        None => item.last_active.clone().unwrap_or_else(|| "-".to_string()),
+    }
+}
+
+pub(crate) fn compute_ahead_behind(
+    repo: &Repository,
+    branch: &str,
+    base: &str,
+) -> Option<AheadBehind> {
     let branch_oid = resolve_oid(repo, branch)?;
     let base_oid = resolve_oid(repo, base)?;
     let (ahead, behind) = repo.graph_ahead_behind(branch_oid, base_oid).ok()?;
@@ -339,6 +480,18 @@ pub struct WorkspaceInfo {
     /// Recent Change-Ids from commits
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub change_ids: Vec<String>,
+    /// Active (not closed) tasks bound to this workspace
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tasks: Vec<WorkspaceTask>,
+}
+
+/// Task bound to a workspace, for display
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceTask {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub priority: String,
 }
 
 /// Lease info for workspace display
@@ -379,11 +532,12 @@ pub fn run_info(opts: InfoOptions) -> Result<()> {
 
     let touched_paths = match git::diff_files(&repo, &entry.base, Some(&entry.branch)) {
         Ok(changes) => {
-            let mut paths: Vec<String> = changes
+            let mut paths: Vec<String> = git::changed_paths(&changes)
                 .into_iter()
-                .map(|change| change.path.to_string_lossy().to_string())
+                .map(|path| path.to_string_lossy().to_string())
                 .collect();
             paths.sort();
+            paths.dedup();
             paths
         }
         Err(_) => Vec::new(),
@@ -409,7 +563,8 @@ pub fn run_info(opts: InfoOptions) -> Result<()> {
     };
 
     let ahead_behind_base = compute_ahead_behind(&repo, &entry.branch, &entry.base);
-    let main_ref = Config::load_from_repo(&workdir).base;
+    let config = Config::load_from_repo(&workdir);
+    let main_ref = config.base.clone();
     let ahead_behind_main = if main_ref != entry.base {
         compute_ahead_behind(&repo, &entry.branch, &main_ref)
     } else {
@@ -418,6 +573,22 @@ pub fn run_info(opts: InfoOptions) -> Result<()> {
 
     let change_ids = collect_change_ids(&repo, &entry.branch, 10);
 
+    let task_store = TaskStore::new(storage.clone(), config.tasks);
+    let tasks = task_store
+        .active_tasks_for_workspaces(
+            std::slice::from_ref(&entry.id),
+            std::slice::from_ref(&entry.name),
+        )
+        .unwrap_or_default()
+        .into_iter()
+        .map(|task| WorkspaceTask {
+            id: task.id,
+            title: task.title,
+            status: task.status,
+            priority: task.priority,
+        })
+        .collect();
+
     let info = WorkspaceInfo {
         id: entry.id,
         name: entry.name,
@@ -434,57 +605,78 @@ pub fn run_info(opts: InfoOptions) -> Result<()> {
         ahead_behind_base,
         ahead_behind_main,
         change_ids,
+        tasks,
     };
 
     if opts.json {
-        println!("{}", serde_json::to_string_pretty(&info)?);
+        outln!("{}", serde_json::to_string_pretty(&info)?);
     } else if !opts.quiet {
-        println!("Workspace: {}", info.name);
-        println!("  ID: {}", info.id);
-        println!("  Path: {}", info.path.display());
-        println!("  Branch: {}", info.branch);
-        println!("  Base: {}", info.base);
+        outln!("Workspace: {}", info.name);
+        outln!("  ID: {}", info.id);
+        outln!("  Path: {}", info.path.display());
+        outln!("  Branch: {}", info.branch);
+        outln!("  Base: {}", info.base);
         if let Some(actor) = &info.actor {
-            println!("  Actor: {}", actor);
+            outln!("  Actor: {}", actor);
         }
-        println!("  Created: {}", info.created_at);
+        outln!("  Created: {}", info.created_at);
         if let Some(last_active) = &info.last_active {
-            println!("  Last active: {}", last_active);
+            outln!("  Last active: {}", last_active);
         }
-        println!("  Exists: {}", info.exists);
+        outln!("  Exists: {}", info.exists);
         if let Some(status) = &info.git_head {
-            println!("  Git HEAD: {}", status);
+            outln!("  Git HEAD: {}", status);
         }
         if let Some(status) = &info.ahead_behind_base {
-            println!(
+            outln!(
                 "  Ahead/behind vs {}: {} ahead / {} behind",
-                status.base, status.ahead, status.behind
+                status.base,
+                status.ahead,
+                status.behind
             );
         }
         if let Some(status) = &info.ahead_behind_main {
-            println!(
+            outln!(
                 "  Ahead/behind vs {}: {} ahead / {} behind",
-                status.base, status.ahead, status.behind
+                status.base,
+                status.ahead,
+                status.behind
             );
         }
         if !info.touched_paths.is_empty() {
-            println!("  Touched paths:");
+            outln!("  Touched paths:");
             for path in &info.touched_paths {
-                println!("    - {}", path);
+                outln!("    - {}", path);
             }
         }
         if !info.leases.is_empty() {
-            println!("  Leases affecting workspace:");
+            outln!("  Leases affecting workspace:");
             for lease in &info.leases {
                 let actor = lease.actor.as_deref().unwrap_or("-");
-                println!(
+                outln!(
                     "    - {} {} [{}] actor={} expires={}",
-                    lease.id, lease.pathspec, lease.strength, actor, lease.expires_at
+                    lease.id,
+                    lease.pathspec,
+                    lease.strength,
+                    actor,
+                    lease.expires_at
+                );
+            }
+        }
+        if !info.tasks.is_empty() {
+            outln!("  Active tasks:");
+            for task in &info.tasks {
+                outln!(
+                    "    - {} [{}] {} {}",
+                    task.id,
+                    task.status,
+                    task.priority,
+                    task.title
                 );
             }
         }
         if !info.change_ids.is_empty() {
-            println!("  Recent Change-Ids: {}", info.change_ids.join(", "));
+            outln!("  Recent Change-Ids: {}", info.change_ids.join(", "));
         }
     }
 
@@ -600,9 +792,9 @@ pub fn run_rm(opts: RmOptions) -> Result<()> {
     };
 
     if opts.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
+        outln!("{}", serde_json::to_string_pretty(&output)?);
     } else if !opts.quiet {
-        println!(
+        outln!(
             "Removed workspace '{}' at {}",
             output.name,
             output.path.display()
@@ -612,6 +804,212 @@ pub fn run_rm(opts: RmOptions) -> Result<()> {
     Ok(())
 }
 
+/// Options for `sv ws archive`
+pub struct ArchiveOptions {
+    pub name: String,
+    pub force: bool,
+    pub actor: Option<String>,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+/// Output for `sv ws archive`
+#[derive(Debug, Serialize)]
+pub struct ArchiveOutput {
+    pub name: String,
+    pub path: PathBuf,
+    pub branch: String,
+    pub released_leases: Vec<String>,
+}
+
+/// Run `sv ws archive` command
+///
+/// Removes the worktree but keeps the branch and the registry record (marked
+/// archived), and releases leases scoped to the workspace.
+pub fn run_archive(opts: ArchiveOptions) -> Result<()> {
+    let repo = git::open_repo(opts.repo.as_deref())?;
+    let workdir = git::workdir(&repo)?;
+    let common_dir = resolve_common_dir(&repo)?;
+    let storage = Storage::new(workdir.clone(), common_dir, workdir.clone());
+
+    let entry = storage
+        .find_workspace(&opts.name)?
+        .ok_or_else(|| Error::WorkspaceNotFound(opts.name.clone()))?;
+    if entry.archived {
+        return Err(Error::InvalidArgument(format!(
+            "workspace '{}' is already archived",
+            opts.name
+        )));
+    }
+    if refs::find_refname(&repo, &entry.branch).is_none() {
+        return Err(Error::OperationFailed(format!(
+            "branch '{}' no longer exists; use sv ws rm instead",
+            entry.branch
+        )));
+    }
+
+    // Mark the record first: registry updates drop entries whose path is
+    // gone unless they are archived.
+    let set_archived = |archived: bool| {
+        storage.update_workspace(&opts.name, |entry| {
+            entry.archived = archived;
+            Ok(())
+        })
+    };
+    set_archived(true)?;
+    if entry.path.exists() {
+        if let Err(err) = git::remove_worktree(&repo, &opts.name, opts.force) {
+            if !opts.force {
+                set_archived(false)?;
+                return Err(err);
+            }
+            std::fs::remove_dir_all(&entry.path)?;
+        }
+    }
+    git::prune_worktrees(&repo).ok();
+
+    let scope = LeaseScope::Workspace(entry.name.clone());
+    let mut released_leases = Vec::new();
+    let mut snapshots = Vec::new();
+    {
+        let lock_path = storage.leases_file().with_extension("lock");
+        let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
+        let mut leases = storage.load_leases()?;
+        let ids: Vec<_> = leases
+            .active()
+            .filter(|lease| lease.scope == scope)
+            .map(|lease| lease.id)
+            .collect();
+        for id in ids {
+            if let Some(lease) = leases.find_mut(&id) {
+                lease.release();
+                released_leases.push(id.to_string());
+                snapshots.push(lease.clone());
+            }
+        }
+        if !released_leases.is_empty() {
+            storage.save_leases(&leases)?;
+        }
+    }
+
+    let actor = actor::resolve_actor_optional(Some(&workdir), opts.actor.as_deref())?;
+    let mut record = OpRecord::new(format!("sv ws archive {}", opts.name), actor);
+    record.affected_workspaces.push(opts.name.clone());
+    if !snapshots.is_empty() {
+        record.affected_paths = snapshots
+            .iter()
+            .map(|lease| lease.pathspec.clone())
+            .collect();
+        record.details = Some(OpDetails {
+            leases: snapshots,
+            ..OpDetails::default()
+        });
+    }
+    record.undo_data = Some(UndoData {
+        lease_changes: released_leases
+            .iter()
+            .map(|id| LeaseChange {
+                lease_id: id.clone(),
+                action: "release".to_string(),
+            })
+            .collect(),
+        ..Default::default()
+    });
+    let _ = OpLog::for_storage(&storage).append(&record);
+
+    let output = ArchiveOutput {
+        name: entry.name,
+        path: entry.path,
+        branch: entry.branch,
+        released_leases,
+    };
+    if opts.json {
+        outln!("{}", serde_json::to_string_pretty(&output)?);
+    } else if !opts.quiet {
+        outln!(
+            "Archived workspace '{}' (branch {} kept{})",
+            output.name,
+            output.branch,
+            match output.released_leases.len() {
+                0 => String::new(),
+                count => format!(", {count} lease(s) released"),
+            }
+        );
+        outln!("Restore with: sv ws restore {}", output.name);
+    }
+
+    Ok(())
+}
+
+/// Options for `sv ws restore`
+pub struct RestoreOptions {
+    pub name: String,
+    pub actor: Option<String>,
+    pub repo: Option<PathBuf>,
+    pub json: bool,
+    pub quiet: bool,
+}
+
+/// Output for `sv ws restore`
+#[derive(Debug, Serialize)]
+pub struct RestoreOutput {
+    pub name: String,
+    pub path: PathBuf,
+    pub branch: String,
+}
+
+/// Run `sv ws restore` command
+///
+/// Recreates the worktree of an archived workspace at its recorded path,
+/// checked out on its kept branch.
+pub fn run_restore(opts: RestoreOptions) -> Result<()> {
+    let repo = git::open_repo(opts.repo.as_deref())?;
+    let workdir = git::workdir(&repo)?;
+    let common_dir = resolve_common_dir(&repo)?;
+    let storage = Storage::new(workdir.clone(), common_dir, workdir.clone());
+
+    let entry = storage
+        .find_workspace(&opts.name)?
+        .ok_or_else(|| Error::WorkspaceNotFound(opts.name.clone()))?;
+    if !entry.archived {
+        return Err(Error::InvalidArgument(format!(
+            "workspace '{}' is not archived",
+            opts.name
+        )));
+    }
+
+    git::add_worktree_for_branch(&repo, &entry.path, &entry.branch)?;
+    storage.update_workspace(&opts.name, |entry| {
+        entry.archived = false;
+        entry.last_active = Some(Utc::now().to_rfc3339());
+        Ok(())
+    })?;
+
+    let actor = actor::resolve_actor_optional(Some(&workdir), opts.actor.as_deref())?;
+    let mut record = OpRecord::new(format!("sv ws restore {}", opts.name), actor);
+    record.affected_workspaces.push(opts.name.clone());
+    let _ = OpLog::for_storage(&storage).append(&record);
+
+    let output = RestoreOutput {
+        name: entry.name,
+        path: entry.path,
+        branch: entry.branch,
+    };
+    if opts.json {
+        outln!("{}", serde_json::to_string_pretty(&output)?);
+    } else if !opts.quiet {
+        outln!(
+            "Restored workspace '{}' at {} ({})",
+            output.name,
+            output.path.display(),
+            output.branch
+        );
+    }
+
+    Ok(())
+}
+
 /// Options for `sv ws clean`
 pub struct CleanOptions {
     pub selector: Option<String>,
@@ -702,7 +1100,7 @@ pub fn run_clean(opts: CleanOptions) -> Result<()> {
         .selector
         .clone()
         .unwrap_or_else(|| "ws(active)".to_string());
-    let matching = super::resolve_hoist_workspaces(&repo, &registry, &selector)?;
+    let matching = super::resolve_hoist_workspaces(&repo, &registry, &selector, false)?;
     let matched = matching.len();
 
     let mut candidates = Vec::new();
@@ -742,23 +1140,23 @@ pub fn run_clean(opts: CleanOptions) -> Result<()> {
     };
 
     if opts.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
+        outln!("{}", serde_json::to_string_pretty(&output)?);
     } else if !opts.quiet {
         let header = if opts.dry_run {
             "Workspace cleanup (dry run)"
         } else {
             "Workspace cleanup"
         };
-        println!("{header}");
-        println!("  Selector: {}", output.selector);
+        outln!("{header}");
+        outln!("  Selector: {}", output.selector);
         if let Some(dest) = &output.dest {
-            println!("  Dest: {}", dest);
+            outln!("  Dest: {}", dest);
         } else {
-            println!("  Dest: workspace base");
+            outln!("  Dest: workspace base");
         }
-        println!("  Matched: {}", output.matched);
-        println!("  Merged: {}", output.merged);
-        println!("  Removed: {}", output.cleanup.removed.len());
+        outln!("  Matched: {}", output.matched);
+        outln!("  Merged: {}", output.merged);
+        outln!("  Removed: {}", output.cleanup.removed.len());
 
         if !output.cleanup.removed.is_empty() {
             let label = if opts.dry_run {
@@ -766,21 +1164,21 @@ pub fn run_clean(opts: CleanOptions) -> Result<()> {
             } else {
                 "Removed"
             };
-            println!("{label}:");
+            outln!("{label}:");
             for name in &output.cleanup.removed {
-                println!("  - {}", name);
+                outln!("  - {}", name);
             }
         }
         if !output.cleanup.skipped.is_empty() {
-            println!("Skipped:");
+            outln!("Skipped:");
             for skip in &output.cleanup.skipped {
-                println!("  - {} ({})", skip.name, skip.reason);
+                outln!("  - {} ({})", skip.name, skip.reason);
             }
         }
         if !output.cleanup.failed.is_empty() {
-            println!("Failed:");
+            outln!("Failed:");
             for failure in &output.cleanup.failed {
-                println!("  - {} ({})", failure.name, failure.error);
+                outln!("  - {} ({})", failure.name, failure.error);
             }
         }
     }
@@ -964,11 +1362,11 @@ pub fn run_here(opts: HereOptions) -> Result<()> {
     };
 
     if opts.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
+        outln!("{}", serde_json::to_string_pretty(&output)?);
     } else if !opts.quiet {
-        println!("Registered current directory as workspace '{}'", name);
-        println!("  Path: {}", output.path.display());
-        println!("  Branch: {}", output.branch);
+        outln!("Registered current directory as workspace '{}'", name);
+        outln!("  Path: {}", output.path.display());
+        outln!("  Branch: {}", output.branch);
     }
 
     Ok(())
@@ -1,10 +1,52 @@
 //! Configuration loading and management
 //!
-//! Handles parsing of `.sv.toml` configuration files.
+//! Handles parsing of `.sv.toml` configuration files, overlaid by an optional
+//! worktree-local `.sv.local.toml` (local over repo over defaults).
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
+use crate::lease::{ActorQuota, LeaseIntent, LeaseStrength, LimitedTtl, TtlOverflow};
+use crate::risk::RiskThresholds;
+
+/// Repo-wide configuration file, tracked in git
+pub const REPO_CONFIG_FILE: &str = ".sv.toml";
+
+/// Worktree-local overlay on top of [`REPO_CONFIG_FILE`], kept untracked
+pub const LOCAL_CONFIG_FILE: &str = ".sv.local.toml";
+
+/// Where an effective configuration value came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSource {
+    Default,
+    Repo,
+    Local,
+}
+
+/// Configuration with the files it was layered from
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    pub config: Config,
+    /// `.sv.toml`, when present
+    pub repo_file: Option<PathBuf>,
+    /// `.sv.local.toml`, when present
+    pub local_file: Option<PathBuf>,
+    /// Source of every key set by a file (dotted path); unlisted keys are defaults
+    pub origins: BTreeMap<String, ConfigSource>,
+}
+
+impl LayeredConfig {
+    /// Source of a dotted key such as `protect.mode`.
+    pub fn source_of(&self, key: &str) -> ConfigSource {
+        self.origins
+            .get(key)
+            .copied()
+            .unwrap_or(ConfigSource::Default)
+    }
+}
+
 /// Main configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -12,6 +54,10 @@ pub struct Config {
     #[serde(default = "default_base")]
     pub base: String,
 
+    /// Per-branch base overrides (`[[base_rules]]`), first match wins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub base_rules: Vec<BaseRule>,
+
     /// Actor configuration
     #[serde(default)]
     pub actor: ActorConfig,
@@ -27,16 +73,32 @@ pub struct Config {
     /// Tasks configuration
     #[serde(default)]
     pub tasks: TasksConfig,
+
+    /// Ref namespace configuration
+    #[serde(default)]
+    pub refs: RefsConfig,
+
+    /// Risk analysis configuration
+    #[serde(default)]
+    pub risk: RiskConfig,
+
+    /// Event emission configuration
+    #[serde(default)]
+    pub events: EventsConfig,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             base: default_base(),
+            base_rules: Vec::new(),
             actor: ActorConfig::default(),
             leases: LeaseConfig::default(),
             protect: ProtectConfig::default(),
             tasks: TasksConfig::default(),
+            refs: RefsConfig::default(),
+            risk: RiskConfig::default(),
+            events: EventsConfig::default(),
         }
     }
 }
@@ -45,6 +107,28 @@ fn default_base() -> String {
     "main".to_string()
 }
 
+/// Default base for branches matching a glob pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseRule {
+    /// Glob matched against the branch, or the workspace name for `sv/ws/*`
+    pub pattern: String,
+
+    /// Base ref used when the pattern matches
+    pub base: String,
+}
+
+impl BaseRule {
+    fn matches(&self, branch: &str) -> bool {
+        let Ok(pattern) = glob::Pattern::new(&self.pattern) else {
+            return false;
+        };
+        pattern.matches(branch)
+            || branch
+                .strip_prefix("sv/ws/")
+                .is_some_and(|name| pattern.matches(name))
+    }
+}
+
 /// Actor-related configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActorConfig {
@@ -88,6 +172,22 @@ pub struct LeaseConfig {
     #[serde(default = "default_require_note")]
     pub require_note: bool,
 
+    /// Maximum TTL for take/renew (unset means unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ttl: Option<String>,
+
+    /// What to do when a TTL exceeds max_ttl: clamp or reject
+    #[serde(default = "default_ttl_overflow")]
+    pub ttl_overflow: String,
+
+    /// Maximum active leases per actor (unset means unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_per_actor: Option<usize>,
+
+    /// Maximum active exclusive leases per actor (unset means unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_exclusive_per_actor: Option<usize>,
+
     /// Compatibility rules
     #[serde(default)]
     pub compat: LeaseCompatConfig,
@@ -113,6 +213,10 @@ fn default_require_note() -> bool {
     true
 }
 
+fn default_ttl_overflow() -> String {
+    "clamp".to_string()
+}
+
 impl Default for LeaseConfig {
     fn default() -> Self {
         Self {
@@ -121,6 +225,10 @@ impl Default for LeaseConfig {
             default_ttl: default_ttl(),
             expiration_grace: default_expiration_grace(),
             require_note: default_require_note(),
+            max_ttl: None,
+            ttl_overflow: default_ttl_overflow(),
+            max_per_actor: None,
+            max_exclusive_per_actor: None,
             compat: LeaseCompatConfig::default(),
         }
     }
@@ -161,6 +269,10 @@ pub struct ProtectConfig {
     /// Protected path patterns
     #[serde(default)]
     pub paths: Vec<ProtectPath>,
+
+    /// Require `--reason` when `sv commit --allow-protected` bypasses a rule
+    #[serde(default)]
+    pub require_bypass_reason: bool,
 }
 
 /// Protected path entry with optional per-path mode override.
@@ -193,10 +305,76 @@ impl Default for ProtectConfig {
         Self {
             mode: default_protect_mode(),
             paths: vec![],
+            require_bypass_reason: false,
+        }
+    }
+}
+
+/// Ref namespace configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefsConfig {
+    /// Where workspace and hoist refs are created: `refs/heads` (ordinary
+    /// branches) or `refs/sv` (kept out of `git branch`)
+    #[serde(default = "default_refs_namespace")]
+    pub namespace: String,
+}
+
+fn default_refs_namespace() -> String {
+    crate::refs::DEFAULT_REF_NAMESPACE.to_string()
+}
+
+impl Default for RefsConfig {
+    fn default() -> Self {
+        Self {
+            namespace: default_refs_namespace(),
+        }
+    }
+}
+
+/// Risk analysis configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskConfig {
+    /// Score cutoffs for overlap severities
+    #[serde(default)]
+    pub thresholds: RiskThresholds,
+
+    /// Globs for generated/vendored paths whose overlaps are dropped from
+    /// risk reports (setting this replaces the defaults; `[]` disables)
+    #[serde(default = "default_risk_ignore")]
+    pub ignore: Vec<String>,
+}
+
+fn default_risk_ignore() -> Vec<String> {
+    [
+        "target/**",
+        "**/node_modules/**",
+        "vendor/**",
+        "dist/**",
+        "**/*.min.js",
+    ]
+    .into_iter()
+    .map(str::to_string)
+    .collect()
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            thresholds: RiskThresholds::default(),
+            ignore: default_risk_ignore(),
         }
     }
 }
 
+/// Event emission configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventsConfig {
+    /// Coalesce consecutive events of the same kind within this many
+    /// milliseconds into one event with a `coalesced` count (0 = off)
+    #[serde(default)]
+    pub coalesce_ms: u64,
+}
+
 /// Tasks configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TasksConfig {
@@ -208,6 +386,10 @@ pub struct TasksConfig {
     #[serde(default = "default_task_id_min_len")]
     pub id_min_len: usize,
 
+    /// Source of task ID suffixes: `ulid` or `uuid`
+    #[serde(default = "default_task_id_scheme")]
+    pub id_scheme: String,
+
     /// Allowed task statuses
     #[serde(default = "default_task_statuses")]
     pub statuses: Vec<String>,
@@ -224,6 +406,13 @@ pub struct TasksConfig {
     #[serde(default = "default_task_closed_statuses")]
     pub closed_statuses: Vec<String>,
 
+    /// Snapshot maintenance on append: `eager` rewrites both snapshots per
+    /// event, `deferred` only appends to the logs and drops the local
+    /// snapshot, which is rebuilt on the next read; the git-tracked snapshot
+    /// is only rewritten by `sv task sync`
+    #[serde(default = "default_task_snapshot_writes")]
+    pub snapshot_writes: String,
+
     /// Epic-specific task behavior configuration
     #[serde(default, skip_serializing_if = "TasksEpicConfig::is_empty")]
     pub epics: TasksEpicConfig,
@@ -279,6 +468,14 @@ fn default_task_id_min_len() -> usize {
     3
 }
 
+fn default_task_id_scheme() -> String {
+    "ulid".to_string()
+}
+
+fn default_task_snapshot_writes() -> String {
+    "eager".to_string()
+}
+
 fn default_task_status() -> String {
     "open".to_string()
 }
@@ -314,16 +511,89 @@ impl Default for TasksConfig {
         Self {
             id_prefix: default_task_id_prefix(),
             id_min_len: default_task_id_min_len(),
+            id_scheme: default_task_id_scheme(),
             statuses: default_task_statuses(),
             default_status: default_task_status(),
             in_progress_status: default_task_in_progress_status(),
             closed_statuses: default_task_closed_statuses(),
+            snapshot_writes: default_task_snapshot_writes(),
             epics: TasksEpicConfig::default(),
             compaction: TasksCompactionConfig::default(),
         }
     }
 }
 
+impl LeaseConfig {
+    /// Resolve a lease strength, falling back to `default_strength` when the
+    /// flag was omitted.
+    pub fn resolve_strength(&self, requested: Option<&str>) -> crate::error::Result<LeaseStrength> {
+        match requested {
+            Some(value) => value.parse(),
+            None => self.default_strength.parse().map_err(|_| {
+                crate::error::Error::InvalidConfig(format!(
+                    "leases.default_strength: invalid value '{}'",
+                    self.default_strength
+                ))
+            }),
+        }
+    }
+
+    /// Resolve a lease intent, falling back to `default_intent` when the flag
+    /// was omitted.
+    pub fn resolve_intent(&self, requested: Option<&str>) -> crate::error::Result<LeaseIntent> {
+        match requested {
+            Some(value) => value.parse(),
+            None => self.default_intent.parse().map_err(|_| {
+                crate::error::Error::InvalidConfig(format!(
+                    "leases.default_intent: invalid value '{}'",
+                    self.default_intent
+                ))
+            }),
+        }
+    }
+
+    /// Apply `max_ttl` and `ttl_overflow` to a requested TTL.
+    pub fn limit_ttl(&self, requested: &str) -> crate::error::Result<LimitedTtl> {
+        let overflow: TtlOverflow = self.ttl_overflow.parse()?;
+        crate::lease::limit_ttl(requested, self.max_ttl.as_deref(), overflow)
+    }
+
+    /// Per-actor limits from `max_per_actor` and `max_exclusive_per_actor`.
+    pub fn actor_quota(&self) -> ActorQuota {
+        ActorQuota {
+            max_leases: self.max_per_actor,
+            max_exclusive: self.max_exclusive_per_actor,
+        }
+    }
+
+    fn validate(&self) -> crate::error::Result<()> {
+        self.resolve_strength(None)?;
+        self.resolve_intent(None)?;
+        self.ttl_overflow.parse::<TtlOverflow>().map_err(|_| {
+            crate::error::Error::InvalidConfig(format!(
+                "leases.ttl_overflow: invalid value '{}' (expected clamp|reject)",
+                self.ttl_overflow
+            ))
+        })?;
+        if let Some(max_ttl) = self.max_ttl.as_deref() {
+            crate::lease::parse_duration(max_ttl).map_err(|err| {
+                crate::error::Error::InvalidConfig(format!("leases.max_ttl: {err}"))
+            })?;
+        }
+        for (field, limit) in [
+            ("max_per_actor", self.max_per_actor),
+            ("max_exclusive_per_actor", self.max_exclusive_per_actor),
+        ] {
+            if limit == Some(0) {
+                return Err(crate::error::Error::InvalidConfig(format!(
+                    "leases.{field} must be > 0"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 impl ProtectConfig {
     pub fn rules(&self) -> crate::error::Result<Vec<ProtectRule>> {
         self.validate()?;
@@ -360,6 +630,47 @@ impl ProtectConfig {
     }
 }
 
+/// Merge `overlay` into `base`: tables recursively, everything else replaced.
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Record `source` for every non-table value in `value`, keyed by dotted path.
+fn record_origins(
+    prefix: &str,
+    value: &toml::Value,
+    source: ConfigSource,
+    origins: &mut BTreeMap<String, ConfigSource>,
+) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                record_origins(&path, value, source, origins);
+            }
+        }
+        _ => {
+            origins.insert(prefix.to_string(), source);
+        }
+    }
+}
+
 fn validate_pattern(pattern: &str, field: &str) -> crate::error::Result<()> {
     if pattern.trim().is_empty() {
         return Err(crate::error::Error::InvalidConfig(format!(
@@ -383,6 +694,36 @@ fn validate_protect_mode(mode: &str, field: &str) -> crate::error::Result<()> {
     }
 }
 
+impl RefsConfig {
+    fn validate(&self) -> crate::error::Result<()> {
+        match self.namespace.as_str() {
+            crate::refs::DEFAULT_REF_NAMESPACE | crate::refs::DEDICATED_REF_NAMESPACE => Ok(()),
+            other => Err(crate::error::Error::InvalidConfig(format!(
+                "refs.namespace: invalid value '{other}' (expected refs/heads|refs/sv)"
+            ))),
+        }
+    }
+}
+
+impl RiskConfig {
+    fn validate(&self) -> crate::error::Result<()> {
+        let thresholds = &self.thresholds;
+        if !(0 < thresholds.medium
+            && thresholds.medium < thresholds.high
+            && thresholds.high < thresholds.critical)
+        {
+            return Err(crate::error::Error::InvalidConfig(format!(
+                "risk.thresholds must satisfy 0 < medium < high < critical (got {}, {}, {})",
+                thresholds.medium, thresholds.high, thresholds.critical
+            )));
+        }
+        for pattern in &self.ignore {
+            validate_pattern(pattern, "risk.ignore")?;
+        }
+        Ok(())
+    }
+}
+
 impl Config {
     /// Load configuration from a `.sv.toml` file
     pub fn load(path: &PathBuf) -> crate::error::Result<Self> {
@@ -393,15 +734,47 @@ impl Config {
     }
 
     /// Load configuration from repo root, or return defaults
+    ///
+    /// Applies the `.sv.local.toml` overlay; if the layered result is invalid
+    /// the repo file alone is used.
     pub fn load_from_repo(repo_root: &Path) -> Self {
-        let config_path = repo_root.join(".sv.toml");
-        if config_path.exists() {
-            Self::load(&config_path).unwrap_or_default()
-        } else {
-            Self::default()
+        let config_path = repo_root.join(REPO_CONFIG_FILE);
+        match Self::load_layered(repo_root) {
+            Ok(layered) => layered.config,
+            Err(_) if config_path.exists() => Self::load(&config_path).unwrap_or_default(),
+            Err(_) => Self::default(),
         }
     }
 
+    /// Load `.sv.toml` overlaid by `.sv.local.toml`, recording each key's source.
+    ///
+    /// Tables merge key by key; any other value (including arrays) set in the
+    /// local file replaces the repo value.
+    pub fn load_layered(repo_root: &Path) -> crate::error::Result<LayeredConfig> {
+        let mut merged = toml::Value::Table(toml::map::Map::new());
+        let mut origins = BTreeMap::new();
+        let mut layer = |path: PathBuf, source: ConfigSource| -> crate::error::Result<_> {
+            if !path.exists() {
+                return Ok(None);
+            }
+            let value: toml::Value = toml::from_str(&std::fs::read_to_string(&path)?)?;
+            record_origins("", &value, source, &mut origins);
+            merge_toml(&mut merged, value);
+            Ok(Some(path))
+        };
+        let repo_file = layer(repo_root.join(REPO_CONFIG_FILE), ConfigSource::Repo)?;
+        let local_file = layer(repo_root.join(LOCAL_CONFIG_FILE), ConfigSource::Local)?;
+
+        let config: Config = merged.try_into()?;
+        config.validate()?;
+        Ok(LayeredConfig {
+            config,
+            repo_file,
+            local_file,
+            origins,
+        })
+    }
+
     /// Save configuration to a file
     pub fn save(&self, path: &PathBuf) -> crate::error::Result<()> {
         let content = toml::to_string_pretty(self)?;
@@ -409,9 +782,35 @@ impl Config {
         Ok(())
     }
 
+    /// Default base ref for `branch`: the first matching `[[base_rules]]`
+    /// entry, otherwise `base`.
+    pub fn base_for(&self, branch: &str) -> &str {
+        self.base_rules
+            .iter()
+            .find(|rule| rule.matches(branch))
+            .map(|rule| rule.base.as_str())
+            .unwrap_or(&self.base)
+    }
+
     fn validate(&self) -> crate::error::Result<()> {
+        for (idx, rule) in self.base_rules.iter().enumerate() {
+            if let Err(err) = glob::Pattern::new(&rule.pattern) {
+                return Err(crate::error::Error::InvalidConfig(format!(
+                    "base_rules[{idx}].pattern: invalid glob '{}': {err}",
+                    rule.pattern
+                )));
+            }
+            if rule.base.trim().is_empty() {
+                return Err(crate::error::Error::InvalidConfig(format!(
+                    "base_rules[{idx}].base cannot be empty"
+                )));
+            }
+        }
+        self.leases.validate()?;
         self.protect.validate()?;
         self.tasks.validate()?;
+        self.refs.validate()?;
+        self.risk.validate()?;
         Ok(())
     }
 }
@@ -439,6 +838,18 @@ impl TasksConfig {
                 "tasks.id_min_len must be <= 16".to_string(),
             ));
         }
+        if !matches!(self.id_scheme.as_str(), "ulid" | "uuid") {
+            return Err(crate::error::Error::InvalidConfig(format!(
+                "tasks.id_scheme: invalid value '{}' (expected ulid|uuid)",
+                self.id_scheme
+            )));
+        }
+        if !matches!(self.snapshot_writes.as_str(), "eager" | "deferred") {
+            return Err(crate::error::Error::InvalidConfig(format!(
+                "tasks.snapshot_writes: invalid value '{}' (expected eager|deferred)",
+                self.snapshot_writes
+            )));
+        }
 
         if self.statuses.is_empty() {
             return Err(crate::error::Error::InvalidConfig(
@@ -528,6 +939,10 @@ mod tests {
         assert_eq!(cfg.leases.default_intent, "other");
         assert_eq!(cfg.leases.default_ttl, "2h");
         assert_eq!(cfg.leases.expiration_grace, "0s");
+        assert_eq!(cfg.leases.max_ttl, None);
+        assert_eq!(cfg.leases.ttl_overflow, "clamp");
+        assert_eq!(cfg.leases.max_per_actor, None);
+        assert_eq!(cfg.leases.max_exclusive_per_actor, None);
         assert!(cfg.leases.compat.allow_overlap_cooperative);
         assert!(cfg.leases.compat.require_flag_for_strong_overlap);
         assert_eq!(cfg.protect.mode, "guard");
@@ -542,6 +957,8 @@ mod tests {
         );
         assert_eq!(cfg.tasks.id_prefix, "sv");
         assert_eq!(cfg.tasks.id_min_len, 3);
+        assert_eq!(cfg.tasks.id_scheme, "ulid");
+        assert_eq!(cfg.tasks.snapshot_writes, "eager");
         assert_eq!(cfg.tasks.default_status, "open");
         assert_eq!(cfg.tasks.in_progress_status, "in_progress");
         assert_eq!(cfg.tasks.closed_statuses, vec!["closed".to_string()]);
@@ -549,6 +966,8 @@ mod tests {
         assert!(!cfg.tasks.compaction.auto);
         assert_eq!(cfg.tasks.compaction.max_log_mb, 200);
         assert_eq!(cfg.tasks.compaction.older_than, "180d");
+        assert_eq!(cfg.refs.namespace, "refs/heads");
+        assert_eq!(cfg.risk.thresholds, RiskThresholds::default());
     }
 
     #[test]
@@ -672,6 +1091,183 @@ default_status = "missing"
         }
     }
 
+    #[test]
+    fn base_for_uses_first_matching_rule() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".sv.toml");
+        fs::write(
+            &path,
+            r#"
+base = "main"
+
+[[base_rules]]
+pattern = "feature/*"
+base = "develop"
+
+[[base_rules]]
+pattern = "release/**"
+base = "stable"
+
+[[base_rules]]
+pattern = "feature/legacy-*"
+base = "legacy"
+"#,
+        )
+        .expect("write config");
+        let cfg = Config::load(&path).expect("load config");
+
+        assert_eq!(cfg.base_for("feature/login"), "develop");
+        assert_eq!(cfg.base_for("feature/legacy-auth"), "develop");
+        assert_eq!(cfg.base_for("sv/ws/feature/login"), "develop");
+        assert_eq!(cfg.base_for("release/2.0/rc1"), "stable");
+        assert_eq!(cfg.base_for("sv/ws/agent1"), "main");
+        assert_eq!(cfg.base_for("bugfix/x"), "main");
+    }
+
+    #[test]
+    fn base_rules_reject_invalid_patterns() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".sv.toml");
+        fs::write(
+            &path,
+            "[[base_rules]]\npattern = \"feature/[\"\nbase = \"develop\"\n",
+        )
+        .expect("write config");
+        let err = Config::load(&path).expect_err("invalid config");
+        match err {
+            crate::error::Error::InvalidConfig(message) => {
+                assert!(message.contains("base_rules[0].pattern"))
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn tasks_id_scheme_must_be_known() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".sv.toml");
+        fs::write(&path, "[tasks]\nid_scheme = \"uuid\"\n").expect("write config");
+        assert_eq!(
+            Config::load(&path).expect("load config").tasks.id_scheme,
+            "uuid"
+        );
+
+        fs::write(&path, "[tasks]\nid_scheme = \"snowflake\"\n").expect("write config");
+        let err = Config::load(&path).expect_err("invalid config");
+        match err {
+            crate::error::Error::InvalidConfig(message) => {
+                assert!(message.contains("tasks.id_scheme"))
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn refs_namespace_must_be_known() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".sv.toml");
+        fs::write(&path, "[refs]\nnamespace = \"refs/sv\"\n").expect("write config");
+        assert_eq!(
+            Config::load(&path).expect("load config").refs.namespace,
+            "refs/sv"
+        );
+
+        fs::write(&path, "[refs]\nnamespace = \"refs/agents\"\n").expect("write config");
+        let err = Config::load(&path).expect_err("invalid config");
+        match err {
+            crate::error::Error::InvalidConfig(message) => {
+                assert!(message.contains("refs.namespace"))
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn invalid_default_strength_rejected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".sv.toml");
+        fs::write(&path, "[leases]\ndefault_strength = \"mighty\"\n").expect("write config");
+
+        let err = Config::load(&path).expect_err("invalid config");
+        match err {
+            crate::error::Error::InvalidConfig(message) => {
+                assert!(message.contains("leases.default_strength"))
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn lease_quotas_must_be_positive() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".sv.toml");
+        fs::write(&path, "[leases]\nmax_per_actor = 3\n").expect("write config");
+        let quota = Config::load(&path)
+            .expect("load config")
+            .leases
+            .actor_quota();
+        assert_eq!(quota.max_leases, Some(3));
+        assert_eq!(quota.max_exclusive, None);
+
+        fs::write(&path, "[leases]\nmax_exclusive_per_actor = 0\n").expect("write config");
+        let err = Config::load(&path).expect_err("invalid config");
+        match err {
+            crate::error::Error::InvalidConfig(message) => {
+                assert!(message.contains("leases.max_exclusive_per_actor"))
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn risk_ignore_defaults_and_overrides() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".sv.toml");
+        fs::write(&path, "[risk.thresholds]\nhigh = 7\n").expect("write config");
+        let risk = Config::load(&path).expect("load config").risk;
+        assert!(risk.ignore.iter().any(|pattern| pattern == "target/**"));
+
+        fs::write(&path, "[risk]\nignore = []\n").expect("write config");
+        assert!(Config::load(&path)
+            .expect("load config")
+            .risk
+            .ignore
+            .is_empty());
+
+        fs::write(&path, "[risk]\nignore = [\"gen/[\"]\n").expect("write config");
+        let err = Config::load(&path).expect_err("invalid config");
+        match err {
+            crate::error::Error::InvalidConfig(message) => {
+                assert!(message.contains("risk.ignore"), "{message}")
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn risk_thresholds_default_and_validate_ordering() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".sv.toml");
+        fs::write(&path, "[risk.thresholds]\nhigh = 7\n").expect("write config");
+        let thresholds = Config::load(&path).expect("load config").risk.thresholds;
+        assert_eq!(
+            thresholds,
+            RiskThresholds {
+                high: 7,
+                ..RiskThresholds::default()
+            }
+        );
+
+        fs::write(&path, "[risk.thresholds]\nmedium = 9\nhigh = 8\n").expect("write config");
+        let err = Config::load(&path).expect_err("invalid config");
+        match err {
+            crate::error::Error::InvalidConfig(message) => {
+                assert!(message.contains("medium < high < critical"), "{message}")
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
     #[test]
     fn load_from_repo_defaults_when_missing() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -689,6 +1285,46 @@ default_status = "missing"
         assert_eq!(cfg.base, "feature");
     }
 
+    #[test]
+    fn local_overlay_takes_precedence_over_repo() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            dir.path().join(REPO_CONFIG_FILE),
+            "base = \"dev\"\n[protect]\nmode = \"warn\"\npaths = [\"Cargo.lock\"]\n",
+        )
+        .expect("write repo config");
+        fs::write(
+            dir.path().join(LOCAL_CONFIG_FILE),
+            "[protect]\npaths = [\"infra/**\"]\n",
+        )
+        .expect("write local config");
+
+        let layered = Config::load_layered(dir.path()).expect("load layered");
+        assert_eq!(layered.config.base, "dev");
+        assert_eq!(layered.config.protect.mode, "warn");
+        let rules = layered.config.protect.rules().expect("rules");
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].pattern, "infra/**");
+        assert_eq!(layered.source_of("base"), ConfigSource::Repo);
+        assert_eq!(layered.source_of("protect.mode"), ConfigSource::Repo);
+        assert_eq!(layered.source_of("protect.paths"), ConfigSource::Local);
+        assert_eq!(layered.source_of("tasks.id_prefix"), ConfigSource::Default);
+        assert_eq!(
+            Config::load_from_repo(dir.path())
+                .protect
+                .rules()
+                .expect("rules"),
+            rules
+        );
+
+        fs::write(
+            dir.path().join(LOCAL_CONFIG_FILE),
+            "[protect]\nmode = \"nope\"\n",
+        )
+        .expect("write invalid local config");
+        assert_eq!(Config::load_from_repo(dir.path()).protect.mode, "warn");
+    }
+
     #[test]
     fn save_writes_toml() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -3,8 +3,11 @@
 //! Exit codes per spec:
 //! - 0: Success
 //! - 2: User error (bad args, missing repo)
-//! - 3: Blocked by policy (protected paths, active exclusive lease conflict)
+//! - 3: Blocked by policy (protected paths, active exclusive lease conflict,
+//!   failed gates such as `sv risk --fail-on`)
 //! - 4: Operation failed (git error, merge conflict)
+//! - 130: Interrupted by Ctrl-C after reporting partial results
+//!   (`sv risk --simulate`)
 
 use std::path::PathBuf;
 use thiserror::Error;
@@ -15,6 +18,7 @@ pub mod exit_codes {
     pub const USER_ERROR: i32 = 2;
     pub const POLICY_BLOCKED: i32 = 3;
     pub const OPERATION_FAILED: i32 = 4;
+    pub const INTERRUPTED: i32 = 130;
 }
 
 /// Main error type for sv operations
@@ -43,6 +47,9 @@ pub enum Error {
     #[error("Protected path would be committed: {0}")]
     ProtectedPath(PathBuf),
 
+    #[error("{}", describe_protected(.0))]
+    ProtectedViolations(Vec<ProtectedViolation>),
+
     #[error("Lease conflict: {path} is held by {holder} with {strength} strength")]
     LeaseConflict {
         path: PathBuf,
@@ -53,6 +60,10 @@ pub enum Error {
     #[error("Note required for {0} strength lease")]
     NoteRequired(String),
 
+    /// A gating flag tripped after the command already reported its result.
+    #[error("Gate failed: {0}")]
+    GateFailed(String),
+
     // Operation failures (exit code 4)
     #[error("Git error: {0}")]
     Git(#[from] git2::Error),
@@ -75,8 +86,30 @@ pub enum Error {
     #[error("Merge conflict in {0}")]
     MergeConflict(PathBuf),
 
+    /// A rebase, merge or cherry-pick stopped with conflicted files.
+    #[error("{}", describe_conflicts(operation, commit.as_deref(), files))]
+    Conflicted {
+        operation: String,
+        commit: Option<String>,
+        files: Vec<ConflictedFile>,
+    },
+
+    /// A multi-repo run where some repositories failed; `code` is the exit
+    /// code of the first failure and `results` holds every repo's output.
+    #[error("{} of {total} repositories failed: {}", failed.len(), failed.join(", "))]
+    ReposFailed {
+        failed: Vec<String>,
+        total: usize,
+        code: i32,
+        results: serde_json::Value,
+    },
+
     #[error("Operation failed: {0}")]
     OperationFailed(String),
+
+    /// Ctrl-C stopped the command after it reported partial results.
+    #[error("Interrupted: {0}")]
+    Interrupted(String),
 }
 
 impl Error {
@@ -92,9 +125,11 @@ impl Error {
             | Error::LeaseNotFound(_) => exit_codes::USER_ERROR,
 
             // Policy blocks
-            Error::ProtectedPath(_) | Error::LeaseConflict { .. } | Error::NoteRequired(_) => {
-                exit_codes::POLICY_BLOCKED
-            }
+            Error::ProtectedPath(_)
+            | Error::ProtectedViolations(_)
+            | Error::LeaseConflict { .. }
+            | Error::NoteRequired(_)
+            | Error::GateFailed(_) => exit_codes::POLICY_BLOCKED,
 
             // Operation failures
             Error::Git(_)
@@ -104,7 +139,12 @@ impl Error {
             | Error::TomlSerialize(_)
             | Error::LockFailed(_)
             | Error::MergeConflict(_)
+            | Error::Conflicted { .. }
             | Error::OperationFailed(_) => exit_codes::OPERATION_FAILED,
+
+            Error::ReposFailed { code, .. } => *code,
+
+            Error::Interrupted(_) => exit_codes::INTERRUPTED,
         }
     }
 
@@ -121,6 +161,10 @@ impl Error {
             Error::WorkspaceNotFound(name) => Some(json!({ "name": name })),
             Error::LeaseNotFound(id) => Some(json!({ "id": id })),
             Error::ProtectedPath(path) => Some(path_value(path)),
+            Error::ProtectedViolations(violations) => Some(json!({
+                "path": violations.first().map(|v| v.path.clone()),
+                "protected_violations": violations,
+            })),
             Error::LeaseConflict {
                 path,
                 holder,
@@ -131,6 +175,7 @@ impl Error {
                 "strength": strength,
             })),
             Error::NoteRequired(strength) => Some(json!({ "strength": strength })),
+            Error::GateFailed(message) => Some(json!({ "message": message })),
             Error::Git(err) => Some(json!({
                 "message": err.message(),
                 "code": format!("{:?}", err.code()),
@@ -144,7 +189,19 @@ impl Error {
             Error::TomlSerialize(err) => Some(json!({ "message": err.to_string() })),
             Error::LockFailed(path) => Some(path_value(path)),
             Error::MergeConflict(path) => Some(path_value(path)),
+            Error::Conflicted { commit, files, .. } => Some(json!({
+                "status": "conflicted",
+                "commit": commit,
+                "files": files,
+            })),
+            Error::ReposFailed {
+                failed, results, ..
+            } => Some(json!({
+                "failed": failed,
+                "repos": results,
+            })),
             Error::OperationFailed(message) => Some(json!({ "message": message })),
+            Error::Interrupted(message) => Some(json!({ "message": message })),
         };
 
         let sources = error_sources(self);
@@ -160,6 +217,54 @@ impl Error {
     }
 }
 
+/// A file left with unresolved conflicts, with its conflict marker count
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ConflictedFile {
+    pub path: String,
+    pub hunks: usize,
+}
+
+fn describe_conflicts(operation: &str, commit: Option<&str>, files: &[ConflictedFile]) -> String {
+    let mut message = match commit {
+        Some(commit) => format!(
+            "{operation} stopped on conflicts applying {}:",
+            &commit[..8.min(commit.len())]
+        ),
+        None => format!("{operation} stopped on conflicts:"),
+    };
+    for file in files {
+        message.push_str(&format!("\n  {} ({} hunk(s))", file.path, file.hunks));
+    }
+    message
+}
+
+/// A staged path blocked by a guard-mode protect rule
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProtectedViolation {
+    pub path: String,
+    pub pattern: String,
+    pub mode: String,
+}
+
+fn describe_protected(violations: &[ProtectedViolation]) -> String {
+    match violations {
+        [only] => format!(
+            "Protected path would be committed: {} (pattern: {}, mode: {})",
+            only.path, only.pattern, only.mode
+        ),
+        _ => {
+            let mut message = format!("Protected paths would be committed ({}):", violations.len());
+            for violation in violations {
+                message.push_str(&format!(
+                    "\n  {} (pattern: {}, mode: {})",
+                    violation.path, violation.pattern, violation.mode
+                ));
+            }
+            message
+        }
+    }
+}
+
 /// Result type alias for sv operations
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -1,21 +1,33 @@
 //! Event output for external integrations.
 //!
-//! Events are emitted as JSON lines to stdout or a configured file.
+//! Events are emitted as JSON lines to stdout, a configured file, or a Unix
+//! socket a supervising process listens on. With the `webhook` feature,
+//! events can also be POSTed to an `http://` or `https://` URL.
 
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use crate::config::EventsConfig;
 use crate::error::{Error, Result};
 
+/// Version stamped on every emitted event envelope.
+///
+/// Bump the trailing number whenever the envelope or an existing payload
+/// changes shape incompatibly (a field is removed, renamed or retyped);
+/// adding fields does not require a bump.
 pub const EVENT_SCHEMA_VERSION: &str = "sv.event.v1";
 
 #[derive(Debug, Clone)]
 pub enum EventDestination {
     Stdout,
     File(PathBuf),
+    /// `unix:<path>`: a listening Unix domain socket
+    UnixSocket(PathBuf),
+    /// `http://` or `https://` URL each event is POSTed to
+    Webhook(String),
 }
 
 impl EventDestination {
@@ -28,6 +40,12 @@ impl EventDestination {
             if trimmed == "-" {
                 return Some(EventDestination::Stdout);
             }
+            if let Some(path) = trimmed.strip_prefix("unix:") {
+                return Some(EventDestination::UnixSocket(PathBuf::from(path)));
+            }
+            if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
+                return Some(EventDestination::Webhook(trimmed.to_string()));
+            }
             Some(EventDestination::File(PathBuf::from(trimmed)))
         })
     }
@@ -36,20 +54,31 @@ impl EventDestination {
         match self {
             EventDestination::Stdout => Ok(EventSink::stdout()),
             EventDestination::File(path) => EventSink::file(path),
+            EventDestination::UnixSocket(path) => Ok(EventSink::unix_socket(path)),
+            EventDestination::Webhook(url) => EventSink::webhook(url),
         }
     }
+
+    /// Open the sink with `[events]` settings such as coalescing applied.
+    pub fn open_with(&self, config: &EventsConfig) -> Result<EventSink> {
+        Ok(self.open()?.coalesce_ms(config.coalesce_ms))
+    }
 }
 
 /// High-level event kinds emitted by sv.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EventKind {
     LeaseCreated,
     LeaseReleased,
+    LeaseRenewed,
+    LeaseExpired,
     WorkspaceCreated,
     WorkspaceRemoved,
     CommitBlocked,
     CommitCreated,
+    ProtectionBypassed,
+    ProtectionWarned,
     TaskCreated,
     TaskStarted,
     TaskStatusChanged,
@@ -82,6 +111,9 @@ pub struct Event {
     pub actor: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<serde_json::Value>,
+    /// Number of events this one stands for when coalescing merged a run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coalesced: Option<u64>,
 }
 
 impl Event {
@@ -93,6 +125,7 @@ impl Event {
             timestamp: Utc::now(),
             actor,
             data: None,
+            coalesced: None,
         }
     }
 
@@ -103,32 +136,193 @@ impl Event {
     }
 }
 
+/// Check one line of a captured event stream against the envelope schema.
+///
+/// Returns the event kind, or a description of the first problem found:
+/// invalid JSON, a missing or mistyped envelope field, an unknown
+/// `schema_version`, or an unknown `event` kind.
+pub fn validate_event_line(line: &str) -> std::result::Result<EventKind, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(line).map_err(|err| format!("invalid JSON: {err}"))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| "event is not a JSON object".to_string())?;
+
+    let version = object
+        .get("schema_version")
+        .ok_or_else(|| "missing schema_version".to_string())?
+        .as_str()
+        .ok_or_else(|| "schema_version is not a string".to_string())?;
+    if version != EVENT_SCHEMA_VERSION {
+        return Err(format!(
+            "unknown schema_version '{version}' (expected {EVENT_SCHEMA_VERSION})"
+        ));
+    }
+
+    let name = object
+        .get("event")
+        .ok_or_else(|| "missing event".to_string())?
+        .as_str()
+        .ok_or_else(|| "event is not a string".to_string())?;
+    let kind: EventKind = serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| format!("unknown event kind '{name}'"))?;
+
+    let timestamp = object
+        .get("timestamp")
+        .ok_or_else(|| "missing timestamp".to_string())?
+        .as_str()
+        .ok_or_else(|| "timestamp is not a string".to_string())?;
+    DateTime::parse_from_rfc3339(timestamp)
+        .map_err(|err| format!("invalid timestamp '{timestamp}': {err}"))?;
+
+    if let Some(actor) = object.get("actor") {
+        if !actor.is_string() && !actor.is_null() {
+            return Err("actor is not a string".to_string());
+        }
+    }
+    Ok(kind)
+}
+
 /// Event sink that writes JSONL output to a destination.
 pub struct EventSink {
     writer: Box<dyn Write + Send>,
+    coalesce: Option<chrono::Duration>,
+    pending: Option<PendingEvent>,
+}
+
+/// A run of same-kind events held back while coalescing.
+struct PendingEvent {
+    /// Latest event of the run
+    event: Event,
+    /// Timestamp of the first event, which anchors the window
+    first_at: DateTime<Utc>,
+    count: u64,
+    /// Payloads of every event in the run, oldest first
+    payloads: Vec<serde_json::Value>,
 }
 
 impl EventSink {
-    /// Emit events to stdout.
-    pub fn stdout() -> Self {
+    fn new(writer: Box<dyn Write + Send>) -> Self {
         Self {
-            writer: Box::new(std::io::stdout()),
+            writer,
+            coalesce: None,
+            pending: None,
         }
     }
 
+    /// Coalesce consecutive same-kind events within `window_ms` (0 = off).
+    ///
+    /// A run is written as its latest event with a `coalesced` count and a
+    /// `data` array holding every event's payload, once a different kind
+    /// arrives, the window has passed, or the sink is flushed or dropped.
+    pub fn coalesce_ms(mut self, window_ms: u64) -> Self {
+        self.coalesce = (window_ms > 0)
+            .then(|| chrono::Duration::milliseconds(i64::try_from(window_ms).unwrap_or(i64::MAX)));
+        self
+    }
+
+    /// Emit events to stdout.
+    pub fn stdout() -> Self {
+        Self::new(Box::new(std::io::stdout()))
+    }
+
     /// Emit events to a file, creating it if necessary.
     pub fn file(path: &Path) -> Result<Self> {
         let file = std::fs::OpenOptions::new()
             .create(true)
             .append(true)
             .open(path)?;
-        Ok(Self {
-            writer: Box::new(file),
-        })
+        Ok(Self::new(Box::new(file)))
+    }
+
+    /// Emit events to a Unix socket.
+    ///
+    /// A supervisor that is not listening must not fail the command, so a
+    /// connection failure is reported on stderr and events are discarded.
+    pub fn unix_socket(path: &Path) -> Self {
+        match Self::connect_unix(path) {
+            Ok(sink) => sink,
+            Err(err) => {
+                eprintln!(
+                    "Warning: cannot connect to event socket {}: {err}; events disabled",
+                    path.display()
+                );
+                Self::new(Box::new(std::io::sink()))
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn connect_unix(path: &Path) -> std::io::Result<Self> {
+        let stream = std::os::unix::net::UnixStream::connect(path)?;
+        Ok(Self::new(Box::new(stream)))
+    }
+
+    #[cfg(not(unix))]
+    fn connect_unix(_path: &Path) -> std::io::Result<Self> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "Unix sockets are not supported on this platform",
+        ))
+    }
+
+    /// POST events to a URL from a background thread.
+    ///
+    /// Events are queued (bounded) so a slow or unreachable endpoint never
+    /// blocks the command; delivery is retried with backoff, and dropping the
+    /// sink waits for the queue to drain.
+    #[cfg(feature = "webhook")]
+    pub fn webhook(url: &str) -> Result<Self> {
+        Ok(Self::new(Box::new(webhook::WebhookWriter::spawn(
+            url.to_string(),
+        ))))
+    }
+
+    #[cfg(not(feature = "webhook"))]
+    pub fn webhook(url: &str) -> Result<Self> {
+        Err(Error::InvalidArgument(format!(
+            "cannot send events to {url}: sv was built without the `webhook` feature"
+        )))
     }
 
-    /// Write a single event as JSONL.
+    /// Write a single event as JSONL, or hold it back while coalescing.
     pub fn emit(&mut self, event: &Event) -> Result<()> {
+        let Some(window) = self.coalesce else {
+            return self.write_event(event);
+        };
+        if let Some(pending) = &mut self.pending {
+            if pending.event.event == event.event && event.timestamp - pending.first_at <= window {
+                pending.event = event.clone();
+                pending.count += 1;
+                pending.payloads.extend(event.data.clone());
+                return Ok(());
+            }
+        }
+        self.flush_pending()?;
+        self.pending = Some(PendingEvent {
+            event: event.clone(),
+            first_at: event.timestamp,
+            count: 1,
+            payloads: event.data.iter().cloned().collect(),
+        });
+        Ok(())
+    }
+
+    /// Write out a held-back coalesced run, if any.
+    pub fn flush_pending(&mut self) -> Result<()> {
+        if let Some(pending) = self.pending.take() {
+            let mut event = pending.event;
+            if pending.count > 1 {
+                event.coalesced = Some(pending.count);
+                event.data = (!pending.payloads.is_empty())
+                    .then_some(serde_json::Value::Array(pending.payloads));
+            }
+            self.write_event(&event)?;
+        }
+        Ok(())
+    }
+
+    fn write_event(&mut self, event: &Event) -> Result<()> {
         let serialized = serde_json::to_vec(event)?;
         self.writer.write_all(&serialized)?;
         self.writer.write_all(b"\n")?;
@@ -136,3 +330,337 @@ impl EventSink {
         Ok(())
     }
 }
+
+impl Drop for EventSink {
+    fn drop(&mut self) {
+        if let Err(err) = self.flush_pending() {
+            eprintln!("Warning: failed to write coalesced event: {err}");
+        }
+    }
+}
+
+#[cfg(feature = "webhook")]
+mod webhook {
+    use std::io::Write;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc::{sync_channel, SyncSender, TrySendError};
+    use std::sync::Arc;
+    use std::thread::JoinHandle;
+    use std::time::{Duration, Instant};
+
+    /// Events buffered while the endpoint is slow; further events are dropped.
+    const QUEUE_CAPACITY: usize = 256;
+    const MAX_ATTEMPTS: u32 = 4;
+    const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+    /// How long the command waits on exit for queued events to be delivered.
+    const DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// `Write` adapter that turns each JSONL line into one POST.
+    pub(super) struct WebhookWriter {
+        buffer: Vec<u8>,
+        sender: Option<SyncSender<Vec<u8>>>,
+        worker: Option<JoinHandle<()>>,
+        /// Events queued or in flight, shared with the worker.
+        pending: Arc<AtomicUsize>,
+        dropped: usize,
+    }
+
+    impl WebhookWriter {
+        pub(super) fn spawn(url: String) -> Self {
+            let (sender, receiver) = sync_channel::<Vec<u8>>(QUEUE_CAPACITY);
+            let pending = Arc::new(AtomicUsize::new(0));
+            let worker_pending = Arc::clone(&pending);
+            let worker = std::thread::spawn(move || {
+                let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build();
+                // After a persistent failure the endpoint is considered down:
+                // drain the queue without retrying each event.
+                let mut down = false;
+                for body in receiver {
+                    if !down {
+                        if let Err(err) = deliver(&agent, &url, &body) {
+                            eprintln!("Warning: webhook {url} failed: {err}; events disabled");
+                            down = true;
+                        }
+                    }
+                    worker_pending.fetch_sub(1, Ordering::SeqCst);
+                }
+            });
+            Self {
+                buffer: Vec::new(),
+                sender: Some(sender),
+                worker: Some(worker),
+                pending,
+                dropped: 0,
+            }
+        }
+    }
+
+    fn deliver(agent: &ureq::Agent, url: &str, body: &[u8]) -> std::result::Result<(), String> {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            let error = match agent
+                .post(url)
+                .set("Content-Type", "application/json")
+                .send_bytes(body)
+            {
+                Ok(_) => return Ok(()),
+                // Client errors will not succeed on retry.
+                Err(ureq::Error::Status(code, _)) if (400..500).contains(&code) => {
+                    return Err(format!("HTTP {code}"));
+                }
+                Err(err) => err.to_string(),
+            };
+            if attempt >= MAX_ATTEMPTS {
+                return Err(format!("{error} (after {attempt} attempts)"));
+            }
+            std::thread::sleep(backoff);
+            backoff *= 2;
+            attempt += 1;
+        }
+    }
+
+    impl Write for WebhookWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.buffer.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            let Some(sender) = self.sender.as_ref() else {
+                return Ok(());
+            };
+            while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=end).take(end).collect();
+                self.pending.fetch_add(1, Ordering::SeqCst);
+                match sender.try_send(line) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => {
+                        self.pending.fetch_sub(1, Ordering::SeqCst);
+                        self.dropped += 1;
+                    }
+                    Err(TrySendError::Disconnected(_)) => {
+                        self.pending.fetch_sub(1, Ordering::SeqCst);
+                        return Ok(());
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl Drop for WebhookWriter {
+        fn drop(&mut self) {
+            let _ = self.flush();
+            if self.dropped > 0 {
+                eprintln!(
+                    "Warning: webhook queue full; dropped {} event(s)",
+                    self.dropped
+                );
+            }
+            self.sender = None;
+            let Some(worker) = self.worker.take() else {
+                return;
+            };
+            // Give the worker a bounded time to drain the queue; a slow or
+            // hanging endpoint must not keep the command from exiting.
+            let deadline = Instant::now() + DRAIN_TIMEOUT;
+            while !worker.is_finished() {
+                if Instant::now() >= deadline {
+                    eprintln!(
+                        "Warning: webhook did not finish within {}s; dropped {} undelivered event(s)",
+                        DRAIN_TIMEOUT.as_secs(),
+                        self.pending.load(Ordering::SeqCst)
+                    );
+                    return;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_KINDS: &[EventKind] = &[
+        EventKind::LeaseCreated,
+        EventKind::LeaseReleased,
+        EventKind::LeaseRenewed,
+        EventKind::LeaseExpired,
+        EventKind::WorkspaceCreated,
+        EventKind::WorkspaceRemoved,
+        EventKind::CommitBlocked,
+        EventKind::CommitCreated,
+        EventKind::ProtectionBypassed,
+        EventKind::ProtectionWarned,
+        EventKind::TaskCreated,
+        EventKind::TaskStarted,
+        EventKind::TaskStatusChanged,
+        EventKind::TaskPriorityChanged,
+        EventKind::TaskEdited,
+        EventKind::TaskClosed,
+        EventKind::TaskDeleted,
+        EventKind::TaskCommented,
+        EventKind::TaskEpicSet,
+        EventKind::TaskEpicCleared,
+        EventKind::TaskEpicAutoCloseSet,
+        EventKind::TaskEpicAutoCloseCleared,
+        EventKind::TaskProjectSet,
+        EventKind::TaskProjectCleared,
+        EventKind::TaskParentSet,
+        EventKind::TaskParentCleared,
+        EventKind::TaskBlocked,
+        EventKind::TaskUnblocked,
+        EventKind::TaskRelated,
+        EventKind::TaskUnrelated,
+    ];
+
+    /// Fails to compile when a kind is added, as a reminder to list it in
+    /// `ALL_KINDS`.
+    fn listed(kind: &EventKind) -> bool {
+        match kind {
+            EventKind::LeaseCreated
+            | EventKind::LeaseReleased
+            | EventKind::LeaseRenewed
+            | EventKind::LeaseExpired
+            | EventKind::WorkspaceCreated
+            | EventKind::WorkspaceRemoved
+            | EventKind::CommitBlocked
+            | EventKind::CommitCreated
+            | EventKind::ProtectionBypassed
+            | EventKind::ProtectionWarned
+            | EventKind::TaskCreated
+            | EventKind::TaskStarted
+            | EventKind::TaskStatusChanged
+            | EventKind::TaskPriorityChanged
+            | EventKind::TaskEdited
+            | EventKind::TaskClosed
+            | EventKind::TaskDeleted
+            | EventKind::TaskCommented
+            | EventKind::TaskEpicSet
+            | EventKind::TaskEpicCleared
+            | EventKind::TaskEpicAutoCloseSet
+            | EventKind::TaskEpicAutoCloseCleared
+            | EventKind::TaskProjectSet
+            | EventKind::TaskProjectCleared
+            | EventKind::TaskParentSet
+            | EventKind::TaskParentCleared
+            | EventKind::TaskBlocked
+            | EventKind::TaskUnblocked
+            | EventKind::TaskRelated
+            | EventKind::TaskUnrelated => true,
+        }
+    }
+
+    #[test]
+    fn every_event_kind_carries_the_schema_version() {
+        assert_eq!(EVENT_SCHEMA_VERSION, "sv.event.v1");
+        for kind in ALL_KINDS {
+            assert!(listed(kind));
+            let event = Event::new(kind.clone(), Some("alice".to_string()))
+                .with_data(serde_json::json!({ "id": "x" }))
+                .expect("payload");
+            let value = serde_json::to_value(&event).expect("serialize");
+            assert_eq!(value["schema_version"], EVENT_SCHEMA_VERSION, "{kind:?}");
+
+            let bare = serde_json::to_value(Event::new(kind.clone(), None)).expect("serialize");
+            assert_eq!(bare["schema_version"], EVENT_SCHEMA_VERSION, "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn validate_event_line_accepts_emitted_events() {
+        for kind in ALL_KINDS {
+            let line = serde_json::to_string(&Event::new(kind.clone(), Some("alice".to_string())))
+                .expect("serialize");
+            assert_eq!(validate_event_line(&line).as_ref(), Ok(kind));
+        }
+    }
+
+    #[test]
+    fn validate_event_line_reports_problems() {
+        let cases = [
+            ("not json", "invalid JSON"),
+            ("[]", "not a JSON object"),
+            (r#"{"event":"lease_created"}"#, "missing schema_version"),
+            (
+                r#"{"schema_version":"sv.event.v9","event":"lease_created","timestamp":"2025-01-01T00:00:00Z"}"#,
+                "unknown schema_version 'sv.event.v9'",
+            ),
+            (
+                r#"{"schema_version":"sv.event.v1","event":"lease_stolen","timestamp":"2025-01-01T00:00:00Z"}"#,
+                "unknown event kind 'lease_stolen'",
+            ),
+            (
+                r#"{"schema_version":"sv.event.v1","event":"lease_created","timestamp":"yesterday"}"#,
+                "invalid timestamp",
+            ),
+            (
+                r#"{"schema_version":"sv.event.v1","event":"lease_created","timestamp":"2025-01-01T00:00:00Z","actor":7}"#,
+                "actor is not a string",
+            ),
+        ];
+        for (line, expected) in cases {
+            let err = validate_event_line(line).expect_err(line);
+            assert!(err.contains(expected), "{line}: {err}");
+        }
+    }
+
+    #[test]
+    fn emitted_lines_start_with_the_schema_version() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("events.jsonl");
+        let mut sink = EventSink::file(&path).expect("open sink");
+        for kind in ALL_KINDS {
+            sink.emit(&Event::new(kind.clone(), None)).expect("emit");
+        }
+        let contents = std::fs::read_to_string(&path).expect("read events");
+        assert_eq!(contents.lines().count(), ALL_KINDS.len());
+        for line in contents.lines() {
+            assert!(
+                line.starts_with(&format!("{{\"schema_version\":\"{EVENT_SCHEMA_VERSION}\"")),
+                "{line}"
+            );
+        }
+    }
+
+    #[test]
+    fn coalescing_merges_runs_of_the_same_kind() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("events.jsonl");
+        let mut sink = EventSink::file(&path)
+            .expect("open sink")
+            .coalesce_ms(60_000);
+        for id in ["a", "b", "c"] {
+            let event = Event::new(EventKind::LeaseCreated, None)
+                .with_data(serde_json::json!({ "id": id }))
+                .expect("payload");
+            sink.emit(&event).expect("emit");
+        }
+        sink.emit(&Event::new(EventKind::LeaseReleased, None))
+            .expect("emit");
+        drop(sink);
+
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(&path)
+            .expect("read events")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event"], "lease_created");
+        assert_eq!(lines[0]["coalesced"], 3);
+        let ids: Vec<&str> = lines[0]["data"]
+            .as_array()
+            .expect("merged payloads")
+            .iter()
+            .map(|payload| payload["id"].as_str().expect("id"))
+            .collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert_eq!(lines[1]["event"], "lease_released");
+        assert!(lines[1].get("coalesced").is_none());
+    }
+}
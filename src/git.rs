@@ -117,8 +117,9 @@ pub fn head_info(repo: &Repository) -> Result<HeadInfo> {
         .target()
         .ok_or_else(|| Error::OperationFailed("HEAD has no target commit".to_string()))?;
 
-    // Check if HEAD is detached by seeing if it's a symbolic reference
-    let is_detached = !head.is_branch();
+    // HEAD attached to a ref outside refs/heads (see `[refs] namespace`) is
+    // not a branch but is not detached either.
+    let is_detached = repo.head_detached()?;
 
     Ok(HeadInfo {
         oid,
@@ -238,6 +239,29 @@ pub fn create_worktree(
     path: &Path,
     base_ref: &str,
     branch_name: Option<&str>,
+) -> Result<PathBuf> {
+    create_worktree_in(
+        repo,
+        name,
+        path,
+        base_ref,
+        branch_name,
+        crate::refs::DEFAULT_REF_NAMESPACE,
+    )
+}
+
+/// Create a new worktree whose branch is created under `namespace`
+/// (see `[refs] namespace`).
+///
+/// Outside `refs/heads` the worktree is added detached and its HEAD is then
+/// attached to the new ref, so commits made there advance it like a branch.
+pub fn create_worktree_in(
+    repo: &Repository,
+    name: &str,
+    path: &Path,
+    base_ref: &str,
+    branch_name: Option<&str>,
+    namespace: &str,
 ) -> Result<PathBuf> {
     // Determine the branch name
     let branch = branch_name
@@ -253,7 +277,7 @@ pub fn create_worktree(
         })?;
 
     // Check if branch already exists
-    if repo.find_branch(&branch, BranchType::Local).is_ok() {
+    if crate::refs::find_refname(repo, &branch).is_some() {
         return Err(Error::OperationFailed(format!(
             "Branch '{}' already exists",
             branch
@@ -279,6 +303,11 @@ pub fn create_worktree(
         std::fs::create_dir_all(parent)?;
     }
 
+    let refname = crate::refs::managed_refname(namespace, &branch);
+    if !refname.starts_with("refs/heads/") {
+        return create_namespaced_worktree(repo, path, &base_commit, &refname);
+    }
+
     // Create the worktree using git command (libgit2's worktree API is limited)
     // This is more reliable than using libgit2 directly for worktree creation
     // We use -b instead of -B to fail early if branch exists (we check above)
@@ -315,6 +344,99 @@ pub fn create_worktree(
     Ok(path.to_path_buf())
 }
 
+fn create_namespaced_worktree(
+    repo: &Repository,
+    path: &Path,
+    base_commit: &git2::Commit<'_>,
+    refname: &str,
+) -> Result<PathBuf> {
+    let repo_path = repo.path();
+    let output = Command::new("git")
+        .args([
+            "worktree",
+            "add",
+            "--checkout",
+            "--detach",
+            &path.to_string_lossy(),
+            &base_commit.id().to_string(),
+        ])
+        .current_dir(repo_path.parent().unwrap_or(repo_path))
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::OperationFailed(format!(
+            "Failed to create workspace (worktree): {}",
+            stderr.trim()
+        )));
+    }
+
+    repo.reference(refname, base_commit.id(), false, "sv ws new")?;
+    let worktree_repo = Repository::open(path)?;
+    worktree_repo.reference_symbolic("HEAD", refname, true, "sv ws new")?;
+    Ok(path.to_path_buf())
+}
+
+/// Add a worktree at `path` for the existing branch `branch` (a short name
+/// under `refs/heads` or the `refs/sv` namespace).
+pub fn add_worktree_for_branch(repo: &Repository, path: &Path, branch: &str) -> Result<PathBuf> {
+    let refname = crate::refs::find_refname(repo, branch)
+        .ok_or_else(|| Error::OperationFailed(format!("Branch '{}' does not exist", branch)))?;
+    if path.exists()
+        && path
+            .read_dir()
+            .map(|mut d| d.next().is_some())
+            .unwrap_or(true)
+    {
+        return Err(Error::OperationFailed(format!(
+            "Workspace (worktree) path already exists and is not empty: {}",
+            path.display()
+        )));
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let commit = repo.find_reference(&refname)?.peel_to_commit()?;
+    if !refname.starts_with("refs/heads/") {
+        let repo_path = repo.path();
+        let output = Command::new("git")
+            .args([
+                "worktree",
+                "add",
+                "--checkout",
+                "--detach",
+                &path.to_string_lossy(),
+                &commit.id().to_string(),
+            ])
+            .current_dir(repo_path.parent().unwrap_or(repo_path))
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(Error::OperationFailed(format!(
+                "Failed to create workspace (worktree): {}",
+                stderr.trim()
+            )));
+        }
+        let worktree_repo = Repository::open(path)?;
+        worktree_repo.reference_symbolic("HEAD", &refname, true, "sv ws restore")?;
+        return Ok(path.to_path_buf());
+    }
+
+    let repo_path = repo.path();
+    let output = Command::new("git")
+        .args(["worktree", "add", &path.to_string_lossy(), branch])
+        .current_dir(repo_path.parent().unwrap_or(repo_path))
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::OperationFailed(format!(
+            "Failed to create workspace (worktree): {}",
+            stderr.trim()
+        )));
+    }
+    Ok(path.to_path_buf())
+}
+
 /// Remove a worktree.
 ///
 /// # Arguments
@@ -482,6 +604,14 @@ pub struct FileChange {
     pub old_path: Option<PathBuf>,
 }
 
+impl FileChange {
+    /// Every path the change touches: the old path of a rename as well as
+    /// the new one.
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.path).chain(self.old_path.as_ref())
+    }
+}
+
 /// Get the list of changed files between two refs.
 ///
 /// # Arguments
@@ -490,7 +620,8 @@ pub struct FileChange {
 /// * `to_ref` - Ending reference (use "HEAD" for current state, or None for working tree)
 ///
 /// # Returns
-/// A list of file changes between the two references.
+/// A list of file changes between the two references, with renames
+/// detected (status `Renamed` carrying `old_path`).
 pub fn diff_files(
     repo: &Repository,
     from_ref: &str,
@@ -505,7 +636,7 @@ pub fn diff_files(
 
     // If to_ref is None, diff against the working tree (including staged changes)
     // Otherwise, diff between two tree references
-    let diff = match to_ref {
+    let mut diff = match to_ref {
         Some(ref_name) => {
             let to_tree = repo
                 .revparse_single(ref_name)?
@@ -521,6 +652,18 @@ pub fn diff_files(
         }
     };
 
+    diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))?;
+    parse_diff_to_changes(&diff)
+}
+
+/// Get the list of changed files between two trees, with renames detected.
+pub fn diff_trees(
+    repo: &Repository,
+    from_tree: &git2::Tree<'_>,
+    to_tree: &git2::Tree<'_>,
+) -> Result<Vec<FileChange>> {
+    let mut diff = repo.diff_tree_to_tree(Some(from_tree), Some(to_tree), None)?;
+    diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))?;
     parse_diff_to_changes(&diff)
 }
 
@@ -589,8 +732,14 @@ fn parse_diff_to_changes(diff: &git2::Diff) -> Result<Vec<FileChange>> {
 }
 
 /// Get paths only from file changes (convenience function).
+///
+/// Renames contribute both their old and new paths.
 pub fn changed_paths(changes: &[FileChange]) -> Vec<PathBuf> {
-    changes.iter().map(|c| c.path.clone()).collect()
+    changes
+        .iter()
+        .flat_map(FileChange::paths)
+        .cloned()
+        .collect()
 }
 
 /// Get staged file paths only.
@@ -824,6 +973,12 @@ pub fn commits_ahead(repo: &Repository, base_ref: &str, branch_ref: &str) -> Res
     Ok(commits)
 }
 
+/// Commit time of the tip of `rev`, or `None` if it does not resolve.
+pub fn last_commit_time(repo: &Repository, rev: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let commit = repo.revparse_single(rev).ok()?.peel_to_commit().ok()?;
+    chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+}
+
 /// Check whether `ancestor_ref` is an ancestor of `descendant_ref`.
 pub fn is_ancestor(repo: &Repository, ancestor_ref: &str, descendant_ref: &str) -> Result<bool> {
     let ancestor = repo.revparse_single(ancestor_ref)?.peel_to_commit()?.id();
@@ -835,6 +990,12 @@ pub fn is_ancestor(repo: &Repository, ancestor_ref: &str, descendant_ref: &str)
         .map_err(Error::Git)
 }
 
+/// Check whether `branch_ref` is fully merged into `base_ref`, i.e. it has
+/// no commits that `base_ref` lacks.
+pub fn is_merged(repo: &Repository, branch_ref: &str, base_ref: &str) -> Result<bool> {
+    Ok(commits_ahead(repo, base_ref, branch_ref)?.is_empty())
+}
+
 /// Compute a stable patch-id for a commit.
 ///
 /// Uses `git patch-id --stable` to match Git's own dedup behavior.
@@ -884,6 +1045,279 @@ pub fn patch_id(repo: &Repository, oid: Oid) -> Result<String> {
     Ok(patch_id.to_string())
 }
 
+/// Commit the working-tree contents of `paths` on top of HEAD, leaving any
+/// other staged changes out of the commit.
+///
+/// Paths are relative to the workdir. Returns `None` when the resulting tree
+/// matches HEAD (nothing to commit).
+pub fn commit_paths(repo: &Repository, paths: &[PathBuf], message: &str) -> Result<Option<Oid>> {
+    let workdir = workdir(repo)?;
+    let parent = match repo.head() {
+        Ok(head) => Some(head.peel_to_commit()?),
+        Err(e) if e.code() == ErrorCode::UnbornBranch => None,
+        Err(e) => return Err(Error::Git(e)),
+    };
+
+    let mut index = git2::Index::new()?;
+    if let Some(parent) = &parent {
+        index.read_tree(&parent.tree()?)?;
+    }
+    add_workdir_files(repo, &mut index, &workdir, paths)?;
+    let tree_oid = index.write_tree_to(repo)?;
+    if parent.as_ref().map(|commit| commit.tree_id()) == Some(tree_oid) {
+        return Ok(None);
+    }
+
+    let tree = repo.find_tree(tree_oid)?;
+    let signature = repo.signature()?;
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    let oid = repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )?;
+
+    // Keep the real index in step so the committed paths don't show as staged
+    // reversions of the new HEAD.
+    let mut repo_index = repo.index()?;
+    for path in paths {
+        repo_index.add_path(path)?;
+    }
+    repo_index.write()?;
+
+    Ok(Some(oid))
+}
+
+/// Like [`commit_paths`], but first integrates `upstream` (e.g. a fetched
+/// remote tip) so the result fast-forwards it.
+///
+/// When `upstream` descends from HEAD the commit goes on top of it; when the
+/// two diverged it is a merge with `upstream` as second parent, where
+/// conflicts in `paths` take the working-tree contents and conflicts anywhere
+/// else are an error. Other files changed upstream are checked out (refusing
+/// to overwrite local modifications) and the current branch moves to the
+/// result. Returns `None` when HEAD simply fast-forwards to `upstream`.
+pub fn commit_paths_onto(
+    repo: &Repository,
+    paths: &[PathBuf],
+    message: &str,
+    upstream: Oid,
+) -> Result<Option<Oid>> {
+    let head = repo.head()?;
+    let head_commit = head.peel_to_commit()?;
+    if upstream == head_commit.id() || repo.graph_descendant_of(head_commit.id(), upstream)? {
+        return commit_paths(repo, paths, message);
+    }
+
+    let workdir = workdir(repo)?;
+    let upstream_commit = repo.find_commit(upstream)?;
+    let fast_forward = repo.graph_descendant_of(upstream, head_commit.id())?;
+    let mut index = if fast_forward {
+        let mut index = git2::Index::new()?;
+        index.read_tree(&upstream_commit.tree()?)?;
+        index
+    } else {
+        let mut merged = repo.merge_commits(&head_commit, &upstream_commit, None)?;
+        let mut unresolved = Vec::new();
+        for conflict in merged.conflicts()? {
+            let conflict = conflict?;
+            let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) else {
+                continue;
+            };
+            let path = PathBuf::from(String::from_utf8_lossy(&entry.path).into_owned());
+            if !paths.contains(&path) {
+                unresolved.push(path.display().to_string());
+            }
+        }
+        if !unresolved.is_empty() {
+            return Err(Error::OperationFailed(format!(
+                "cannot integrate {}: conflicts in {}; merge it manually and retry",
+                upstream,
+                unresolved.join(", ")
+            )));
+        }
+        for path in paths {
+            merged.remove_path(path)?;
+        }
+        merged
+    };
+    add_workdir_files(repo, &mut index, &workdir, paths)?;
+    let tree_oid = index.write_tree_to(repo)?;
+
+    let commit = if fast_forward && tree_oid == upstream_commit.tree_id() {
+        None
+    } else {
+        let tree = repo.find_tree(tree_oid)?;
+        let signature = repo.signature()?;
+        let parents: Vec<&git2::Commit> = if fast_forward {
+            vec![&upstream_commit]
+        } else {
+            vec![&head_commit, &upstream_commit]
+        };
+        Some(repo.commit(None, &signature, &signature, message, &tree, &parents)?)
+    };
+    let target = commit.unwrap_or(upstream);
+
+    // Bring along files changed upstream; `paths` already hold the result.
+    let target_tree = repo.find_commit(target)?.tree()?;
+    let changes = diff_trees(repo, &head_commit.tree()?, &target_tree)?;
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.safe();
+    let mut upstream_paths = 0;
+    for path in changed_paths(&changes) {
+        if !paths.contains(&path) {
+            checkout.path(path);
+            upstream_paths += 1;
+        }
+    }
+    if upstream_paths > 0 {
+        repo.checkout_tree(target_tree.as_object(), Some(&mut checkout))
+            .map_err(|err| {
+                Error::OperationFailed(format!(
+                    "cannot update the working tree to {}: {} (commit or stash local changes and retry)",
+                    target,
+                    err.message()
+                ))
+            })?;
+    }
+    head.resolve()?.set_target(target, message)?;
+
+    let mut repo_index = repo.index()?;
+    for path in paths {
+        repo_index.add_path(path)?;
+    }
+    repo_index.write()?;
+
+    Ok(commit)
+}
+
+/// Stage the working-tree contents of `paths` into `index` as regular files.
+fn add_workdir_files(
+    repo: &Repository,
+    index: &mut git2::Index,
+    workdir: &Path,
+    paths: &[PathBuf],
+) -> Result<()> {
+    for path in paths {
+        let content = std::fs::read(workdir.join(path))?;
+        let entry = git2::IndexEntry {
+            ctime: git2::IndexTime::new(0, 0),
+            mtime: git2::IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode: 0o100644,
+            uid: 0,
+            gid: 0,
+            file_size: content.len() as u32,
+            id: repo.blob(&content)?,
+            flags: 0,
+            flags_extended: 0,
+            path: path.to_string_lossy().replace('\\', "/").into_bytes(),
+        };
+        index.add(&entry)?;
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Remote Operations
+// =============================================================================
+
+/// Credential callbacks for fetch/push: ssh-agent for SSH remotes, the
+/// configured git credential helper for HTTPS, default credentials otherwise.
+fn remote_callbacks(repo: &Repository) -> Result<git2::RemoteCallbacks<'static>> {
+    let config = repo.config()?;
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(move |url, username, allowed| {
+        if allowed.contains(git2::CredentialType::SSH_KEY) {
+            git2::Cred::ssh_key_from_agent(username.unwrap_or("git"))
+        } else if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+            git2::Cred::credential_helper(&config, url, username)
+        } else {
+            git2::Cred::default()
+        }
+    });
+    Ok(callbacks)
+}
+
+/// Fetch `branch` from `remote` into `refs/remotes/<remote>/<branch>`.
+///
+/// Returns the fetched tip, or `None` when the remote has no such branch.
+pub fn fetch_branch(repo: &Repository, remote: &str, branch: &str) -> Result<Option<Oid>> {
+    let mut handle = repo.find_remote(remote).map_err(|err| {
+        Error::OperationFailed(format!("remote '{}' not found: {}", remote, err.message()))
+    })?;
+    let mut options = git2::FetchOptions::new();
+    options.remote_callbacks(remote_callbacks(repo)?);
+    let refspec = format!("+refs/heads/{branch}:refs/remotes/{remote}/{branch}");
+    handle
+        .fetch(&[&refspec], Some(&mut options), None)
+        .map_err(|err| {
+            Error::OperationFailed(format!("fetch from '{}' failed: {}", remote, err.message()))
+        })?;
+
+    match repo.refname_to_id(&format!("refs/remotes/{remote}/{branch}")) {
+        Ok(oid) => Ok(Some(oid)),
+        Err(err) if err.code() == ErrorCode::NotFound => Ok(None),
+        Err(err) => Err(Error::Git(err)),
+    }
+}
+
+/// Push the local `branch` to the same branch name on `remote`.
+///
+/// A rejected update (e.g. non-fast-forward) is reported as an error rather
+/// than forced.
+pub fn push_branch(repo: &Repository, remote: &str, branch: &str) -> Result<()> {
+    let mut handle = repo.find_remote(remote).map_err(|err| {
+        Error::OperationFailed(format!("remote '{}' not found: {}", remote, err.message()))
+    })?;
+    let rejected = std::cell::RefCell::new(None);
+    let mut callbacks = remote_callbacks(repo)?;
+    callbacks.push_update_reference(|refname, status| {
+        if let Some(status) = status {
+            *rejected.borrow_mut() = Some(format!("{refname}: {status}"));
+        }
+        Ok(())
+    });
+    let mut options = git2::PushOptions::new();
+    options.remote_callbacks(callbacks);
+    let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+    let pushed = handle.push(&[&refspec], Some(&mut options));
+    drop(options);
+
+    let hint = "fetch and integrate the remote branch, then retry";
+    if let Err(err) = pushed {
+        return Err(Error::OperationFailed(format!(
+            "push to '{}' failed: {} ({})",
+            remote,
+            err.message(),
+            hint
+        )));
+    }
+    if let Some(reason) = rejected.into_inner() {
+        return Err(Error::OperationFailed(format!(
+            "push to '{}' rejected: {} ({})",
+            remote, reason, hint
+        )));
+    }
+    Ok(())
+}
+
+/// Read the blob at `path` in the tree of `commit`, if present.
+pub fn read_blob_at(repo: &Repository, commit: Oid, path: &Path) -> Result<Option<Vec<u8>>> {
+    let tree = repo.find_commit(commit)?.tree()?;
+    let entry = match tree.get_path(path) {
+        Ok(entry) => entry,
+        Err(err) if err.code() == ErrorCode::NotFound => return Ok(None),
+        Err(err) => return Err(Error::Git(err)),
+    };
+    let object = entry.to_object(repo)?;
+    Ok(object.as_blob().map(|blob| blob.content().to_vec()))
+}
+
 // =============================================================================
 // Trailer Operations
 // =============================================================================
@@ -1017,7 +1451,7 @@ pub fn set_trailer(message: &str, key: &str, value: &str) -> (String, bool) {
 /// Append a trailer to a commit message.
 ///
 /// Ensures proper formatting with a blank line before the trailer block if needed.
-fn append_trailer(message: &str, key: &str, value: &str) -> String {
+pub fn append_trailer(message: &str, key: &str, value: &str) -> String {
     let trimmed = message.trim_end();
     let trailer_line = format!("{}: {}", key, value);
 
@@ -1072,14 +1506,16 @@ pub fn filter_changes_by_pathspec(
     changes
         .into_iter()
         .filter(|change| {
-            let path_str = change.path.to_string_lossy();
-            pathspec.iter().any(|pattern| {
-                // Simple glob matching (could be enhanced with glob crate)
-                if pattern.contains('*') {
-                    glob_match(pattern, &path_str)
-                } else {
-                    path_str.starts_with(pattern) || path_str == *pattern
-                }
+            change.paths().any(|path| {
+                let path_str = path.to_string_lossy();
+                pathspec.iter().any(|pattern| {
+                    // Simple glob matching (could be enhanced with glob crate)
+                    if pattern.contains('*') {
+                        glob_match(pattern, &path_str)
+                    } else {
+                        path_str.starts_with(pattern) || path_str == *pattern
+                    }
+                })
             })
         })
         .collect()
@@ -1321,6 +1757,33 @@ mod tests {
         assert_eq!(changes[0].status, FileStatus::Added);
     }
 
+    #[test]
+    fn test_diff_files_detects_renames() {
+        let (temp, repo) = init_test_repo();
+        let first_commit = repo.head().unwrap().target().unwrap().to_string();
+
+        Command::new("git")
+            .args(["mv", "README.md", "GUIDE.md"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "Rename"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+
+        let changes = diff_files(&repo, &first_commit, Some("HEAD")).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].status, FileStatus::Renamed);
+        assert_eq!(changes[0].path, PathBuf::from("GUIDE.md"));
+        assert_eq!(changes[0].old_path, Some(PathBuf::from("README.md")));
+        assert_eq!(
+            changed_paths(&changes),
+            vec![PathBuf::from("GUIDE.md"), PathBuf::from("README.md")]
+        );
+    }
+
     #[test]
     fn test_is_ancestor() {
         let (temp, repo) = init_test_repo();
@@ -1351,6 +1814,42 @@ mod tests {
         assert!(!is_ancestor(&repo, "feature", &base_branch).unwrap());
     }
 
+    #[test]
+    fn test_is_merged() {
+        let (temp, repo) = init_test_repo();
+        let base_branch = repo
+            .head()
+            .ok()
+            .and_then(|head| head.shorthand().map(String::from))
+            .unwrap_or_else(|| "master".to_string());
+
+        Command::new("git")
+            .args(["branch", "landed"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["checkout", "-b", "feature"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        std::fs::write(temp.path().join("feature.txt"), "feature\n").unwrap();
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "feature work"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+
+        assert!(is_merged(&repo, "landed", &base_branch).unwrap());
+        assert!(!is_merged(&repo, "feature", &base_branch).unwrap());
+        assert!(is_merged(&repo, &base_branch, "feature").unwrap());
+    }
+
     #[test]
     fn test_file_statuses_untracked() {
         let (temp, repo) = init_test_repo();
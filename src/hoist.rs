@@ -27,7 +27,8 @@ pub enum OrderMode {
     /// Stable sort by workspace name, preserving commit order per workspace.
     #[default]
     Workspace,
-    /// Sort by commit time (oldest first), stable by original order.
+    /// Sort by commit time (oldest first); ties go by workspace name, then
+    /// by each workspace's own commit order.
     Time,
     /// Prioritize an explicit workspace order, appending remaining workspaces alphabetically.
     Explicit(Vec<String>),
@@ -223,7 +224,19 @@ pub fn replay_commits(
     commits: &[Oid],
     options: &ReplayOptions,
 ) -> Result<ReplayOutcome> {
-    let refname = normalize_refname(integration_ref);
+    replay_commits_with_progress(repo, integration_ref, commits, options, &mut |_, _, _| {})
+}
+
+/// [`replay_commits`], calling `progress(n, total, summary)` before the
+/// n-th (1-based) commit is replayed.
+pub fn replay_commits_with_progress(
+    repo: &Repository,
+    integration_ref: &str,
+    commits: &[Oid],
+    options: &ReplayOptions,
+    progress: &mut dyn FnMut(usize, usize, &str),
+) -> Result<ReplayOutcome> {
+    let refname = normalize_refname(repo, integration_ref);
     let mut current = repo.revparse_single(integration_ref)?.peel_to_commit()?;
 
     let mut outcome = ReplayOutcome::default();
@@ -231,6 +244,11 @@ pub fn replay_commits(
         let commit = repo.find_commit(*oid)?;
         let message = commit.message().unwrap_or_default();
         let summary = commit_summary(message);
+        progress(
+            idx + 1,
+            commits.len(),
+            summary.as_deref().unwrap_or_default(),
+        );
         let change_id = find_change_id(message);
 
         let merge_opts = MergeOptions::new();
@@ -331,6 +349,150 @@ pub fn replay_commits(
     Ok(outcome)
 }
 
+/// Replay candidates with each workspace's commits squashed into one commit.
+///
+/// Workspaces are squashed in the order their first commit appears in
+/// `candidates`. The squashed commit keeps the first commit's author and
+/// lists the original subjects in its message body; a workspace with a
+/// single commit keeps that commit's message. Every original commit gets an
+/// entry, and applied entries share the squashed commit as `applied_id`.
+pub fn squash_commits(
+    repo: &Repository,
+    integration_ref: &str,
+    candidates: &[HoistCandidate],
+    options: &ReplayOptions,
+) -> Result<ReplayOutcome> {
+    let refname = normalize_refname(repo, integration_ref);
+    let mut current = repo.revparse_single(integration_ref)?.peel_to_commit()?;
+
+    let mut groups: Vec<(&str, Vec<Oid>)> = Vec::new();
+    for candidate in candidates {
+        match groups
+            .iter_mut()
+            .find(|(workspace, _)| *workspace == candidate.workspace)
+        {
+            Some((_, oids)) => oids.push(candidate.oid),
+            None => groups.push((candidate.workspace.as_str(), vec![candidate.oid])),
+        }
+    }
+
+    let mut outcome = ReplayOutcome::default();
+    let mut stopped = false;
+    for (workspace, oids) in groups {
+        let commits = oids
+            .iter()
+            .map(|oid| repo.find_commit(*oid))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let messages: Vec<&str> = commits
+            .iter()
+            .map(|commit| commit.message().unwrap_or_default())
+            .collect();
+
+        if stopped {
+            for (oid, message) in oids.iter().zip(&messages) {
+                outcome.entries.push(ReplayEntry {
+                    commit_id: *oid,
+                    applied_id: None,
+                    status: HoistCommitStatus::Skipped,
+                    change_id: find_change_id(message),
+                    summary: commit_summary(message),
+                });
+            }
+            continue;
+        }
+
+        // Apply each commit's own changes (parent..commit) to the combined
+        // tree, the same three-way merge a cherry-pick performs.
+        let mut tree = current.tree()?;
+        let mut conflicted: Vec<usize> = Vec::new();
+        let mut conflict_files: Vec<String> = Vec::new();
+        let mut failed_at = None;
+        for (idx, commit) in commits.iter().enumerate() {
+            let base = match commit.parent(0) {
+                Ok(parent) => parent.tree()?,
+                Err(_) => repo.find_tree(repo.treebuilder(None)?.write()?)?,
+            };
+            let merge_opts = MergeOptions::new();
+            let mut index = repo.merge_trees(&base, &tree, &commit.tree()?, Some(&merge_opts))?;
+            if index.has_conflicts() {
+                let files = conflict_paths(&index)?;
+                outcome.conflicts.push(ReplayConflict {
+                    commit_id: commit.id(),
+                    files: files.clone(),
+                    message: Some("conflict applying commit".to_string()),
+                });
+                if !options.propagate_conflicts {
+                    failed_at = Some(idx);
+                    break;
+                }
+                conflicted.push(idx);
+                conflict_files.extend(files);
+                tree = repo.find_tree(write_conflict_tree(repo, &mut index)?)?;
+                continue;
+            }
+            tree = repo.find_tree(index.write_tree_to(repo)?)?;
+        }
+
+        if let Some(failed) = failed_at {
+            // Nothing from this workspace was committed.
+            for (idx, (oid, message)) in oids.iter().zip(&messages).enumerate() {
+                outcome.entries.push(ReplayEntry {
+                    commit_id: *oid,
+                    applied_id: None,
+                    status: if idx == failed {
+                        HoistCommitStatus::Conflict
+                    } else {
+                        HoistCommitStatus::Skipped
+                    },
+                    change_id: find_change_id(message),
+                    summary: commit_summary(message),
+                });
+            }
+            stopped = !options.continue_on_conflict;
+            continue;
+        }
+
+        let mut message = squash_message(workspace, &messages);
+        if !conflict_files.is_empty() {
+            conflict_files.sort();
+            conflict_files.dedup();
+            message = format!(
+                "{}\n\nSv-Conflict: true\nSv-Conflict-Files: {}",
+                message.trim_end(),
+                conflict_files.join(", ")
+            );
+        }
+        let first = &commits[0];
+        let last = &commits[commits.len() - 1];
+        let committer = repo.signature().unwrap_or_else(|_| last.committer());
+        let new_oid = repo.commit(
+            Some(&refname),
+            &first.author(),
+            &committer,
+            &message,
+            &tree,
+            &[&current],
+        )?;
+        current = repo.find_commit(new_oid)?;
+
+        for (idx, (oid, message)) in oids.iter().zip(&messages).enumerate() {
+            outcome.entries.push(ReplayEntry {
+                commit_id: *oid,
+                applied_id: Some(new_oid),
+                status: if conflicted.contains(&idx) {
+                    HoistCommitStatus::InConflict
+                } else {
+                    HoistCommitStatus::Applied
+                },
+                change_id: find_change_id(message),
+                summary: commit_summary(message),
+            });
+        }
+    }
+
+    Ok(outcome)
+}
+
 /// Convert replay conflicts into persisted hoist conflict records.
 pub fn conflict_records_for(
     hoist_id: &str,
@@ -487,11 +649,11 @@ fn conflict_paths(index: &Index) -> Result<Vec<String>> {
     Ok(out)
 }
 
-fn normalize_refname(refname: &str) -> String {
+fn normalize_refname(repo: &Repository, refname: &str) -> String {
     if refname.starts_with("refs/") {
         refname.to_string()
     } else {
-        format!("refs/heads/{refname}")
+        crate::refs::find_refname(repo, refname).unwrap_or_else(|| format!("refs/heads/{refname}"))
     }
 }
 
@@ -503,6 +665,20 @@ fn commit_summary(message: &str) -> Option<String> {
         .map(|line| line.to_string())
 }
 
+/// Message for a squashed workspace commit.
+fn squash_message(workspace: &str, messages: &[&str]) -> String {
+    if let [message] = messages {
+        return message.to_string();
+    }
+    let mut out = format!("{workspace}: squash {} commits\n\n", messages.len());
+    for message in messages {
+        if let Some(summary) = commit_summary(message) {
+            out.push_str(&format!("- {summary}\n"));
+        }
+    }
+    out
+}
+
 fn order_by_workspace(candidates: &[HoistCandidate]) -> Vec<HoistCandidate> {
     let mut by_workspace: HashMap<String, Vec<HoistCandidate>> = HashMap::new();
     for candidate in candidates {
@@ -556,15 +732,27 @@ fn order_by_explicit(candidates: &[HoistCandidate], order: &[String]) -> Vec<Hoi
 }
 
 fn order_by_time(repo: &Repository, candidates: &[HoistCandidate]) -> Result<Vec<HoistCandidate>> {
-    let mut indexed: Vec<(usize, i64, HoistCandidate)> = Vec::with_capacity(candidates.len());
-    for (idx, candidate) in candidates.iter().enumerate() {
+    // Commit times only have one-second resolution, so same-second commits
+    // from different workspaces are common. Break ties by workspace name so
+    // the result does not depend on registry order, and by position within
+    // the workspace (parent first) rather than commit id so a workspace's own
+    // commits are never replayed out of order.
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    let mut keyed: Vec<(i64, usize, HoistCandidate)> = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
         let commit = repo.find_commit(candidate.oid)?;
-        indexed.push((idx, commit.time().seconds(), candidate.clone()));
+        let position = positions.entry(candidate.workspace.as_str()).or_insert(0);
+        keyed.push((commit.time().seconds(), *position, candidate.clone()));
+        *position += 1;
     }
 
-    indexed.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    keyed.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.2.workspace.cmp(&b.2.workspace))
+            .then_with(|| a.1.cmp(&b.1))
+    });
 
-    Ok(indexed
+    Ok(keyed
         .into_iter()
         .map(|(_, _, candidate)| candidate)
         .collect())
@@ -852,6 +1040,41 @@ mod tests {
         assert_eq!(ordered_oids, vec![older, newer]);
     }
 
+    #[test]
+    fn order_by_time_breaks_ties_by_workspace_name() {
+        let (temp, repo, base_branch) = init_test_repo();
+        let date = "2000-01-01T00:00:00Z";
+
+        git(temp.path(), &["checkout", "-b", "bravo"]);
+        let bravo_a = commit_with_date(temp.path(), "bravo.txt", "b1", "bravo-1", date);
+        let bravo_b = commit_with_date(temp.path(), "bravo.txt", "b2", "bravo-2", date);
+
+        git(temp.path(), &["checkout", &base_branch]);
+        git(temp.path(), &["checkout", "-b", "alpha"]);
+        let alpha = commit_with_date(temp.path(), "alpha.txt", "a1", "alpha-1", date);
+
+        let bravo = WorkspaceRef {
+            name: "bravo".to_string(),
+            branch: "bravo".to_string(),
+        };
+        let alpha_ref = WorkspaceRef {
+            name: "alpha".to_string(),
+            branch: "alpha".to_string(),
+        };
+
+        // Same-second commits: alpha sorts before bravo regardless of the
+        // workspace input order, and bravo keeps its parent-first order.
+        for workspaces in [
+            vec![bravo.clone(), alpha_ref.clone()],
+            vec![alpha_ref.clone(), bravo.clone()],
+        ] {
+            let ordered = select_hoist_commits(&repo, &base_branch, &workspaces, &OrderMode::Time)
+                .expect("select");
+            let ordered_oids: Vec<Oid> = ordered.into_iter().map(|c| c.oid).collect();
+            assert_eq!(ordered_oids, vec![alpha, bravo_a, bravo_b]);
+        }
+    }
+
     #[test]
     fn collect_workspace_commits_lists_ahead_commits() {
         let (temp, repo, base_branch) = init_test_repo();
@@ -1066,6 +1289,114 @@ mod tests {
         assert_eq!(summary.skipped, 1);
     }
 
+    fn candidate(oid: Oid, workspace: &str) -> HoistCandidate {
+        HoistCandidate {
+            oid,
+            workspace: workspace.to_string(),
+        }
+    }
+
+    #[test]
+    fn squash_commits_collapses_each_workspace() {
+        let (temp, repo, base_branch) = init_test_repo();
+
+        git(temp.path(), &["checkout", "-b", "alpha"]);
+        let alpha1 = commit_simple(temp.path(), "alpha.txt", "one\n", "alpha-1");
+        let alpha2 = commit_simple(temp.path(), "alpha.txt", "two\n", "alpha-2");
+
+        git(temp.path(), &["checkout", &base_branch]);
+        git(temp.path(), &["checkout", "-b", "bravo"]);
+        let bravo = commit_simple(temp.path(), "bravo.txt", "bravo\n", "bravo-1");
+
+        create_branch(temp.path(), "sv/hoist/main", &base_branch);
+
+        let outcome = squash_commits(
+            &repo,
+            "sv/hoist/main",
+            &[
+                candidate(alpha1, "alpha"),
+                candidate(alpha2, "alpha"),
+                candidate(bravo, "bravo"),
+            ],
+            &ReplayOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(outcome.summary().applied, 3);
+        let alpha_squash = outcome.entries[0].applied_id.expect("applied id");
+        assert_eq!(outcome.entries[1].applied_id, Some(alpha_squash));
+
+        let tip = repo
+            .revparse_single("sv/hoist/main")
+            .unwrap()
+            .peel_to_commit()
+            .unwrap();
+        assert_eq!(Some(tip.id()), outcome.entries[2].applied_id);
+        assert_eq!(tip.message(), Some("bravo-1\n"));
+        assert_eq!(tip.parent_id(0).unwrap(), alpha_squash);
+
+        let squashed = repo.find_commit(alpha_squash).unwrap();
+        assert_eq!(
+            squashed.message(),
+            Some("alpha: squash 2 commits\n\n- alpha-1\n- alpha-2\n")
+        );
+        assert_eq!(squashed.author().name(), Some("Test"));
+        let blob = squashed
+            .tree()
+            .unwrap()
+            .get_path(Path::new("alpha.txt"))
+            .unwrap()
+            .to_object(&repo)
+            .unwrap()
+            .peel_to_blob()
+            .unwrap();
+        assert_eq!(blob.content(), b"two\n");
+    }
+
+    #[test]
+    fn squash_commits_stops_on_conflict_without_committing_workspace() {
+        let (temp, repo, base_branch) = init_test_repo();
+
+        git(temp.path(), &["checkout", "-b", "alpha"]);
+        let alpha = commit_simple(temp.path(), "README.md", "alpha", "alpha-1");
+
+        git(temp.path(), &["checkout", &base_branch]);
+        git(temp.path(), &["checkout", "-b", "bravo"]);
+        let bravo1 = commit_simple(temp.path(), "bravo.txt", "bravo", "bravo-1");
+        let bravo2 = commit_simple(temp.path(), "README.md", "bravo", "bravo-2");
+
+        create_branch(temp.path(), "sv/hoist/main", &base_branch);
+
+        let outcome = squash_commits(
+            &repo,
+            "sv/hoist/main",
+            &[
+                candidate(alpha, "alpha"),
+                candidate(bravo1, "bravo"),
+                candidate(bravo2, "bravo"),
+            ],
+            &ReplayOptions {
+                continue_on_conflict: false,
+                propagate_conflicts: false,
+            },
+        )
+        .unwrap();
+
+        let statuses: Vec<HoistCommitStatus> =
+            outcome.entries.iter().map(|e| e.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                HoistCommitStatus::Applied,
+                HoistCommitStatus::Skipped,
+                HoistCommitStatus::Conflict,
+            ]
+        );
+        assert_eq!(outcome.conflicts[0].commit_id, bravo2);
+        let tip = repo.revparse_single("sv/hoist/main").unwrap().id();
+        assert_eq!(Some(tip), outcome.entries[0].applied_id);
+    }
+
     #[test]
     fn conflict_records_include_metadata() {
         let (temp, repo, base_branch) = init_test_repo();
@@ -3,6 +3,7 @@ use std::process::Command;
 
 #[derive(Debug, Clone, Copy)]
 pub enum ForgeTaskHookKind {
+    TaskCreate,
     TaskStart,
     TaskBlock,
     TaskClose,
@@ -12,6 +13,9 @@ pub enum ForgeTaskHookKind {
 struct ForgeHooksConfig {
     enabled: bool,
     loop_ref: String,
+    create_issues: bool,
+    link_commits: bool,
+    on_task_create_cmd: Option<String>,
     on_task_start_cmd: Option<String>,
     on_task_block_cmd: Option<String>,
     on_task_close_cmd: Option<String>,
@@ -22,6 +26,9 @@ impl ForgeHooksConfig {
         Self {
             enabled: false,
             loop_ref: "{actor}".to_string(),
+            create_issues: false,
+            link_commits: false,
+            on_task_create_cmd: None,
             on_task_start_cmd: None,
             on_task_block_cmd: None,
             on_task_close_cmd: None,
@@ -62,7 +69,21 @@ impl ForgeHooksConfig {
             .and_then(|v| v.as_str())
             .unwrap_or("{actor}")
             .to_string();
+        let create_issues = forge
+            .get("create_issues")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let link_commits = forge
+            .get("link_commits")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
+        let on_task_create_cmd = forge
+            .get("on_task_create")
+            .and_then(|v| v.as_table())
+            .and_then(|t| t.get("cmd"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
         let on_task_start_cmd = forge
             .get("on_task_start")
             .and_then(|v| v.as_table())
@@ -85,6 +106,9 @@ impl ForgeHooksConfig {
         Self {
             enabled,
             loop_ref,
+            create_issues,
+            link_commits,
+            on_task_create_cmd,
             on_task_start_cmd,
             on_task_block_cmd,
             on_task_close_cmd,
@@ -93,6 +117,10 @@ impl ForgeHooksConfig {
 
     fn cmd_for(&self, kind: ForgeTaskHookKind) -> Option<&str> {
         match kind {
+            ForgeTaskHookKind::TaskCreate if self.create_issues => {
+                self.on_task_create_cmd.as_deref()
+            }
+            ForgeTaskHookKind::TaskCreate => None,
             ForgeTaskHookKind::TaskStart => self.on_task_start_cmd.as_deref(),
             ForgeTaskHookKind::TaskBlock => self.on_task_block_cmd.as_deref(),
             ForgeTaskHookKind::TaskClose => self.on_task_close_cmd.as_deref(),
@@ -128,12 +156,78 @@ pub fn run_task_hook_best_effort(
         return Some("forge hooks: empty cmd; skipping".to_string());
     }
 
-    match run_shell(repo_root, &cmd) {
-        Ok(()) => None,
+    match run_shell(repo_root, &cmd, &[]) {
+        Ok(_) => None,
         Err(err) => Some(format!("forge hooks: command failed; {}", err)),
     }
 }
 
+/// Run the `on_task_create` hook when `create_issues` is enabled and return
+/// the issue reference it prints (first non-empty stdout line).
+///
+/// The task title is passed as `SV_TASK_TITLE` rather than templated into the
+/// command. `Ok(None)` means issue creation is off or the hook printed nothing;
+/// `Err` carries a warning, and task creation should carry on without a ref.
+pub fn create_issue_best_effort(
+    repo_root: &Path,
+    task_id: &str,
+    actor: &str,
+    title: &str,
+) -> Result<Option<String>, String> {
+    let cfg = ForgeHooksConfig::load_from_repo(repo_root)
+        .map_err(|err| format!("forge hooks: failed to load .sv.toml; skipping ({})", err))?;
+
+    if !cfg.enabled {
+        return Ok(None);
+    }
+
+    let Some(cmd_template) = cfg.cmd_for(ForgeTaskHookKind::TaskCreate) else {
+        return Ok(None);
+    };
+    let loop_ref = render_template(&cfg.loop_ref, task_id, actor, None);
+    let cmd = render_template(cmd_template, task_id, actor, Some(&loop_ref));
+
+    if cmd.trim().is_empty() {
+        return Err("forge hooks: empty cmd; skipping".to_string());
+    }
+
+    let stdout = run_shell(repo_root, &cmd, &[("SV_TASK_TITLE", title)])
+        .map_err(|err| format!("forge hooks: command failed; {}", err))?;
+    Ok(stdout
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| truncate(line, 400)))
+}
+
+/// Whether `sv commit` should add `Task:` trailers for the workspace's active
+/// tasks. An unreadable config counts as disabled.
+pub fn link_commits_enabled(repo_root: &Path) -> bool {
+    ForgeHooksConfig::load_from_repo(repo_root)
+        .map(|cfg| cfg.enabled && cfg.link_commits)
+        .unwrap_or(false)
+}
+
+/// Ensure a `Task: <id>` trailer is present for each task id.
+///
+/// Ids that already have a trailer are skipped, so amending is idempotent.
+/// Returns the updated message and whether it was modified.
+pub fn ensure_task_trailers(message: &str, task_ids: &[String]) -> (String, bool) {
+    let existing = crate::git::parse_trailers(message);
+    let mut updated = message.to_string();
+    let mut changed = false;
+    for id in task_ids {
+        let linked = existing
+            .iter()
+            .any(|trailer| trailer.key.eq_ignore_ascii_case("Task") && trailer.value == *id);
+        if !linked {
+            updated = crate::git::append_trailer(&updated, "Task", id);
+            changed = true;
+        }
+    }
+    (updated, changed)
+}
+
 fn render_template(template: &str, task_id: &str, actor: &str, loop_ref: Option<&str>) -> String {
     let mut rendered = template
         .replace("{task_id}", task_id)
@@ -144,13 +238,14 @@ fn render_template(template: &str, task_id: &str, actor: &str, loop_ref: Option<
     rendered
 }
 
-fn run_shell(repo_root: &Path, cmd: &str) -> std::io::Result<()> {
+fn run_shell(repo_root: &Path, cmd: &str, envs: &[(&str, &str)]) -> std::io::Result<String> {
     let mut command = build_shell_command(cmd);
     command.current_dir(repo_root);
+    command.envs(envs.iter().copied());
     let output = command.output()?;
 
     if output.status.success() {
-        return Ok(());
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
     }
 
     let stderr = String::from_utf8_lossy(&output.stderr);
@@ -218,6 +313,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn task_trailers_join_change_id_and_are_idempotent() {
+        let ids = vec!["sv-abc".to_string()];
+        let (updated, changed) = ensure_task_trailers("Fix parser\n\nChange-Id: 1234\n", &ids);
+        assert!(changed);
+        assert_eq!(updated, "Fix parser\n\nChange-Id: 1234\nTask: sv-abc\n");
+
+        let (again, changed) = ensure_task_trailers(&updated, &ids);
+        assert!(!changed);
+        assert_eq!(again, updated);
+
+        let (plain, _) = ensure_task_trailers("Fix parser", &ids);
+        assert_eq!(plain, "Fix parser\n\nTask: sv-abc\n");
+    }
+
     #[cfg(unix)]
     #[test]
     fn run_hook_executes_shell_command() {
@@ -244,6 +354,38 @@ cmd = "printf 'start:{task_id}:{actor}:{loop_ref}\\n' >> hooks.txt"
         assert_eq!(content, "start:sv-abc:alice:alice\n");
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn create_issue_returns_hook_stdout_when_enabled() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = |create_issues: bool| {
+            format!(
+                r#"
+[integrations.forge]
+enabled = true
+create_issues = {create_issues}
+
+[integrations.forge.on_task_create]
+cmd = "printf '%s\n' \"$SV_TASK_TITLE\" > title.txt; printf '\nhttps://example.test/issues/{{task_id}}\n'"
+"#
+            )
+        };
+
+        std::fs::write(dir.path().join(".sv.toml"), config(false)).expect("write config");
+        let created = create_issue_best_effort(dir.path(), "sv-abc", "alice", "Fix it");
+        assert_eq!(created, Ok(None));
+        assert!(!dir.path().join("title.txt").exists());
+
+        std::fs::write(dir.path().join(".sv.toml"), config(true)).expect("write config");
+        let created = create_issue_best_effort(dir.path(), "sv-abc", "alice", "Fix 'it' $now");
+        assert_eq!(
+            created,
+            Ok(Some("https://example.test/issues/sv-abc".to_string()))
+        );
+        let title = std::fs::read_to_string(dir.path().join("title.txt")).expect("read title");
+        assert_eq!(title, "Fix 'it' $now\n");
+    }
+
     #[cfg(unix)]
     #[test]
     fn run_hook_failure_is_warning() {
@@ -17,6 +17,7 @@
 //! Each record has a status field to track active/released/expired state.
 
 use std::fmt;
+use std::path::{Component, Path, PathBuf};
 use std::str::FromStr;
 
 use chrono::{DateTime, Duration, Utc};
@@ -221,6 +222,24 @@ pub enum LeaseScope {
     Workspace(String),
 }
 
+impl LeaseScope {
+    /// Check whether two scopes can apply to the same edit.
+    ///
+    /// Repo scope intersects everything; branch and workspace scopes only
+    /// intersect their own kind when the names match. A branch scope and a
+    /// workspace scope are assumed to intersect, since a workspace can be on
+    /// any branch.
+    pub fn intersects(&self, other: &LeaseScope) -> bool {
+        match (self, other) {
+            (LeaseScope::Repo, _) | (_, LeaseScope::Repo) => true,
+            (LeaseScope::Branch(a), LeaseScope::Branch(b)) => a == b,
+            (LeaseScope::Workspace(a), LeaseScope::Workspace(b)) => a == b,
+            (LeaseScope::Branch(_), LeaseScope::Workspace(_))
+            | (LeaseScope::Workspace(_), LeaseScope::Branch(_)) => true,
+        }
+    }
+}
+
 impl fmt::Display for LeaseScope {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -276,7 +295,7 @@ pub enum LeaseStatus {
 // =============================================================================
 
 /// Optional hints for more granular lease information
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct LeaseHints {
     /// Specific symbols (functions, classes) being modified
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -302,7 +321,7 @@ impl LeaseHints {
 // =============================================================================
 
 /// A lease reservation over a path
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Lease {
     /// Unique identifier
     pub id: Uuid,
@@ -370,52 +389,14 @@ impl Lease {
     ///
     /// Uses glob matching: the lease pathspec is treated as a glob pattern.
     pub fn matches_path(&self, path: &str) -> bool {
-        // Try exact match first
-        if self.pathspec == path {
-            return true;
-        }
-
-        // Try glob match
-        if let Ok(pattern) = glob::Pattern::new(&self.pathspec) {
-            return pattern.matches(path);
-        }
-
-        // Try prefix match for directories
-        if self.pathspec.ends_with('/') || self.pathspec.ends_with("/**") {
-            let prefix = self.pathspec.trim_end_matches("/**").trim_end_matches('/');
-            return path.starts_with(prefix);
-        }
-
-        false
+        pathspec_matches_path(&self.pathspec, path)
     }
 
     /// Check if this lease's pathspec overlaps with another pathspec
     ///
     /// This is symmetric: checks if either pattern could match paths matched by the other.
     pub fn pathspec_overlaps(&self, other_pathspec: &str) -> bool {
-        // Direct match
-        if self.pathspec == other_pathspec {
-            return true;
-        }
-
-        // Check if self matches other as a path
-        if self.matches_path(other_pathspec) {
-            return true;
-        }
-
-        // Check if other matches self as a path (create temp lease for convenience)
-        let other_as_pattern = glob::Pattern::new(other_pathspec).ok();
-        if let Some(pattern) = other_as_pattern {
-            if pattern.matches(&self.pathspec) {
-                return true;
-            }
-        }
-
-        // Check prefix overlaps for directory patterns
-        let self_prefix = self.pathspec.trim_end_matches("/**").trim_end_matches('/');
-        let other_prefix = other_pathspec.trim_end_matches("/**").trim_end_matches('/');
-
-        self_prefix.starts_with(other_prefix) || other_prefix.starts_with(self_prefix)
+        pathspecs_overlap(&self.pathspec, other_pathspec)
     }
 
     /// Mark this lease as released
@@ -460,6 +441,33 @@ impl Lease {
         Ok(())
     }
 
+    /// Build an unsaved lease used only to test for conflicts
+    ///
+    /// Probes skip note validation and never get written to storage.
+    pub fn probe(
+        pathspec: impl Into<String>,
+        strength: LeaseStrength,
+        actor: Option<&str>,
+    ) -> Self {
+        let now = Utc::now();
+        Lease {
+            id: Uuid::nil(),
+            pathspec: pathspec.into(),
+            strength,
+            intent: LeaseIntent::default(),
+            actor: actor.map(str::to_string),
+            scope: LeaseScope::default(),
+            note: None,
+            ttl: String::new(),
+            expires_at: now,
+            created_at: now,
+            status: LeaseStatus::Active,
+            hints: LeaseHints::default(),
+            status_changed_at: None,
+            status_reason: None,
+        }
+    }
+
     /// Validate the lease
     pub fn validate(&self) -> Result<()> {
         self.validate_with_note_requirement(true)
@@ -497,6 +505,7 @@ pub struct LeaseBuilder {
     require_note: bool,
     ttl: String,
     hints: LeaseHints,
+    id: Option<Uuid>,
 }
 
 impl LeaseBuilder {
@@ -512,9 +521,16 @@ impl LeaseBuilder {
             require_note: true,
             ttl: "2h".to_string(),
             hints: LeaseHints::default(),
+            id: None,
         }
     }
 
+    /// Use a specific ID instead of a random one
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.id = Some(id);
+        self
+    }
+
     /// Set the lease strength
     pub fn strength(mut self, strength: LeaseStrength) -> Self {
         self.strength = strength;
@@ -575,7 +591,7 @@ impl LeaseBuilder {
         let now = Utc::now();
 
         let lease = Lease {
-            id: Uuid::new_v4(),
+            id: self.id.unwrap_or_else(Uuid::new_v4),
             pathspec: self.pathspec,
             strength: self.strength,
             intent: self.intent,
@@ -639,6 +655,600 @@ pub fn parse_duration(s: &str) -> Result<Duration> {
     Ok(duration)
 }
 
+// =============================================================================
+// TTL Limits
+// =============================================================================
+
+/// How to handle a requested TTL that exceeds the configured maximum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TtlOverflow {
+    /// Shorten the TTL to the maximum and warn
+    #[default]
+    Clamp,
+    /// Refuse the request
+    Reject,
+}
+
+impl fmt::Display for TtlOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TtlOverflow::Clamp => write!(f, "clamp"),
+            TtlOverflow::Reject => write!(f, "reject"),
+        }
+    }
+}
+
+impl FromStr for TtlOverflow {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "clamp" => Ok(TtlOverflow::Clamp),
+            "reject" => Ok(TtlOverflow::Reject),
+            _ => Err(Error::InvalidArgument(format!(
+                "Invalid ttl overflow policy '{}'. Expected: clamp, reject",
+                s
+            ))),
+        }
+    }
+}
+
+/// A requested TTL after applying the configured maximum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitedTtl {
+    /// TTL to use for the lease
+    pub ttl: String,
+    /// Original request, if it was clamped
+    pub clamped_from: Option<String>,
+}
+
+impl LimitedTtl {
+    /// Warning to surface when the TTL was clamped
+    pub fn warning(&self) -> Option<String> {
+        self.clamped_from.as_ref().map(|requested| {
+            format!(
+                "ttl {} exceeds leases.max_ttl; clamped to {}",
+                requested, self.ttl
+            )
+        })
+    }
+}
+
+/// Apply `max_ttl` to a requested TTL.
+///
+/// Requests within the limit (or with no limit) pass through unchanged.
+pub fn limit_ttl(
+    requested: &str,
+    max_ttl: Option<&str>,
+    overflow: TtlOverflow,
+) -> Result<LimitedTtl> {
+    let unchanged = LimitedTtl {
+        ttl: requested.to_string(),
+        clamped_from: None,
+    };
+    let Some(max_ttl) = max_ttl else {
+        return Ok(unchanged);
+    };
+
+    if parse_duration(requested)? <= parse_duration(max_ttl)? {
+        return Ok(unchanged);
+    }
+
+    match overflow {
+        TtlOverflow::Clamp => Ok(LimitedTtl {
+            ttl: max_ttl.trim().to_string(),
+            clamped_from: Some(requested.to_string()),
+        }),
+        TtlOverflow::Reject => Err(Error::InvalidArgument(format!(
+            "ttl {} exceeds leases.max_ttl ({})",
+            requested, max_ttl
+        ))),
+    }
+}
+
+/// Per-actor lease limits (`[leases] max_per_actor`, `max_exclusive_per_actor`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActorQuota {
+    /// Maximum active leases per actor (unset means unlimited)
+    pub max_leases: Option<usize>,
+    /// Maximum active exclusive leases per actor (unset means unlimited)
+    pub max_exclusive: Option<usize>,
+}
+
+impl ActorQuota {
+    /// Check an actor's active leases in `store` after a take was planned.
+    ///
+    /// `held` are the actor's active leases before the take. A count only
+    /// fails when it grew past the limit, so renewing leases taken before a
+    /// quota was lowered keeps working. The error lists the held leases so
+    /// the caller can pick one to release.
+    pub fn check(&self, actor: &str, held: &[Lease], store: &LeaseStore) -> Result<()> {
+        fn count<'a>(leases: impl Iterator<Item = &'a Lease>, exclusive_only: bool) -> usize {
+            leases
+                .filter(|lease| !exclusive_only || lease.strength == LeaseStrength::Exclusive)
+                .count()
+        }
+
+        let limits = [
+            (self.max_leases, false, "leases", "max_per_actor"),
+            (
+                self.max_exclusive,
+                true,
+                "exclusive leases",
+                "max_exclusive_per_actor",
+            ),
+        ];
+        for (limit, exclusive_only, what, field) in limits {
+            let Some(limit) = limit else {
+                continue;
+            };
+            let before = count(held.iter(), exclusive_only);
+            let after = count(
+                store.by_actor(actor).filter(|lease| lease.is_active()),
+                exclusive_only,
+            );
+            if after <= limit || after <= before {
+                continue;
+            }
+
+            let current = if held.is_empty() {
+                "none".to_string()
+            } else {
+                held.iter()
+                    .map(|lease| format!("{} ({})", lease.pathspec, lease.strength))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            return Err(Error::InvalidArgument(format!(
+                "actor '{actor}' would hold {after} {what}, over leases.{field} ({limit}); \
+                 current leases: {current}; release one first (sv release <path>)"
+            )));
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Path Normalization
+// =============================================================================
+
+/// Normalize a pathspec to the repo-relative form stored on leases.
+///
+/// Absolute paths must fall inside `root`. `.` segments are dropped and `..`
+/// segments are resolved lexically; traversal above the repo root is
+/// rejected. Glob characters pass through untouched, and a trailing `/` is
+/// kept so directory pathspecs still match by prefix.
+pub fn normalize_pathspec(root: &Path, raw: &str) -> Result<String> {
+    let outside = || Error::InvalidArgument(format!("path is outside the repository: {raw}"));
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(Error::InvalidArgument("path cannot be empty".to_string()));
+    }
+
+    let relative = if Path::new(trimmed).is_absolute() {
+        let absolute = lexical_normalize(Path::new(trimmed)).ok_or_else(outside)?;
+        let canonical_root = root.canonicalize().ok();
+        let stripped = absolute.strip_prefix(root).ok().or_else(|| {
+            canonical_root
+                .as_deref()
+                .and_then(|root| absolute.strip_prefix(root).ok())
+        });
+        stripped.ok_or_else(outside)?.to_path_buf()
+    } else {
+        lexical_normalize(Path::new(trimmed)).ok_or_else(outside)?
+    };
+
+    let mut normalized = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    if normalized.is_empty() {
+        return Ok(".".to_string());
+    }
+    if trimmed.ends_with('/') {
+        normalized.push('/');
+    }
+    Ok(normalized)
+}
+
+/// Check if a (possibly glob) pathspec matches a path.
+pub fn pathspec_matches_path(pathspec: &str, path: &str) -> bool {
+    // Try exact match first
+    if pathspec == path {
+        return true;
+    }
+
+    // Try glob match
+    if let Ok(pattern) = glob::Pattern::new(pathspec) {
+        return pattern.matches(path);
+    }
+
+    // Try prefix match for directories
+    if pathspec.ends_with('/') || pathspec.ends_with("/**") {
+        let prefix = pathspec.trim_end_matches("/**").trim_end_matches('/');
+        return path.starts_with(prefix);
+    }
+
+    false
+}
+
+/// Check if two pathspecs overlap.
+///
+/// This is symmetric: checks if either pattern could match paths matched by the other.
+pub fn pathspecs_overlap(pathspec: &str, other_pathspec: &str) -> bool {
+    // Direct match
+    if pathspec == other_pathspec {
+        return true;
+    }
+
+    // Check if the first matches the other as a path
+    if pathspec_matches_path(pathspec, other_pathspec) {
+        return true;
+    }
+
+    // Check if the other matches the first as a path
+    let other_as_pattern = glob::Pattern::new(other_pathspec).ok();
+    if let Some(pattern) = other_as_pattern {
+        if pattern.matches(pathspec) {
+            return true;
+        }
+    }
+
+    // Check prefix overlaps for directory patterns
+    let prefix = pathspec.trim_end_matches("/**").trim_end_matches('/');
+    let other_prefix = other_pathspec.trim_end_matches("/**").trim_end_matches('/');
+
+    prefix.starts_with(other_prefix) || other_prefix.starts_with(prefix)
+}
+
+/// Check if every path matched by `inner` is also matched by `outer`.
+///
+/// Unlike [`pathspecs_overlap`] this is one-directional and conservative:
+/// glob-to-glob containment is only recognized for directory patterns
+/// (`src/**` covers `src/auth/**`), otherwise two different globs never
+/// cover each other.
+pub fn pathspec_covers(outer: &str, inner: &str) -> bool {
+    if outer == inner || outer == "**" || outer == "." {
+        return true;
+    }
+
+    if outer.ends_with('/') || outer.ends_with("/**") {
+        let prefix = outer.trim_end_matches("/**").trim_end_matches('/');
+        let inner_prefix = inner.trim_end_matches("/**").trim_end_matches('/');
+        if inner_prefix
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        {
+            return true;
+        }
+    }
+
+    !is_glob(inner) && pathspec_matches_path(outer, inner)
+}
+
+/// Whether a pathspec contains glob metacharacters.
+pub fn is_glob(pathspec: &str) -> bool {
+    pathspec.contains(['*', '?', '['])
+}
+
+/// Resolve a pathspec typed in `cwd` to repo-relative form.
+///
+/// Relative pathspecs are interpreted from `cwd` when it lies inside `root`
+/// (so `token.rs` typed in `src/auth/` becomes `src/auth/token.rs`) and from
+/// the repo root otherwise. See [`normalize_pathspec`] for the rest.
+pub fn resolve_pathspec(root: &Path, cwd: &Path, raw: &str) -> Result<String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() || Path::new(trimmed).is_absolute() {
+        return normalize_pathspec(root, trimmed);
+    }
+
+    let canonical_root = root.canonicalize().ok();
+    let canonical_cwd = cwd.canonicalize().ok();
+    let prefix = cwd.strip_prefix(root).ok().or_else(|| {
+        let root = canonical_root.as_deref()?;
+        canonical_cwd.as_deref()?.strip_prefix(root).ok()
+    });
+    match prefix {
+        Some(prefix) if !prefix.as_os_str().is_empty() => {
+            let joined = format!("{}/{}", prefix.to_string_lossy(), trimmed);
+            normalize_pathspec(root, &joined).map_err(|_| {
+                Error::InvalidArgument(format!("path is outside the repository: {raw}"))
+            })
+        }
+        _ => normalize_pathspec(root, trimmed),
+    }
+}
+
+/// Resolve `.` and `..` without touching the filesystem. Returns `None` when
+/// `..` would climb above the start of `path`.
+fn lexical_normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => normalized.push(component.as_os_str()),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if depth == 0 {
+                    return None;
+                }
+                normalized.pop();
+                depth -= 1;
+            }
+            Component::Normal(part) => {
+                normalized.push(part);
+                depth += 1;
+            }
+        }
+    }
+    Some(normalized)
+}
+
+// =============================================================================
+// Conflict Resolution
+// =============================================================================
+
+/// Overlap rules applied when checking two leases for conflicts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Policy {
+    /// Caller passed `--allow-overlap`
+    pub allow_overlap: bool,
+    /// Cooperative leases may overlap each other
+    pub allow_overlap_cooperative: bool,
+    /// Strong/cooperative overlaps need `allow_overlap`
+    pub require_flag_for_strong_overlap: bool,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            allow_overlap: false,
+            allow_overlap_cooperative: true,
+            require_flag_for_strong_overlap: true,
+        }
+    }
+}
+
+impl Policy {
+    /// Build a policy from `[leases.compat]` config
+    pub fn from_compat(compat: &crate::config::LeaseCompatConfig, allow_overlap: bool) -> Self {
+        Self {
+            allow_overlap,
+            allow_overlap_cooperative: compat.allow_overlap_cooperative,
+            require_flag_for_strong_overlap: compat.require_flag_for_strong_overlap,
+        }
+    }
+}
+
+/// Why two leases conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// One side holds an exclusive lease
+    Exclusive,
+    /// Both sides hold strong leases
+    StrongOverlap,
+    /// Strong and cooperative overlap without `--allow-overlap`
+    RequiresAllowOverlap,
+    /// Cooperative leases overlap and policy forbids it
+    CooperativeOverlap,
+}
+
+/// Structured reason returned by [`conflicts`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConflictReason {
+    pub kind: ConflictKind,
+    /// ID of the existing lease
+    pub lease_id: Uuid,
+    /// Actor holding the existing lease
+    pub holder: Option<String>,
+    /// Strength of the existing lease
+    pub holder_strength: LeaseStrength,
+    /// Strength that was requested
+    pub requested_strength: LeaseStrength,
+}
+
+impl fmt::Display for ConflictReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let holder = self.holder.as_deref().unwrap_or("(ownerless)");
+        match self.kind {
+            ConflictKind::Exclusive => write!(
+                f,
+                "{} lease held by {} blocks {}",
+                self.holder_strength, holder, self.requested_strength
+            ),
+            ConflictKind::StrongOverlap => {
+                write!(f, "strong lease held by {} blocks strong", holder)
+            }
+            ConflictKind::RequiresAllowOverlap => write!(
+                f,
+                "{} lease held by {} overlaps {} (requires --allow-overlap)",
+                self.holder_strength, holder, self.requested_strength
+            ),
+            ConflictKind::CooperativeOverlap => write!(
+                f,
+                "cooperative lease held by {} (cooperative overlap disabled)",
+                holder
+            ),
+        }
+    }
+}
+
+/// Check whether `requested` conflicts with the `existing` lease.
+///
+/// Leases held by the same actor, ownerless leases, inactive leases, leases
+/// in disjoint scopes, and non-overlapping pathspecs never conflict.
+pub fn conflicts(existing: &Lease, requested: &Lease, policy: &Policy) -> Option<ConflictReason> {
+    use LeaseStrength::*;
+
+    if !existing.is_active() {
+        return None;
+    }
+
+    // Ownerless leases are informational only and never block.
+    let (Some(existing_actor), Some(requested_actor)) = (&existing.actor, &requested.actor) else {
+        return None;
+    };
+    if existing_actor == requested_actor {
+        return None;
+    }
+
+    if !existing.scope.intersects(&requested.scope) {
+        return None;
+    }
+
+    if !existing.pathspec_overlaps(&requested.pathspec) {
+        return None;
+    }
+
+    let kind = match (existing.strength, requested.strength) {
+        (Observe, _) | (_, Observe) => return None,
+        (Exclusive, _) | (_, Exclusive) => ConflictKind::Exclusive,
+        (Strong, Strong) => ConflictKind::StrongOverlap,
+        (Strong, Cooperative) | (Cooperative, Strong) => {
+            if policy.allow_overlap || !policy.require_flag_for_strong_overlap {
+                return None;
+            }
+            ConflictKind::RequiresAllowOverlap
+        }
+        (Cooperative, Cooperative) => {
+            if policy.allow_overlap_cooperative {
+                return None;
+            }
+            ConflictKind::CooperativeOverlap
+        }
+    };
+
+    Some(ConflictReason {
+        kind,
+        lease_id: existing.id,
+        holder: existing.actor.clone(),
+        holder_strength: existing.strength,
+        requested_strength: requested.strength,
+    })
+}
+
+// =============================================================================
+// Lease Log Loading
+// =============================================================================
+
+/// A line of the lease log that could not be parsed
+#[derive(Debug, Clone, Serialize)]
+pub struct MalformedLease {
+    /// 1-based line number in the log
+    pub line: usize,
+    /// Parse error
+    pub error: String,
+    /// Raw line content, kept so repair can set it aside
+    pub content: String,
+}
+
+/// Read a lease log, collecting malformed lines instead of failing on them
+pub fn read_lease_log(path: &Path) -> Result<(Vec<Lease>, Vec<MalformedLease>)> {
+    if !path.exists() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let content = std::fs::read(path)?;
+    let content = String::from_utf8_lossy(&content);
+    let mut leases = Vec::new();
+    let mut malformed = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Lease>(line) {
+            Ok(lease) => leases.push(lease),
+            Err(err) => malformed.push(MalformedLease {
+                line: idx + 1,
+                error: err.to_string(),
+                content: line.to_string(),
+            }),
+        }
+    }
+    Ok((leases, malformed))
+}
+
+/// Read a lease log, skipping malformed lines with a warning on stderr
+///
+/// Writers keep the skipped lines (see [`write_lease_log`]).
+///
+/// The warning is printed once per process so commands that read the log
+/// several times don't repeat it.
+pub fn load_lease_log(path: &Path) -> Result<Vec<Lease>> {
+    static WARNED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+    let (leases, malformed) = read_lease_log(path)?;
+    if !malformed.is_empty() && !WARNED.swap(true, std::sync::atomic::Ordering::Relaxed) {
+        let lines = malformed
+            .iter()
+            .map(|entry| entry.line.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        eprintln!(
+            "Warning: skipped {} malformed lease record(s) in {} (line {}); run `sv lease doctor`",
+            malformed.len(),
+            path.display(),
+            lines
+        );
+    }
+    Ok(leases)
+}
+
+/// Rewrite a lease log atomically, carrying its malformed lines through
+///
+/// Malformed lines are kept verbatim after the leases so a writer never
+/// loses them; only `sv lease doctor --repair` removes them, after setting
+/// them aside (see [`write_clean_lease_log`]).
+pub fn write_lease_log(path: &Path, leases: &[Lease]) -> Result<()> {
+    let (_, malformed) = read_lease_log(path)?;
+    write_lease_lines(path, leases, &malformed)
+}
+
+/// Rewrite a lease log atomically with exactly `leases`, dropping malformed lines
+pub fn write_clean_lease_log(path: &Path, leases: &[Lease]) -> Result<()> {
+    write_lease_lines(path, leases, &[])
+}
+
+fn write_lease_lines(path: &Path, leases: &[Lease], malformed: &[MalformedLease]) -> Result<()> {
+    let mut contents = String::new();
+    for lease in leases {
+        contents.push_str(&serde_json::to_string(lease)?);
+        contents.push('\n');
+    }
+    for entry in malformed {
+        contents.push_str(&entry.content);
+        contents.push('\n');
+    }
+    crate::lock::write_atomic(path, contents.as_bytes())
+}
+
+// =============================================================================
+// Lease IDs
+// =============================================================================
+
+/// Environment variable that makes lease IDs deterministic (tests, scripts).
+///
+/// Only consulted when set to a non-empty value.
+pub const LEASE_ID_SEED_ENV: &str = "SV_LEASE_ID_SEED";
+
+/// Deterministic lease ID for `seed` and `sequence`.
+///
+/// The seed's FNV-1a hash fills the first half and the sequence number the
+/// second, with UUID v4 version/variant bits applied.
+pub fn seeded_lease_id(seed: &str, sequence: u64) -> Uuid {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in seed.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&hash.to_be_bytes());
+    bytes[8..].copy_from_slice(&sequence.to_be_bytes());
+    uuid::Builder::from_random_bytes(bytes).into_uuid()
+}
+
 // =============================================================================
 // Lease Store (for managing multiple leases)
 // =============================================================================
@@ -670,6 +1280,25 @@ impl LeaseStore {
         self.leases.iter().filter(|l| l.is_active())
     }
 
+    /// ID for the next lease added to this store
+    ///
+    /// Random unless `SV_LEASE_ID_SEED` is set, in which case IDs follow
+    /// `seeded_lease_id` from the store's record count, skipping taken IDs.
+    pub fn next_id(&self) -> Uuid {
+        let seed = match std::env::var(LEASE_ID_SEED_ENV) {
+            Ok(seed) if !seed.is_empty() => seed,
+            _ => return Uuid::new_v4(),
+        };
+        let mut sequence = self.leases.len() as u64;
+        loop {
+            let id = seeded_lease_id(&seed, sequence);
+            if self.find(&id).is_none() {
+                return id;
+            }
+            sequence += 1;
+        }
+    }
+
     /// Find a lease by ID
     pub fn find(&self, id: &Uuid) -> Option<&Lease> {
         self.leases.iter().find(|l| l.id == *id)
@@ -687,6 +1316,16 @@ impl LeaseStore {
             .filter(move |l| l.is_active() && l.matches_path(path))
     }
 
+    /// Find all active leases whose pathspec overlaps a (possibly glob) pathspec
+    pub fn overlapping_pathspec<'a>(
+        &'a self,
+        pathspec: &'a str,
+    ) -> impl Iterator<Item = &'a Lease> {
+        self.leases
+            .iter()
+            .filter(move |l| l.is_active() && l.pathspec_overlaps(pathspec))
+    }
+
     /// Find all leases held by an actor
     pub fn by_actor(&self, actor: &str) -> impl Iterator<Item = &Lease> {
         let actor = actor.to_string();
@@ -730,27 +1369,26 @@ impl LeaseStore {
         actor: Option<&str>,
         allow_overlap: bool,
     ) -> Vec<&Lease> {
-        self.active()
-            .filter(|existing| {
-                // Skip own leases
-                if let (Some(new_actor), Some(existing_actor)) = (actor, &existing.actor) {
-                    if new_actor == existing_actor {
-                        return false;
-                    }
-                }
-
-                // Ownerless leases are informational only and never block.
-                if actor.is_none() || existing.actor.is_none() {
-                    return false;
-                }
-
-                // Check pathspec overlap
-                if !existing.pathspec_overlaps(pathspec) {
-                    return false;
-                }
+        let requested = Lease::probe(pathspec, strength, actor);
+        let policy = Policy {
+            allow_overlap,
+            ..Policy::default()
+        };
+        self.conflicts_with(&requested, &policy)
+            .into_iter()
+            .map(|(lease, _)| lease)
+            .collect()
+    }
 
-                // Check strength compatibility
-                !strength.is_compatible_with(&existing.strength, allow_overlap)
+    /// Find existing leases that conflict with `requested`, with reasons
+    pub fn conflicts_with(
+        &self,
+        requested: &Lease,
+        policy: &Policy,
+    ) -> Vec<(&Lease, ConflictReason)> {
+        self.active()
+            .filter_map(|existing| {
+                conflicts(existing, requested, policy).map(|reason| (existing, reason))
             })
             .collect()
     }
@@ -1129,4 +1767,277 @@ mod tests {
         assert_eq!(expired.len(), 1);
         assert!(store.all().is_empty());
     }
+
+    #[test]
+    fn test_limit_ttl_clamps_to_max() {
+        let limited = limit_ttl("30d", Some("1d"), TtlOverflow::Clamp).unwrap();
+        assert_eq!(limited.ttl, "1d");
+        assert_eq!(limited.clamped_from.as_deref(), Some("30d"));
+        assert!(limited.warning().unwrap().contains("clamped to 1d"));
+
+        let within = limit_ttl("2h", Some("1d"), TtlOverflow::Clamp).unwrap();
+        assert_eq!(within.ttl, "2h");
+        assert!(within.warning().is_none());
+    }
+
+    #[test]
+    fn test_limit_ttl_rejects_over_max() {
+        assert!(limit_ttl("30d", Some("1d"), TtlOverflow::Reject).is_err());
+        assert!(limit_ttl("1d", Some("1d"), TtlOverflow::Reject).is_ok());
+        assert!(limit_ttl("30d", None, TtlOverflow::Reject).is_ok());
+    }
+
+    #[test]
+    fn test_actor_quota_boundary() {
+        let lease = |path: &str, strength| {
+            Lease::builder(path)
+                .strength(strength)
+                .actor("alice")
+                .note("quota")
+                .build()
+                .unwrap()
+        };
+        let quota = ActorQuota {
+            max_leases: Some(2),
+            max_exclusive: Some(1),
+        };
+
+        let held = vec![lease("a.rs", LeaseStrength::Cooperative)];
+        let mut store = LeaseStore::from_vec(held.clone());
+        store.add(lease("b.rs", LeaseStrength::Exclusive));
+        assert!(quota.check("alice", &held, &store).is_ok());
+        assert!(quota.check("bob", &[], &store).is_ok());
+
+        let held = store.all().to_vec();
+        store.add(lease("c.rs", LeaseStrength::Cooperative));
+        let err = quota.check("alice", &held, &store).unwrap_err().to_string();
+        assert!(err.contains("leases.max_per_actor (2)"), "{err}");
+        assert!(
+            err.contains("a.rs (cooperative), b.rs (exclusive)"),
+            "{err}"
+        );
+
+        let mut store = LeaseStore::from_vec(held.clone());
+        store.add(lease("d.rs", LeaseStrength::Exclusive));
+        let quota = ActorQuota {
+            max_exclusive: Some(1),
+            ..ActorQuota::default()
+        };
+        let err = quota.check("alice", &held, &store).unwrap_err().to_string();
+        assert!(err.contains("leases.max_exclusive_per_actor (1)"), "{err}");
+    }
+
+    #[test]
+    fn test_actor_quota_allows_existing_overage() {
+        let held: Vec<Lease> = ["a.rs", "b.rs", "c.rs"]
+            .iter()
+            .map(|path| Lease::builder(*path).actor("alice").build().unwrap())
+            .collect();
+        let store = LeaseStore::from_vec(held.clone());
+        let quota = ActorQuota {
+            max_leases: Some(1),
+            max_exclusive: None,
+        };
+        assert!(quota.check("alice", &held, &store).is_ok());
+    }
+
+    #[test]
+    fn test_normalize_pathspec() {
+        let root = Path::new("/work/repo");
+        assert_eq!(
+            normalize_pathspec(root, "src/lib.rs").unwrap(),
+            "src/lib.rs"
+        );
+        assert_eq!(
+            normalize_pathspec(root, "./src//auth/../lib.rs").unwrap(),
+            "src/lib.rs"
+        );
+        assert_eq!(
+            normalize_pathspec(root, "/work/repo/src/**").unwrap(),
+            "src/**"
+        );
+        assert_eq!(normalize_pathspec(root, "docs/").unwrap(), "docs/");
+        assert_eq!(normalize_pathspec(root, "src/../*.toml").unwrap(), "*.toml");
+        assert_eq!(normalize_pathspec(root, "/work/repo").unwrap(), ".");
+    }
+
+    #[test]
+    fn test_resolve_pathspec_from_subdirectory() {
+        let root = Path::new("/work/repo");
+        let cwd = Path::new("/work/repo/src/auth");
+        assert_eq!(
+            resolve_pathspec(root, cwd, "token.rs").unwrap(),
+            "src/auth/token.rs"
+        );
+        assert_eq!(
+            resolve_pathspec(root, cwd, "../lib.rs").unwrap(),
+            "src/lib.rs"
+        );
+        assert_eq!(resolve_pathspec(root, cwd, ".").unwrap(), "src/auth");
+        assert_eq!(
+            resolve_pathspec(root, cwd, "/work/repo/Cargo.toml").unwrap(),
+            "Cargo.toml"
+        );
+        assert!(resolve_pathspec(root, cwd, "../../../x").is_err());
+        assert_eq!(
+            resolve_pathspec(root, Path::new("/elsewhere"), "src/lib.rs").unwrap(),
+            "src/lib.rs"
+        );
+    }
+
+    #[test]
+    fn test_normalize_pathspec_rejects_escapes() {
+        let root = Path::new("/work/repo");
+        for raw in [
+            "../other-repo/file",
+            "src/../../x",
+            "/etc/passwd",
+            "/work/repo/../x",
+            "",
+        ] {
+            assert!(
+                normalize_pathspec(root, raw).is_err(),
+                "{raw} should be rejected"
+            );
+        }
+    }
+
+    fn owned(pathspec: &str, strength: LeaseStrength, actor: &str) -> Lease {
+        Lease::builder(pathspec)
+            .strength(strength)
+            .actor(actor)
+            .require_note(false)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_conflicts_strength_matrix() {
+        use LeaseStrength::*;
+
+        let policy = Policy::default();
+        let cases = [
+            (Observe, Exclusive, None),
+            (Cooperative, Cooperative, None),
+            (
+                Cooperative,
+                Strong,
+                Some(ConflictKind::RequiresAllowOverlap),
+            ),
+            (
+                Strong,
+                Cooperative,
+                Some(ConflictKind::RequiresAllowOverlap),
+            ),
+            (Strong, Strong, Some(ConflictKind::StrongOverlap)),
+            (Exclusive, Cooperative, Some(ConflictKind::Exclusive)),
+            (Cooperative, Exclusive, Some(ConflictKind::Exclusive)),
+            (Exclusive, Observe, None),
+        ];
+
+        for (held, wanted, expected) in cases {
+            let existing = owned("src/lib.rs", held, "alice");
+            let requested = Lease::probe("src/lib.rs", wanted, Some("bob"));
+            let reason = conflicts(&existing, &requested, &policy);
+            assert_eq!(
+                reason.map(|r| r.kind),
+                expected,
+                "{held} held vs {wanted} requested"
+            );
+        }
+    }
+
+    #[test]
+    fn test_conflicts_respects_policy_and_owners() {
+        let existing = owned("src/**", LeaseStrength::Strong, "alice");
+        let requested = Lease::probe("src/lib.rs", LeaseStrength::Cooperative, Some("bob"));
+
+        let allow = Policy {
+            allow_overlap: true,
+            ..Policy::default()
+        };
+        assert!(conflicts(&existing, &requested, &allow).is_none());
+
+        let reason = conflicts(&existing, &requested, &Policy::default()).unwrap();
+        assert_eq!(reason.lease_id, existing.id);
+        assert_eq!(reason.holder.as_deref(), Some("alice"));
+
+        let same_actor = Lease::probe("src/lib.rs", LeaseStrength::Strong, Some("alice"));
+        assert!(conflicts(&existing, &same_actor, &Policy::default()).is_none());
+
+        let ownerless = Lease::probe("src/lib.rs", LeaseStrength::Strong, None);
+        assert!(conflicts(&existing, &ownerless, &Policy::default()).is_none());
+
+        let elsewhere = Lease::probe("docs/readme.md", LeaseStrength::Strong, Some("bob"));
+        assert!(conflicts(&existing, &elsewhere, &Policy::default()).is_none());
+
+        let strict = Policy {
+            allow_overlap_cooperative: false,
+            ..Policy::default()
+        };
+        let coop = owned("src/lib.rs", LeaseStrength::Cooperative, "alice");
+        assert_eq!(
+            conflicts(&coop, &requested, &strict).map(|r| r.kind),
+            Some(ConflictKind::CooperativeOverlap)
+        );
+    }
+
+    #[test]
+    fn test_scope_intersection() {
+        let repo = LeaseScope::Repo;
+        let main = LeaseScope::Branch("main".to_string());
+        let dev = LeaseScope::Branch("dev".to_string());
+        let ws1 = LeaseScope::Workspace("agent1".to_string());
+        let ws2 = LeaseScope::Workspace("agent2".to_string());
+
+        assert!(repo.intersects(&ws1));
+        assert!(main.intersects(&repo));
+        assert!(main.intersects(&main));
+        assert!(!main.intersects(&dev));
+        assert!(ws1.intersects(&ws1));
+        assert!(!ws1.intersects(&ws2));
+        assert!(main.intersects(&ws1));
+    }
+
+    #[test]
+    fn test_conflicts_ignore_disjoint_scopes() {
+        let mut existing = owned("src/lib.rs", LeaseStrength::Exclusive, "alice");
+        existing.scope = LeaseScope::Workspace("agent1".to_string());
+
+        let mut requested = Lease::probe("src/lib.rs", LeaseStrength::Exclusive, Some("bob"));
+        requested.scope = LeaseScope::Workspace("agent2".to_string());
+        assert!(conflicts(&existing, &requested, &Policy::default()).is_none());
+
+        requested.scope = LeaseScope::Workspace("agent1".to_string());
+        assert!(conflicts(&existing, &requested, &Policy::default()).is_some());
+
+        requested.scope = LeaseScope::Repo;
+        assert!(conflicts(&existing, &requested, &Policy::default()).is_some());
+    }
+
+    #[test]
+    fn test_seeded_lease_id_is_stable_per_seed_and_sequence() {
+        let first = seeded_lease_id("demo", 0);
+        assert_eq!(first, seeded_lease_id("demo", 0));
+        assert_ne!(first, seeded_lease_id("demo", 1));
+        assert_ne!(first, seeded_lease_id("other", 0));
+        assert_eq!(first.get_version_num(), 4);
+        assert_eq!(
+            seeded_lease_id("demo", 1).to_string(),
+            "a5e41b67-4276-4396-8000-000000000001"
+        );
+    }
+
+    #[test]
+    fn test_pathspec_covers() {
+        assert!(pathspec_covers("src/lib.rs", "src/lib.rs"));
+        assert!(pathspec_covers("src/**", "src/lib.rs"));
+        assert!(pathspec_covers("src/**", "src/auth/**"));
+        assert!(pathspec_covers("src/", "src/auth/token.rs"));
+        assert!(pathspec_covers("src/*.rs", "src/lib.rs"));
+        assert!(!pathspec_covers("src/lib.rs", "src/**"));
+        assert!(!pathspec_covers("src/**", "srcx/lib.rs"));
+        assert!(!pathspec_covers("src/*.rs", "src/**"));
+        assert!(!pathspec_covers("docs/**", "src/lib.rs"));
+    }
 }
@@ -3,9 +3,8 @@
 //! A standalone CLI that makes Git practical for many parallel agents by adding
 //! workspaces, leases, protected paths, risk prediction, and operation undo.
 
-use clap::Parser;
 use sv::cli::Cli;
-use sv::output::{emit_error, infer_command_name_from_args};
+use sv::output::{emit_error, emit_result_line, infer_command_name_from_args};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 fn main() {
@@ -31,7 +30,8 @@ fn main() {
 }
 
 fn real_main() -> i32 {
-    // Tracing is opt-in via RUST_LOG.
+    // Tracing is opt-in via RUST_LOG and always goes to stderr so it never
+    // mixes with command results.
     // Keep startup robust in CI/robot envs: ignore invalid/huge filters.
     if let Some(filter) = std::env::var("RUST_LOG").ok().and_then(|raw| {
         let raw = raw.trim();
@@ -41,23 +41,24 @@ fn real_main() -> i32 {
         EnvFilter::try_new(raw).ok()
     }) {
         tracing_subscriber::registry()
-            .with(fmt::layer())
+            .with(fmt::layer().with_writer(std::io::stderr))
             .with(filter)
             .init();
     }
 
     let command = infer_command_name_from_args();
-    let cli = Cli::parse();
+    let cli = Cli::parse_args();
     let events_to_stdout = cli
         .events
         .as_deref()
         .map(|value| value.trim() == "-")
         .unwrap_or(false);
-    let json = cli.json && !events_to_stdout;
+    let json = cli.json && (!events_to_stdout || cli.output.is_some());
     if let Err(err) = cli.run() {
         let _ = emit_error(&command, &err, json);
         return err.exit_code();
     }
+    emit_result_line(&command);
 
     0
 }
@@ -45,9 +45,18 @@ pub enum MergeConflictKind {
     AddAdd,
     ModifyDelete,
     Rename,
+    /// Both sides changed a binary file; needs a manual pick, never a text merge
+    Binary,
     Unknown,
 }
 
+impl MergeConflictKind {
+    /// Human-readable label used in summaries and reports.
+    pub fn label(self) -> &'static str {
+        conflict_kind_label(self)
+    }
+}
+
 /// Summarize conflicts for human-readable output.
 pub fn summarize_conflicts(conflicts: &[MergeConflict]) -> Vec<String> {
     conflicts
@@ -79,7 +88,7 @@ pub fn simulate_merge(
 
     let index = repo.merge_trees(&base_tree, &ours_tree, &theirs_tree, Some(&options))?;
 
-    let conflicts = collect_conflicts(&index)?;
+    let conflicts = collect_conflicts(repo, &index)?;
 
     Ok(MergeSimulation {
         base: base_commit.id(),
@@ -105,7 +114,11 @@ fn merge_base_commit<'a>(repo: &'a Repository, ours: Oid, theirs: Oid) -> Result
     repo.find_commit(base_oid).map_err(Error::Git)
 }
 
-fn collect_conflicts(index: &Index) -> Result<Vec<MergeConflict>> {
+/// Conflict entries recorded in an index, sorted by path.
+///
+/// Content conflicts where any side's blob is binary are reported as
+/// [`MergeConflictKind::Binary`].
+pub fn collect_conflicts(repo: &Repository, index: &Index) -> Result<Vec<MergeConflict>> {
     if !index.has_conflicts() {
         return Ok(Vec::new());
     }
@@ -118,7 +131,15 @@ fn collect_conflicts(index: &Index) -> Result<Vec<MergeConflict>> {
         let ours_path = entry.our.as_ref().map(entry_path);
         let theirs_path = entry.their.as_ref().map(entry_path);
 
-        let kind = classify_conflict(&ancestor_path, &ours_path, &theirs_path);
+        let mut kind = classify_conflict(&ancestor_path, &ours_path, &theirs_path);
+        if matches!(kind, MergeConflictKind::Content | MergeConflictKind::AddAdd)
+            && [&entry.ancestor, &entry.our, &entry.their]
+                .into_iter()
+                .flatten()
+                .any(|side| is_binary_blob(repo, side.id))
+        {
+            kind = MergeConflictKind::Binary;
+        }
         let path = primary_path(&ancestor_path, &ours_path, &theirs_path);
 
         conflicts.push(MergeConflict {
@@ -134,6 +155,10 @@ fn collect_conflicts(index: &Index) -> Result<Vec<MergeConflict>> {
     Ok(conflicts)
 }
 
+fn is_binary_blob(repo: &Repository, id: Oid) -> bool {
+    repo.find_blob(id).is_ok_and(|blob| blob.is_binary())
+}
+
 fn entry_path(entry: &IndexEntry) -> String {
     String::from_utf8_lossy(&entry.path).into_owned()
 }
@@ -190,6 +215,7 @@ fn conflict_kind_label(kind: MergeConflictKind) -> &'static str {
         MergeConflictKind::AddAdd => "add/add",
         MergeConflictKind::ModifyDelete => "modify/delete",
         MergeConflictKind::Rename => "rename",
+        MergeConflictKind::Binary => "binary conflict (manual)",
         MergeConflictKind::Unknown => "unknown",
     }
 }
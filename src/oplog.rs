@@ -1,6 +1,8 @@
 //! Operation log storage for sv
 //!
-//! Stores append-only operation records under `.git/sv/oplog/`.
+//! Stores append-only operation records under `.git/sv/oplog/`. Undo and redo
+//! are recorded like any other operation, so which operations are undone is
+//! part of the log itself.
 
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -10,9 +12,172 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::error::{Error, Result};
+use crate::lease::{pathspecs_overlap, Lease, LeaseIntent, LeaseStatus, LeaseStrength};
 use crate::lock::{self, FileLock, DEFAULT_LOCK_TIMEOUT_MS};
 use crate::storage::Storage;
 
+/// Kind of operation recorded in the log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    Init,
+    ActorSet,
+    WsNew,
+    WsRm,
+    WsClean,
+    WsHere,
+    WsRegister,
+    WsArchive,
+    WsRestore,
+    Take,
+    Release,
+    #[serde(alias = "renew")]
+    LeaseRenew,
+    #[serde(alias = "break")]
+    LeaseBreak,
+    #[serde(alias = "keepalive")]
+    LeaseKeepalive,
+    Commit,
+    Onto,
+    Hoist,
+    RefsPrune,
+    RefsMigrate,
+    Undo,
+    Redo,
+    Protect,
+    Task,
+    Other,
+}
+
+impl OperationKind {
+    pub const ALL: &'static [OperationKind] = &[
+        OperationKind::Init,
+        OperationKind::ActorSet,
+        OperationKind::WsNew,
+        OperationKind::WsRm,
+        OperationKind::WsClean,
+        OperationKind::WsHere,
+        OperationKind::WsRegister,
+        OperationKind::WsArchive,
+        OperationKind::WsRestore,
+        OperationKind::Take,
+        OperationKind::Release,
+        OperationKind::LeaseRenew,
+        OperationKind::LeaseBreak,
+        OperationKind::LeaseKeepalive,
+        OperationKind::Commit,
+        OperationKind::Onto,
+        OperationKind::Hoist,
+        OperationKind::RefsPrune,
+        OperationKind::RefsMigrate,
+        OperationKind::Undo,
+        OperationKind::Redo,
+        OperationKind::Protect,
+        OperationKind::Task,
+        OperationKind::Other,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OperationKind::Init => "init",
+            OperationKind::ActorSet => "actor_set",
+            OperationKind::WsNew => "ws_new",
+            OperationKind::WsRm => "ws_rm",
+            OperationKind::WsClean => "ws_clean",
+            OperationKind::WsHere => "ws_here",
+            OperationKind::WsRegister => "ws_register",
+            OperationKind::WsArchive => "ws_archive",
+            OperationKind::WsRestore => "ws_restore",
+            OperationKind::Take => "take",
+            OperationKind::Release => "release",
+            OperationKind::LeaseRenew => "lease_renew",
+            OperationKind::LeaseBreak => "lease_break",
+            OperationKind::LeaseKeepalive => "lease_keepalive",
+            OperationKind::Commit => "commit",
+            OperationKind::Onto => "onto",
+            OperationKind::Hoist => "hoist",
+            OperationKind::RefsPrune => "refs_prune",
+            OperationKind::RefsMigrate => "refs_migrate",
+            OperationKind::Undo => "undo",
+            OperationKind::Redo => "redo",
+            OperationKind::Protect => "protect",
+            OperationKind::Task => "task",
+            OperationKind::Other => "other",
+        }
+    }
+
+    /// Infer the kind from a recorded command line (used for entries written
+    /// before kinds were recorded).
+    pub fn from_command(command: &str) -> Self {
+        let mut words = command.split_whitespace().peekable();
+        if words.peek() == Some(&"sv") {
+            words.next();
+        }
+        let first = words.next().unwrap_or_default();
+        let second = words.next().unwrap_or_default();
+        match (first, second) {
+            ("init", _) => OperationKind::Init,
+            ("actor", _) => OperationKind::ActorSet,
+            ("ws", "new") => OperationKind::WsNew,
+            ("ws", "rm") => OperationKind::WsRm,
+            ("ws", "clean") => OperationKind::WsClean,
+            ("ws", "here") => OperationKind::WsHere,
+            ("ws", "archive") => OperationKind::WsArchive,
+            ("ws", "restore") => OperationKind::WsRestore,
+            ("auto-register", _) => OperationKind::WsRegister,
+            ("take", _) => OperationKind::Take,
+            ("release", _) => OperationKind::Release,
+            ("lease", "renew") => OperationKind::LeaseRenew,
+            ("lease", "break") => OperationKind::LeaseBreak,
+            ("lease", "keepalive") => OperationKind::LeaseKeepalive,
+            ("commit", _) => OperationKind::Commit,
+            ("onto", _) => OperationKind::Onto,
+            ("hoist", _) => OperationKind::Hoist,
+            ("refs", "prune") => OperationKind::RefsPrune,
+            ("refs", "migrate") => OperationKind::RefsMigrate,
+            ("undo", _) => OperationKind::Undo,
+            ("redo", _) => OperationKind::Redo,
+            ("protect", _) => OperationKind::Protect,
+            ("task", _) => OperationKind::Task,
+            _ => OperationKind::Other,
+        }
+    }
+
+    /// Whether an `--operation` value selects this kind: either the exact
+    /// kind (`ws_new`, `ws-new`) or a family prefix (`ws` selects every `ws_*`,
+    /// `lease` selects renew, break and keepalive).
+    pub fn matches_filter(self, filter: &str) -> bool {
+        let filter = normalize_operation_filter(filter);
+        let name = self.as_str();
+        name == filter
+            || name
+                .strip_prefix(filter.as_str())
+                .is_some_and(|rest| rest.starts_with('_'))
+    }
+
+    /// Check that an `--operation` value selects at least one kind.
+    pub fn validate_filter(filter: &str) -> Result<()> {
+        if Self::ALL.iter().any(|kind| kind.matches_filter(filter)) {
+            return Ok(());
+        }
+        let valid: Vec<&str> = Self::ALL.iter().map(|kind| kind.as_str()).collect();
+        Err(Error::InvalidArgument(format!(
+            "unknown operation '{filter}' (expected one of: {}, or a family such as 'ws' or 'lease')",
+            valid.join(", ")
+        )))
+    }
+}
+
+impl std::fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn normalize_operation_filter(filter: &str) -> String {
+    filter.trim().to_ascii_lowercase().replace('-', "_")
+}
+
 /// Operation log record
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct OpRecord {
@@ -21,31 +186,69 @@ pub struct OpRecord {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub actor: Option<String>,
     pub command: String,
+    /// Recorded operation kind; older entries infer it from `command`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<OperationKind>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub affected_refs: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub affected_workspaces: Vec<String>,
+    /// Repo-relative paths or pathspecs the operation touched
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub affected_paths: Vec<String>,
+    /// Commits the operation created, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commits: Vec<String>,
     pub outcome: OpOutcome,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub details: Option<OpDetails>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub undo_data: Option<UndoData>,
+    /// Operation an `sv undo` record reverted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undoes: Option<Uuid>,
+    /// Operation an `sv redo` record re-applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redoes: Option<Uuid>,
+    /// On an `sv undo` record, the inverse of what the undo changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redo_data: Option<UndoData>,
 }
 
 impl OpRecord {
     pub fn new(command: impl Into<String>, actor: Option<String>) -> Self {
+        let command = command.into();
         Self {
             op_id: Uuid::new_v4(),
             timestamp: Utc::now(),
             actor,
-            command: command.into(),
+            kind: Some(OperationKind::from_command(&command)),
+            command,
             affected_refs: Vec::new(),
             affected_workspaces: Vec::new(),
+            affected_paths: Vec::new(),
+            commits: Vec::new(),
             outcome: OpOutcome::success(),
             details: None,
             undo_data: None,
+            undoes: None,
+            redoes: None,
+            redo_data: None,
         }
     }
+
+    /// Operation kind, inferred from the command for legacy entries
+    pub fn kind(&self) -> OperationKind {
+        self.kind
+            .unwrap_or_else(|| OperationKind::from_command(&self.command))
+    }
+
+    /// Whether any recorded path overlaps a (possibly glob) pathspec
+    pub fn touches(&self, pathspec: &str) -> bool {
+        self.affected_paths
+            .iter()
+            .any(|path| pathspecs_overlap(path, pathspec))
+    }
 }
 
 /// Operation outcome summary
@@ -92,6 +295,9 @@ pub struct UndoData {
 pub struct OpDetails {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commit: Option<CommitDetails>,
+    /// Lease state after a take/renew, replayed by [`OpLog::leases_at`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub leases: Vec<Lease>,
 }
 
 /// Commit details for op log entries.
@@ -104,10 +310,63 @@ pub struct CommitDetails {
     pub files: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_protected: Option<bool>,
+    /// Guard-protected paths committed under `--allow-protected`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protected_bypassed: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bypass_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub override_readonly: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub force_lease: Option<bool>,
 }
 
+/// One step in a lease's coordination history, replayed from the oplog
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LeaseHistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub op_id: Uuid,
+    /// take, renew, keepalive, release, or break
+    pub action: String,
+    pub lease_id: Uuid,
+    pub pathspec: String,
+    /// Lease holder
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holder: Option<String>,
+    /// Actor who ran the operation (differs from the holder for breaks)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub by: Option<String>,
+    pub strength: LeaseStrength,
+    pub intent: LeaseIntent,
+    /// Expiry set by a take, renew, or keepalive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Break reason
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl LeaseHistoryEntry {
+    fn new(record: &OpRecord, action: &str, lease: &Lease) -> Self {
+        Self {
+            timestamp: record.timestamp,
+            op_id: record.op_id,
+            action: action.to_string(),
+            lease_id: lease.id,
+            pathspec: lease.pathspec.clone(),
+            holder: lease.actor.clone(),
+            by: record.actor.clone(),
+            strength: lease.strength,
+            intent: lease.intent,
+            expires_at: Some(lease.expires_at),
+            note: lease.note.clone(),
+            reason: None,
+        }
+    }
+}
+
 /// Ref update for undo
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RefUpdate {
@@ -200,6 +459,150 @@ impl OpLog {
         Ok(records)
     }
 
+    /// Reconstruct the leases that were active at `at`.
+    ///
+    /// Replays the lease snapshots and the lease changes of every record up
+    /// to `at`, whatever command wrote it: releases, breaks and expiries drop
+    /// a lease, and undo/redo records drop or restore the leases they
+    /// touched. Entries written before snapshots were recorded contribute
+    /// nothing, so older history is incomplete.
+    pub fn leases_at(&self, at: DateTime<Utc>) -> Result<Vec<Lease>> {
+        let mut records = self.read_all()?;
+        records.sort_by_key(|record| record.timestamp);
+
+        let mut leases: Vec<Lease> = Vec::new();
+        // Dropped leases, kept so an undo or redo can bring them back
+        let mut dropped: Vec<Lease> = Vec::new();
+        let mut redo_data: Vec<(Uuid, &UndoData)> = Vec::new();
+        let drop_lease = |leases: &mut Vec<Lease>, dropped: &mut Vec<Lease>, id: &str| {
+            if let Some(idx) = leases.iter().position(|lease| lease.id.to_string() == id) {
+                let lease = leases.remove(idx);
+                dropped.retain(|other| other.id != lease.id);
+                dropped.push(lease);
+            }
+        };
+        let restore_lease = |leases: &mut Vec<Lease>, dropped: &mut Vec<Lease>, id: &str| {
+            if let Some(idx) = dropped.iter().position(|lease| lease.id.to_string() == id) {
+                leases.push(dropped.remove(idx));
+            }
+        };
+
+        for record in records.iter().take_while(|record| record.timestamp <= at) {
+            if record.outcome.status != "success" {
+                continue;
+            }
+            let snapshots = record.details.iter().flat_map(|d| d.leases.iter());
+            for snapshot in snapshots {
+                dropped.retain(|lease| lease.id != snapshot.id);
+                match leases.iter_mut().find(|lease| lease.id == snapshot.id) {
+                    Some(lease) => *lease = snapshot.clone(),
+                    None => leases.push(snapshot.clone()),
+                }
+            }
+
+            let changes = record.undo_data.iter().flat_map(|u| u.lease_changes.iter());
+            for change in changes {
+                if change.action == "release"
+                    || change.action == "expire"
+                    || change.action.starts_with("break")
+                {
+                    drop_lease(&mut leases, &mut dropped, &change.lease_id);
+                }
+            }
+
+            // Redo data actions name what the undo did to each lease:
+            // "release" dropped it and "create" restored it.
+            if let (Some(op_id), Some(data)) = (record.undoes, &record.redo_data) {
+                for change in &data.lease_changes {
+                    match change.action.as_str() {
+                        "release" => drop_lease(&mut leases, &mut dropped, &change.lease_id),
+                        "create" => restore_lease(&mut leases, &mut dropped, &change.lease_id),
+                        _ => {}
+                    }
+                }
+                redo_data.push((op_id, data));
+            }
+            if let Some(op_id) = record.redoes {
+                let data = redo_data.iter().rev().find(|(id, _)| *id == op_id);
+                for change in data.iter().flat_map(|(_, data)| data.lease_changes.iter()) {
+                    match change.action.as_str() {
+                        "release" => restore_lease(&mut leases, &mut dropped, &change.lease_id),
+                        "create" => drop_lease(&mut leases, &mut dropped, &change.lease_id),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        leases.retain(|lease| lease.expires_at > at);
+        for lease in &mut leases {
+            lease.status = LeaseStatus::Active;
+        }
+        Ok(leases)
+    }
+
+    /// Replay the coordination history of leases selected by `include`.
+    ///
+    /// Yields one entry per take, renew, keepalive, release, or break of a
+    /// matching lease, oldest first. Releases and breaks are attributed via
+    /// the last snapshot of the lease, so leases taken before snapshots were
+    /// recorded do not appear.
+    pub fn lease_history(
+        &self,
+        include: impl Fn(&Lease) -> bool,
+    ) -> Result<Vec<LeaseHistoryEntry>> {
+        let mut records = self.read_all()?;
+        records.sort_by_key(|record| record.timestamp);
+
+        let mut known: Vec<Lease> = Vec::new();
+        let mut history = Vec::new();
+        for record in &records {
+            if record.outcome.status != "success" {
+                continue;
+            }
+            let action = match record.kind() {
+                OperationKind::Take => Some("take"),
+                OperationKind::LeaseRenew => Some("renew"),
+                OperationKind::LeaseKeepalive => Some("keepalive"),
+                _ => None,
+            };
+            if let Some(action) = action {
+                let snapshots = record.details.iter().flat_map(|d| d.leases.iter());
+                for snapshot in snapshots {
+                    match known.iter_mut().find(|lease| lease.id == snapshot.id) {
+                        Some(lease) => *lease = snapshot.clone(),
+                        None => known.push(snapshot.clone()),
+                    }
+                    if include(snapshot) {
+                        history.push(LeaseHistoryEntry::new(record, action, snapshot));
+                    }
+                }
+            }
+
+            let changes = record.undo_data.iter().flat_map(|u| u.lease_changes.iter());
+            for change in changes {
+                let (action, reason) = match change.action.split_once(':') {
+                    Some(("break", reason)) => ("break", Some(reason.trim().to_string())),
+                    None if change.action == "release" => ("release", None),
+                    _ => continue,
+                };
+                let Some(lease) = known
+                    .iter()
+                    .find(|lease| lease.id.to_string() == change.lease_id)
+                else {
+                    continue;
+                };
+                if include(lease) {
+                    let mut entry = LeaseHistoryEntry::new(record, action, lease);
+                    entry.expires_at = None;
+                    entry.reason = reason;
+                    history.push(entry);
+                }
+            }
+        }
+        Ok(history)
+    }
+
     /// Read operation records filtered and sorted by timestamp desc
     pub fn read_filtered(
         &self,
@@ -207,7 +610,7 @@ impl OpLog {
         limit: Option<usize>,
     ) -> Result<Vec<OpRecord>> {
         let mut records = self.read_all()?;
-        records.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        records.sort_by_key(|record| std::cmp::Reverse(record.timestamp));
 
         let mut filtered: Vec<OpRecord> = records
             .into_iter()
@@ -229,6 +632,8 @@ pub struct OpLogFilter {
     pub since: Option<DateTime<Utc>>,
     pub until: Option<DateTime<Utc>>,
     pub operation: Option<String>,
+    /// Keep only entries whose affected paths overlap this pathspec
+    pub touching: Option<String>,
 }
 
 impl OpLogFilter {
@@ -252,8 +657,13 @@ impl OpLogFilter {
         }
 
         if let Some(operation) = &self.operation {
-            let record_op = operation_from_command(&record.command);
-            if record_op != operation {
+            if !record.kind().matches_filter(operation) {
+                return false;
+            }
+        }
+
+        if let Some(touching) = &self.touching {
+            if !record.touches(touching) {
                 return false;
             }
         }
@@ -276,6 +686,11 @@ pub fn format_record(record: &OpRecord) -> String {
     } else {
         record.affected_workspaces.join(",")
     };
+    let paths = if record.affected_paths.is_empty() {
+        "-".to_string()
+    } else {
+        record.affected_paths.join(",")
+    };
     let outcome = match &record.outcome.message {
         Some(msg) => format!("{} ({})", record.outcome.status, msg),
         None => record.outcome.status.clone(),
@@ -284,8 +699,9 @@ pub fn format_record(record: &OpRecord) -> String {
     let details = format_details(record);
 
     format!(
-        "{ts} {op_id} actor={actor} outcome={outcome} command=\"{command}\" refs=[{refs}] workspaces=[{workspaces}]",
+        "{ts} {op_id} kind={kind} actor={actor} outcome={outcome} command=\"{command}\" refs=[{refs}] workspaces=[{workspaces}] paths=[{paths}]",
         op_id = record.op_id,
+        kind = record.kind(),
         command = record.command
     )
     + &details
@@ -318,26 +734,29 @@ fn format_details(record: &OpRecord) -> String {
         if commit.allow_protected.unwrap_or(false) {
             overrides.push("allow_protected");
         }
+        if commit.override_readonly.unwrap_or(false) {
+            overrides.push("override_readonly");
+        }
         if commit.force_lease.unwrap_or(false) {
             overrides.push("force_lease");
         }
         if !overrides.is_empty() {
             parts.push(format!("overrides={}", overrides.join(",")));
         }
+        if !commit.protected_bypassed.is_empty() {
+            parts.push(format!("bypassed={}", commit.protected_bypassed.len()));
+        }
+        if let Some(reason) = &commit.bypass_reason {
+            parts.push(format!("reason={reason:?}"));
+        }
         return format!(" details=[{}]", parts.join(" "));
     }
 
-    String::new()
-}
-
-fn operation_from_command(command: &str) -> &str {
-    let mut parts = command.split_whitespace();
-    let first = parts.next().unwrap_or_default();
-    if first == "sv" {
-        parts.next().unwrap_or(first)
-    } else {
-        first
+    if !details.leases.is_empty() {
+        return format!(" details=[leases={}]", details.leases.len());
     }
+
+    String::new()
 }
 
 fn oplog_lock_path(dir: &Path) -> PathBuf {
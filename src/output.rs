@@ -1,11 +1,238 @@
 //! Shared output formatting for sv CLI commands.
 
+use std::fmt;
+use std::fs::File;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
 use serde::Serialize;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 
 pub const SCHEMA_VERSION: &str = "sv.v1";
 
+/// Narrowest a column is truncated to when fitting a table to the terminal.
+const MIN_COLUMN_WIDTH: usize = 8;
+
+static NO_COLOR: AtomicBool = AtomicBool::new(false);
+
+static RESULT_LINE: AtomicBool = AtomicBool::new(false);
+
+/// Prefix of the grep-able summary line printed by `--result-line`.
+pub const RESULT_LINE_PREFIX: &str = "SV_RESULT";
+
+/// Command name and counts recorded by `emit_success` for the result line.
+type ResultSummary = (String, Vec<(String, String)>);
+static RESULT_SUMMARY: Mutex<Option<ResultSummary>> = Mutex::new(None);
+
+/// Destination for the command's primary result when `--output` is set.
+static RESULT_FILE: Mutex<Option<File>> = Mutex::new(None);
+
+/// Buffer collecting results while `capture_result` runs (multi-repo runs).
+static RESULT_CAPTURE: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+
+/// Print a line of the command's primary result.
+///
+/// Behaves like `println!` but honors the global `--output <path>` flag, so
+/// results can be kept apart from events and diagnostics (which use stderr).
+macro_rules! outln {
+    () => {
+        $crate::output::write_result(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::write_result(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+pub(crate) use outln;
+
+/// Send the command's primary result to `path` instead of stdout.
+///
+/// The file is created (or truncated) immediately so that an unwritable path
+/// fails before the command does any work.
+pub fn set_output_path(path: Option<&Path>) -> Result<()> {
+    let file = match path {
+        Some(path) => Some(File::create(path).map_err(|err| {
+            Error::InvalidArgument(format!(
+                "cannot open --output file {}: {err}",
+                path.display()
+            ))
+        })?),
+        None => None,
+    };
+    *result_file() = file;
+    Ok(())
+}
+
+/// Run `f` with its primary result collected instead of written, returning
+/// the collected text alongside `f`'s value.
+pub fn capture_result<T>(f: impl FnOnce() -> T) -> (T, String) {
+    *result_capture() = Some(Vec::new());
+    let value = f();
+    let captured = result_capture().take().unwrap_or_default();
+    (value, String::from_utf8_lossy(&captured).into_owned())
+}
+
+fn result_capture() -> MutexGuard<'static, Option<Vec<u8>>> {
+    RESULT_CAPTURE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Write part of the command's primary result to stdout or the `--output` file.
+pub fn write_result(args: fmt::Arguments<'_>) {
+    if let Some(buffer) = result_capture().as_mut() {
+        // Writing into a Vec cannot fail
+        let _ = buffer.write_fmt(args);
+        return;
+    }
+    let written = match result_file().as_mut() {
+        Some(file) => file.write_fmt(args).and_then(|_| file.flush()),
+        None => std::io::stdout().lock().write_fmt(args),
+    };
+    if let Err(err) = written {
+        panic!("failed writing command output: {err}");
+    }
+}
+
+/// Writer for streaming results (e.g. JSONL) to stdout or the `--output` file.
+pub fn result_writer() -> ResultWriter {
+    ResultWriter
+}
+
+pub struct ResultWriter;
+
+impl Write for ResultWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Some(buffer) = result_capture().as_mut() {
+            return buffer.write(buf);
+        }
+        match result_file().as_mut() {
+            Some(file) => file.write(buf),
+            None => std::io::stdout().lock().write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match result_file().as_mut() {
+            Some(file) => file.flush(),
+            None => std::io::stdout().lock().flush(),
+        }
+    }
+}
+
+fn result_summary() -> MutexGuard<'static, Option<ResultSummary>> {
+    RESULT_SUMMARY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn result_file() -> MutexGuard<'static, Option<File>> {
+    RESULT_FILE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Whether `--output` moved the command's primary result off stdout.
+pub fn result_redirected() -> bool {
+    result_file().is_some()
+}
+
+/// Whether results are going to a terminal (false when `--output` is set).
+fn result_is_terminal() -> bool {
+    result_file().is_none() && std::io::stdout().is_terminal()
+}
+
+/// Transient progress for multi-second operations, written to stderr.
+///
+/// Only shown when stderr is a terminal and neither `--json` nor `--quiet`
+/// is set. Redraws a single line in place unless color is disabled
+/// (`--no-color`/`NO_COLOR`), in which case each update is a plain line.
+pub struct Progress {
+    enabled: bool,
+    in_place: bool,
+    drawn: bool,
+}
+
+impl Progress {
+    pub fn new(options: OutputOptions) -> Self {
+        let enabled = !options.json && !options.quiet && std::io::stderr().is_terminal();
+        Self {
+            enabled,
+            in_place: enabled && !color_disabled(),
+            drawn: false,
+        }
+    }
+
+    /// Show `message` as the current step.
+    pub fn update(&mut self, message: impl fmt::Display) {
+        if !self.enabled {
+            return;
+        }
+        let mut stderr = std::io::stderr().lock();
+        let _ = if self.in_place {
+            write!(stderr, "\r\x1b[2K{message}")
+        } else {
+            writeln!(stderr, "{message}")
+        };
+        let _ = stderr.flush();
+        self.drawn = true;
+    }
+
+    /// Clear the progress line before results are printed.
+    pub fn finish(&mut self) {
+        if self.in_place && self.drawn {
+            let mut stderr = std::io::stderr().lock();
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        }
+        self.drawn = false;
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Disable ANSI styling for the rest of the process (set by `--no-color`).
+pub fn set_no_color(disabled: bool) {
+    NO_COLOR.store(disabled, Ordering::Relaxed);
+}
+
+/// Print a `SV_RESULT` summary line to stderr after each command (set by
+/// `--result-line`).
+pub fn set_result_line(enabled: bool) {
+    RESULT_LINE.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether human output may use ANSI styling.
+///
+/// Color requires results to go to a terminal and is disabled by `--no-color` or a
+/// non-empty `NO_COLOR` environment variable.
+pub fn color_enabled() -> bool {
+    !color_disabled() && result_is_terminal()
+}
+
+/// Whether `--no-color` or a non-empty `NO_COLOR` turned styling off.
+fn color_disabled() -> bool {
+    NO_COLOR.load(Ordering::Relaxed)
+        || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Terminal width used to truncate tables, if results go to a terminal.
+fn terminal_width() -> Option<usize> {
+    if !result_is_terminal() {
+        return None;
+    }
+    crossterm::terminal::size()
+        .ok()
+        .map(|(cols, _)| cols as usize)
+        .filter(|cols| *cols > 0)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct OutputOptions {
     pub json: bool,
@@ -19,6 +246,13 @@ pub struct HumanOutput {
     details: Vec<String>,
     warnings: Vec<String>,
     next_steps: Vec<String>,
+    tables: Vec<Table>,
+}
+
+#[derive(Debug, Clone)]
+struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
 }
 
 impl HumanOutput {
@@ -29,6 +263,7 @@ impl HumanOutput {
             details: Vec::new(),
             warnings: Vec::new(),
             next_steps: Vec::new(),
+            tables: Vec::new(),
         }
     }
 
@@ -47,6 +282,26 @@ impl HumanOutput {
     pub fn push_next_step(&mut self, value: impl Into<String>) {
         self.next_steps.push(value.into());
     }
+
+    /// Add an aligned table rendered after the summary in human mode.
+    ///
+    /// Tables are presentation only and never appear in the JSON envelope.
+    pub fn push_table<H, R, C>(&mut self, headers: H, rows: R)
+    where
+        H: IntoIterator,
+        H::Item: Into<String>,
+        R: IntoIterator,
+        R::Item: IntoIterator<Item = C>,
+        C: Into<String>,
+    {
+        self.tables.push(Table {
+            headers: headers.into_iter().map(Into::into).collect(),
+            rows: rows
+                .into_iter()
+                .map(|row| row.into_iter().map(Into::into).collect())
+                .collect(),
+        });
+    }
 }
 
 pub fn emit_success<T: Serialize>(
@@ -55,6 +310,11 @@ pub fn emit_success<T: Serialize>(
     data: &T,
     human: Option<&HumanOutput>,
 ) -> Result<()> {
+    if RESULT_LINE.load(Ordering::Relaxed) {
+        let value = serde_json::to_value(data)?;
+        *result_summary() = Some((command.to_string(), result_counts(&value)));
+    }
+
     if options.json {
         let warnings = human.map(|h| h.warnings.clone()).unwrap_or_default();
         let next_steps = human.map(|h| h.next_steps.clone()).unwrap_or_default();
@@ -80,7 +340,7 @@ pub fn emit_success<T: Serialize>(
             next_steps,
         };
 
-        println!("{}", serde_json::to_string_pretty(&payload)?);
+        outln!("{}", serde_json::to_string_pretty(&payload)?);
         return Ok(());
     }
 
@@ -89,15 +349,78 @@ pub fn emit_success<T: Serialize>(
     }
 
     if let Some(human) = human {
-        println!("{}", format_human(human));
+        outln!("{}", format_human(human));
     }
 
     Ok(())
 }
 
+/// Print the `--result-line` summary for a command that finished successfully.
+///
+/// Called once after the command returns, so commands that print their own
+/// output instead of going through `emit_success` still get a line (without
+/// counts) under the name `command`.
+pub fn emit_result_line(command: &str) {
+    if !RESULT_LINE.load(Ordering::Relaxed) {
+        return;
+    }
+    let (command, counts) = result_summary()
+        .take()
+        .unwrap_or_else(|| (command.to_string(), Vec::new()));
+    eprintln!("{}", format_result_line(&command, "ok", &counts));
+}
+
+/// Render a `--result-line` summary, e.g. `SV_RESULT take ok created=2`.
+///
+/// Multi-word commands are joined with `.` so the line splits cleanly on
+/// whitespace.
+pub fn format_result_line(command: &str, status: &str, counts: &[(String, String)]) -> String {
+    let mut line = format!(
+        "{RESULT_LINE_PREFIX} {} {status}",
+        command.split_whitespace().collect::<Vec<_>>().join(".")
+    );
+    for (key, value) in counts {
+        line.push_str(&format!(" {key}={value}"));
+    }
+    line
+}
+
+/// Key counts for the result line: top-level numbers and list lengths,
+/// sorted by key.
+fn result_counts(data: &serde_json::Value) -> Vec<(String, String)> {
+    let serde_json::Value::Object(map) = data else {
+        return Vec::new();
+    };
+    map.iter()
+        .filter_map(|(key, value)| match value {
+            serde_json::Value::Number(number) => Some((key.clone(), number.to_string())),
+            serde_json::Value::Array(items) => Some((key.clone(), items.len().to_string())),
+            _ => None,
+        })
+        .collect()
+}
+
 pub fn emit_error(command: &str, err: &crate::error::Error, json: bool) -> Result<()> {
+    if RESULT_LINE.load(Ordering::Relaxed) {
+        let counts = vec![
+            ("code".to_string(), err.exit_code().to_string()),
+            ("kind".to_string(), error_kind(err).to_string()),
+        ];
+        eprintln!("{}", format_result_line(command, "error", &counts));
+    }
+
     let next_steps = error_next_steps(err);
     let hint = next_steps.first().map(|step| step.as_str());
+    // The command already wrote its JSON result; the exit code carries the gate
+    // (or the interruption).
+    if json
+        && matches!(
+            err,
+            crate::error::Error::GateFailed(_) | crate::error::Error::Interrupted(_)
+        )
+    {
+        return Ok(());
+    }
     if json {
         #[derive(Serialize)]
         struct ErrorBody<'a> {
@@ -134,7 +457,7 @@ pub fn emit_error(command: &str, err: &crate::error::Error, json: bool) -> Resul
             next_steps,
         };
 
-        println!("{}", serde_json::to_string_pretty(&payload)?);
+        outln!("{}", serde_json::to_string_pretty(&payload)?);
         return Ok(());
     }
 
@@ -150,6 +473,12 @@ pub fn format_human(output: &HumanOutput) -> String {
     lines.push(output.header.clone());
 
     push_summary(&mut lines, &output.summary);
+    let width = terminal_width();
+    let bold = color_enabled();
+    for table in &output.tables {
+        lines.push(String::new());
+        lines.extend(render_table(table, width, bold));
+    }
     push_section(&mut lines, "Details", &output.details);
     push_section(&mut lines, "Warnings", &output.warnings);
     push_section(&mut lines, "Next steps", &output.next_steps);
@@ -199,6 +528,7 @@ fn error_kind(err: &crate::error::Error) -> &'static str {
     match err.exit_code() {
         2 => "user_error",
         3 => "policy_blocked",
+        130 => "interrupted",
         _ => "operation_failed",
     }
 }
@@ -208,12 +538,26 @@ fn error_next_steps(err: &crate::error::Error) -> Vec<String> {
 
     match err {
         Error::ProtectedPath(_) => vec!["sv protect status".to_string()],
+        Error::ProtectedViolations(violations) => {
+            let mut steps = vec!["sv protect status".to_string()];
+            if violations.iter().any(|v| v.mode != "readonly") {
+                steps.push("sv commit --allow-protected".to_string());
+            }
+            if violations.iter().any(|v| v.mode == "readonly") {
+                steps.push("sv commit --override-readonly --reason \"...\"".to_string());
+            }
+            steps
+        }
         Error::LeaseConflict { path, .. } => {
             vec![format!("sv lease who {}", path.to_string_lossy())]
         }
         Error::NoteRequired(_) => vec!["sv take <path> --note \"...\"".to_string()],
         Error::RepoNotFound(_) | Error::NotARepo(_) => vec!["sv init".to_string()],
         Error::InvalidConfig(_) => vec!["fix .sv.toml then retry".to_string()],
+        Error::Conflicted { operation, .. } => vec![
+            format!("resolve the files, git add them, then git {operation} --continue"),
+            format!("git {operation} --abort"),
+        ],
         _ => Vec::new(),
     }
 }
@@ -245,3 +589,109 @@ fn push_section(lines: &mut Vec<String>, title: &str, items: &[String]) {
         lines.push(format!("- {item}"));
     }
 }
+
+fn render_table(table: &Table, max_width: Option<usize>, bold: bool) -> Vec<String> {
+    let columns = table
+        .rows
+        .iter()
+        .map(Vec::len)
+        .chain(std::iter::once(table.headers.len()))
+        .max()
+        .unwrap_or(0);
+    if columns == 0 {
+        return Vec::new();
+    }
+
+    let cell = |row: &[String], index: usize| row.get(index).cloned().unwrap_or_default();
+    let mut widths = vec![0usize; columns];
+    for row in std::iter::once(&table.headers).chain(&table.rows) {
+        for (index, width) in widths.iter_mut().enumerate() {
+            *width = (*width).max(cell(row, index).chars().count());
+        }
+    }
+
+    if let Some(max_width) = max_width {
+        let separators = 2 * (columns - 1);
+        while widths.iter().sum::<usize>() + separators > max_width {
+            let Some((widest, width)) = widths
+                .iter()
+                .copied()
+                .enumerate()
+                .max_by_key(|(_, width)| *width)
+            else {
+                break;
+            };
+            if width <= MIN_COLUMN_WIDTH {
+                break;
+            }
+            widths[widest] = width - 1;
+        }
+    }
+
+    let render_row = |row: &[String]| {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(index, width)| format!("{:<width$}", truncate_cell(&cell(row, index), *width)))
+            .collect();
+        cells.join("  ").trim_end().to_string()
+    };
+
+    let mut lines = Vec::with_capacity(table.rows.len() + 1);
+    let header = render_row(&table.headers);
+    if bold {
+        lines.push(format!("\x1b[1m{header}\x1b[0m"));
+    } else {
+        lines.push(header);
+    }
+    lines.extend(table.rows.iter().map(|row| render_row(row)));
+    lines
+}
+
+fn truncate_cell(value: &str, width: usize) -> String {
+    if value.chars().count() <= width {
+        return value.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut truncated: String = value.chars().take(width - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(headers: &[&str], rows: &[&[&str]]) -> Table {
+        Table {
+            headers: headers.iter().map(|s| s.to_string()).collect(),
+            rows: rows
+                .iter()
+                .map(|row| row.iter().map(|s| s.to_string()).collect())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn render_table_aligns_columns() {
+        let table = table(
+            &["ID", "PATH"],
+            &[&["a1", "src/lib.rs"], &["b22", "docs/**"]],
+        );
+        let lines = render_table(&table, None, false);
+        assert_eq!(lines, vec!["ID   PATH", "a1   src/lib.rs", "b22  docs/**"]);
+    }
+
+    #[test]
+    fn render_table_truncates_widest_column_to_fit() {
+        let table = table(
+            &["NAME", "PATH"],
+            &[&["ws1", "/very/long/path/to/some/worktree"]],
+        );
+        let lines = render_table(&table, Some(20), false);
+        assert!(lines.iter().all(|line| line.chars().count() <= 20));
+        assert!(lines[1].ends_with('…'));
+    }
+}
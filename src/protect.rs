@@ -64,8 +64,18 @@ pub fn compute_status(
     })
 }
 
+/// Compile a protect pattern with the glob engine used for matching.
+///
+/// Returns the parse error message for empty or malformed patterns.
+pub fn compile_pattern(pattern: &str) -> std::result::Result<glob::Pattern, String> {
+    if pattern.trim().is_empty() {
+        return Err("pattern cannot be empty".to_string());
+    }
+    glob::Pattern::new(pattern).map_err(|err| err.to_string())
+}
+
 fn match_pattern(pattern: &str, path: &Path) -> Result<Option<PathBuf>> {
-    let matcher = glob::Pattern::new(pattern).map_err(|err| {
+    let matcher = compile_pattern(pattern).map_err(|err| {
         Error::InvalidConfig(format!("invalid protect pattern '{pattern}': {err}"))
     })?;
     let normalized = normalize_path(path);
@@ -88,6 +98,13 @@ fn normalize_path(path: &Path) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn compile_pattern_rejects_malformed_globs() {
+        assert!(compile_pattern(".beads/**").is_ok());
+        assert!(compile_pattern("[unclosed").is_err());
+        assert!(compile_pattern("  ").is_err());
+    }
+
     #[test]
     fn compute_status_marks_disabled_patterns() {
         let mut config = Config::default();
@@ -1,9 +1,73 @@
 //! Branch and ref operations using libgit2.
 
 use git2::{BranchType, Oid, Repository};
+use serde::Serialize;
 
 use crate::error::{Error, Result};
 
+/// Branch prefix for workspace branches created by `sv ws new`.
+pub const WORKSPACE_REF_PREFIX: &str = "sv/ws/";
+
+/// Branch prefix for default hoist integration branches.
+pub const HOIST_REF_PREFIX: &str = "sv/hoist/";
+
+/// Namespace for sv-managed refs by default: plain local branches.
+pub const DEFAULT_REF_NAMESPACE: &str = "refs/heads";
+
+/// Dedicated namespace that keeps sv-managed refs out of `git branch`.
+///
+/// `sv/ws/<name>` is stored as `refs/sv/ws/<name>`, so the short name still
+/// resolves through git's `refs/<name>` lookup rule.
+pub const DEDICATED_REF_NAMESPACE: &str = "refs/sv";
+
+/// Kind of sv-managed ref.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SvRefKind {
+    Workspace,
+    Hoist,
+}
+
+/// A ref in one of the sv-managed namespaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvRef {
+    /// Branch name, e.g. `sv/ws/agent1`
+    pub name: String,
+    /// Full ref name, e.g. `refs/heads/sv/ws/agent1` or `refs/sv/ws/agent1`
+    pub refname: String,
+    pub kind: SvRefKind,
+    pub target: Oid,
+}
+
+/// Whether `name` is in one of the sv-managed prefixes (`sv/ws/`, `sv/hoist/`).
+pub fn is_managed(name: &str) -> bool {
+    name.starts_with(WORKSPACE_REF_PREFIX) || name.starts_with(HOIST_REF_PREFIX)
+}
+
+/// Full ref name for a branch called `name` created under `namespace`.
+///
+/// Only sv-managed names move into the dedicated namespace; any other branch
+/// (e.g. one passed with `--branch`) stays in `refs/heads`.
+pub fn managed_refname(namespace: &str, name: &str) -> String {
+    if namespace == DEDICATED_REF_NAMESPACE && is_managed(name) {
+        format!("refs/{name}")
+    } else {
+        format!("refs/heads/{name}")
+    }
+}
+
+/// Full ref name of an existing branch called `name`, looking in both
+/// namespaces for sv-managed names.
+pub fn find_refname(repo: &Repository, name: &str) -> Option<String> {
+    let mut candidates = vec![format!("refs/heads/{name}")];
+    if is_managed(name) {
+        candidates.push(format!("refs/{name}"));
+    }
+    candidates
+        .into_iter()
+        .find(|refname| repo.find_reference(refname).is_ok())
+}
+
 /// Create a local branch from a ref (commit-ish).
 pub fn create_branch_from_ref(
     repo: &Repository,
@@ -17,10 +81,17 @@ pub fn create_branch_from_ref(
     Ok(())
 }
 
-/// Delete a local branch by name.
+/// Delete a local branch (or sv-managed ref) by name.
 pub fn delete_branch(repo: &Repository, name: &str) -> Result<()> {
-    let mut branch = repo.find_branch(name, BranchType::Local)?;
-    branch.delete()?;
+    match find_refname(repo, name) {
+        Some(refname) if !refname.starts_with("refs/heads/") => {
+            repo.find_reference(&refname)?.delete()?;
+        }
+        _ => {
+            let mut branch = repo.find_branch(name, BranchType::Local)?;
+            branch.delete()?;
+        }
+    }
     Ok(())
 }
 
@@ -33,7 +104,7 @@ pub fn resolve_ref_oid(repo: &Repository, spec: &str) -> Result<Oid> {
 
 /// Move a local branch reference to a new target commit.
 pub fn move_branch_ref(repo: &Repository, name: &str, target: Oid) -> Result<()> {
-    let refname = format!("refs/heads/{name}");
+    let refname = find_refname(repo, name).unwrap_or_else(|| format!("refs/heads/{name}"));
     let mut reference = repo.find_reference(&refname)?;
     reference.set_target(target, "sv move branch")?;
     Ok(())
@@ -65,3 +136,70 @@ pub fn list_branches(repo: &Repository, pattern: Option<&str>) -> Result<Vec<Str
 
     Ok(branches)
 }
+
+/// List sv-managed refs (`sv/ws/*` and `sv/hoist/*`) in either namespace,
+/// sorted by name.
+pub fn list_sv_refs(repo: &Repository) -> Result<Vec<SvRef>> {
+    let mut refs = Vec::new();
+    for pattern in ["refs/heads/sv/*", "refs/sv/*"] {
+        for reference in repo.references_glob(pattern)? {
+            let reference = reference?;
+            let (Some(refname), Some(target)) = (reference.name(), reference.target()) else {
+                continue;
+            };
+            let name = refname
+                .strip_prefix("refs/heads/")
+                .unwrap_or_else(|| refname.trim_start_matches("refs/"));
+            let kind = if name.starts_with(WORKSPACE_REF_PREFIX) {
+                SvRefKind::Workspace
+            } else if name.starts_with(HOIST_REF_PREFIX) {
+                SvRefKind::Hoist
+            } else {
+                continue;
+            };
+            refs.push(SvRef {
+                name: name.to_string(),
+                refname: refname.to_string(),
+                kind,
+                target,
+            });
+        }
+    }
+    refs.sort_by(|a, b| a.name.cmp(&b.name).then(a.refname.cmp(&b.refname)));
+    Ok(refs)
+}
+
+/// Move an sv-managed ref to another full ref name, repointing every worktree
+/// whose HEAD is attached to it.
+pub fn move_sv_ref(repo: &Repository, from: &str, to: &str) -> Result<()> {
+    let target = repo.find_reference(from)?.target().ok_or_else(|| {
+        Error::OperationFailed(format!("{from} is a symbolic ref and cannot be moved"))
+    })?;
+    if repo.find_reference(to).is_ok() {
+        return Err(Error::OperationFailed(format!("{to} already exists")));
+    }
+    let message = format!("sv refs migrate: {from} -> {to}");
+    repo.reference(to, target, false, &message)?;
+
+    let mut heads = vec![Repository::open(crate::git::common_dir(repo))?];
+    for name in repo.worktrees()?.iter().flatten() {
+        if let Ok(worktree) = repo.find_worktree(name) {
+            if let Ok(worktree_repo) = Repository::open_from_worktree(&worktree) {
+                heads.push(worktree_repo);
+            }
+        }
+    }
+    for head_repo in &heads {
+        let attached = head_repo
+            .find_reference("HEAD")
+            .ok()
+            .and_then(|head| head.symbolic_target().map(|name| name == from))
+            .unwrap_or(false);
+        if attached {
+            head_repo.reference_symbolic("HEAD", to, true, &message)?;
+        }
+    }
+
+    repo.find_reference(from)?.delete()?;
+    Ok(())
+}
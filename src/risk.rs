@@ -3,14 +3,17 @@
 //! Computes touched files per workspace (vs a base ref) and summarizes overlaps.
 //! Also provides virtual merge simulation for conflict prediction.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use git2::{DiffDelta, DiffOptions, Repository};
-use serde::Serialize;
+use git2::Repository;
+use serde::{Deserialize, Serialize};
 
+use crate::config::RiskConfig;
 use crate::error::{Error, Result};
-use crate::lease::Lease;
+use crate::git;
+use crate::lease::{pathspec_matches_path, Lease};
 use crate::merge::{self, MergeConflictKind};
 use crate::storage::Storage;
 
@@ -19,7 +22,25 @@ use crate::storage::Storage;
 pub struct WorkspaceTouched {
     pub name: String,
     pub branch: String,
+    /// Touched paths; a rename contributes both its old and new path
     pub files: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub renames: Vec<RenamedPath>,
+}
+
+/// A file renamed within a workspace's changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RenamedPath {
+    pub from: String,
+    pub to: String,
+}
+
+/// A rename by one of an overlap's workspaces that involves its path.
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlapRename {
+    pub workspace: String,
+    pub from: String,
+    pub to: String,
 }
 
 /// Overlap summary for a specific path.
@@ -28,6 +49,12 @@ pub struct Overlap {
     pub path: String,
     pub workspaces: Vec<String>,
     pub severity: RiskSeverity,
+    /// Set when the overlap is already coordinated through a lease.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coordination: Option<String>,
+    /// Set when the overlap is an edit-vs-rename collision.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub renames: Vec<OverlapRename>,
     pub suggestions: Vec<Suggestion>,
 }
 
@@ -35,12 +62,17 @@ pub struct Overlap {
 #[derive(Debug, Clone, Serialize)]
 pub struct RiskReport {
     pub base_ref: String,
+    /// Set when touched files were scoped to `since..branch`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since_ref: Option<String>,
     pub workspaces: Vec<WorkspaceTouched>,
     pub overlaps: Vec<Overlap>,
+    /// Overlaps dropped because their path matched `risk.ignore`
+    pub suppressed_overlaps: usize,
 }
 
-/// Severity rating for an overlap.
-#[derive(Debug, Clone, Copy, Serialize)]
+/// Severity rating for an overlap, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum RiskSeverity {
     Low,
@@ -49,6 +81,77 @@ pub enum RiskSeverity {
     Critical,
 }
 
+impl std::fmt::Display for RiskSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RiskSeverity::Low => write!(f, "low"),
+            RiskSeverity::Medium => write!(f, "medium"),
+            RiskSeverity::High => write!(f, "high"),
+            RiskSeverity::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+impl std::str::FromStr for RiskSeverity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(RiskSeverity::Low),
+            "medium" => Ok(RiskSeverity::Medium),
+            "high" => Ok(RiskSeverity::High),
+            "critical" => Ok(RiskSeverity::Critical),
+            _ => Err(Error::InvalidArgument(format!(
+                "invalid severity '{s}' (expected low|medium|high|critical)"
+            ))),
+        }
+    }
+}
+
+/// Score cutoffs mapping an overlap to a [`RiskSeverity`] (`[risk.thresholds]`).
+///
+/// An overlap scores the number of overlapping workspaces (capped at
+/// `max_overlap`) plus the strength and intent weights of the matching
+/// leases. Scores below `medium` are low.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskThresholds {
+    /// Overlapping workspaces counted toward the score
+    pub max_overlap: u32,
+    /// Minimum score for medium severity
+    pub medium: u32,
+    /// Minimum score for high severity
+    pub high: u32,
+    /// Minimum score for critical severity
+    pub critical: u32,
+}
+
+impl Default for RiskThresholds {
+    fn default() -> Self {
+        Self {
+            max_overlap: 4,
+            medium: 5,
+            high: 8,
+            critical: 11,
+        }
+    }
+}
+
+impl RiskThresholds {
+    /// Severity for an overlap score.
+    pub fn severity(&self, score: u32) -> RiskSeverity {
+        if score >= self.critical {
+            RiskSeverity::Critical
+        } else if score >= self.high {
+            RiskSeverity::High
+        } else if score >= self.medium {
+            RiskSeverity::Medium
+        } else {
+            RiskSeverity::Low
+        }
+    }
+}
+
 /// Suggested follow-up action for an overlap.
 #[derive(Debug, Clone, Serialize)]
 pub struct Suggestion {
@@ -63,6 +166,9 @@ pub struct Suggestion {
 pub struct SimulationReport {
     pub base_ref: String,
     pub workspace_pairs: Vec<WorkspacePairConflict>,
+    /// Set when the run was interrupted; `workspace_pairs` is then partial
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub cancelled: bool,
 }
 
 /// Conflict summary for a pair of workspaces.
@@ -83,27 +189,76 @@ pub struct SimulatedConflict {
 }
 
 /// Compute a risk report for all registered workspaces.
-pub fn compute_risk(repo: &Repository, base_ref: &str) -> Result<RiskReport> {
+///
+/// With `since`, each workspace's touched files are those changed in
+/// `since..branch` (diffed from their merge base) rather than the full
+/// divergence from `base_ref`. Overlaps on paths matching `risk.ignore`
+/// are left out and only counted.
+pub fn compute_risk(
+    repo: &Repository,
+    base_ref: &str,
+    since: Option<&str>,
+    config: &RiskConfig,
+) -> Result<RiskReport> {
+    compute_risk_with_progress(repo, base_ref, since, config, &mut |_, _, _| {})
+}
+
+/// [`compute_risk`], calling `progress(n, total, workspace)` before the
+/// n-th (1-based) workspace is diffed.
+pub fn compute_risk_with_progress(
+    repo: &Repository,
+    base_ref: &str,
+    since: Option<&str>,
+    config: &RiskConfig,
+    progress: &mut dyn FnMut(usize, usize, &str),
+) -> Result<RiskReport> {
+    let since_commit = since
+        .map(|rev| {
+            repo.revparse_single(rev)
+                .and_then(|object| object.peel_to_commit())
+                .map_err(|err| {
+                    Error::InvalidArgument(format!("cannot resolve --since ref '{rev}': {err}"))
+                })
+        })
+        .transpose()?;
     let storage = load_storage(repo)?;
     let registry = storage.read_workspaces()?;
-    let leases: Vec<Lease> = storage.read_jsonl(&storage.leases_file())?;
+    let leases: Vec<Lease> = storage.read_leases()?;
     let mut workspace_reports = Vec::new();
 
-    for entry in registry.workspaces {
-        let files = touched_files(repo, base_ref, &entry.branch)?;
+    let total = registry.workspaces.len();
+    for (idx, entry) in registry.workspaces.into_iter().enumerate() {
+        progress(idx + 1, total, &entry.name);
+        let (files, renames) = match &since_commit {
+            Some(since) => {
+                let branch = repo.revparse_single(&entry.branch)?.peel_to_commit()?;
+                let from = match repo.merge_base(since.id(), branch.id()) {
+                    Ok(oid) => repo.find_commit(oid)?,
+                    Err(_) => since.clone(),
+                };
+                diff_files(repo, &from, &branch)?
+            }
+            None => touched_files(repo, base_ref, &entry.branch)?,
+        };
         workspace_reports.push(WorkspaceTouched {
             name: entry.name,
             branch: entry.branch,
             files,
+            renames,
         });
     }
 
-    let overlaps = compute_overlaps(&workspace_reports, &leases);
+    let (overlaps, suppressed): (Vec<Overlap>, Vec<Overlap>) =
+        compute_overlaps(&workspace_reports, &leases, &config.thresholds)
+            .into_iter()
+            .partition(|overlap| !is_ignored(&config.ignore, &overlap.path));
 
     Ok(RiskReport {
         base_ref: base_ref.to_string(),
+        since_ref: since.map(str::to_string),
         workspaces: workspace_reports,
         overlaps,
+        suppressed_overlaps: suppressed.len(),
     })
 }
 
@@ -112,6 +267,21 @@ pub fn compute_risk(repo: &Repository, base_ref: &str) -> Result<RiskReport> {
 /// For each pair of registered workspaces, performs a virtual merge
 /// simulation to detect actual conflicts without modifying the working tree.
 pub fn simulate_conflicts(repo: &Repository, base_ref: &str) -> Result<SimulationReport> {
+    simulate_conflicts_with_progress(repo, base_ref, &mut |_, _, _| {}, &AtomicBool::new(false))
+}
+
+/// [`simulate_conflicts`], calling `progress(n, total, "a vs b")` before the
+/// n-th (1-based) workspace pair is merged.
+///
+/// `cancel` is checked between pairs; once set, the pairs simulated so far
+/// are returned with `cancelled: true`. Merges happen in memory, so there is
+/// no temporary state to clean up.
+pub fn simulate_conflicts_with_progress(
+    repo: &Repository,
+    base_ref: &str,
+    progress: &mut dyn FnMut(usize, usize, &str),
+    cancel: &AtomicBool,
+) -> Result<SimulationReport> {
     let storage = load_storage(repo)?;
     let registry = storage.read_workspaces()?;
     let mut workspace_pairs = Vec::new();
@@ -124,10 +294,19 @@ pub fn simulate_conflicts(repo: &Repository, base_ref: &str) -> Result<Simulatio
         .collect();
 
     // For each unique pair of workspaces, simulate merge
-    for i in 0..workspaces.len() {
+    let total_pairs = workspaces.len() * workspaces.len().saturating_sub(1) / 2;
+    let mut pair_index = 0;
+    let mut cancelled = false;
+    'pairs: for i in 0..workspaces.len() {
         for j in (i + 1)..workspaces.len() {
+            if cancel.load(Ordering::SeqCst) {
+                cancelled = true;
+                break 'pairs;
+            }
             let (name_a, branch_a) = &workspaces[i];
             let (name_b, branch_b) = &workspaces[j];
+            pair_index += 1;
+            progress(pair_index, total_pairs, &format!("{name_a} vs {name_b}"));
 
             // Try to simulate merge between the two branches
             match merge::simulate_merge(repo, branch_a, branch_b, Some(base_ref)) {
@@ -165,10 +344,21 @@ pub fn simulate_conflicts(repo: &Repository, base_ref: &str) -> Result<Simulatio
     Ok(SimulationReport {
         base_ref: base_ref.to_string(),
         workspace_pairs,
+        cancelled,
     })
 }
 
-fn compute_overlaps(workspaces: &[WorkspaceTouched], leases: &[Lease]) -> Vec<Overlap> {
+fn is_ignored(patterns: &[String], path: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| pathspec_matches_path(pattern, path))
+}
+
+fn compute_overlaps(
+    workspaces: &[WorkspaceTouched],
+    leases: &[Lease],
+    thresholds: &RiskThresholds,
+) -> Vec<Overlap> {
     let mut map: HashMap<String, Vec<String>> = HashMap::new();
 
     for workspace in workspaces {
@@ -186,15 +376,39 @@ fn compute_overlaps(workspaces: &[WorkspaceTouched], leases: &[Lease]) -> Vec<Ov
             if unique.len() < 2 {
                 return None;
             }
+            let renames: Vec<OverlapRename> = workspaces
+                .iter()
+                .filter(|workspace| unique.contains(&workspace.name))
+                .flat_map(|workspace| {
+                    workspace
+                        .renames
+                        .iter()
+                        .filter(|rename| rename.from == path || rename.to == path)
+                        .map(|rename| OverlapRename {
+                            workspace: workspace.name.clone(),
+                            from: rename.from.clone(),
+                            to: rename.to.clone(),
+                        })
+                })
+                .collect();
             let mut workspaces: Vec<String> = unique.drain().collect();
             workspaces.sort();
             let matching = matching_leases(leases, &path);
-            let severity = severity_for(workspaces.len(), &matching);
+            let coordinating = coordinating_lease(&matching);
+            // A single actor's exclusive lease means the overlap is already
+            // being coordinated, so lease strength no longer adds risk.
+            let severity = match coordinating {
+                Some(_) => severity_for(workspaces.len(), &[], thresholds),
+                None => severity_for(workspaces.len(), &matching, thresholds),
+            };
             let suggestions = suggestions_for(&path, &workspaces, severity);
             Some(Overlap {
                 path,
                 workspaces,
                 severity,
+                coordination: coordinating
+                    .map(|lease| format!("coordinated via lease {}", lease.id)),
+                renames,
                 suggestions,
             })
         })
@@ -204,8 +418,12 @@ fn compute_overlaps(workspaces: &[WorkspaceTouched], leases: &[Lease]) -> Vec<Ov
     overlaps
 }
 
-fn severity_for(overlap_count: usize, leases: &[&Lease]) -> RiskSeverity {
-    let overlap_score = overlap_count.min(4) as i32;
+fn severity_for(
+    overlap_count: usize,
+    leases: &[&Lease],
+    thresholds: &RiskThresholds,
+) -> RiskSeverity {
+    let overlap_score = (overlap_count as u32).min(thresholds.max_overlap);
     let strength_score = leases
         .iter()
         .map(|lease| strength_weight(lease))
@@ -213,17 +431,11 @@ fn severity_for(overlap_count: usize, leases: &[&Lease]) -> RiskSeverity {
         .unwrap_or(0);
     let intent_score = leases
         .iter()
-        .map(|lease| lease.intent.conflict_risk() as i32)
+        .map(|lease| u32::from(lease.intent.conflict_risk()))
         .max()
         .unwrap_or(0);
 
-    let score = overlap_score + strength_score + intent_score;
-    match score {
-        0..=4 => RiskSeverity::Low,
-        5..=7 => RiskSeverity::Medium,
-        8..=10 => RiskSeverity::High,
-        _ => RiskSeverity::Critical,
-    }
+    thresholds.severity(overlap_score + strength_score + intent_score)
 }
 
 fn suggestions_for(path: &str, workspaces: &[String], severity: RiskSeverity) -> Vec<Suggestion> {
@@ -318,32 +530,39 @@ fn suggestions_for(path: &str, workspaces: &[String], severity: RiskSeverity) ->
     ordered
 }
 
-fn touched_files(repo: &Repository, base_ref: &str, branch_ref: &str) -> Result<Vec<String>> {
+fn touched_files(
+    repo: &Repository,
+    base_ref: &str,
+    branch_ref: &str,
+) -> Result<(Vec<String>, Vec<RenamedPath>)> {
     let base_commit = repo.revparse_single(base_ref)?.peel_to_commit()?;
     let branch_commit = repo.revparse_single(branch_ref)?.peel_to_commit()?;
+    diff_files(repo, &base_commit, &branch_commit)
+}
 
-    let base_tree = base_commit.tree()?;
-    let branch_tree = branch_commit.tree()?;
-
-    let mut opts = DiffOptions::new();
-    let diff = repo.diff_tree_to_tree(Some(&base_tree), Some(&branch_tree), Some(&mut opts))?;
+/// Paths that differ between two commits' trees, sorted, plus the renames
+/// among them
+fn diff_files(
+    repo: &Repository,
+    base_commit: &git2::Commit<'_>,
+    branch_commit: &git2::Commit<'_>,
+) -> Result<(Vec<String>, Vec<RenamedPath>)> {
+    let changes = git::diff_trees(repo, &base_commit.tree()?, &branch_commit.tree()?)?;
 
-    let mut files = HashSet::new();
-    diff.foreach(
-        &mut |delta: DiffDelta<'_>, _| {
-            if let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) {
-                files.insert(path.to_string_lossy().to_string());
-            }
-            true
-        },
-        None,
-        None,
-        None,
-    )?;
-
-    let mut list: Vec<String> = files.into_iter().collect();
-    list.sort();
-    Ok(list)
+    let renames = changes
+        .iter()
+        .filter_map(|change| {
+            change.old_path.as_ref().map(|old_path| RenamedPath {
+                from: old_path.to_string_lossy().to_string(),
+                to: change.path.to_string_lossy().to_string(),
+            })
+        })
+        .collect();
+    let files: BTreeSet<String> = git::changed_paths(&changes)
+        .into_iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    Ok((files.into_iter().collect(), renames))
 }
 
 fn load_storage(repo: &Repository) -> Result<Storage> {
@@ -381,7 +600,22 @@ fn matching_leases<'a>(leases: &'a [Lease], path: &str) -> Vec<&'a Lease> {
         .collect()
 }
 
-fn strength_weight(lease: &Lease) -> i32 {
+/// The exclusive lease covering a path when every lease that claims it
+/// (ignoring observe leases) belongs to that lease's actor.
+fn coordinating_lease<'a>(leases: &[&'a Lease]) -> Option<&'a Lease> {
+    let exclusive = leases
+        .iter()
+        .copied()
+        .find(|lease| lease.strength == crate::lease::LeaseStrength::Exclusive)?;
+    let actor = exclusive.actor.as_deref()?;
+    leases
+        .iter()
+        .filter(|lease| lease.strength != crate::lease::LeaseStrength::Observe)
+        .all(|lease| lease.actor.as_deref() == Some(actor))
+        .then_some(exclusive)
+}
+
+fn strength_weight(lease: &Lease) -> u32 {
     match lease.strength {
         crate::lease::LeaseStrength::Observe => 0,
         crate::lease::LeaseStrength::Cooperative => 1,
@@ -398,7 +632,7 @@ mod tests {
     #[test]
     fn severity_scores_account_for_strength_and_intent() {
         let overlap_count = 2;
-        let low = severity_for(overlap_count, &[]);
+        let low = severity_for(overlap_count, &[], &RiskThresholds::default());
         assert!(matches!(low, RiskSeverity::Low));
 
         // Strong lease with docs intent: overlap(2) + strength(3) + intent(1) = 6 => Medium
@@ -408,7 +642,7 @@ mod tests {
             .note("x")
             .build()
             .unwrap();
-        let medium = severity_for(overlap_count, &[&strong]);
+        let medium = severity_for(overlap_count, &[&strong], &RiskThresholds::default());
         assert!(matches!(medium, RiskSeverity::Medium));
 
         // Exclusive lease with rename intent: overlap(2) + strength(4) + intent(5) = 11 => Critical
@@ -418,7 +652,7 @@ mod tests {
             .note("x")
             .build()
             .unwrap();
-        let critical = severity_for(overlap_count, &[&exclusive]);
+        let critical = severity_for(overlap_count, &[&exclusive], &RiskThresholds::default());
         assert!(matches!(critical, RiskSeverity::Critical));
 
         // Strong lease with refactor intent: overlap(2) + strength(3) + intent(4) = 9 => High
@@ -428,7 +662,11 @@ mod tests {
             .note("x")
             .build()
             .unwrap();
-        let high = severity_for(overlap_count, &[&strong_refactor]);
+        let high = severity_for(
+            overlap_count,
+            &[&strong_refactor],
+            &RiskThresholds::default(),
+        );
         assert!(matches!(high, RiskSeverity::High));
     }
 
@@ -440,12 +678,121 @@ mod tests {
             .build()
             .unwrap();
 
-        let low = severity_for(2, &[&cooperative]);
-        let medium = severity_for(3, &[&cooperative]);
+        let low = severity_for(2, &[&cooperative], &RiskThresholds::default());
+        let medium = severity_for(3, &[&cooperative], &RiskThresholds::default());
         assert!(matches!(low, RiskSeverity::Low | RiskSeverity::Medium));
         assert!(matches!(medium, RiskSeverity::Medium | RiskSeverity::High));
     }
 
+    #[test]
+    fn severity_uses_configured_thresholds() {
+        let strong = LeaseBuilder::new("src/lib.rs")
+            .strength(LeaseStrength::Strong)
+            .intent(LeaseIntent::Docs)
+            .note("x")
+            .build()
+            .unwrap();
+        // overlap(2) + strength(3) + intent(1) = 6
+        let strict = RiskThresholds {
+            max_overlap: 4,
+            medium: 2,
+            high: 4,
+            critical: 6,
+        };
+        assert!(matches!(
+            severity_for(2, &[&strong], &strict),
+            RiskSeverity::Critical
+        ));
+        assert!(matches!(
+            severity_for(2, &[], &strict),
+            RiskSeverity::Medium
+        ));
+
+        // Capping counted workspaces keeps wide overlaps from escalating.
+        let capped = RiskThresholds {
+            max_overlap: 1,
+            ..RiskThresholds::default()
+        };
+        assert!(matches!(
+            severity_for(6, &[&strong], &capped),
+            RiskSeverity::Medium
+        ));
+        assert!(matches!(
+            severity_for(6, &[&strong], &RiskThresholds::default()),
+            RiskSeverity::High
+        ));
+    }
+
+    fn touched(name: &str, files: &[&str]) -> WorkspaceTouched {
+        WorkspaceTouched {
+            name: name.to_string(),
+            branch: name.to_string(),
+            files: files.iter().map(|file| file.to_string()).collect(),
+            renames: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn overlap_covered_by_single_actor_lease_is_downgraded() {
+        let workspaces = [
+            touched("ws-a", &["src/lib.rs"]),
+            touched("ws-b", &["src/lib.rs"]),
+        ];
+        let exclusive = LeaseBuilder::new("src/**")
+            .strength(LeaseStrength::Exclusive)
+            .intent(LeaseIntent::Refactor)
+            .actor("alice")
+            .note("x")
+            .build()
+            .unwrap();
+        let observer = LeaseBuilder::new("src/lib.rs")
+            .strength(LeaseStrength::Observe)
+            .actor("bob")
+            .build()
+            .unwrap();
+
+        let overlaps = compute_overlaps(
+            &workspaces,
+            &[exclusive.clone(), observer],
+            &RiskThresholds::default(),
+        );
+        assert_eq!(overlaps.len(), 1);
+        assert!(matches!(overlaps[0].severity, RiskSeverity::Low));
+        assert_eq!(
+            overlaps[0].coordination.as_deref(),
+            Some(format!("coordinated via lease {}", exclusive.id).as_str())
+        );
+    }
+
+    #[test]
+    fn overlap_without_covering_lease_stays_high() {
+        let workspaces = [
+            touched("ws-a", &["src/lib.rs"]),
+            touched("ws-b", &["src/lib.rs"]),
+        ];
+        let exclusive = LeaseBuilder::new("src/lib.rs")
+            .strength(LeaseStrength::Exclusive)
+            .intent(LeaseIntent::Refactor)
+            .actor("alice")
+            .note("x")
+            .build()
+            .unwrap();
+        let other = LeaseBuilder::new("src/lib.rs")
+            .strength(LeaseStrength::Cooperative)
+            .intent(LeaseIntent::Feature)
+            .actor("bob")
+            .build()
+            .unwrap();
+
+        let overlaps =
+            compute_overlaps(&workspaces, &[exclusive, other], &RiskThresholds::default());
+        assert!(matches!(overlaps[0].severity, RiskSeverity::High));
+        assert!(overlaps[0].coordination.is_none());
+
+        let unleased = compute_overlaps(&workspaces, &[], &RiskThresholds::default());
+        assert!(unleased[0].coordination.is_none());
+    }
+
     #[test]
     fn suggestions_include_expected_actions() {
         let suggestions = suggestions_for(
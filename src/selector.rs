@@ -4,6 +4,7 @@
 //! - ws(active)
 //! - ws(active) & ahead("main")
 //! - ws(name~"agent") ~ ws(blocked)
+//! - lease(held("me")) & expiring("1h")
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SelectorExpr {
@@ -13,6 +14,23 @@ pub enum SelectorExpr {
     Difference(Box<SelectorExpr>, Box<SelectorExpr>),
 }
 
+impl SelectorExpr {
+    /// Every predicate in the expression, in source order.
+    pub fn predicates(&self) -> Vec<&Predicate> {
+        match self {
+            SelectorExpr::Atom(SelectorAtom::Entity(entity)) => entity.predicate.iter().collect(),
+            SelectorExpr::Atom(SelectorAtom::Predicate(predicate)) => vec![predicate],
+            SelectorExpr::Union(left, right)
+            | SelectorExpr::Intersection(left, right)
+            | SelectorExpr::Difference(left, right) => {
+                let mut out = left.predicates();
+                out.extend(right.predicates());
+                out
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SelectorAtom {
     Entity(EntitySelector),
@@ -41,6 +59,10 @@ pub enum Predicate {
     Ahead(String),
     Touching(String),
     Overlaps(String),
+    /// Leases held by an actor (`"me"` is the current actor)
+    Held(String),
+    /// Active leases expiring within a duration such as `"1h"`
+    Expiring(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -527,7 +549,9 @@ impl<'a> Parser<'a> {
             "ahead" => Ok(Predicate::Ahead(self.parse_call_arg()?)),
             "touching" => Ok(Predicate::Touching(self.parse_call_arg()?)),
             "overlaps" => Ok(Predicate::Overlaps(self.parse_call_arg()?)),
-            _ => Err(self.error_here(&format!("Unknown predicate '{ident}'"))),
+            "held" => Ok(Predicate::Held(self.parse_call_arg()?)),
+            "expiring" => Ok(Predicate::Expiring(self.parse_call_arg()?)),
+            _ => Err(self.error_at_previous(&format!("Unknown predicate '{ident}'"))),
         }
     }
 
@@ -569,6 +593,20 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Error pointing at the token just consumed (e.g. an unknown identifier).
+    fn error_at_previous(&self, message: &str) -> SelectorError {
+        let pos = self
+            .index
+            .checked_sub(1)
+            .and_then(|idx| self.tokens.get(idx))
+            .map(|t| t.pos)
+            .unwrap_or(0);
+        SelectorError {
+            message: message.to_string(),
+            position: pos,
+        }
+    }
+
     fn next_token(&mut self) -> Token {
         let token = self.tokens[self.index].clone();
         self.index = (self.index + 1).min(self.tokens.len() - 1);
@@ -273,6 +273,28 @@ impl Storage {
         Ok(())
     }
 
+    /// Append several records to a JSONL file with a single write and sync
+    pub fn append_jsonl_all<T: Serialize>(&self, path: &Path, records: &[T]) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut buffer = Vec::new();
+        for record in records {
+            serde_json::to_writer(&mut buffer, record)?;
+            buffer.push(b'\n');
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+
+        file.write_all(&buffer)?;
+        file.sync_all()?;
+
+        Ok(())
+    }
+
     /// Read all records from a JSONL file
     pub fn read_jsonl<T: DeserializeOwned>(&self, path: &Path) -> Result<Vec<T>> {
         if !path.exists() {
@@ -357,7 +379,7 @@ impl Storage {
                 .ok_or_else(|| Error::WorkspaceNotFound(name.to_string()))?;
             mutator(entry)?;
             entry.ensure_id();
-            if !entry.path.exists() {
+            if !entry.archived && !entry.path.exists() {
                 return Err(Error::InvalidArgument(format!(
                     "workspace path does not exist: {}",
                     entry.path.display()
@@ -427,36 +449,21 @@ impl Storage {
     // Lease operations
     // =========================================================================
 
+    /// Read all leases from the leases file, skipping malformed lines
+    /// (see `lease::load_lease_log`)
+    pub fn read_leases(&self) -> Result<Vec<crate::lease::Lease>> {
+        crate::lease::load_lease_log(&self.leases_file())
+    }
+
     /// Load all leases from the leases file into a LeaseStore
     pub fn load_leases(&self) -> Result<crate::lease::LeaseStore> {
-        let leases: Vec<crate::lease::Lease> = self.read_jsonl(&self.leases_file())?;
-        Ok(crate::lease::LeaseStore::from_vec(leases))
+        Ok(crate::lease::LeaseStore::from_vec(self.read_leases()?))
     }
 
-    /// Save all leases to the leases file (overwrites)
+    /// Save all leases to the leases file (overwrites, keeping malformed
+    /// lines; see `lease::write_lease_log`)
     pub fn save_leases(&self, store: &crate::lease::LeaseStore) -> Result<()> {
-        let path = self.leases_file();
-
-        // Ensure parent directory exists
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        // Write to temp file first
-        let temp_path = path.with_extension("tmp");
-        let mut file = File::create(&temp_path)?;
-
-        for lease in store.all() {
-            let json = serde_json::to_string(lease)?;
-            writeln!(file, "{}", json)?;
-        }
-
-        file.sync_all()?;
-
-        // Atomic rename
-        fs::rename(&temp_path, &path)?;
-
-        Ok(())
+        crate::lease::write_lease_log(&self.leases_file(), store.all())
     }
 
     // =========================================================================
@@ -534,9 +541,30 @@ impl Storage {
         Ok(Some(state))
     }
 
-    /// Write hoist state for a destination ref.
+    /// Read every recorded hoist state, across destinations and named
+    /// integration branches.
+    pub fn list_hoist_states(&self) -> Result<Vec<HoistState>> {
+        let dir = self.hoist_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut states = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path().join("state.json");
+            if !path.exists() {
+                continue;
+            }
+            let lock_path = path.with_extension("lock");
+            let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
+            states.push(self.read_json(&path)?);
+        }
+        Ok(states)
+    }
+
+    /// Write hoist state under its [`HoistState::storage_key`].
     pub fn write_hoist_state(&self, state: &HoistState) -> Result<()> {
-        let path = self.hoist_state_file(&state.dest_ref);
+        let path = self.hoist_state_file(state.storage_key());
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
@@ -626,6 +654,9 @@ pub struct WorkspaceEntry {
     pub created_at: String,
     /// Timestamp of last activity
     pub last_active: Option<String>,
+    /// Worktree removed by `sv ws archive`; the branch and record are kept
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
 }
 
 impl WorkspacesRegistry {
@@ -670,14 +701,16 @@ impl WorkspacesRegistry {
         }
     }
 
-    /// Remove workspaces whose paths no longer exist
+    /// Remove workspaces whose paths no longer exist (archived workspaces
+    /// have no worktree by design and are kept)
     pub fn cleanup_stale(&mut self) -> usize {
         let before = self.workspaces.len();
-        self.workspaces.retain(|entry| entry.path.exists());
+        self.workspaces
+            .retain(|entry| entry.archived || entry.path.exists());
         before - self.workspaces.len()
     }
 
-    /// Validate registry entries (unique names, existing paths)
+    /// Validate registry entries (unique names, existing paths unless archived)
     pub fn validate(&self) -> Result<()> {
         let mut names = HashSet::new();
         for entry in &self.workspaces {
@@ -687,7 +720,7 @@ impl WorkspacesRegistry {
                     entry.name
                 )));
             }
-            if !entry.path.exists() {
+            if !entry.archived && !entry.path.exists() {
                 return Err(Error::InvalidArgument(format!(
                     "workspace path does not exist: {}",
                     entry.path.display()
@@ -716,6 +749,26 @@ pub struct HoistState {
     pub updated_at: DateTime<Utc>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub commits: Vec<HoistCommit>,
+    /// Run with `--squash`: each workspace's commits were replayed as one
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub squash: bool,
+}
+
+impl HoistState {
+    /// Key the state is stored under: the destination ref for the default
+    /// `sv/hoist/<dest>` branch, or the integration branch itself for a hoist
+    /// run with `--name`, so named hoists onto the same destination do not
+    /// overwrite each other's state.
+    pub fn storage_key(&self) -> &str {
+        let dest = self
+            .dest_ref
+            .strip_prefix("refs/heads/")
+            .unwrap_or(&self.dest_ref);
+        match self.integration_ref.strip_prefix("sv/hoist/") {
+            Some(name) if name == dest => &self.dest_ref,
+            _ => &self.integration_ref,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, serde::Deserialize)]
@@ -783,6 +836,7 @@ impl WorkspaceEntry {
             actor,
             created_at,
             last_active,
+            archived: false,
         }
     }
 
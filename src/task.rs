@@ -11,6 +11,7 @@ use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
+use uuid::Uuid;
 
 use crate::config::TasksConfig;
 use crate::error::{Error, Result};
@@ -32,6 +33,10 @@ const ULID_TIME_LEN: usize = 10;
 const ULID_RANDOM_LEN: usize = 16;
 const ULID_CHARSET: &str = "0123456789abcdefghjkmnpqrstvwxyz";
 const ULID_CHARSET_LEN: u128 = 32;
+/// Random hex digits in a v4 UUID (the version and variant digits excluded)
+const UUID_RANDOM_LEN: usize = 30;
+const UUID_CHARSET: &str = "0123456789abcdef";
+const UUID_CHARSET_LEN: u128 = 16;
 const DEFAULT_TASK_PRIORITY: &str = "P2";
 const TASK_PRIORITIES: [&str; 5] = ["P0", "P1", "P2", "P3", "P4"];
 
@@ -95,6 +100,8 @@ pub struct TaskEvent {
     pub relation_description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub epic_auto_close: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forge_ref: Option<String>,
 }
 
 impl TaskEvent {
@@ -116,6 +123,7 @@ impl TaskEvent {
             related_task_id: None,
             relation_description: None,
             epic_auto_close: None,
+            forge_ref: None,
         }
     }
 }
@@ -156,6 +164,9 @@ pub struct TaskRecord {
     pub comments_count: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_comment_at: Option<DateTime<Utc>>,
+    /// Forge issue reference recorded when the task was created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forge_ref: Option<String>,
 }
 
 pub fn sort_tasks(tasks: &mut [TaskRecord], config: &TasksConfig, blocked_ids: &HashSet<String>) {
@@ -311,6 +322,7 @@ pub struct TaskSyncReport {
     pub total_tasks: usize,
     pub compacted: bool,
     pub removed_events: usize,
+    pub imported_events: usize,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
@@ -422,9 +434,23 @@ impl TaskStore {
         self.ensure_dirs()?;
         self.append_event_to_log(&self.tracked_log_path(), &event)?;
         self.append_event_to_log(&self.shared_log_path(), &event)?;
-        self.apply_event_to_snapshot(&self.tracked_snapshot_path(), &event)?;
-        self.apply_event_to_snapshot(&self.shared_snapshot_path(), &event)?;
-        Ok(())
+        self.update_snapshots(std::slice::from_ref(&event))
+    }
+
+    /// Append `events` in order to both logs, taking each lock once and
+    /// updating the snapshots a single time. Equivalent to calling
+    /// `append_event` for each event.
+    pub fn append_events(&self, events: &[TaskEvent]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.ensure_dirs()?;
+        for path in [self.tracked_log_path(), self.shared_log_path()] {
+            let lock_path = path.with_extension("lock");
+            let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
+            self.storage.append_jsonl_all(&path, events)?;
+        }
+        self.update_snapshots(events)
     }
 
     pub fn start_task(&self, request: StartTaskRequest) -> Result<StartTaskOutcome> {
@@ -436,8 +462,6 @@ impl TaskStore {
         let in_progress = self.config.in_progress_status.clone();
         let tracked_log_path = self.tracked_log_path();
         let shared_log_path = self.shared_log_path();
-        let tracked_snapshot_path = self.tracked_snapshot_path();
-        let shared_snapshot_path = self.shared_snapshot_path();
 
         let lock_path = tracked_log_path.with_extension("lock");
         let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
@@ -476,8 +500,7 @@ impl TaskStore {
 
         self.storage.append_jsonl(&tracked_log_path, &event)?;
         self.storage.append_jsonl(&shared_log_path, &event)?;
-        self.apply_event_to_snapshot(&tracked_snapshot_path, &event)?;
-        self.apply_event_to_snapshot(&shared_snapshot_path, &event)?;
+        self.update_snapshots(std::slice::from_ref(&event))?;
 
         let previous_owner = if task.status == self.config.in_progress_status {
             current_owner
@@ -491,17 +514,53 @@ impl TaskStore {
         })
     }
 
-    pub fn list(&self, status: Option<&str>) -> Result<Vec<TaskRecord>> {
+    /// List tasks whose status is any of `statuses` (all tasks when empty).
+    pub fn list(&self, statuses: &[String]) -> Result<Vec<TaskRecord>> {
+        let statuses = self.status_filter(statuses)?;
         let snapshot = self.load_snapshot_prefer_shared()?;
         let mut tasks = snapshot.tasks;
-        if let Some(status) = status {
-            let status = status.trim();
-            self.validate_status(status)?;
-            tasks.retain(|task| task.status == status);
+        if !statuses.is_empty() {
+            tasks.retain(|task| statuses.contains(&task.status));
         }
         Ok(tasks)
     }
 
+    /// Count tasks whose status is any of `statuses` (all tasks when empty)
+    /// without building full task records. Only the status of each snapshot
+    /// entry is decoded.
+    pub fn count(&self, statuses: &[String]) -> Result<usize> {
+        let filter = self.status_filter(statuses)?;
+        let statuses = match self.load_snapshot_statuses()? {
+            Some(statuses) => statuses,
+            None => self
+                .load_snapshot_prefer_shared()?
+                .tasks
+                .into_iter()
+                .map(|task| task.status)
+                .collect(),
+        };
+        Ok(if filter.is_empty() {
+            statuses.len()
+        } else {
+            statuses
+                .iter()
+                .filter(|value| filter.contains(*value))
+                .count()
+        })
+    }
+
+    /// Trim and validate a status filter.
+    fn status_filter(&self, statuses: &[String]) -> Result<HashSet<String>> {
+        statuses
+            .iter()
+            .map(|status| {
+                let status = status.trim();
+                self.validate_status(status)?;
+                Ok(status.to_string())
+            })
+            .collect()
+    }
+
     pub fn list_with_ready(&self) -> Result<(Vec<TaskRecord>, HashSet<String>)> {
         let snapshot = self.load_snapshot_prefer_shared()?;
         let tasks = snapshot.tasks;
@@ -564,12 +623,40 @@ impl TaskStore {
         Some(candidate.to_string())
     }
 
-    fn select_task_suffix_len(min_len: usize, ulid_suffix_counts: &HashMap<usize, usize>) -> usize {
+    /// Take a `len`-digit suffix from the random digits of a v4 UUID (simple
+    /// form), unless it is already in use.
+    fn unique_task_suffix_from_uuid(
+        uuid: &str,
+        len: usize,
+        existing_suffixes: &HashSet<String>,
+    ) -> Option<String> {
+        // Skip the version (13th) and variant (17th) digits.
+        let random: String = uuid
+            .to_lowercase()
+            .char_indices()
+            .filter(|(idx, _)| *idx != 12 && *idx != 16)
+            .map(|(_, ch)| ch)
+            .collect();
+        if random.len() < UUID_RANDOM_LEN || len == 0 || len > UUID_RANDOM_LEN {
+            return None;
+        }
+        let candidate = &random[..len];
+        if existing_suffixes.contains(candidate) {
+            return None;
+        }
+        Some(candidate.to_string())
+    }
+
+    fn select_task_suffix_len(
+        scheme: TaskIdScheme,
+        min_len: usize,
+        suffix_counts: &HashMap<usize, usize>,
+    ) -> usize {
         let mut len = min_len;
         loop {
-            let used = ulid_suffix_counts.get(&len).copied().unwrap_or(0) as u128;
-            let space = ulid_space_for_len(len);
-            if used >= space && len < ULID_RANDOM_LEN {
+            let used = suffix_counts.get(&len).copied().unwrap_or(0) as u128;
+            let space = scheme.space_for_len(len);
+            if used >= space && len < scheme.max_len() {
                 len += 1;
                 continue;
             }
@@ -579,9 +666,10 @@ impl TaskStore {
 
     pub fn generate_task_id(&self) -> Result<String> {
         let prefix = self.config.id_prefix.trim();
+        let scheme = TaskIdScheme::from_config(&self.config.id_scheme);
         let snapshot = self.snapshot_readonly()?;
         let mut existing_suffixes = HashSet::new();
-        let mut ulid_suffix_counts: HashMap<usize, usize> = HashMap::new();
+        let mut suffix_counts: HashMap<usize, usize> = HashMap::new();
         for task in snapshot.tasks {
             let id_norm = normalize_id(&task.id);
             let suffix = suffix_from_id(&id_norm);
@@ -589,24 +677,69 @@ impl TaskStore {
                 continue;
             }
             existing_suffixes.insert(suffix.to_string());
-            if is_ulid_suffix(suffix) {
-                *ulid_suffix_counts.entry(suffix.len()).or_insert(0) += 1;
+            if scheme.is_suffix(suffix) {
+                *suffix_counts.entry(suffix.len()).or_insert(0) += 1;
             }
         }
 
         let min_len = self.config.id_min_len;
-        let target_len = Self::select_task_suffix_len(min_len, &ulid_suffix_counts);
+        let target_len = Self::select_task_suffix_len(scheme, min_len, &suffix_counts);
 
         loop {
-            let base = Ulid::new().to_string();
-            if let Some(suffix) =
-                Self::unique_task_suffix_from_base(&base, target_len, &existing_suffixes)
-            {
+            let suffix = match scheme {
+                TaskIdScheme::Ulid => Self::unique_task_suffix_from_base(
+                    &Ulid::new().to_string(),
+                    target_len,
+                    &existing_suffixes,
+                ),
+                TaskIdScheme::Uuid => Self::unique_task_suffix_from_uuid(
+                    &Uuid::new_v4().simple().to_string(),
+                    target_len,
+                    &existing_suffixes,
+                ),
+            };
+            if let Some(suffix) = suffix {
                 return Ok(format!("{}-{}", prefix, suffix));
             }
         }
     }
 
+    /// Validate a caller-supplied task ID for `sv task new --id`.
+    ///
+    /// A bare suffix gets the configured prefix; a value containing a
+    /// delimiter is taken as a full ID. Both halves must be alphanumeric, and
+    /// neither the ID nor its suffix may already be used by another task.
+    pub fn explicit_task_id(&self, input: &str) -> Result<String> {
+        let trimmed = input.trim();
+        let id = if TASK_ID_DELIMS.iter().any(|delim| trimmed.contains(delim)) {
+            trimmed.to_string()
+        } else {
+            format!("{}-{}", self.config.id_prefix.trim(), trimmed)
+        };
+        let id_norm = normalize_id(&id);
+        let suffix = suffix_from_id(&id_norm);
+        let prefix = id_norm[..id_norm.len() - suffix.len()].trim_end_matches(['-', '/']);
+        let valid =
+            |part: &str| !part.is_empty() && part.chars().all(|ch| ch.is_ascii_alphanumeric());
+        if !valid(prefix) || !valid(suffix) {
+            return Err(Error::InvalidArgument(format!(
+                "invalid task id '{trimmed}': expected <prefix>-<suffix> or <suffix>, alphanumeric"
+            )));
+        }
+
+        let snapshot = self.snapshot_readonly()?;
+        if let Some(existing) = snapshot.tasks.iter().find(|task| {
+            let existing_norm = normalize_id(&task.id);
+            existing_norm == id_norm || suffix_from_id(&existing_norm) == suffix
+        }) {
+            return Err(Error::InvalidArgument(format!(
+                "task id '{id}' collides with existing task {}",
+                existing.id
+            )));
+        }
+        Ok(id)
+    }
+
     pub fn resolve_task_id(&self, input: &str) -> Result<String> {
         let trimmed = input.trim();
         if trimmed.is_empty() {
@@ -737,16 +870,46 @@ impl TaskStore {
         })
     }
 
+    /// All events for a task, including relation events that reference it,
+    /// in log order.
+    pub fn events_for(&self, task_id: &str) -> Result<Vec<TaskEvent>> {
+        Ok(self
+            .load_merged_events()?
+            .into_iter()
+            .filter(|event| {
+                event.task_id == task_id || event.related_task_id.as_deref() == Some(task_id)
+            })
+            .collect())
+    }
+
     pub fn relations(&self, task_id: &str) -> Result<TaskRelations> {
         let events = self.load_merged_events()?;
         build_relations(task_id, &events)
     }
 
     pub fn sync(&self, policy: Option<CompactionPolicy>) -> Result<TaskSyncReport> {
+        self.sync_with(Vec::new(), policy)
+    }
+
+    /// Sync as `sync` does, additionally unioning `incoming` events (e.g. a
+    /// fetched remote log) by event_id.
+    pub fn sync_with(
+        &self,
+        incoming: Vec<TaskEvent>,
+        policy: Option<CompactionPolicy>,
+    ) -> Result<TaskSyncReport> {
         self.ensure_dirs()?;
         let tracked = self.load_events(&self.tracked_log_path())?;
         let shared = self.load_events(&self.shared_log_path())?;
-        let mut merged = merge_events(tracked, shared);
+        let local = merge_events(tracked, shared);
+        let known: HashSet<&str> = local.iter().map(|event| event.event_id.as_str()).collect();
+        let mut imported_ids = HashSet::new();
+        for event in &incoming {
+            if !known.contains(event.event_id.as_str()) {
+                imported_ids.insert(event.event_id.clone());
+            }
+        }
+        let mut merged = merge_events(local, incoming);
         sort_events(&mut merged);
 
         let mut compacted = false;
@@ -772,6 +935,7 @@ impl TaskStore {
             total_tasks: snapshot.tasks.len(),
             compacted,
             removed_events,
+            imported_events: imported_ids.len(),
         })
     }
 
@@ -1030,9 +1194,10 @@ impl TaskStore {
             return Ok(snapshot);
         }
         if self.shared_log_path().exists() {
+            let log_len = file_len(&self.shared_log_path());
             let events = self.load_events(&self.shared_log_path())?;
             let snapshot = self.build_snapshot(&events)?;
-            let _ = self.write_snapshot(&self.shared_snapshot_path(), &snapshot);
+            let _ = self.write_rebuilt_snapshot(&snapshot, log_len);
             return Ok(snapshot);
         }
         if let Some(snapshot) = self.load_snapshot(&self.tracked_snapshot_path())? {
@@ -1042,6 +1207,35 @@ impl TaskStore {
         self.build_snapshot(&events)
     }
 
+    /// Statuses from the snapshot `load_snapshot_prefer_shared` would read,
+    /// or `None` when that snapshot has to be rebuilt from the event log.
+    fn load_snapshot_statuses(&self) -> Result<Option<Vec<String>>> {
+        #[derive(Deserialize)]
+        struct StatusEntry {
+            status: String,
+        }
+        #[derive(Deserialize)]
+        struct StatusSnapshot {
+            tasks: Vec<StatusEntry>,
+        }
+
+        let path = if self.shared_snapshot_path().exists() {
+            self.shared_snapshot_path()
+        } else if self.shared_log_path().exists() || !self.tracked_snapshot_path().exists() {
+            return Ok(None);
+        } else {
+            self.tracked_snapshot_path()
+        };
+        let snapshot: StatusSnapshot = self.storage.read_json(&path)?;
+        Ok(Some(
+            snapshot
+                .tasks
+                .into_iter()
+                .map(|entry| entry.status)
+                .collect(),
+        ))
+    }
+
     fn build_snapshot(&self, events: &[TaskEvent]) -> Result<TaskSnapshot> {
         let mut map: HashMap<String, TaskRecord> = HashMap::new();
         let mut sorted = events.to_vec();
@@ -1064,7 +1258,8 @@ impl TaskStore {
         })
     }
 
-    fn load_merged_events(&self) -> Result<Vec<TaskEvent>> {
+    /// All task events from the tracked and shared logs, oldest first.
+    pub fn load_merged_events(&self) -> Result<Vec<TaskEvent>> {
         let tracked = self.load_events(&self.tracked_log_path())?;
         let shared = self.load_events(&self.shared_log_path())?;
         let mut merged = merge_events(tracked, shared);
@@ -1144,7 +1339,44 @@ impl TaskStore {
         self.storage.write_json(path, snapshot)
     }
 
-    fn apply_event_to_snapshot(&self, path: &Path, event: &TaskEvent) -> Result<()> {
+    /// Cache a snapshot rebuilt from a shared log of `log_len` bytes, unless
+    /// the log grew meanwhile. Deferred appends drop the snapshot after
+    /// appending under the same lock, so a stale rebuild is never kept.
+    fn write_rebuilt_snapshot(&self, snapshot: &TaskSnapshot, log_len: Option<u64>) -> Result<()> {
+        let path = self.shared_snapshot_path();
+        let lock_path = path.with_extension("lock");
+        let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
+        if log_len.is_none() || file_len(&self.shared_log_path()) != log_len {
+            return Ok(());
+        }
+        self.storage.write_json(&path, snapshot)
+    }
+
+    /// Bring both snapshots up to date with newly appended events. When
+    /// `tasks.snapshot_writes` is `deferred`, only the local snapshot is
+    /// dropped; the git-tracked one is left stale (reads prefer the shared
+    /// log) until `sv task sync` rewrites it, so task edits don't dirty the
+    /// working tree.
+    fn update_snapshots(&self, events: &[TaskEvent]) -> Result<()> {
+        if self.config.snapshot_writes == "deferred" {
+            return self.invalidate_snapshot(&self.shared_snapshot_path());
+        }
+        for path in [self.tracked_snapshot_path(), self.shared_snapshot_path()] {
+            self.apply_events_to_snapshot(&path, events)?;
+        }
+        Ok(())
+    }
+
+    fn invalidate_snapshot(&self, path: &Path) -> Result<()> {
+        let lock_path = path.with_extension("lock");
+        let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
+        match std::fs::remove_file(path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn apply_events_to_snapshot(&self, path: &Path, events: &[TaskEvent]) -> Result<()> {
         let lock_path = path.with_extension("lock");
         let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
         let mut snapshot = self
@@ -1155,7 +1387,9 @@ impl TaskStore {
             .drain(..)
             .map(|task| (task.id.clone(), task))
             .collect();
-        apply_event(&mut map, event, &self.config)?;
+        for event in events {
+            apply_event(&mut map, event, &self.config)?;
+        }
         let mut tasks: Vec<TaskRecord> = map.into_values().collect();
         tasks.sort_by(|a, b| {
             b.updated_at
@@ -1194,6 +1428,49 @@ impl TaskStore {
     }
 }
 
+/// Parse a task event log (JSONL) held in memory, e.g. a blob read from git.
+pub fn parse_events_jsonl(content: &str) -> Result<Vec<TaskEvent>> {
+    let mut events = Vec::new();
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        events.push(serde_json::from_str(line)?);
+    }
+    Ok(events)
+}
+
+/// Three-way merge of task event logs (JSONL), as run by the git merge
+/// driver for `.tasks/tasks.jsonl`.
+///
+/// Events from both sides are unioned by event_id. Events present in `base`
+/// but dropped by either side (repair, compaction) stay dropped. The result
+/// is in stable `timestamp`, `event_id` order.
+pub fn merge_event_logs(base: &str, ours: &str, theirs: &str) -> Result<String> {
+    let base = parse_events_jsonl(base)?;
+    let ours = parse_events_jsonl(ours)?;
+    let theirs = parse_events_jsonl(theirs)?;
+
+    let ours_ids: HashSet<String> = ours.iter().map(|event| event.event_id.clone()).collect();
+    let theirs_ids: HashSet<String> = theirs.iter().map(|event| event.event_id.clone()).collect();
+    let dropped: HashSet<String> = base
+        .into_iter()
+        .map(|event| event.event_id)
+        .filter(|id| !ours_ids.contains(id) || !theirs_ids.contains(id))
+        .collect();
+
+    let mut merged = merge_events(ours, theirs);
+    merged.retain(|event| !dropped.contains(&event.event_id));
+    sort_events(&mut merged);
+
+    let mut output = String::new();
+    for event in &merged {
+        output.push_str(&serde_json::to_string(event)?);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
 fn merge_events(mut a: Vec<TaskEvent>, b: Vec<TaskEvent>) -> Vec<TaskEvent> {
     let mut seen = HashSet::new();
     a.retain(|event| seen.insert(event.event_id.clone()));
@@ -1392,6 +1669,7 @@ fn apply_event(
                     closed_by: None,
                     comments_count: 0,
                     last_comment_at: None,
+                    forge_ref: event.forge_ref.clone(),
                 },
             );
         }
@@ -1796,6 +2074,10 @@ fn prune_project_group_parent_links(state: &mut RelationState) {
         .retain(|_, parent| !project_group_ids.contains(parent));
 }
 
+fn file_len(path: &Path) -> Option<u64> {
+    std::fs::metadata(path).ok().map(|meta| meta.len())
+}
+
 fn status_map_from_tasks(tasks: &[TaskRecord]) -> HashMap<String, String> {
     tasks
         .iter()
@@ -1992,6 +2274,45 @@ fn suffix_from_id(id_norm: &str) -> &str {
     }
 }
 
+/// Where task ID suffixes come from (`[tasks] id_scheme`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TaskIdScheme {
+    Ulid,
+    Uuid,
+}
+
+impl TaskIdScheme {
+    fn from_config(value: &str) -> Self {
+        match value {
+            "uuid" => TaskIdScheme::Uuid,
+            _ => TaskIdScheme::Ulid,
+        }
+    }
+
+    fn is_suffix(self, value: &str) -> bool {
+        match self {
+            TaskIdScheme::Ulid => is_ulid_suffix(value),
+            TaskIdScheme::Uuid => {
+                !value.is_empty() && value.chars().all(|ch| UUID_CHARSET.contains(ch))
+            }
+        }
+    }
+
+    fn space_for_len(self, len: usize) -> u128 {
+        match self {
+            TaskIdScheme::Ulid => ulid_space_for_len(len),
+            TaskIdScheme::Uuid => UUID_CHARSET_LEN.saturating_pow(len as u32),
+        }
+    }
+
+    fn max_len(self) -> usize {
+        match self {
+            TaskIdScheme::Ulid => ULID_RANDOM_LEN,
+            TaskIdScheme::Uuid => UUID_RANDOM_LEN,
+        }
+    }
+}
+
 fn is_ulid_suffix(value: &str) -> bool {
     !value.is_empty() && value.chars().all(|ch| ULID_CHARSET.contains(ch))
 }
@@ -2040,6 +2361,7 @@ mod tests {
             closed_by: None,
             comments_count: 0,
             last_comment_at: None,
+            forge_ref: None,
         }
     }
 
@@ -2625,6 +2947,182 @@ mod tests {
         assert!(relations.relates.is_empty());
     }
 
+    #[test]
+    fn count_matches_list_with_and_without_snapshot() {
+        let dir = tempdir().expect("tempdir");
+        let repo_root = dir.path().to_path_buf();
+        let storage = Storage::new(repo_root.clone(), repo_root.join(".git"), repo_root.clone());
+        let store = TaskStore::new(storage, TasksConfig::default());
+
+        let now = Utc::now();
+        for (idx, (id, status)) in [("task-a", "open"), ("task-b", "open"), ("task-c", "closed")]
+            .into_iter()
+            .enumerate()
+        {
+            let mut create = TaskEvent::new(TaskEventType::TaskCreated, id);
+            create.title = Some(id.to_string());
+            create.status = Some(status.to_string());
+            create.timestamp = now + chrono::Duration::milliseconds(idx as i64);
+            store.append_event(create).expect("create");
+        }
+
+        let check = |store: &TaskStore| {
+            for statuses in [
+                &[][..],
+                &["open"][..],
+                &["closed"][..],
+                &["in_progress"][..],
+                &["open", "closed"][..],
+            ] {
+                let statuses: Vec<String> = statuses.iter().map(|s| s.to_string()).collect();
+                let listed = store.list(&statuses).expect("list").len();
+                assert_eq!(
+                    store.count(&statuses).expect("count"),
+                    listed,
+                    "{statuses:?}"
+                );
+            }
+        };
+        check(&store);
+        assert_eq!(store.count(&[" open ".to_string()]).expect("count"), 2);
+        assert_eq!(
+            store
+                .count(&["open".to_string(), "closed".to_string()])
+                .expect("count"),
+            3
+        );
+        assert!(store.count(&["bogus".to_string()]).is_err());
+
+        // Without snapshots, counts fall back to replaying the event log.
+        std::fs::remove_file(store.shared_snapshot_path()).expect("remove shared snapshot");
+        std::fs::remove_file(store.tracked_snapshot_path()).expect("remove tracked snapshot");
+        check(&store);
+    }
+
+    #[test]
+    fn deferred_snapshot_writes_rebuild_from_log() {
+        let dir = tempdir().expect("tempdir");
+        let repo_root = dir.path().to_path_buf();
+        let storage = Storage::new(repo_root.clone(), repo_root.join(".git"), repo_root.clone());
+        let config = TasksConfig {
+            snapshot_writes: "deferred".to_string(),
+            ..TasksConfig::default()
+        };
+        let store = TaskStore::new(storage, config);
+
+        let now = Utc::now();
+        for (idx, id) in ["task-a", "task-b"].into_iter().enumerate() {
+            let mut create = TaskEvent::new(TaskEventType::TaskCreated, id);
+            create.title = Some(id.to_string());
+            create.timestamp = now + chrono::Duration::milliseconds(idx as i64);
+            store.append_event(create).expect("create");
+        }
+        assert!(!store.shared_snapshot_path().exists());
+        assert!(!store.tracked_snapshot_path().exists());
+
+        // Reads rebuild and cache the local snapshot from the shared log.
+        assert_eq!(store.list(&[]).expect("list").len(), 2);
+        assert!(store.shared_snapshot_path().exists());
+        assert!(!store.tracked_snapshot_path().exists());
+
+        let mut close = TaskEvent::new(TaskEventType::TaskClosed, "task-a");
+        close.status = Some("closed".to_string());
+        close.timestamp = now + chrono::Duration::milliseconds(5);
+        store.append_event(close).expect("close");
+        assert!(!store.shared_snapshot_path().exists());
+        assert_eq!(store.count(&["closed".to_string()]).expect("count"), 1);
+
+        // A rebuild from an older log length is not cached.
+        let stale = store.snapshot_readonly().expect("snapshot");
+        std::fs::remove_file(store.shared_snapshot_path()).ok();
+        store
+            .write_rebuilt_snapshot(&stale, Some(0))
+            .expect("write rebuilt");
+        assert!(!store.shared_snapshot_path().exists());
+
+        store.sync(None).expect("sync");
+        assert!(store.shared_snapshot_path().exists());
+        assert!(store.tracked_snapshot_path().exists());
+        assert_eq!(store.list(&["open".to_string()]).expect("list").len(), 1);
+
+        // The git-tracked snapshot is left as sync wrote it.
+        let tracked = std::fs::read_to_string(store.tracked_snapshot_path()).expect("read tracked");
+        let mut reopen = TaskEvent::new(TaskEventType::TaskStatusChanged, "task-a");
+        reopen.status = Some("open".to_string());
+        reopen.timestamp = now + chrono::Duration::milliseconds(10);
+        store.append_event(reopen).expect("reopen");
+        assert!(!store.shared_snapshot_path().exists());
+        assert_eq!(
+            std::fs::read_to_string(store.tracked_snapshot_path()).expect("read tracked"),
+            tracked
+        );
+        assert_eq!(store.list(&["open".to_string()]).expect("list").len(), 2);
+    }
+
+    #[test]
+    fn append_events_matches_sequential_appends() {
+        let open_store = |dir: &Path| {
+            let root = dir.to_path_buf();
+            let storage = Storage::new(root.clone(), root.join(".git"), root);
+            TaskStore::new(storage, TasksConfig::default())
+        };
+        let now = Utc::now();
+        let mut events = Vec::new();
+        for (idx, id) in ["task-a", "task-b", "task-c"].into_iter().enumerate() {
+            let mut create = TaskEvent::new(TaskEventType::TaskCreated, id);
+            create.title = Some(id.to_string());
+            create.timestamp = now + chrono::Duration::milliseconds(idx as i64);
+            events.push(create);
+        }
+        let mut close = TaskEvent::new(TaskEventType::TaskClosed, "task-b");
+        close.status = Some("closed".to_string());
+        close.timestamp = now + chrono::Duration::milliseconds(10);
+        events.push(close);
+
+        let sequential_dir = tempdir().expect("tempdir");
+        let sequential = open_store(sequential_dir.path());
+        let mut seed = TaskEvent::new(TaskEventType::TaskCreated, "task-seed");
+        seed.title = Some("seed".to_string());
+        seed.timestamp = now - chrono::Duration::seconds(1);
+        sequential.append_event(seed.clone()).expect("seed");
+        for event in &events {
+            sequential.append_event(event.clone()).expect("append");
+        }
+
+        let batch_dir = tempdir().expect("tempdir");
+        let batch = open_store(batch_dir.path());
+        batch.append_event(seed).expect("seed");
+        batch.append_events(&events).expect("append events");
+        batch.append_events(&[]).expect("empty batch");
+
+        for (left, right) in [
+            (sequential.tracked_log_path(), batch.tracked_log_path()),
+            (sequential.shared_log_path(), batch.shared_log_path()),
+        ] {
+            assert_eq!(
+                std::fs::read_to_string(left).expect("read log"),
+                std::fs::read_to_string(right).expect("read log")
+            );
+        }
+        for (left, right) in [
+            (
+                sequential.tracked_snapshot_path(),
+                batch.tracked_snapshot_path(),
+            ),
+            (
+                sequential.shared_snapshot_path(),
+                batch.shared_snapshot_path(),
+            ),
+        ] {
+            let left: TaskSnapshot = sequential.storage.read_json(&left).expect("snapshot");
+            let right: TaskSnapshot = batch.storage.read_json(&right).expect("snapshot");
+            assert_eq!(
+                serde_json::to_value(&left.tasks).expect("json"),
+                serde_json::to_value(&right.tasks).expect("json")
+            );
+        }
+    }
+
     #[test]
     fn list_ready_excludes_blocked_tasks() {
         let dir = tempdir().expect("tempdir");
@@ -2805,14 +3303,114 @@ mod tests {
     fn task_id_suffix_length_stays_min_until_exhausted() {
         let mut counts = HashMap::new();
         counts.insert(3, 1);
-        assert_eq!(TaskStore::select_task_suffix_len(3, &counts), 3);
+        assert_eq!(
+            TaskStore::select_task_suffix_len(TaskIdScheme::Ulid, 3, &counts),
+            3
+        );
     }
 
     #[test]
     fn task_id_suffix_length_grows_after_exhausted() {
         let mut counts = HashMap::new();
         counts.insert(3, ulid_space_for_len(3) as usize);
-        assert_eq!(TaskStore::select_task_suffix_len(3, &counts), 4);
+        assert_eq!(
+            TaskStore::select_task_suffix_len(TaskIdScheme::Ulid, 3, &counts),
+            4
+        );
+    }
+
+    #[test]
+    fn merge_event_logs_unions_by_event_id_and_keeps_drops() {
+        let event = |id: &str| {
+            let mut event = TaskEvent::new(TaskEventType::TaskCreated, id);
+            event.title = Some(id.to_string());
+            serde_json::to_string(&event).unwrap() + "\n"
+        };
+        let shared = event("task-shared");
+        let dropped = event("task-dropped");
+        let ours_only = event("task-ours");
+        let theirs_only = event("task-theirs");
+
+        let base = format!("{shared}{dropped}");
+        let ours = format!("{shared}{ours_only}");
+        let theirs = format!("{shared}{dropped}{theirs_only}");
+
+        let merged = merge_event_logs(&base, &ours, &theirs).expect("merge");
+        let events = parse_events_jsonl(&merged).expect("parse");
+        let mut ids: Vec<&str> = events.iter().map(|event| event.task_id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["task-ours", "task-shared", "task-theirs"]);
+
+        // Merging is idempotent once both sides agree.
+        assert_eq!(merge_event_logs(&merged, &merged, &merged).unwrap(), merged);
+    }
+
+    #[test]
+    fn uuid_task_id_suffix_skips_version_and_variant_digits() {
+        let existing = HashSet::new();
+        let suffix = TaskStore::unique_task_suffix_from_uuid(
+            "0123456789ab4cdeafedcba987654321",
+            14,
+            &existing,
+        )
+        .expect("suffix");
+        assert_eq!(suffix, "0123456789abcd");
+    }
+
+    #[test]
+    fn uuid_task_id_suffix_length_grows_after_exhausted() {
+        let mut counts = HashMap::new();
+        counts.insert(3, 16usize.pow(3));
+        assert_eq!(
+            TaskStore::select_task_suffix_len(TaskIdScheme::Uuid, 3, &counts),
+            4
+        );
+        assert_eq!(
+            TaskStore::select_task_suffix_len(TaskIdScheme::Ulid, 3, &counts),
+            3
+        );
+    }
+
+    fn generate_and_create(store: &TaskStore, count: usize) -> Vec<String> {
+        let mut ids = Vec::new();
+        for idx in 0..count {
+            let id = store.generate_task_id().expect("generate id");
+            let mut event = TaskEvent::new(TaskEventType::TaskCreated, id.clone());
+            event.title = Some(format!("Task {idx}"));
+            store.append_event(event).expect("create task");
+            ids.push(id);
+        }
+        ids
+    }
+
+    #[test]
+    fn generated_task_ids_are_unique_and_resolvable_under_each_scheme() {
+        for scheme in ["ulid", "uuid"] {
+            let dir = tempdir().expect("tempdir");
+            let repo_root = dir.path().to_path_buf();
+            let storage =
+                Storage::new(repo_root.clone(), repo_root.join(".git"), repo_root.clone());
+            let config = TasksConfig {
+                id_prefix: "sv".to_string(),
+                id_scheme: scheme.to_string(),
+                ..TasksConfig::default()
+            };
+            let store = TaskStore::new(storage, config);
+
+            let ids = generate_and_create(&store, 40);
+            let unique: HashSet<&String> = ids.iter().collect();
+            assert_eq!(unique.len(), ids.len(), "duplicate ids under {scheme}");
+
+            for id in &ids {
+                let suffix = suffix_from_id(id);
+                assert!(suffix.len() >= 3, "{id}");
+                if scheme == "uuid" {
+                    assert!(TaskIdScheme::Uuid.is_suffix(suffix), "{id}");
+                }
+                assert_eq!(&store.resolve_task_id(id).expect("full id"), id);
+                assert_eq!(&store.resolve_task_id(suffix).expect("suffix"), id);
+            }
+        }
     }
 
     #[test]
@@ -2976,6 +3574,7 @@ mod tests {
                     closed_by: None,
                     comments_count: 0,
                     last_comment_at: None,
+                    forge_ref: None,
                 },
                 TaskRecord {
                     id: "prefix-b1c".to_string(),
@@ -2998,6 +3597,7 @@ mod tests {
                     closed_by: None,
                     comments_count: 0,
                     last_comment_at: None,
+                    forge_ref: None,
                 },
                 TaskRecord {
                     id: "legacy-a9b".to_string(),
@@ -3020,6 +3620,7 @@ mod tests {
                     closed_by: None,
                     comments_count: 0,
                     last_comment_at: None,
+                    forge_ref: None,
                 },
             ],
         };
@@ -515,7 +515,7 @@ pub fn change_status(
 }
 
 fn load_task(store: &TaskStore, task_id: &str) -> Result<TaskRecord> {
-    let tasks = store.list(None)?;
+    let tasks = store.list(&[])?;
     tasks
         .into_iter()
         .find(|task| task.id == task_id)
@@ -774,7 +774,7 @@ mod tests {
         let outcome = delete_task(&store, None, &parent_id).expect("delete");
         assert!(outcome.changed);
 
-        let tasks = store.list(None).expect("list");
+        let tasks = store.list(&[]).expect("list");
         assert!(!tasks.iter().any(|task| task.id == parent_id));
         let child_relations = store.relations(&child_id).expect("child relations");
         assert!(child_relations.parent.is_none());
@@ -1582,7 +1582,7 @@ fn spawn_loader(store: TaskStore, req_rx: Receiver<LoadRequest>, ui_tx: Sender<U
     thread::spawn(move || {
         while let Ok(req) = req_rx.recv() {
             match req {
-                LoadRequest::Reload => match store.list(None) {
+                LoadRequest::Reload => match store.list(&[]) {
                     Ok(tasks) => {
                         let (blocked_ids, mut blocked_error, parent_by_child, epic_by_task) =
                             match store.blocked_and_parents() {
@@ -1795,6 +1795,7 @@ mod tests {
                 closed_by: None,
                 comments_count: 0,
                 last_comment_at: None,
+                forge_ref: None,
             },
             TaskRecord {
                 id: "sv-epic-b".to_string(),
@@ -1817,6 +1818,7 @@ mod tests {
                 closed_by: None,
                 comments_count: 0,
                 last_comment_at: None,
+                forge_ref: None,
             },
         ];
 
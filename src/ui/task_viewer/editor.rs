@@ -877,11 +877,9 @@ impl MultiTaskPicker {
             KeyCode::Char(' ') => {
                 self.toggle_selected();
             }
-            KeyCode::Char(ch) => {
-                if !ch.is_control() {
-                    self.query.push(ch);
-                    self.rebuild_filter();
-                }
+            KeyCode::Char(ch) if !ch.is_control() => {
+                self.query.push(ch);
+                self.rebuild_filter();
             }
             _ => {}
         }
@@ -778,6 +778,7 @@ mod tests {
             closed_by: None,
             comments_count: 0,
             last_comment_at: None,
+            forge_ref: None,
         }
     }
 
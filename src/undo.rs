@@ -2,21 +2,32 @@
 //!
 //! Basic semantics:
 //! - Ref updates are restored to previous tips
-//! - Created paths are removed (unless keep_worktree)
+//! - Created paths are removed (unless keep_worktree); a worktree with
+//!   uncommitted changes is never removed
 //! - Deleted paths are not restored (error)
 //! - Lease changes are reverted when possible
+//!
+//! Undo and redo are themselves recorded in the oplog: an `sv undo` record
+//! names the operation it reverted and carries the inverse of its changes, and
+//! an `sv redo` record names the operation it re-applied. The [`RedoStack`] is
+//! replayed from those records: `sv redo` re-applies the most recently undone
+//! operation, and any other operation recorded after an undo clears the stack.
+//! An undone operation stays undone (and is not offered to `sv undo` again)
+//! until it is redone, whether or not the stack was cleared.
 
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use git2::{Oid, Repository};
+use serde::Serialize;
 use uuid::Uuid;
 
 use crate::error::{Error, Result};
+use crate::git;
 use crate::lease::{Lease, LeaseStatus};
-use crate::lock::{self, FileLock, DEFAULT_LOCK_TIMEOUT_MS};
-use crate::oplog::{LeaseChange, OpLog, OpRecord, UndoData};
+use crate::lock::{FileLock, DEFAULT_LOCK_TIMEOUT_MS};
+use crate::oplog::{LeaseChange, OpLog, OpRecord, OperationKind, RefUpdate, UndoData};
 use crate::storage::Storage;
 
 /// Options for undoing an operation.
@@ -36,38 +47,356 @@ pub struct UndoSummary {
     pub reverted_leases: Vec<String>,
 }
 
+/// Summary of a redo.
+#[derive(Debug, Clone, Default)]
+pub struct RedoSummary {
+    pub op_id: Uuid,
+    pub command: String,
+    pub restored_refs: Vec<String>,
+    pub reverted_leases: Vec<String>,
+}
+
+/// Changes an undo would make, computed without applying any of them.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UndoPlan {
+    pub op_id: Uuid,
+    /// Command of the operation being undone
+    pub command: String,
+    pub ref_updates: Vec<PlannedRefUpdate>,
+    pub remove_paths: Vec<PathBuf>,
+    /// Created directories left in place because of `keep_worktree`
+    pub keep_paths: Vec<PathBuf>,
+    pub unregister_workspaces: Vec<String>,
+    pub lease_changes: Vec<PlannedLeaseChange>,
+}
+
+impl UndoPlan {
+    /// Whether undoing would change nothing.
+    pub fn is_empty(&self) -> bool {
+        self.ref_updates.is_empty()
+            && self.remove_paths.is_empty()
+            && self.unregister_workspaces.is_empty()
+            && self.lease_changes.is_empty()
+    }
+}
+
+/// A ref undo would reset (`to` set) or delete (`to` unset).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedRefUpdate {
+    pub name: String,
+    /// Current target, if the ref exists
+    pub current: Option<String>,
+    pub to: Option<String>,
+}
+
+/// A lease undo would release (`remove`) or reactivate (`restore`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedLeaseChange {
+    pub lease_id: String,
+    pub pathspec: String,
+    pub action: PlannedLeaseAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlannedLeaseAction {
+    Remove,
+    Restore,
+}
+
+/// Compute what undoing the last operation (or `op_id`) would change.
+///
+/// Fails in the same cases [`undo`] does, so a clean plan means the undo can
+/// be applied.
+pub fn plan(storage: &Storage, options: &UndoOptions) -> Result<UndoPlan> {
+    let repo_root = repo_root_from_storage(storage)?;
+    let repo = Repository::discover(&repo_root)?;
+    let (record, undo) = select_undoable(storage, &OpLog::for_storage(storage), options.op_id)?;
+
+    let mut plan = UndoPlan {
+        op_id: record.op_id,
+        command: record.command,
+        ..UndoPlan::default()
+    };
+
+    for update in &undo.ref_updates {
+        let current = repo
+            .find_reference(&update.name)
+            .ok()
+            .and_then(|reference| reference.target())
+            .map(|oid| oid.to_string());
+        match update.old.as_deref() {
+            Some(old) => {
+                Oid::from_str(old)
+                    .map_err(|_| Error::OperationFailed(format!("invalid oid: {old}")))?;
+            }
+            None if current.is_none() => continue,
+            None => {}
+        }
+        plan.ref_updates.push(PlannedRefUpdate {
+            name: update.name.clone(),
+            current,
+            to: update.old.clone(),
+        });
+    }
+
+    for path_str in &undo.created_paths {
+        let path = PathBuf::from(path_str);
+        if !path.exists() {
+            continue;
+        }
+        if options.keep_worktree && path.is_dir() {
+            plan.keep_paths.push(path);
+            continue;
+        }
+        if path.is_dir() {
+            if let Ok(worktree) = Repository::open(&path) {
+                if git::has_uncommitted_changes(&worktree)? {
+                    return Err(Error::OperationFailed(format!(
+                        "worktree {} has uncommitted changes; commit or stash them, or re-run with --keep-worktree",
+                        path.display()
+                    )));
+                }
+            }
+        }
+        plan.remove_paths.push(path);
+    }
+
+    plan.unregister_workspaces = undo
+        .workspace_changes
+        .iter()
+        .filter(|change| matches!(change.action.as_str(), "create" | "register"))
+        .map(|change| change.name.clone())
+        .collect();
+
+    if !undo.lease_changes.is_empty() {
+        let leases: Vec<Lease> = storage.read_leases()?;
+        for change in &undo.lease_changes {
+            let id = Uuid::parse_str(&change.lease_id).map_err(|_| {
+                Error::OperationFailed(format!("invalid lease id: {}", change.lease_id))
+            })?;
+            let Some(lease) = leases.iter().find(|lease| lease.id == id) else {
+                continue;
+            };
+            let action = match change.action.as_str() {
+                "create" | "add" => PlannedLeaseAction::Remove,
+                "release" | "break" | "expire" => PlannedLeaseAction::Restore,
+                other => {
+                    return Err(Error::OperationFailed(format!(
+                        "unsupported lease undo action: {other}"
+                    )));
+                }
+            };
+            plan.lease_changes.push(PlannedLeaseChange {
+                lease_id: change.lease_id.clone(),
+                pathspec: lease.pathspec.clone(),
+                action,
+            });
+        }
+    }
+
+    Ok(plan)
+}
+
 /// Undo the last operation (or a specific op_id if provided).
 pub fn undo(storage: &Storage, options: UndoOptions) -> Result<UndoSummary> {
     let repo_root = repo_root_from_storage(storage)?;
     let repo = Repository::discover(&repo_root)?;
 
     let log = OpLog::for_storage(storage);
-    let record = select_record(&log, options.op_id)?;
-    let undo = record
-        .undo_data
-        .clone()
-        .ok_or_else(|| Error::OperationFailed("operation has no undo data".to_string()))?;
-
-    if !undo.deleted_paths.is_empty() {
-        return Err(Error::OperationFailed(format!(
-            "cannot restore deleted paths: {}",
-            undo.deleted_paths.join(", ")
-        )));
-    }
+    let (record, undo) = select_undoable(storage, &log, options.op_id)?;
+    let redo_data = inverse(&plan(storage, &options)?);
 
     let mut summary = UndoSummary {
         op_id: record.op_id,
         ..UndoSummary::default()
     };
 
-    apply_ref_updates(&repo, &undo, &mut summary)?;
+    apply_ref_updates(&repo, &undo, "sv undo", &mut summary.restored_refs)?;
     apply_created_paths(&undo, options.keep_worktree, &mut summary)?;
     apply_workspace_changes(storage, &undo)?;
-    apply_lease_changes(storage, &undo.lease_changes, &mut summary)?;
+    apply_lease_changes(storage, &undo.lease_changes, &mut summary.reverted_leases)?;
+
+    let mut undo_record = OpRecord::new("sv undo", record.actor.clone());
+    undo_record.affected_refs = undo.ref_updates.iter().map(|u| u.name.clone()).collect();
+    undo_record.affected_paths = record.affected_paths.clone();
+    undo_record.undoes = Some(record.op_id);
+    undo_record.redo_data = Some(redo_data);
+    log.append(&undo_record)?;
 
     Ok(summary)
 }
 
+/// Re-apply the most recently undone operation.
+///
+/// Refs and leases are put back the way they were before the undo. Paths the
+/// undo removed (e.g. a workspace created by `sv ws new`) cannot be
+/// recreated, so such an operation cannot be redone.
+pub fn redo(storage: &Storage) -> Result<RedoSummary> {
+    let repo_root = repo_root_from_storage(storage)?;
+    let repo = Repository::discover(&repo_root)?;
+
+    let entry = RedoStack::for_storage(storage)
+        .entries()?
+        .pop()
+        .ok_or_else(|| Error::OperationFailed("nothing to redo".to_string()))?;
+    if !entry.redo_data.deleted_paths.is_empty() {
+        return Err(Error::OperationFailed(format!(
+            "cannot redo {}: undo removed {}",
+            entry.command,
+            entry.redo_data.deleted_paths.join(", ")
+        )));
+    }
+
+    let mut summary = RedoSummary {
+        op_id: entry.op_id,
+        command: entry.command.clone(),
+        ..RedoSummary::default()
+    };
+    apply_ref_updates(
+        &repo,
+        &entry.redo_data,
+        "sv redo",
+        &mut summary.restored_refs,
+    )?;
+    apply_lease_changes(
+        storage,
+        &entry.redo_data.lease_changes,
+        &mut summary.reverted_leases,
+    )?;
+
+    let mut redo_record = OpRecord::new("sv redo", None);
+    redo_record.affected_refs = entry
+        .redo_data
+        .ref_updates
+        .iter()
+        .map(|u| u.name.clone())
+        .collect();
+    redo_record.redoes = Some(entry.op_id);
+    OpLog::for_storage(storage).append(&redo_record)?;
+
+    Ok(summary)
+}
+
+/// An undone operation that `sv redo` can re-apply.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RedoEntry {
+    pub op_id: Uuid,
+    pub command: String,
+    pub undone_at: DateTime<Utc>,
+    /// The inverse of what the undo changed, applied the same way undo data is
+    pub redo_data: UndoData,
+}
+
+/// Stack of undone operations, replayed from the undo and redo records in
+/// the oplog.
+#[derive(Debug, Clone)]
+pub struct RedoStack {
+    log: OpLog,
+}
+
+impl RedoStack {
+    pub fn new(log: OpLog) -> Self {
+        Self { log }
+    }
+
+    pub fn for_storage(storage: &Storage) -> Self {
+        Self::new(OpLog::for_storage(storage))
+    }
+
+    /// Entries from oldest to most recently undone.
+    pub fn entries(&self) -> Result<Vec<RedoEntry>> {
+        let mut records = self.log.read_all()?;
+        records.sort_by_key(|record| record.timestamp);
+
+        let mut stack: Vec<RedoEntry> = Vec::new();
+        for (idx, record) in records.iter().enumerate() {
+            if record.outcome.status != "success" {
+                continue;
+            }
+            match record.kind() {
+                OperationKind::Undo => {
+                    let (Some(op_id), Some(redo_data)) = (record.undoes, &record.redo_data) else {
+                        continue;
+                    };
+                    let command = records[..idx]
+                        .iter()
+                        .find(|original| original.op_id == op_id)
+                        .map(|original| original.command.clone())
+                        .unwrap_or_default();
+                    stack.push(RedoEntry {
+                        op_id,
+                        command,
+                        undone_at: record.timestamp,
+                        redo_data: redo_data.clone(),
+                    });
+                }
+                OperationKind::Redo => {
+                    if let Some(op_id) = record.redoes {
+                        stack.retain(|entry| entry.op_id != op_id);
+                    }
+                }
+                // A new operation makes previously undone ones unreachable.
+                _ => stack.clear(),
+            }
+        }
+        Ok(stack)
+    }
+
+    /// Operations currently undone: undone and not redone since, whether or
+    /// not they can still be redone.
+    pub fn undone(&self) -> Result<Vec<Uuid>> {
+        let mut records = self.log.read_all()?;
+        records.sort_by_key(|record| record.timestamp);
+
+        let mut undone = Vec::new();
+        for record in records.iter().filter(|r| r.outcome.status == "success") {
+            if let Some(op_id) = record.undoes {
+                undone.push(op_id);
+            }
+            if let Some(op_id) = record.redoes {
+                undone.retain(|id| *id != op_id);
+            }
+        }
+        Ok(undone)
+    }
+}
+
+/// Undo data that reverses the changes in `plan`.
+fn inverse(plan: &UndoPlan) -> UndoData {
+    UndoData {
+        ref_updates: plan
+            .ref_updates
+            .iter()
+            .map(|update| RefUpdate {
+                name: update.name.clone(),
+                old: update.current.clone(),
+                new: update.to.clone(),
+            })
+            .collect(),
+        deleted_paths: plan
+            .remove_paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect(),
+        // Undo data actions describe the original change: reverting a
+        // "release" reactivates the lease and reverting a "create" releases it.
+        lease_changes: plan
+            .lease_changes
+            .iter()
+            .map(|change| LeaseChange {
+                lease_id: change.lease_id.clone(),
+                action: match change.action {
+                    PlannedLeaseAction::Remove => "release",
+                    PlannedLeaseAction::Restore => "create",
+                }
+                .to_string(),
+            })
+            .collect(),
+        ..UndoData::default()
+    }
+}
+
 fn apply_workspace_changes(storage: &Storage, undo: &UndoData) -> Result<()> {
     if undo.workspace_changes.is_empty() {
         return Ok(());
@@ -87,11 +416,40 @@ fn apply_workspace_changes(storage: &Storage, undo: &UndoData) -> Result<()> {
     Ok(())
 }
 
-fn select_record(log: &OpLog, op_id: Option<Uuid>) -> Result<OpRecord> {
+/// Select the record to undo and its undo data, rejecting operations that
+/// cannot be undone or were already undone.
+fn select_undoable(
+    storage: &Storage,
+    log: &OpLog,
+    op_id: Option<Uuid>,
+) -> Result<(OpRecord, UndoData)> {
+    let undone = RedoStack::for_storage(storage).undone()?;
+    let record = select_record(log, op_id, &undone)?;
+    let undo = record
+        .undo_data
+        .clone()
+        .ok_or_else(|| Error::OperationFailed("operation has no undo data".to_string()))?;
+
+    if !undo.deleted_paths.is_empty() {
+        return Err(Error::OperationFailed(format!(
+            "cannot restore deleted paths: {}",
+            undo.deleted_paths.join(", ")
+        )));
+    }
+
+    Ok((record, undo))
+}
+
+fn select_record(log: &OpLog, op_id: Option<Uuid>, undone: &[Uuid]) -> Result<OpRecord> {
     let mut records = log.read_all()?;
-    records.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    records.sort_by_key(|record| std::cmp::Reverse(record.timestamp));
 
     if let Some(id) = op_id {
+        if undone.contains(&id) {
+            return Err(Error::OperationFailed(format!(
+                "operation {id} has already been undone (sv redo re-applies it)"
+            )));
+        }
         return records
             .into_iter()
             .find(|record| record.op_id == id)
@@ -100,23 +458,28 @@ fn select_record(log: &OpLog, op_id: Option<Uuid>) -> Result<OpRecord> {
 
     records
         .into_iter()
-        .find(|record| record.undo_data.is_some())
+        .find(|record| record.undo_data.is_some() && !undone.contains(&record.op_id))
         .ok_or_else(|| Error::OperationFailed("no undoable operations found".to_string()))
 }
 
-fn apply_ref_updates(repo: &Repository, undo: &UndoData, summary: &mut UndoSummary) -> Result<()> {
+fn apply_ref_updates(
+    repo: &Repository,
+    undo: &UndoData,
+    message: &str,
+    restored: &mut Vec<String>,
+) -> Result<()> {
     for update in &undo.ref_updates {
         match update.old.as_deref() {
             Some(old) => {
                 let oid = Oid::from_str(old)
                     .map_err(|_| Error::OperationFailed(format!("invalid oid: {old}")))?;
-                repo.reference(&update.name, oid, true, "sv undo")?;
-                summary.restored_refs.push(update.name.clone());
+                repo.reference(&update.name, oid, true, message)?;
+                restored.push(update.name.clone());
             }
             None => {
                 if let Ok(mut reference) = repo.find_reference(&update.name) {
                     reference.delete()?;
-                    summary.restored_refs.push(update.name.clone());
+                    restored.push(update.name.clone());
                 }
             }
         }
@@ -151,7 +514,7 @@ fn apply_created_paths(
 fn apply_lease_changes(
     storage: &Storage,
     changes: &[LeaseChange],
-    summary: &mut UndoSummary,
+    reverted: &mut Vec<String>,
 ) -> Result<()> {
     if changes.is_empty() {
         return Ok(());
@@ -161,7 +524,7 @@ fn apply_lease_changes(
     let lock_path = PathBuf::from(format!("{}.lock", leases_path.display()));
     let _lock = FileLock::acquire(&lock_path, DEFAULT_LOCK_TIMEOUT_MS)?;
 
-    let mut leases: Vec<Lease> = storage.read_jsonl(&leases_path)?;
+    let mut leases: Vec<Lease> = storage.read_leases()?;
     let mut touched = false;
 
     for change in changes {
@@ -193,18 +556,12 @@ fn apply_lease_changes(
             }
         }
 
-        summary.reverted_leases.push(change.lease_id.clone());
+        reverted.push(change.lease_id.clone());
         touched = true;
     }
 
     if touched {
-        let mut contents = String::new();
-        for lease in leases {
-            let line = serde_json::to_string(&lease)?;
-            contents.push_str(&line);
-            contents.push('\n');
-        }
-        lock::write_atomic(&leases_path, contents.as_bytes())?;
+        crate::lease::write_lease_log(&leases_path, &leases)?;
     }
 
     Ok(())
@@ -311,4 +668,102 @@ mod tests {
         let updated = repo.find_reference("refs/heads/feature").unwrap();
         assert_eq!(updated.target().unwrap(), commit_a);
     }
+
+    #[test]
+    fn plan_previews_ref_reset_without_applying() {
+        let (_temp, storage) = setup_storage();
+        let repo_root = repo_root_from_storage(&storage).unwrap();
+        let repo = Repository::open(repo_root).unwrap();
+        let commit_a = commit(&repo, "A");
+        let commit_b = commit(&repo, "B");
+        repo.reference("refs/heads/feature", commit_b, true, "test")
+            .unwrap();
+
+        let log = OpLog::for_storage(&storage);
+        let mut record = OpRecord::new("sv onto", Some("tester".to_string()));
+        record.undo_data = Some(UndoData {
+            ref_updates: vec![
+                crate::oplog::RefUpdate {
+                    name: "refs/heads/feature".to_string(),
+                    old: Some(commit_a.to_string()),
+                    new: Some(commit_b.to_string()),
+                },
+                crate::oplog::RefUpdate {
+                    name: "refs/heads/gone".to_string(),
+                    old: None,
+                    new: Some(commit_b.to_string()),
+                },
+            ],
+            ..UndoData::default()
+        });
+        log.append(&record).unwrap();
+
+        let plan = plan(&storage, &UndoOptions::default()).unwrap();
+        assert_eq!(plan.op_id, record.op_id);
+        assert_eq!(
+            plan.ref_updates,
+            vec![PlannedRefUpdate {
+                name: "refs/heads/feature".to_string(),
+                current: Some(commit_b.to_string()),
+                to: Some(commit_a.to_string()),
+            }]
+        );
+        let feature = repo.find_reference("refs/heads/feature").unwrap();
+        assert_eq!(feature.target().unwrap(), commit_b);
+    }
+
+    #[test]
+    fn redo_reapplies_undo_until_a_new_operation_clears_it() {
+        let (_temp, storage) = setup_storage();
+        let repo_root = repo_root_from_storage(&storage).unwrap();
+        let repo = Repository::open(repo_root).unwrap();
+        let commit_a = commit(&repo, "A");
+        let commit_b = commit(&repo, "B");
+        repo.reference("refs/heads/feature", commit_b, true, "test")
+            .unwrap();
+
+        let log = OpLog::for_storage(&storage);
+        let mut record = OpRecord::new("sv onto", Some("tester".to_string()));
+        record.undo_data = Some(UndoData {
+            ref_updates: vec![crate::oplog::RefUpdate {
+                name: "refs/heads/feature".to_string(),
+                old: Some(commit_a.to_string()),
+                new: Some(commit_b.to_string()),
+            }],
+            ..UndoData::default()
+        });
+        log.append(&record).unwrap();
+        let feature = || {
+            repo.find_reference("refs/heads/feature")
+                .unwrap()
+                .target()
+                .unwrap()
+        };
+
+        undo(&storage, UndoOptions::default()).unwrap();
+        assert_eq!(feature(), commit_a);
+        // The undone operation is not offered for undo again.
+        assert!(undo(&storage, UndoOptions::default()).is_err());
+
+        let summary = redo(&storage).unwrap();
+        assert_eq!(summary.op_id, record.op_id);
+        assert_eq!(feature(), commit_b);
+        assert!(redo(&storage).is_err());
+
+        undo(&storage, UndoOptions::default()).unwrap();
+        assert_eq!(RedoStack::for_storage(&storage).entries().unwrap().len(), 1);
+        log.append(&OpRecord::new("sv take src/**", None)).unwrap();
+        assert!(RedoStack::for_storage(&storage)
+            .entries()
+            .unwrap()
+            .is_empty());
+        assert!(redo(&storage).is_err());
+        assert_eq!(feature(), commit_a);
+        // The log still records the operation as undone.
+        assert_eq!(
+            RedoStack::for_storage(&storage).undone().unwrap(),
+            vec![record.op_id]
+        );
+        assert!(undo(&storage, UndoOptions::default()).is_err());
+    }
 }
@@ -0,0 +1,107 @@
+mod support;
+
+use predicates::str::{contains, is_empty};
+use serde_json::Value;
+
+use support::TestRepo;
+
+#[test]
+fn change_id_generates_fresh_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+
+    let output = support::sv_cmd()
+        .current_dir(repo.path())
+        .arg("change-id")
+        .output()?;
+    assert!(output.status.success());
+    let first = String::from_utf8(output.stdout)?.trim().to_string();
+    assert!(uuid_like(&first), "unexpected id {first}");
+
+    let output = support::sv_cmd()
+        .current_dir(repo.path())
+        .args(["change-id", "--json"])
+        .output()?;
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    let second = value["data"]["change_id"].as_str().ok_or("missing id")?;
+    assert_ne!(first, second);
+
+    Ok(())
+}
+
+#[test]
+fn change_id_of_reads_commit_trailer() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    let plain = repo.commit_file("a.txt", "a\n", "plain commit")?;
+    repo.commit_file(
+        "b.txt",
+        "b\n",
+        "tagged commit\n\nChange-Id: 0f3a5c1e-7d2b-4c1a-9e8f-123456789abc\n",
+    )?;
+
+    support::sv_cmd()
+        .current_dir(repo.path())
+        .args(["change-id", "--of", "HEAD"])
+        .assert()
+        .success()
+        .stdout("0f3a5c1e-7d2b-4c1a-9e8f-123456789abc\n");
+
+    support::sv_cmd()
+        .current_dir(repo.path())
+        .args(["change-id", "--of", &plain.to_string()])
+        .assert()
+        .success()
+        .stdout(is_empty())
+        .stderr(contains("no Change-Id trailer"));
+
+    let output = support::sv_cmd()
+        .current_dir(repo.path())
+        .args(["change-id", "--of", &plain.to_string(), "--json"])
+        .output()?;
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert!(value["data"]["change_id"].is_null());
+    assert_eq!(value["data"]["commit"], plain.to_string());
+
+    Ok(())
+}
+
+fn uuid_like(value: &str) -> bool {
+    value.len() == 36 && value.chars().filter(|ch| *ch == '-').count() == 4
+}
+
+#[test]
+fn change_id_verify_reports_missing_and_duplicates() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    let base = repo.commit_file("base.txt", "base\n", "base")?;
+    let first = repo.commit_file("a.txt", "a\n", "first\n\nChange-Id: CID-1\n")?;
+    let missing = repo.commit_file("b.txt", "b\n", "no trailer")?;
+    let duplicate = repo.commit_file("c.txt", "c\n", "rebased copy\n\nChange-Id: CID-1\n")?;
+
+    let output = support::sv_cmd()
+        .current_dir(repo.path())
+        .args(["change-id", "verify", "--base", &base.to_string(), "--json"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["command"], "change-id verify");
+    assert_eq!(
+        value["data"]["missing"],
+        serde_json::json!([missing.to_string()])
+    );
+    assert_eq!(
+        value["data"]["duplicates"],
+        serde_json::json!([[first.to_string(), duplicate.to_string()]])
+    );
+
+    support::sv_cmd()
+        .current_dir(repo.path())
+        .args(["change-id", "verify", "--base", &missing.to_string()])
+        .assert()
+        .success()
+        .stdout(contains("Change-Ids ok"));
+
+    Ok(())
+}
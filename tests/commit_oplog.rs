@@ -63,6 +63,65 @@ fn commit_writes_oplog_entry() -> Result<(), Box<dyn std::error::Error>> {
     assert!(files
         .iter()
         .any(|value| value.as_str() == Some("README.md")));
+    assert_eq!(commit_record["affected_paths"][0], "README.md");
+
+    Ok(())
+}
+
+#[test]
+fn commit_dry_run_reports_files_without_committing() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.write_file("README.md", "# sv\n")?;
+    repo.stage_path("README.md")?;
+
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "-m", "test commit", "--dry-run", "--json"])
+        .output()?;
+    assert!(output.status.success());
+
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["would"], "commit");
+    assert_eq!(report["files_committed"][0], "README.md");
+    assert!(repo.repo().head().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn op_log_graph_lists_commits_created_by_operations() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.write_file("README.md", "# sv\n")?;
+    repo.stage_path("README.md")?;
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "-m", "graph commit"])
+        .assert()
+        .success();
+    let head = repo.repo().head()?.peel_to_commit()?.id().to_string();
+
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["op", "log", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["records"][0]["commits"][0], head.as_str());
+
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["op", "log", "--graph"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.starts_with("* "), "{stdout}");
+    assert!(
+        stdout.contains(&format!("|   {} graph commit", &head[..8])),
+        "{stdout}"
+    );
 
     Ok(())
 }
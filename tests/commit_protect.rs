@@ -55,6 +55,38 @@ fn protected_path_blocks_commit() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn protected_block_reports_violations_in_json() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.write_file(
+        ".sv.toml",
+        "[protect]\nmode = \"guard\"\npaths = [\".beads/**\", \"Cargo.lock\"]\n",
+    )?;
+    repo.write_file(".beads/issues.jsonl", "[]\n")?;
+    repo.write_file("Cargo.lock", "# lock\n")?;
+    repo.commit_all("initial commit")?;
+    repo.write_file(".beads/issues.jsonl", "[1]\n")?;
+    repo.write_file("Cargo.lock", "# lock v2\n")?;
+
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "-a", "-m", "commit protected", "--json"])
+        .output()?;
+    assert_eq!(output.status.code(), Some(3));
+    let payload: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let violations = payload["error"]["details"]["protected_violations"]
+        .as_array()
+        .expect("protected_violations array");
+    assert_eq!(violations.len(), 2);
+    assert_eq!(violations[0]["path"], ".beads/issues.jsonl");
+    assert_eq!(violations[0]["pattern"], ".beads/**");
+    assert_eq!(violations[0]["mode"], "guard");
+    assert_eq!(violations[1]["path"], "Cargo.lock");
+
+    Ok(())
+}
+
 #[test]
 fn allow_protected_overrides_guard() -> Result<(), Box<dyn std::error::Error>> {
     let repo = TestRepo::init()?;
@@ -80,6 +112,179 @@ fn allow_protected_overrides_guard() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn warn_mode_commits_with_warning_and_event() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.write_file(
+        ".sv.toml",
+        "[protect]\nmode = \"warn\"\npaths = [\"*.lock\"]\n",
+    )?;
+    repo.write_file("Cargo.lock", "# lock\n")?;
+    repo.commit_all("initial commit")?;
+    repo.write_file("Cargo.lock", "# lock v2\n")?;
+
+    let events_path = repo.path().join("events.jsonl");
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "-a", "-m", "touch lock", "--json", "--events"])
+        .arg(&events_path)
+        .output()?;
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("warn mode"));
+    assert!(stderr.contains("Cargo.lock"));
+
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(result["success"], true);
+    assert_eq!(result["protected_files"][0]["file"], "Cargo.lock");
+
+    let events = fs::read_to_string(&events_path)?;
+    let event: serde_json::Value = serde_json::from_str(events.lines().next().unwrap_or(""))?;
+    assert_eq!(event["event"], "protection_warned");
+    assert_eq!(event["data"]["paths"][0]["path"], "Cargo.lock");
+    assert_eq!(event["data"]["paths"][0]["mode"], "warn");
+
+    Ok(())
+}
+
+#[test]
+fn allow_protected_records_bypass_reason() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.write_file(
+        ".sv.toml",
+        "[protect]\nmode = \"guard\"\nrequire_bypass_reason = true\npaths = [\".beads/**\"]\n",
+    )?;
+    repo.write_file(".beads/issues.jsonl", "[]\n")?;
+    repo.commit_all("initial commit")?;
+    repo.write_file(".beads/issues.jsonl", "[1]\n")?;
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "-a", "-m", "bypass", "--allow-protected"])
+        .assert()
+        .code(2)
+        .stderr(contains("--reason"));
+
+    let events_path = repo.path().join("events.jsonl");
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "-a", "-m", "bypass", "--allow-protected"])
+        .args(["--reason", "regenerate issues"])
+        .arg("--events")
+        .arg(&events_path)
+        .assert()
+        .success();
+
+    let events = fs::read_to_string(&events_path)?;
+    let event: serde_json::Value = serde_json::from_str(events.lines().next().unwrap_or(""))?;
+    assert_eq!(event["event"], "protection_bypassed");
+    assert_eq!(event["data"]["reason"], "regenerate issues");
+    assert_eq!(event["data"]["paths"][0]["path"], ".beads/issues.jsonl");
+
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["op", "log", "--json"])
+        .output()?;
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let details = report["records"]
+        .as_array()
+        .and_then(|records| {
+            records
+                .iter()
+                .find_map(|record| record["details"]["commit"].as_object().cloned())
+        })
+        .expect("commit details");
+    assert_eq!(details["bypass_reason"], "regenerate issues");
+    assert_eq!(
+        details["protected_bypassed"],
+        serde_json::json!([".beads/issues.jsonl"])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn readonly_blocks_even_with_allow_protected() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.write_file(
+        ".sv.toml",
+        "[protect]\nmode = \"readonly\"\npaths = [\"vendor/**\"]\n",
+    )?;
+    repo.write_file("vendor/lib.rs", "// v1\n")?;
+    repo.commit_all("initial commit")?;
+    repo.write_file("vendor/lib.rs", "// v2\n")?;
+
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args([
+            "commit",
+            "-a",
+            "-m",
+            "edit vendor",
+            "--allow-protected",
+            "--json",
+        ])
+        .output()?;
+    assert_eq!(output.status.code(), Some(3));
+    let payload: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        payload["error"]["details"]["protected_violations"][0]["mode"],
+        "readonly"
+    );
+
+    // A per-workspace `protect off` does not lift a readonly rule
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["protect", "off", "vendor/**"])
+        .assert()
+        .success();
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "-a", "-m", "edit vendor"])
+        .assert()
+        .code(3);
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "-a", "-m", "edit vendor", "--override-readonly"])
+        .args(["--reason", "upstream sync"])
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn override_readonly_does_not_bypass_guard() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.write_file(
+        ".sv.toml",
+        "[protect]\nmode = \"guard\"\npaths = [\".beads/**\"]\n",
+    )?;
+    repo.write_file(".beads/issues.jsonl", "[]\n")?;
+    repo.commit_all("initial commit")?;
+    repo.write_file(".beads/issues.jsonl", "[1]\n")?;
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "-a", "-m", "edit", "--override-readonly"])
+        .assert()
+        .code(3);
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "-a", "-m", "edit", "--reason", "why"])
+        .assert()
+        .code(2)
+        .stderr(contains("--allow-protected"));
+
+    Ok(())
+}
+
 #[test]
 fn protect_add_and_rm_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
     let repo = TestRepo::init()?;
@@ -106,6 +311,98 @@ fn protect_add_and_rm_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn protect_rm_glob_removes_matching_patterns() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["protect", "add", "src/gen/**", "src/*.lock", "Cargo.lock"])
+        .assert()
+        .success();
+
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["protect", "rm", "src/*", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let payload: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        payload["data"]["removed"],
+        serde_json::json!(["src/gen/**", "src/*.lock"])
+    );
+
+    let contents = fs::read_to_string(repo.path().join(".sv.toml"))?;
+    assert!(!contents.contains("src/"));
+    assert!(contents.contains("Cargo.lock"));
+
+    // An argument equal to a configured pattern removes only that pattern.
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["protect", "add", "*.lock", "Cargo.lock"])
+        .assert()
+        .success();
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["protect", "rm", "*.lock"])
+        .assert()
+        .success();
+    let contents = fs::read_to_string(repo.path().join(".sv.toml"))?;
+    assert!(!contents.contains("\"*.lock\""));
+    assert!(contents.contains("Cargo.lock"));
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["protect", "rm", "docs/*"])
+        .assert()
+        .failure()
+        .stderr(contains("docs/*"));
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["protect", "rm", "docs/*", "--force"])
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn protect_add_rejects_invalid_glob_and_dedupes() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    let config_path = repo.path().join(".sv.toml");
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["protect", "add", "docs/**", "[unclosed"])
+        .assert()
+        .code(2)
+        .stderr(contains("[unclosed"));
+    assert!(!config_path.exists());
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["protect", "add", "docs/**"])
+        .assert()
+        .success();
+
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["protect", "add", "docs/**", "*.lock", "*.lock", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let payload: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(payload["data"]["added"], serde_json::json!(["*.lock"]));
+    assert_eq!(
+        payload["data"]["already_exists"],
+        serde_json::json!(["docs/**"])
+    );
+
+    let contents = fs::read_to_string(&config_path)?;
+    assert_eq!(contents.matches("*.lock").count(), 1);
+
+    Ok(())
+}
+
 #[test]
 fn protect_status_reports_staged_matches() -> Result<(), Box<dyn std::error::Error>> {
     let repo = TestRepo::init()?;
@@ -0,0 +1,32 @@
+mod support;
+
+#[test]
+fn completions_generate_scripts_outside_a_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+
+    for (shell, marker) in [
+        ("bash", "_sv()"),
+        ("zsh", "#compdef sv"),
+        ("fish", "complete -c sv"),
+    ] {
+        let output = support::sv_cmd()
+            .current_dir(dir.path())
+            .args(["completions", shell])
+            .output()?;
+        assert!(output.status.success(), "{shell}");
+        let script = String::from_utf8(output.stdout)?;
+        assert!(script.contains(marker), "{shell} script missing {marker}");
+        assert!(
+            script.contains("lease"),
+            "{shell} script missing subcommands"
+        );
+    }
+
+    support::sv_cmd()
+        .current_dir(dir.path())
+        .args(["completions", "tcsh"])
+        .assert()
+        .code(2);
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+mod support;
+
+use assert_cmd::Command;
+use serde_json::Value;
+
+use support::TestRepo;
+
+fn sv_cmd(repo: &TestRepo) -> Command {
+    let mut cmd = support::sv_cmd();
+    cmd.current_dir(repo.path());
+    cmd
+}
+
+#[test]
+fn config_show_effective_applies_local_overlay() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.write_sv_config("base = \"main\"\n\n[leases]\ndefault_ttl = \"4h\"\n")?;
+    repo.write_file(".sv.local.toml", "base = \"develop\"\n")?;
+
+    let output = sv_cmd(&repo)
+        .args(["config", "show", "--effective", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let payload: Value = serde_json::from_slice(&output.stdout)?;
+    let data = &payload["data"];
+    assert_eq!(data["config"]["base"], "develop");
+    assert_eq!(data["config"]["leases"]["default_ttl"], "4h");
+    assert_eq!(data["origins"]["base"], "local");
+    assert_eq!(data["origins"]["leases.default_ttl"], "repo");
+    assert!(data["sources"]["local"]
+        .as_str()
+        .is_some_and(|path| path.ends_with(".sv.local.toml")));
+
+    let output = sv_cmd(&repo).args(["config", "show", "--json"]).output()?;
+    assert!(output.status.success());
+    let payload: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(payload["data"]["config"]["base"], "main");
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+mod support;
+
+use serde_json::Value;
+
+use support::TestRepo;
+
+fn env_json(cmd: &mut assert_cmd::Command) -> Result<Value, Box<dyn std::error::Error>> {
+    let output = cmd.args(["env", "--json"]).output()?;
+    assert!(output.status.success());
+    let payload: Value = serde_json::from_slice(&output.stdout)?;
+    Ok(payload["data"].clone())
+}
+
+#[test]
+fn env_reports_repo_actor_source_and_config_files() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.write_sv_config("base = \"main\"\n")?;
+    repo.write_file(".sv.local.toml", "base = \"develop\"\n")?;
+
+    let data = env_json(
+        support::sv_cmd()
+            .current_dir(repo.path())
+            .env("SV_ACTOR", "env-agent"),
+    )?;
+    assert!(data["repo"].as_str().is_some());
+    assert!(data["state"]["shared"]
+        .as_str()
+        .is_some_and(|path| path.ends_with("sv")));
+    assert_eq!(data["actor"]["name"], "env-agent");
+    assert_eq!(data["actor"]["source"], "env");
+    assert_eq!(data["env"]["SV_ACTOR"], "env-agent");
+    assert!(data["config"]["repo"]
+        .as_str()
+        .is_some_and(|path| path.ends_with(".sv.toml")));
+    assert!(data["config"]["local"]
+        .as_str()
+        .is_some_and(|path| path.ends_with(".sv.local.toml")));
+
+    let data = env_json(
+        support::sv_cmd()
+            .current_dir(repo.path())
+            .env("SV_ACTOR", "env-agent")
+            .args(["--actor", "cli-agent"]),
+    )?;
+    assert_eq!(data["actor"]["name"], "cli-agent");
+    assert_eq!(data["actor"]["source"], "cli");
+
+    // An explicit flag is cli even when it matches SV_ACTOR
+    let data = env_json(
+        support::sv_cmd()
+            .current_dir(repo.path())
+            .env("SV_ACTOR", "env-agent")
+            .args(["--actor", "env-agent"]),
+    )?;
+    assert_eq!(data["actor"]["source"], "cli");
+
+    let outside = tempfile::tempdir()?;
+    let data = env_json(
+        support::sv_cmd()
+            .current_dir(outside.path())
+            .env_remove("SV_REPO"),
+    )?;
+    assert!(data["repo"].is_null());
+    assert!(data["libgit2_version"].as_str().is_some());
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+mod support;
+
+use assert_cmd::Command;
+use serde_json::Value;
+use support::TestRepo;
+
+fn sv_cmd(repo: &TestRepo) -> Command {
+    let mut cmd = support::sv_cmd();
+    cmd.current_dir(repo.path());
+    cmd.env("SV_ACTOR", "alice");
+    cmd
+}
+
+#[test]
+fn events_validate_reports_malformed_lines() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    let events_path = repo.path().join("events.jsonl");
+
+    sv_cmd(&repo)
+        .args(["take", "src/lib.rs", "--events"])
+        .arg(&events_path)
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["release", "src/lib.rs", "--events"])
+        .arg(&events_path)
+        .assert()
+        .success();
+
+    let validate = |repo: &TestRepo| -> Result<Value, Box<dyn std::error::Error>> {
+        let output = sv_cmd(repo)
+            .args(["events", "validate", "--json"])
+            .arg(&events_path)
+            .output()?;
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Ok(serde_json::from_slice(&output.stdout)?)
+    };
+
+    let clean = validate(&repo)?;
+    assert_eq!(clean["data"]["lines"], 2);
+    assert_eq!(clean["data"]["valid"], 2);
+    assert_eq!(clean["data"]["invalid"].as_array().map(Vec::len), Some(0));
+
+    let mut contents = std::fs::read_to_string(&events_path)?;
+    contents.push_str("{not json\n\n");
+    contents.push_str(
+        r#"{"schema_version":"sv.event.v1","event":"lease_stolen","timestamp":"2025-01-01T00:00:00Z"}"#,
+    );
+    contents.push('\n');
+    std::fs::write(&events_path, contents)?;
+
+    let report = validate(&repo)?;
+    let data = &report["data"];
+    assert_eq!(data["lines"], 4);
+    assert_eq!(data["valid"], 2);
+    assert_eq!(data["invalid"][0]["line"], 3);
+    assert!(data["invalid"][0]["error"]
+        .as_str()
+        .unwrap_or_default()
+        .contains("invalid JSON"));
+    assert_eq!(data["invalid"][1]["line"], 5);
+    assert!(data["invalid"][1]["error"]
+        .as_str()
+        .unwrap_or_default()
+        .contains("unknown event kind 'lease_stolen'"));
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+mod support;
+
+use assert_cmd::Command;
+use support::TestRepo;
+
+fn sv_cmd(repo: &TestRepo) -> Command {
+    let mut cmd = support::sv_cmd();
+    cmd.current_dir(repo.path());
+    cmd.env("SV_ACTOR", "alice");
+    cmd
+}
+
+#[cfg(feature = "webhook")]
+#[test]
+fn take_posts_events_to_webhook() {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let repo = TestRepo::init().expect("init repo");
+    repo.init_sv_dirs().expect("init sv dirs");
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+    let url = format!("http://{}/events", listener.local_addr().expect("addr"));
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("accept");
+        let mut reader = BufReader::new(stream);
+        let mut content_length = 0;
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).expect("request line");
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).expect("header");
+            if header.trim().is_empty() {
+                break;
+            }
+            if let Some(value) = header.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().expect("content length");
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).expect("body");
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+            .expect("respond");
+        (request_line, String::from_utf8(body).expect("utf8"))
+    });
+
+    sv_cmd(&repo)
+        .args(["take", "src/lib.rs", "--events", &url])
+        .assert()
+        .success();
+
+    let (request_line, body) = server.join().expect("server thread");
+    assert!(request_line.starts_with("POST /events"));
+    let event: serde_json::Value = serde_json::from_str(&body).expect("event json");
+    assert_eq!(event["event"], "lease_created");
+}
+
+#[cfg(not(feature = "webhook"))]
+#[test]
+fn webhook_destination_requires_feature() {
+    let repo = TestRepo::init().expect("init repo");
+    repo.init_sv_dirs().expect("init sv dirs");
+
+    sv_cmd(&repo)
+        .args([
+            "take",
+            "src/lib.rs",
+            "--events",
+            "http://127.0.0.1:9/events",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("`webhook` feature"));
+}
@@ -95,3 +95,205 @@ cmd = "printf 'close:{task_id}:{actor}:{loop_ref}\\n' >> hooks.txt"
     assert!(hooks.contains(&format!("start:{id}:alice:alice\n")));
     assert!(hooks.contains(&format!("close:{id}:alice:alice\n")));
 }
+
+#[test]
+fn task_new_records_forge_issue_ref_when_enabled() {
+    let repo = TestRepo::init().expect("repo");
+    repo.commit_file("README.md", "test\n", "init")
+        .expect("commit");
+
+    repo.write_sv_config(
+        r#"
+[integrations.forge]
+enabled = true
+create_issues = true
+
+[integrations.forge.on_task_create]
+cmd = "printf 'https://example.test/issues/{task_id}\\n'"
+"#
+        .trim(),
+    )
+    .expect("write config");
+
+    let new_out = sv_cmd()
+        .current_dir(repo.path())
+        .env("SV_ACTOR", "alice")
+        .args(["task", "new", "T1", "--json"])
+        .output()
+        .expect("task new");
+    assert!(new_out.status.success());
+    let envelope: serde_json::Value = serde_json::from_slice(&new_out.stdout).expect("json");
+    let id = envelope["data"]["id"].as_str().expect("id").to_string();
+    let expected = format!("https://example.test/issues/{id}");
+    assert_eq!(
+        envelope["data"]["forge_ref"].as_str(),
+        Some(expected.as_str())
+    );
+
+    let show_out = sv_cmd()
+        .current_dir(repo.path())
+        .args(["task", "show", &id, "--json"])
+        .output()
+        .expect("task show");
+    let show: serde_json::Value = serde_json::from_slice(&show_out.stdout).expect("json");
+    assert_eq!(
+        show["data"]["task"]["forge_ref"].as_str(),
+        Some(expected.as_str())
+    );
+
+    // A failing hook only warns; the task is still created without a ref.
+    repo.write_sv_config(
+        r#"
+[integrations.forge]
+enabled = true
+create_issues = true
+
+[integrations.forge.on_task_create]
+cmd = "exit 3"
+"#
+        .trim(),
+    )
+    .expect("write config");
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["task", "new", "T2"])
+        .assert()
+        .success()
+        .stdout(contains("forge hooks: command failed"));
+}
+
+#[test]
+fn commit_adds_task_trailer_when_link_commits_enabled() {
+    let repo = TestRepo::init().expect("repo");
+    repo.commit_file("README.md", "test\n", "init")
+        .expect("commit");
+    sv_cmd()
+        .current_dir(repo.path())
+        .arg("init")
+        .assert()
+        .success();
+    sv_cmd()
+        .current_dir(repo.path())
+        .env("SV_ACTOR", "alice")
+        .args(["ws", "here", "--name", "local"])
+        .assert()
+        .success();
+
+    repo.write_sv_config(
+        r#"
+[integrations.forge]
+enabled = true
+link_commits = true
+"#
+        .trim(),
+    )
+    .expect("write config");
+
+    let new_out = sv_cmd()
+        .current_dir(repo.path())
+        .args(["task", "new", "T1", "--json"])
+        .output()
+        .expect("task new");
+    let envelope: serde_json::Value = serde_json::from_slice(&new_out.stdout).expect("json");
+    let id = envelope["data"]["id"].as_str().expect("id").to_string();
+    sv_cmd()
+        .current_dir(repo.path())
+        .env("SV_ACTOR", "alice")
+        .args(["task", "start", &id])
+        .assert()
+        .success();
+
+    repo.write_file("src.txt", "one\n").expect("write");
+    repo.stage_path("src.txt").expect("stage");
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "-m", "Add src"])
+        .assert()
+        .success();
+
+    let head_message = || {
+        repo.repo()
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .map(|commit| commit.message().unwrap_or_default().to_string())
+            .expect("head message")
+    };
+    let message = head_message();
+    assert!(message.contains("Change-Id: "), "{message}");
+    assert!(message.ends_with(&format!("\nTask: {id}\n")), "{message}");
+
+    // Amending keeps a single trailer.
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["commit", "--amend", "--no-edit"])
+        .assert()
+        .success();
+    let amended = head_message();
+    assert_eq!(amended.matches(&format!("Task: {id}")).count(), 1);
+}
+
+#[test]
+fn forge_hook_failures_are_reported_in_json() {
+    let repo = TestRepo::init().expect("repo");
+    repo.commit_file("README.md", "test\n", "init")
+        .expect("commit");
+    sv_cmd()
+        .current_dir(repo.path())
+        .arg("init")
+        .assert()
+        .success();
+    sv_cmd()
+        .current_dir(repo.path())
+        .env("SV_ACTOR", "alice")
+        .args(["ws", "here", "--name", "local"])
+        .assert()
+        .success();
+
+    repo.write_sv_config(
+        r#"
+[integrations.forge]
+enabled = true
+create_issues = true
+
+[integrations.forge.on_task_create]
+cmd = "echo create-broke >&2; exit 4"
+
+[integrations.forge.on_task_start]
+cmd = "echo start-broke >&2; exit 5"
+
+[integrations.forge.on_task_close]
+cmd = "true"
+"#
+        .trim(),
+    )
+    .expect("write config");
+
+    let json = |args: &[&str]| -> serde_json::Value {
+        let output = sv_cmd()
+            .current_dir(repo.path())
+            .env("SV_ACTOR", "alice")
+            .args(args)
+            .output()
+            .expect("run sv");
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).expect("json")
+    };
+
+    let created = json(&["task", "new", "T1", "--json"]);
+    let id = created["data"]["id"].as_str().expect("id").to_string();
+    let warnings = created["data"]["hook_warnings"]
+        .as_array()
+        .expect("warnings");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].as_str().unwrap().contains("create-broke"));
+
+    let started = json(&["task", "start", &id, "--json"]);
+    let warnings = started["data"]["hook_warnings"]
+        .as_array()
+        .expect("warnings");
+    assert!(warnings[0].as_str().unwrap().contains("start-broke"));
+
+    // Successful hooks leave no hook_warnings field.
+    let closed = json(&["task", "close", &id, "--json"]);
+    assert!(closed["data"].get("hook_warnings").is_none());
+}
@@ -0,0 +1,479 @@
+mod support;
+
+use std::path::Path;
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use serde_json::Value;
+
+use support::TestRepo;
+
+fn sv_cmd(dir: &Path) -> Command {
+    let mut cmd = support::sv_cmd();
+    cmd.current_dir(dir).env("SV_ACTOR", "alice");
+    cmd
+}
+
+fn setup_repo() -> Result<(TestRepo, String), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    let base = repo.commit_file("README.md", "base\n", "initial commit")?;
+    sv_cmd(repo.path())
+        .args(["ws", "new", "agent1", "--base", &base.to_string()])
+        .assert()
+        .success();
+    Ok((repo, base.to_string()))
+}
+
+#[test]
+fn hoist_selector_typo_reports_parse_error() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+
+    sv_cmd(repo.path())
+        .args(["hoist", "-s", "ws(actve)", "-d", &base, "--dry-run"])
+        .assert()
+        .failure()
+        .stderr(contains("Unknown predicate 'actve' at 3"))
+        .stderr(contains("     ^"))
+        .stderr(contains("--legacy-selector"));
+
+    Ok(())
+}
+
+#[test]
+fn hoist_selector_accepts_obvious_legacy_forms() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+
+    for selector in ["agent*", "actor:alice", "agent1"] {
+        let output = sv_cmd(repo.path())
+            .args(["hoist", "-s", selector, "-d", &base, "--dry-run", "--json"])
+            .output()?;
+        assert!(output.status.success(), "selector {selector} failed");
+        let value: Value = serde_json::from_slice(&output.stdout)?;
+        assert_eq!(value["workspaces"][0], "agent1", "selector {selector}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn hoist_legacy_selector_flag_restores_fallback() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+
+    sv_cmd(repo.path())
+        .args(["hoist", "-s", "ws(actve)", "-d", &base, "--dry-run"])
+        .arg("--legacy-selector")
+        .assert()
+        .failure()
+        .stderr(contains("no workspaces match selector 'ws(actve)'"));
+
+    Ok(())
+}
+
+fn git(dir: &Path, args: &[&str]) -> std::process::Output {
+    std::process::Command::new("git")
+        .args([
+            "-c",
+            "user.name=sv-test",
+            "-c",
+            "user.email=sv-test@example.com",
+        ])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("run git")
+}
+
+fn commit_in(dir: &Path, file: &str, contents: &str, message: &str) -> String {
+    std::fs::write(dir.join(file), contents).expect("write file");
+    assert!(git(dir, &["add", file]).status.success());
+    assert!(git(dir, &["commit", "-m", message]).status.success());
+    let out = git(dir, &["rev-parse", "HEAD"]);
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+}
+
+#[test]
+fn hoist_resolve_commits_resolution_and_replays_skipped() -> Result<(), Box<dyn std::error::Error>>
+{
+    let (repo, base) = setup_repo()?;
+    let dest = String::from_utf8(git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]).stdout)?
+        .trim()
+        .to_string();
+    sv_cmd(repo.path())
+        .args(["ws", "new", "agent2", "--base", &base])
+        .assert()
+        .success();
+    let ws1 = repo.path().join(".sv/worktrees/agent1");
+    let ws2 = repo.path().join(".sv/worktrees/agent2");
+    commit_in(&ws1, "README.md", "alpha\n", "alpha readme");
+    let conflicting = commit_in(&ws2, "README.md", "bravo\n", "bravo readme");
+    let follow_up = commit_in(&ws2, "other.txt", "other\n", "bravo other");
+
+    sv_cmd(repo.path())
+        .args([
+            "hoist",
+            "-s",
+            "agent*",
+            "-d",
+            &dest,
+            "--no-propagate-conflicts",
+        ])
+        .assert()
+        .success();
+
+    let integration = format!("sv/hoist/{dest}");
+    assert!(git(repo.path(), &["checkout", &integration])
+        .status
+        .success());
+    git(repo.path(), &["cherry-pick", "-n", &conflicting]);
+    std::fs::write(repo.path().join("README.md"), "alpha and bravo\n")?;
+
+    let short = &conflicting[..8];
+    let output = sv_cmd(repo.path())
+        .args(["hoist", "resolve", short, "--json"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["data"]["resolved"], conflicting.as_str());
+    assert_eq!(value["data"]["resolution"], "applied");
+    assert_eq!(value["data"]["applied"][0], follow_up.as_str());
+    assert_eq!(
+        value["data"]["remaining_conflicts"]
+            .as_array()
+            .map(Vec::len),
+        Some(0)
+    );
+    assert_eq!(value["data"]["status"], "completed");
+
+    let readme = git(repo.path(), &["show", &format!("{integration}:README.md")]);
+    assert_eq!(String::from_utf8_lossy(&readme.stdout), "alpha and bravo\n");
+    assert!(repo.path().join("other.txt").exists());
+
+    sv_cmd(repo.path())
+        .args(["hoist", "resolve", short])
+        .assert()
+        .failure()
+        .stderr(contains("is not in conflict"));
+
+    Ok(())
+}
+
+#[test]
+fn hoist_squash_creates_one_commit_per_workspace() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+    let dest = String::from_utf8(git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]).stdout)?
+        .trim()
+        .to_string();
+    let ws1 = repo.path().join(".sv/worktrees/agent1");
+    commit_in(&ws1, "a.txt", "one\n", "first change");
+    commit_in(&ws1, "b.txt", "two\n", "second change");
+
+    sv_cmd(repo.path())
+        .args([
+            "hoist",
+            "-s",
+            "agent1",
+            "-d",
+            &base,
+            "--squash",
+            "--strategy",
+            "merge",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("--squash requires --strategy stack or rebase"));
+
+    let output = sv_cmd(repo.path())
+        .args(["hoist", "-s", "agent1", "-d", &dest, "--squash", "--json"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["squash"], true);
+
+    let integration = format!("sv/hoist/{dest}");
+    let count = git(
+        repo.path(),
+        &["rev-list", "--count", &format!("{base}..{integration}")],
+    );
+    assert_eq!(String::from_utf8_lossy(&count.stdout).trim(), "1");
+    let message = git(repo.path(), &["log", "-1", "--format=%B", &integration]);
+    assert_eq!(
+        String::from_utf8_lossy(&message.stdout).trim(),
+        "agent1: squash 2 commits\n\n- first change\n- second change"
+    );
+    let files = git(repo.path(), &["ls-tree", "--name-only", &integration]);
+    let files = String::from_utf8_lossy(&files.stdout);
+    assert!(files.contains("a.txt") && files.contains("b.txt"));
+
+    Ok(())
+}
+
+#[test]
+fn hoist_records_replayed_paths_in_op_log() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, _base) = setup_repo()?;
+    let dest = String::from_utf8(git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]).stdout)?
+        .trim()
+        .to_string();
+    let ws1 = repo.path().join(".sv/worktrees/agent1");
+    commit_in(&ws1, "a.rs", "one\n", "first change");
+    commit_in(&ws1, "b.txt", "two\n", "second change");
+
+    sv_cmd(repo.path())
+        .args(["hoist", "-s", "agent1", "-d", &dest, "--no-apply"])
+        .assert()
+        .success();
+
+    let output = sv_cmd(repo.path())
+        .args(["op", "log", "--touching", "*.rs", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    let records = value["records"].as_array().ok_or("records")?;
+    let hoist = records
+        .iter()
+        .find(|record| {
+            record["command"]
+                .as_str()
+                .is_some_and(|c| c.starts_with("sv hoist"))
+        })
+        .ok_or("hoist record missing")?;
+    assert_eq!(
+        hoist["affected_paths"],
+        serde_json::json!(["a.rs", "b.txt"])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn hoist_resolve_rejects_squashed_hoist() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+    let dest = String::from_utf8(git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]).stdout)?
+        .trim()
+        .to_string();
+    sv_cmd(repo.path())
+        .args(["ws", "new", "agent2", "--base", &base])
+        .assert()
+        .success();
+    commit_in(
+        &repo.path().join(".sv/worktrees/agent1"),
+        "README.md",
+        "alpha\n",
+        "alpha readme",
+    );
+    commit_in(
+        &repo.path().join(".sv/worktrees/agent2"),
+        "notes.txt",
+        "notes\n",
+        "bravo notes",
+    );
+    let conflicting = commit_in(
+        &repo.path().join(".sv/worktrees/agent2"),
+        "README.md",
+        "bravo\n",
+        "bravo readme",
+    );
+
+    sv_cmd(repo.path())
+        .args([
+            "hoist",
+            "-s",
+            "agent*",
+            "-d",
+            &dest,
+            "--squash",
+            "--no-propagate-conflicts",
+        ])
+        .assert()
+        .success();
+    let storage = sv::storage::Storage::for_repo(repo.path().to_path_buf());
+    assert!(storage.read_hoist_state(&dest)?.expect("state").squash);
+
+    sv_cmd(repo.path())
+        .args(["hoist", "resolve", &conflicting, "--dest", &dest])
+        .assert()
+        .failure()
+        .stderr(contains("--squash"));
+
+    Ok(())
+}
+
+fn write_in_progress_state(
+    repo: &TestRepo,
+    dest: &str,
+    integration_ref: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let now = chrono::Utc::now();
+    sv::storage::Storage::for_repo(repo.path().to_path_buf()).write_hoist_state(
+        &sv::storage::HoistState {
+            hoist_id: "hoist-running".to_string(),
+            dest_ref: dest.to_string(),
+            integration_ref: integration_ref.to_string(),
+            status: sv::storage::HoistStatus::InProgress,
+            started_at: now,
+            updated_at: now,
+            commits: vec![],
+            squash: false,
+        },
+    )?;
+    Ok(())
+}
+
+#[test]
+fn hoist_refuses_integration_branch_of_hoist_in_progress() -> Result<(), Box<dyn std::error::Error>>
+{
+    let (repo, _base) = setup_repo()?;
+    let dest = String::from_utf8(git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]).stdout)?
+        .trim()
+        .to_string();
+    commit_in(
+        &repo.path().join(".sv/worktrees/agent1"),
+        "a.txt",
+        "a\n",
+        "change",
+    );
+    let integration = format!("sv/hoist/{dest}");
+    write_in_progress_state(&repo, &dest, &integration)?;
+
+    sv_cmd(repo.path())
+        .args(["hoist", "-s", "agent1", "-d", &dest, "--no-apply"])
+        .assert()
+        .failure()
+        .stderr(contains(format!(
+            "integration branch {integration} is in use by hoist hoist-running"
+        )))
+        .stderr(contains("--name <branch>"));
+
+    let output = sv_cmd(repo.path())
+        .args(["hoist", "-s", "agent1", "-d", &dest, "--no-apply"])
+        .args(["--name", "integration/alt", "--json"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["integration_ref"], "integration/alt");
+    assert!(
+        git(repo.path(), &["rev-parse", "--verify", "integration/alt"])
+            .status
+            .success()
+    );
+
+    // The running hoist's state is left alone.
+    let storage = sv::storage::Storage::for_repo(repo.path().to_path_buf());
+    let running = storage.read_hoist_state(&dest)?.expect("state");
+    assert_eq!(running.hoist_id, "hoist-running");
+
+    sv_cmd(repo.path())
+        .args([
+            "hoist",
+            "-s",
+            "agent1",
+            "-d",
+            &dest,
+            "--no-apply",
+            "--force",
+        ])
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn hoist_refuses_integration_branch_with_unresolved_conflicts(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+    let dest = String::from_utf8(git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]).stdout)?
+        .trim()
+        .to_string();
+    sv_cmd(repo.path())
+        .args(["ws", "new", "agent2", "--base", &base])
+        .assert()
+        .success();
+    commit_in(
+        &repo.path().join(".sv/worktrees/agent1"),
+        "README.md",
+        "alpha\n",
+        "alpha readme",
+    );
+    let conflicting = commit_in(
+        &repo.path().join(".sv/worktrees/agent2"),
+        "README.md",
+        "bravo\n",
+        "bravo readme",
+    );
+
+    let hoist = |extra: &[&str]| {
+        let mut cmd = sv_cmd(repo.path());
+        cmd.args([
+            "hoist",
+            "-s",
+            "agent*",
+            "-d",
+            &dest,
+            "--no-propagate-conflicts",
+        ])
+        .args(extra);
+        cmd
+    };
+    hoist(&[]).assert().success();
+
+    let storage = sv::storage::Storage::for_repo(repo.path().to_path_buf());
+    let failed = storage.read_hoist_state(&dest)?.expect("state");
+    assert_eq!(failed.status, sv::storage::HoistStatus::Failed);
+
+    let integration = format!("sv/hoist/{dest}");
+    let tip = git(repo.path(), &["rev-parse", &integration]).stdout;
+    hoist(&[])
+        .assert()
+        .failure()
+        .stderr(contains(format!(
+            "integration branch {integration} is in use by hoist {}",
+            failed.hoist_id
+        )))
+        .stderr(contains("sv hoist resolve"));
+
+    // Neither the branch nor the state awaiting resolve was touched
+    assert_eq!(git(repo.path(), &["rev-parse", &integration]).stdout, tip);
+    let state = storage.read_hoist_state(&dest)?.expect("state");
+    assert_eq!(state.hoist_id, failed.hoist_id);
+    assert!(state
+        .commits
+        .iter()
+        .any(|commit| commit.commit_id == conflicting
+            && commit.status == sv::storage::HoistCommitStatus::Conflict));
+
+    hoist(&["--force"]).assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn hoist_name_refuses_existing_branch_without_force() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+    assert!(git(repo.path(), &["branch", "feature", &base])
+        .status
+        .success());
+
+    sv_cmd(repo.path())
+        .args(["hoist", "-s", "agent1", "-d", &base, "--dry-run"])
+        .args(["--name", "feature"])
+        .assert()
+        .failure()
+        .stderr(contains(
+            "branch feature already exists and is not a hoist integration branch",
+        ));
+
+    Ok(())
+}
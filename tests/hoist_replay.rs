@@ -3,7 +3,7 @@ use std::process::Command;
 
 use git2::{Oid, Repository};
 
-use sv::hoist::{replay_commits, ReplayOptions};
+use sv::hoist::{replay_commits, replay_commits_with_progress, ReplayOptions};
 use sv::storage::HoistCommitStatus;
 
 fn git(repo: &Path, args: &[&str]) {
@@ -140,3 +140,30 @@ fn replay_commits_continues_on_conflict() {
     let applied = outcome.entries[1].applied_id.expect("applied");
     assert!(tree_has_path(&repo, applied, "other.txt"));
 }
+
+#[test]
+fn replay_commits_reports_progress_per_commit() {
+    let (dir, repo) = init_repo();
+    let (integration_ref, commits) = setup_conflict_commits(dir.path());
+
+    let mut steps = Vec::new();
+    replay_commits_with_progress(
+        &repo,
+        &integration_ref,
+        &commits,
+        &ReplayOptions {
+            continue_on_conflict: true,
+            propagate_conflicts: false,
+        },
+        &mut |n, total, summary| steps.push((n, total, summary.to_string())),
+    )
+    .expect("replay");
+
+    assert_eq!(
+        steps,
+        vec![
+            (1, 2, "feature change".to_string()),
+            (2, 2, "feature clean".to_string()),
+        ]
+    );
+}
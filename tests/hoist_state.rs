@@ -37,6 +37,7 @@ fn hoist_state_roundtrip() {
             change_id: Some("change-1".to_string()),
             summary: Some("Add auth guard".to_string()),
         }],
+        squash: false,
     };
 
     storage.write_hoist_state(&state).expect("write state");
@@ -84,6 +85,7 @@ fn hoist_clear_removes_state_and_conflicts() {
         started_at: parse_ts("2025-03-03T08:00:00Z"),
         updated_at: parse_ts("2025-03-03T08:10:00Z"),
         commits: vec![],
+        squash: false,
     };
 
     let record = HoistConflict {
@@ -113,3 +115,39 @@ fn hoist_clear_removes_state_and_conflicts() {
     assert!(state_after.is_none());
     assert!(conflicts_after.is_empty());
 }
+
+#[test]
+fn named_hoist_state_does_not_replace_default_state() {
+    let (_temp, storage) = setup_storage();
+
+    let state = |hoist_id: &str, integration_ref: &str| HoistState {
+        hoist_id: hoist_id.to_string(),
+        dest_ref: "main".to_string(),
+        integration_ref: integration_ref.to_string(),
+        status: HoistStatus::InProgress,
+        started_at: parse_ts("2025-04-04T09:00:00Z"),
+        updated_at: parse_ts("2025-04-04T09:00:00Z"),
+        commits: vec![],
+        squash: false,
+    };
+    let default = state("hoist-default", "sv/hoist/main");
+    let named = state("hoist-named", "release/integration");
+
+    storage.write_hoist_state(&default).expect("write default");
+    storage.write_hoist_state(&named).expect("write named");
+
+    let loaded = storage
+        .read_hoist_state("main")
+        .expect("read state")
+        .expect("state exists");
+    assert_eq!(loaded, default);
+
+    let mut ids: Vec<String> = storage
+        .list_hoist_states()
+        .expect("list states")
+        .into_iter()
+        .map(|state| state.hoist_id)
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec!["hoist-default", "hoist-named"]);
+}
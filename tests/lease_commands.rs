@@ -7,6 +7,7 @@ use std::time::Duration;
 
 use assert_cmd::Command;
 use support::TestRepo;
+use sv::lease::{LeaseIntent, LeaseStrength};
 
 fn setup_repo() -> TestRepo {
     let repo = TestRepo::init().expect("init repo");
@@ -67,7 +68,11 @@ fn lease_ls_lists_active_leases() {
         .args(["lease", "ls"])
         .assert()
         .success()
-        .stdout(contains("src/lib.rs").and(contains("by alice")));
+        .stdout(
+            contains("src/lib.rs")
+                .and(contains("alice"))
+                .and(contains("STRENGTH")),
+        );
 }
 
 #[test]
@@ -288,3 +293,1470 @@ fn ownerless_lease_is_labeled() {
         .success()
         .stdout(contains("(ownerless)"));
 }
+
+#[test]
+fn take_dry_run_does_not_write_leases() {
+    let repo = setup_repo();
+
+    let output = sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--dry-run", "--json"])
+        .output()
+        .expect("run take");
+    assert!(output.status.success());
+
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(value["data"]["would"], "take");
+    assert_eq!(value["data"]["summary"]["created"], 1);
+    assert!(repo.read_leases().expect("read leases").is_empty());
+}
+
+#[test]
+fn release_dry_run_keeps_lease_active() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs"])
+        .assert()
+        .success();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "src/lib.rs", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains("would release 1 lease(s)"));
+
+    sv_cmd(&repo)
+        .args(["lease", "ls"])
+        .assert()
+        .success()
+        .stdout(contains("src/lib.rs"));
+}
+
+#[test]
+fn take_clamps_ttl_to_max_ttl() {
+    let repo = setup_repo();
+    repo.write_sv_config("[leases]\nmax_ttl = \"4h\"\n")
+        .expect("write config");
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--ttl", "30d"])
+        .assert()
+        .success()
+        .stdout(contains("clamped to 4h"));
+
+    let leases = repo.read_leases().expect("read leases");
+    assert_eq!(leases[0].ttl, "4h");
+}
+
+#[test]
+fn take_rejects_ttl_over_max_ttl() {
+    let repo = setup_repo();
+    repo.write_sv_config("[leases]\nmax_ttl = \"4h\"\nttl_overflow = \"reject\"\n")
+        .expect("write config");
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--ttl", "30d"])
+        .assert()
+        .failure()
+        .stderr(contains("exceeds leases.max_ttl"));
+
+    assert!(repo.read_leases().expect("read leases").is_empty());
+}
+
+#[test]
+fn take_respects_max_per_actor() {
+    let repo = setup_repo();
+    repo.write_sv_config("[leases]\nmax_per_actor = 2\n")
+        .expect("write config");
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/a.rs", "src/b.rs"])
+        .assert()
+        .success();
+    // Renewing a held path does not count as a new lease.
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/a.rs", "--ttl", "4h"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "bob")
+        .args(["take", "src/c.rs"])
+        .assert()
+        .success();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/c.rs"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(
+            contains("over leases.max_per_actor (2)")
+                .and(contains("src/a.rs (cooperative), src/b.rs (cooperative)"))
+                .and(contains("sv release")),
+        );
+    assert_eq!(repo.read_leases().expect("read leases").len(), 3);
+}
+
+#[test]
+fn take_respects_max_exclusive_per_actor() {
+    let repo = setup_repo();
+    repo.write_sv_config("[leases]\nmax_exclusive_per_actor = 1\n")
+        .expect("write config");
+    let take_exclusive = |path: &str| {
+        let mut cmd = sv_cmd(&repo);
+        cmd.env("SV_ACTOR", "alice").args([
+            "take",
+            path,
+            "--strength",
+            "exclusive",
+            "--note",
+            "quota",
+        ]);
+        cmd
+    };
+
+    take_exclusive("src/a.rs").assert().success();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/b.rs"])
+        .assert()
+        .success();
+    take_exclusive("src/c.rs")
+        .assert()
+        .failure()
+        .stderr(contains("over leases.max_exclusive_per_actor (1)"));
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "src/a.rs"])
+        .assert()
+        .success();
+    take_exclusive("src/c.rs").assert().success();
+}
+
+#[test]
+fn lease_renew_honors_max_ttl() {
+    let repo = setup_repo();
+    repo.write_sv_config("[leases]\nmax_ttl = \"2h\"\n")
+        .expect("write config");
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--ttl", "1h"])
+        .assert()
+        .success();
+    let lease_id = repo.read_leases().expect("read leases")[0].id.to_string();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["lease", "renew", &lease_id, "--ttl", "1w"])
+        .assert()
+        .success()
+        .stderr(contains("clamped to 2h"));
+    assert_eq!(repo.read_leases().expect("read leases")[0].ttl, "2h");
+
+    repo.write_sv_config("[leases]\nmax_ttl = \"2h\"\nttl_overflow = \"reject\"\n")
+        .expect("write config");
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["lease", "renew", &lease_id, "--ttl", "1w"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn workspace_scoped_leases_coexist_across_workspaces() {
+    let repo = setup_repo();
+
+    for (actor, scope) in [("alice", "ws:agent1"), ("bob", "ws:agent2")] {
+        sv_cmd(&repo)
+            .env("SV_ACTOR", actor)
+            .args([
+                "take",
+                "src/lib.rs",
+                "--strength",
+                "exclusive",
+                "--scope",
+                scope,
+                "--note",
+                "scoped",
+            ])
+            .assert()
+            .success();
+    }
+    assert_eq!(repo.read_leases().expect("read leases").len(), 2);
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "carol")
+        .args([
+            "take",
+            "src/lib.rs",
+            "--strength",
+            "exclusive",
+            "--scope",
+            "ws:agent1",
+            "--note",
+            "scoped",
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn lease_ls_format_jsonl_prints_one_lease_per_line() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "src/main.rs"])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&repo)
+        .args(["lease", "ls", "--format", "jsonl"])
+        .output()
+        .expect("run lease ls");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("utf8");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2);
+    for line in lines {
+        let value: serde_json::Value = serde_json::from_str(line).expect("json line");
+        assert_eq!(value["actor"], "alice");
+    }
+}
+
+#[test]
+fn lease_ls_by_actor_summarizes_counts() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "src/main.rs", "--ttl", "4h"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args([
+            "take",
+            "Cargo.toml",
+            "--strength",
+            "exclusive",
+            "--note",
+            "deps",
+            "--ttl",
+            "1h",
+        ])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "bob")
+        .args(["take", "docs/**", "--strength", "observe"])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&repo)
+        .args(["lease", "ls", "--by-actor", "--json"])
+        .output()
+        .expect("run lease ls");
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(report["active"], 4);
+
+    let alice = &report["actors"]["alice"];
+    assert_eq!(alice["total"], 3);
+    assert_eq!(alice["cooperative"], 2);
+    assert_eq!(alice["exclusive"], 1);
+    assert_eq!(alice["observe"], 0);
+    let exclusive_expiry = repo
+        .read_leases()
+        .expect("read leases")
+        .into_iter()
+        .find(|lease| lease.pathspec == "Cargo.toml")
+        .expect("exclusive lease")
+        .expires_at;
+    let soonest: chrono::DateTime<chrono::Utc> =
+        serde_json::from_value(alice["soonest_expiry"].clone()).expect("expiry");
+    assert_eq!(soonest, exclusive_expiry);
+    assert_eq!(report["actors"]["bob"]["observe"], 1);
+
+    sv_cmd(&repo)
+        .args(["lease", "ls", "--by-actor"])
+        .assert()
+        .success()
+        .stdout(
+            contains("ACTOR")
+                .and(contains("alice"))
+                .and(contains("bob")),
+        );
+}
+
+#[test]
+fn output_flag_separates_result_and_events() {
+    let repo = setup_repo();
+    let result_path = repo.path().join("result.json");
+    let events_path = repo.path().join("events.jsonl");
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--json", "--output"])
+        .arg(&result_path)
+        .arg("--events")
+        .arg(&events_path)
+        .assert()
+        .success()
+        .stdout("");
+
+    let result: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&result_path).expect("read result"))
+            .expect("parse result");
+    assert_eq!(result["command"], "take");
+    assert_eq!(result["status"], "success");
+
+    let events = std::fs::read_to_string(&events_path).expect("read events");
+    assert!(events.contains("lease_created"));
+}
+
+#[test]
+fn output_flag_captures_human_output() {
+    let repo = setup_repo();
+    let result_path = repo.path().join("leases.txt");
+
+    sv_cmd(&repo)
+        .args(["lease", "ls", "--output"])
+        .arg(&result_path)
+        .assert()
+        .success()
+        .stdout("");
+
+    let contents = std::fs::read_to_string(&result_path).expect("read output");
+    assert!(contents.contains("No active leases."));
+}
+
+#[test]
+fn take_uses_configured_default_strength_and_intent() {
+    let repo = setup_repo();
+    repo.write_sv_config("[leases]\ndefault_strength = \"observe\"\ndefault_intent = \"docs\"\n")
+        .expect("write config");
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "docs/guide.md"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args([
+            "take",
+            "src/lib.rs",
+            "--strength",
+            "cooperative",
+            "--intent",
+            "bugfix",
+        ])
+        .assert()
+        .success();
+
+    let leases = repo.read_leases().expect("read leases");
+    let defaulted = leases
+        .iter()
+        .find(|lease| lease.pathspec == "docs/guide.md")
+        .expect("defaulted lease");
+    assert_eq!(defaulted.strength, LeaseStrength::Observe);
+    assert_eq!(defaulted.intent, LeaseIntent::Docs);
+    let explicit = leases
+        .iter()
+        .find(|lease| lease.pathspec == "src/lib.rs")
+        .expect("explicit lease");
+    assert_eq!(explicit.strength, LeaseStrength::Cooperative);
+    assert_eq!(explicit.intent, LeaseIntent::Bugfix);
+}
+
+#[test]
+fn take_json_without_events_emits_single_document() {
+    let repo = setup_repo();
+
+    let output = sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--json"])
+        .output()
+        .expect("run take");
+    assert!(output.status.success());
+
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).expect("single json");
+    assert_eq!(value["command"], "take");
+}
+
+#[test]
+fn take_json_with_stdout_events_requires_redirect() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--json", "--events", "-"])
+        .assert()
+        .failure()
+        .stderr(contains("--json requires --events <path>"));
+
+    let result_path = repo.path().join("take.json");
+    let output = sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--json", "--events", "-", "--output"])
+        .arg(&result_path)
+        .output()
+        .expect("run take");
+    assert!(output.status.success());
+
+    let events = String::from_utf8(output.stdout).expect("utf8");
+    assert!(events.contains("lease_created"));
+    let result: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&result_path).expect("read result"))
+            .expect("parse result");
+    assert_eq!(result["command"], "take");
+}
+
+#[test]
+fn result_line_summarizes_take_on_stderr() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "src/main.rs", "--result-line"])
+        .assert()
+        .success()
+        .stderr(contains(
+            "SV_RESULT take ok conflicts=0 created=2 updated=0",
+        ));
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "bob")
+        .args([
+            "take",
+            "src/lib.rs",
+            "--strength",
+            "exclusive",
+            "--note",
+            "x",
+            "--json",
+        ])
+        .arg("--result-line")
+        .assert()
+        .failure()
+        .stderr(contains("SV_RESULT take error code=3"));
+}
+
+#[test]
+fn result_line_covers_commands_without_emit_success() {
+    let repo = setup_repo();
+
+    let output = sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--json"])
+        .output()
+        .expect("take");
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    let id = value["data"]["created"][0]["id"]
+        .as_str()
+        .expect("lease id")
+        .to_string();
+
+    for (args, expected) in [
+        (vec!["ws", "list"], "SV_RESULT ws.list ok"),
+        (
+            vec!["lease", "renew", id.as_str()],
+            "SV_RESULT lease.renew ok",
+        ),
+    ] {
+        sv_cmd(&repo)
+            .env("SV_ACTOR", "alice")
+            .args(args)
+            .arg("--result-line")
+            .assert()
+            .success()
+            .stderr(contains(expected));
+    }
+}
+
+#[test]
+fn take_rejects_paths_outside_repo() {
+    let repo = setup_repo();
+
+    for path in ["/etc/passwd", "../other-repo/file", "src/../../escape.rs"] {
+        sv_cmd(&repo)
+            .env("SV_ACTOR", "alice")
+            .args(["take", path])
+            .assert()
+            .failure()
+            .stderr(contains("outside the repository"));
+    }
+
+    assert!(repo.read_leases().expect("read leases").is_empty());
+}
+
+#[test]
+fn take_normalizes_paths_to_repo_relative() {
+    let repo = setup_repo();
+    let absolute = repo.path().join("src/lib.rs");
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .arg("take")
+        .arg(&absolute)
+        .args(["./docs/../README.md", "src/auth/**"])
+        .assert()
+        .success();
+
+    let mut pathspecs: Vec<String> = repo
+        .read_leases()
+        .expect("read leases")
+        .into_iter()
+        .map(|lease| lease.pathspec)
+        .collect();
+    pathspecs.sort();
+    assert_eq!(pathspecs, vec!["README.md", "src/auth/**", "src/lib.rs"]);
+}
+
+#[test]
+fn take_from_subdirectory_stores_repo_relative_path() {
+    let repo = setup_repo();
+    let subdir = repo.path().join("src/auth");
+    std::fs::create_dir_all(&subdir).expect("create subdir");
+
+    support::sv_cmd()
+        .current_dir(&subdir)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "token.rs"])
+        .assert()
+        .success();
+
+    let leases = repo.read_leases().expect("read leases");
+    assert_eq!(leases[0].pathspec, "src/auth/token.rs");
+
+    sv_cmd(&repo)
+        .args(["lease", "who", "src/auth/token.rs"])
+        .assert()
+        .success()
+        .stdout(contains("Leases on 'src/auth/token.rs'").and(contains("alice")));
+
+    support::sv_cmd()
+        .current_dir(&subdir)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "token.rs"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn lease_who_accepts_globs() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args([
+            "take",
+            "src/auth/token.rs",
+            "src/auth/session.rs",
+            "docs/guide.md",
+        ])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "bob")
+        .args(["take", "src/auth/token.rs"])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&repo)
+        .args(["lease", "who", "src/auth/**", "--json"])
+        .output()
+        .expect("run lease who");
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(value["leases"].as_array().map(Vec::len), Some(3));
+    let groups = value["groups"].as_array().expect("groups");
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0]["path"], "src/auth/session.rs");
+    assert_eq!(groups[1]["path"], "src/auth/token.rs");
+    assert_eq!(groups[1]["leases"].as_array().map(Vec::len), Some(2));
+
+    let output = sv_cmd(&repo)
+        .args(["lease", "who", "src/auth/token.rs", "--json"])
+        .output()
+        .expect("run lease who");
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(value["leases"].as_array().map(Vec::len), Some(2));
+    assert!(value.get("groups").is_none());
+}
+
+#[test]
+fn lease_keepalive_renews_expiring_leases() {
+    let repo = setup_repo();
+    let events_path = repo.path().join("events.jsonl");
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--ttl", "10m"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "docs/guide.md", "--ttl", "8h"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "bob")
+        .args(["take", "src/main.rs", "--ttl", "10m"])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args([
+            "lease",
+            "keepalive",
+            "--interval",
+            "10m",
+            "--renew-to",
+            "2h",
+            "--count",
+            "1",
+            "--json",
+            "--events",
+        ])
+        .arg(&events_path)
+        .output()
+        .expect("run keepalive");
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(value["rounds"], 1);
+    assert_eq!(value["renewed"], 1);
+
+    let leases = repo.read_leases().expect("read leases");
+    let ttl_of = |path: &str| {
+        leases
+            .iter()
+            .find(|lease| lease.pathspec == path)
+            .map(|lease| lease.ttl.clone())
+            .expect("lease")
+    };
+    assert_eq!(ttl_of("src/lib.rs"), "2h");
+    assert_eq!(ttl_of("docs/guide.md"), "8h");
+    assert_eq!(ttl_of("src/main.rs"), "10m");
+
+    let events = std::fs::read_to_string(&events_path).expect("read events");
+    assert_eq!(events.lines().count(), 1);
+    assert!(events.contains("lease_renewed"));
+}
+
+#[test]
+fn lease_keepalive_records_each_renewal_round() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--ttl", "2s"])
+        .assert()
+        .success();
+    let output = sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args([
+            "lease",
+            "keepalive",
+            "--interval",
+            "1s",
+            "--renew-to",
+            "2s",
+            "--count",
+            "3",
+            "--json",
+        ])
+        .output()
+        .expect("run keepalive");
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(value["renewed"], 3);
+
+    let output = sv_cmd(&repo)
+        .args(["op", "log", "--json"])
+        .output()
+        .expect("op log");
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    let keepalives: Vec<&serde_json::Value> = value["records"]
+        .as_array()
+        .expect("records")
+        .iter()
+        .filter(|record| record["command"] == "sv lease keepalive")
+        .collect();
+    assert_eq!(keepalives.len(), 3);
+    assert!(keepalives
+        .iter()
+        .all(|record| record["affected_paths"][0] == "src/lib.rs"));
+}
+
+#[test]
+fn lease_keepalive_requires_renew_to_longer_than_interval() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args([
+            "lease",
+            "keepalive",
+            "--interval",
+            "1h",
+            "--renew-to",
+            "30m",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("must be longer than --interval"));
+}
+
+#[cfg(unix)]
+#[test]
+fn lease_keepalive_exits_cleanly_on_sigint() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs"])
+        .assert()
+        .success();
+
+    let child = std::process::Command::new(assert_cmd::cargo::cargo_bin!("sv"))
+        .current_dir(repo.path())
+        .env("SV_ACTOR", "alice")
+        .args(["lease", "keepalive", "--interval", "1m", "--json"])
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .expect("spawn keepalive");
+
+    sleep(Duration::from_millis(800));
+    let status = std::process::Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .expect("send SIGINT");
+    assert!(status.success());
+
+    let output = child.wait_with_output().expect("wait keepalive");
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(value["interrupted"], true);
+
+    let leases = repo.read_leases().expect("read leases");
+    assert_eq!(leases.len(), 1);
+    assert!(leases[0].is_active());
+}
+
+#[test]
+fn take_and_release_record_affected_paths() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/auth/**", "README.md"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "README.md"])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&repo)
+        .args(["op", "log", "--json"])
+        .output()
+        .expect("run op log");
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    let records = value["records"].as_array().expect("records");
+    let paths_of = |kind: &str| {
+        records
+            .iter()
+            .find(|record| record["kind"] == kind)
+            .map(|record| record["affected_paths"].clone())
+            .expect("record")
+    };
+    assert_eq!(
+        paths_of("take"),
+        serde_json::json!(["src/auth/**", "README.md"])
+    );
+    assert_eq!(paths_of("release"), serde_json::json!(["README.md"]));
+}
+
+#[test]
+fn lease_who_at_replays_oplog_history() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/auth/**", "--note", "auth refactor"])
+        .assert()
+        .success();
+    sleep(Duration::from_millis(20));
+    let held = chrono::Utc::now().to_rfc3339();
+    sleep(Duration::from_millis(20));
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "src/auth/**"])
+        .assert()
+        .success();
+
+    sv_cmd(&repo)
+        .args(["lease", "who", "src/auth/token.rs"])
+        .assert()
+        .success()
+        .stdout(contains("No active leases"));
+
+    let output = sv_cmd(&repo)
+        .args(["lease", "who", "src/auth/token.rs", "--at", &held, "--json"])
+        .output()
+        .expect("run lease who");
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(value["leases"].as_array().map(Vec::len), Some(1));
+    assert_eq!(value["leases"][0]["actor"], "alice");
+    assert_eq!(value["leases"][0]["note"], "auth refactor");
+
+    sv_cmd(&repo)
+        .args(["lease", "who", "src/auth/token.rs", "--at", "yesterday"])
+        .assert()
+        .failure()
+        .stderr(contains("invalid --at timestamp"));
+}
+
+#[cfg(unix)]
+#[test]
+fn take_streams_events_to_unix_socket() {
+    let repo = setup_repo();
+    let socket_path = repo.path().join(".sv/events.sock");
+    let listener = std::os::unix::net::UnixListener::bind(&socket_path).expect("bind socket");
+    let reader = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("accept");
+        let mut lines = String::new();
+        std::io::Read::read_to_string(&mut stream, &mut lines).expect("read socket");
+        lines
+    });
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--events"])
+        .arg(format!("unix:{}", socket_path.display()))
+        .assert()
+        .success();
+
+    let events = reader.join().expect("reader thread");
+    let event: serde_json::Value =
+        serde_json::from_str(events.lines().next().expect("one frame")).expect("parse event");
+    assert_eq!(event["event"], "lease_created");
+}
+
+#[test]
+fn take_warns_when_event_socket_is_not_listening() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args([
+            "take",
+            "src/lib.rs",
+            "--events",
+            "unix:/nonexistent/sv.sock",
+        ])
+        .assert()
+        .success()
+        .stderr(contains("cannot connect to event socket"));
+}
+
+#[test]
+fn lease_export_import_merges_by_id() {
+    let source = setup_repo();
+    for (actor, path) in [("alice", "src/a.rs"), ("bob", "src/b.rs")] {
+        sv_cmd(&source)
+            .env("SV_ACTOR", actor)
+            .args(["take", path, "--note", "test"])
+            .assert()
+            .success();
+    }
+    let export_path = source.path().join("leases.jsonl");
+    sv_cmd(&source)
+        .args(["lease", "export", "--output"])
+        .arg(&export_path)
+        .assert()
+        .success();
+
+    let target = setup_repo();
+    let import = |file: &std::path::Path| -> serde_json::Value {
+        let output = sv_cmd(&target)
+            .args(["lease", "import", "--json"])
+            .arg(file)
+            .output()
+            .expect("import");
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).expect("json")
+    };
+
+    let first = import(&export_path);
+    assert_eq!(first["imported"].as_array().unwrap().len(), 2);
+    let again = import(&export_path);
+    assert_eq!(again["imported"].as_array().unwrap().len(), 0);
+    assert_eq!(again["unchanged"], 2);
+
+    // A release seen in the source ends the lease in the target too.
+    sv_cmd(&source)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "src/a.rs"])
+        .assert()
+        .success();
+    let exported = sv_cmd(&source)
+        .args(["lease", "export"])
+        .output()
+        .expect("export");
+    let mut leases: Vec<sv::lease::Lease> = String::from_utf8_lossy(&exported.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("lease"))
+        .collect();
+    let bob = leases
+        .iter_mut()
+        .find(|lease| lease.actor.as_deref() == Some("bob"))
+        .expect("bob lease");
+    bob.actor = Some("carol".to_string());
+    let mut expired = bob.clone();
+    expired.id = uuid::Uuid::new_v4();
+    expired.expires_at = chrono::Utc::now() - chrono::Duration::hours(1);
+    leases.push(expired);
+    let edited_path = source.path().join("edited.jsonl");
+    let content: String = leases
+        .iter()
+        .map(|lease| serde_json::to_string(lease).unwrap() + "\n")
+        .collect();
+    std::fs::write(&edited_path, content).expect("write");
+
+    let merged = import(&edited_path);
+    assert_eq!(merged["updated"].as_array().unwrap().len(), 1);
+    assert_eq!(merged["skipped_expired"], 1);
+    assert_eq!(merged["conflicts"][0]["local_actor"], "bob");
+    assert_eq!(merged["conflicts"][0]["incoming_actor"], "carol");
+
+    let target_leases = target.read_leases().expect("leases");
+    assert!(target_leases
+        .iter()
+        .any(|lease| lease.actor.as_deref() == Some("bob") && lease.is_active()));
+    assert!(target_leases
+        .iter()
+        .all(|lease| lease.actor.as_deref() != Some("alice") || !lease.is_active()));
+
+    sv_cmd(&target)
+        .args(["lease", "import"])
+        .arg(&edited_path)
+        .assert()
+        .success()
+        .stderr(contains("held by bob locally but carol"));
+}
+
+#[test]
+fn lease_import_skips_conflicts_and_can_be_undone() {
+    let source = setup_repo();
+    for (actor, path) in [("alice", "src/a.rs"), ("bob", "src/b.rs")] {
+        sv_cmd(&source)
+            .env("SV_ACTOR", actor)
+            .args(["take", path, "--strength", "exclusive", "--note", "test"])
+            .assert()
+            .success();
+    }
+    let export_path = source.path().join("leases.jsonl");
+    sv_cmd(&source)
+        .args(["lease", "export", "--output"])
+        .arg(&export_path)
+        .assert()
+        .success();
+
+    let target = setup_repo();
+    sv_cmd(&target)
+        .env("SV_ACTOR", "carol")
+        .args([
+            "take",
+            "src/b.rs",
+            "--strength",
+            "exclusive",
+            "--note",
+            "local",
+        ])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&target)
+        .args(["lease", "import", "--json"])
+        .arg(&export_path)
+        .output()
+        .expect("import");
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(report["imported"].as_array().unwrap().len(), 1);
+    assert_eq!(report["conflicts"][0]["incoming_actor"], "bob");
+    assert_eq!(report["conflicts"][0]["local_actor"], "carol");
+    assert!(report["conflicts"][0]["overlaps"].is_string());
+
+    sv_cmd(&target).arg("undo").assert().success();
+    let leases = target.read_leases().expect("leases");
+    assert!(leases
+        .iter()
+        .all(|lease| lease.actor.as_deref() != Some("alice") || !lease.is_active()));
+    assert!(leases
+        .iter()
+        .any(|lease| lease.actor.as_deref() == Some("carol") && lease.is_active()));
+}
+
+#[test]
+fn lease_id_seed_makes_take_ids_deterministic() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .env("SV_LEASE_ID_SEED", "demo")
+        .args(["take", "src/a.rs", "src/b.rs", "--note", "test"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .env("SV_LEASE_ID_SEED", "demo")
+        .args(["take", "src/c.rs", "--note", "test"])
+        .assert()
+        .success();
+
+    let ids: Vec<String> = repo
+        .read_leases()
+        .expect("read leases")
+        .iter()
+        .map(|lease| lease.id.to_string())
+        .collect();
+    assert_eq!(
+        ids,
+        vec![
+            "a5e41b67-4276-4396-8000-000000000000",
+            "a5e41b67-4276-4396-8000-000000000001",
+            "a5e41b67-4276-4396-8000-000000000002",
+        ]
+    );
+}
+
+#[test]
+fn lease_ls_path_filter_composes_with_actor() {
+    let repo = setup_repo();
+    for (actor, path) in [
+        ("alice", "src/auth/**"),
+        ("bob", "src/auth/token.rs"),
+        ("alice", "docs/guide.md"),
+    ] {
+        sv_cmd(&repo)
+            .env("SV_ACTOR", actor)
+            .args(["take", path, "--note", "test"])
+            .assert()
+            .success();
+    }
+
+    let list = |args: &[&str]| -> Vec<String> {
+        let output = sv_cmd(&repo)
+            .args(["lease", "ls", "--format", "jsonl"])
+            .args(args)
+            .output()
+            .expect("lease ls");
+        assert!(output.status.success());
+        let mut paths: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| {
+                let value: serde_json::Value = serde_json::from_str(line).expect("json");
+                value["pathspec"].as_str().expect("pathspec").to_string()
+            })
+            .collect();
+        paths.sort();
+        paths
+    };
+
+    assert_eq!(
+        list(&["--path", "src/auth/token.rs"]),
+        vec!["src/auth/**", "src/auth/token.rs"]
+    );
+    assert_eq!(
+        list(&["--path", "src/**", "--actor", "alice"]),
+        vec!["src/auth/**"]
+    );
+    assert!(list(&["--path", "tests/**"]).is_empty());
+}
+
+#[test]
+fn malformed_lease_line_is_skipped_and_repairable() {
+    let repo = setup_repo();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--note", "test"])
+        .assert()
+        .success();
+
+    let leases_path = repo.git_sv_dir().join("leases.jsonl");
+    let mut content = std::fs::read_to_string(&leases_path).expect("read leases");
+    content.push_str("{\"id\": \"truncated\n");
+    std::fs::write(&leases_path, content).expect("write leases");
+
+    sv_cmd(&repo)
+        .args(["lease", "ls"])
+        .assert()
+        .success()
+        .stdout(contains("src/lib.rs"))
+        .stderr(contains("skipped 1 malformed lease record(s)").and(contains("line 2")));
+
+    // Rewriting the log keeps the malformed line for doctor to set aside.
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "src/lib.rs"])
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(&leases_path).expect("read leases");
+    assert!(content.ends_with("{\"id\": \"truncated\n"), "{content}");
+
+    let output = sv_cmd(&repo)
+        .args(["lease", "doctor", "--json"])
+        .output()
+        .expect("doctor");
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(report["leases"], 1);
+    assert_eq!(report["malformed"][0]["line"], 2);
+    assert_eq!(report["repaired"], false);
+
+    sv_cmd(&repo)
+        .args(["lease", "doctor", "--repair"])
+        .assert()
+        .success()
+        .stdout(contains("malformed lines moved to"));
+    let aside = std::fs::read_to_string(repo.git_sv_dir().join("leases.malformed.jsonl"))
+        .expect("set-aside file");
+    assert_eq!(aside, "{\"id\": \"truncated\n");
+
+    sv_cmd(&repo)
+        .args(["lease", "doctor"])
+        .assert()
+        .success()
+        .stdout(contains("Lease log OK: 1 lease record(s)"))
+        .stderr(contains("malformed").not());
+}
+
+#[test]
+fn lease_doctor_reports_and_repairs_duplicates_and_expired() {
+    let repo = setup_repo();
+    // Records alice in the op log, so only mallory below is unknown.
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/seen.rs", "--note", "test"])
+        .assert()
+        .success();
+    let build = |path: &str| {
+        sv::lease::Lease::builder(path)
+            .actor("alice")
+            .note("test")
+            .build()
+            .expect("lease")
+    };
+
+    let mut expired = build("src/old.rs");
+    expired.expires_at = chrono::Utc::now() - chrono::Duration::hours(1);
+    let active = build("src/dup.rs");
+    let mut released = active.clone();
+    released.release();
+    let mut orphan = build("src/ghost.rs");
+    orphan.scope = sv::lease::LeaseScope::Workspace("ghost".to_string());
+    let mut stranger = build("src/stranger.rs");
+    stranger.actor = Some("mallory".to_string());
+
+    let content: String = [&expired, &released, &active, &orphan, &stranger]
+        .iter()
+        .map(|lease| serde_json::to_string(lease).unwrap() + "\n")
+        .collect();
+    std::fs::write(repo.git_sv_dir().join("leases.jsonl"), content).expect("write leases");
+
+    let doctor = |args: &[&str]| -> serde_json::Value {
+        let output = sv_cmd(&repo)
+            .args(["lease", "doctor", "--json"])
+            .args(args)
+            .output()
+            .expect("doctor");
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).expect("json")
+    };
+
+    let report = doctor(&[]);
+    assert_eq!(report["duplicate_ids"][0]["id"], active.id.to_string());
+    assert_eq!(report["duplicate_ids"][0]["records"], 2);
+    assert_eq!(report["expired_active"][0], expired.id.to_string());
+    assert_eq!(report["unknown_workspaces"][0]["workspace"], "ghost");
+    assert_eq!(report["unknown_actors"].as_array().unwrap().len(), 1);
+    assert_eq!(report["unknown_actors"][0]["actor"], "mallory");
+    assert_eq!(report["repaired"], false);
+
+    assert_eq!(doctor(&["--repair"])["repaired"], true);
+
+    let leases = repo.read_leases().expect("read leases");
+    assert_eq!(leases.len(), 4);
+    let dup = leases.iter().find(|l| l.id == active.id).expect("dup");
+    assert_eq!(dup.status, sv::lease::LeaseStatus::Released);
+    let old = leases.iter().find(|l| l.id == expired.id).expect("expired");
+    assert_eq!(old.status, sv::lease::LeaseStatus::Expired);
+
+    let after = doctor(&[]);
+    assert!(after["duplicate_ids"].as_array().unwrap().is_empty());
+    assert!(after["expired_active"].as_array().unwrap().is_empty());
+    assert_eq!(after["unknown_workspaces"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn take_skips_paths_covered_by_own_lease() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/**", "--strength", "strong", "--note", "auth"])
+        .assert()
+        .success();
+    let holder = repo.read_leases().expect("read leases")[0].id.to_string();
+
+    let output = sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/lib.rs", "--strength", "cooperative", "--json"])
+        .output()
+        .expect("run take");
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    let covered = &report["data"]["covered"][0];
+    assert_eq!(covered["path"], "src/lib.rs");
+    assert_eq!(covered["lease_id"], holder.as_str());
+    assert_eq!(covered["covered_by"], "src/**");
+    assert_eq!(report["data"]["summary"]["created"], 0);
+    assert_eq!(repo.read_leases().expect("read leases").len(), 1);
+
+    // A stronger request is not covered by a weaker lease
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args([
+            "take",
+            "src/lib.rs",
+            "--strength",
+            "exclusive",
+            "--note",
+            "x",
+        ])
+        .assert()
+        .success();
+    assert_eq!(repo.read_leases().expect("read leases").len(), 2);
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/main.rs", "--force"])
+        .assert()
+        .success();
+    assert_eq!(repo.read_leases().expect("read leases").len(), 3);
+
+    // A covering lease that expires before the requested TTL is renewed
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/util.rs", "--ttl", "4h"])
+        .assert()
+        .success();
+    let leases = repo.read_leases().expect("read leases");
+    assert_eq!(leases.len(), 3);
+    let renewed = leases
+        .iter()
+        .find(|lease| lease.id.to_string() == holder)
+        .expect("holder");
+    assert!(renewed.expires_at > chrono::Utc::now() + chrono::Duration::hours(3));
+}
+
+#[test]
+fn release_expired_releases_only_own_expired_leases() {
+    let repo = setup_repo();
+
+    for (actor, path, ttl) in [
+        ("alice", "src/old.rs", "1s"),
+        ("bob", "src/bob.rs", "1s"),
+        ("alice", "src/live.rs", "2h"),
+    ] {
+        sv_cmd(&repo)
+            .env("SV_ACTOR", actor)
+            .args(["take", path, "--ttl", ttl])
+            .assert()
+            .success();
+    }
+
+    sleep(Duration::from_secs(2));
+
+    let events = repo.path().join("events.jsonl");
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "--expired", "--events"])
+        .arg(&events)
+        .assert()
+        .success()
+        .stdout(contains("released 1 expired lease(s)"));
+
+    let leases = repo.read_leases().expect("read leases");
+    assert_eq!(leases.len(), 3);
+    let old = leases
+        .iter()
+        .find(|lease| lease.pathspec == "src/old.rs")
+        .expect("old lease");
+    assert_eq!(old.status, sv::lease::LeaseStatus::Released);
+    assert_eq!(old.status_reason.as_deref(), Some("expired"));
+    let bob = leases
+        .iter()
+        .find(|lease| lease.pathspec == "src/bob.rs")
+        .expect("bob lease");
+    assert_ne!(bob.status, sv::lease::LeaseStatus::Released);
+
+    sv_cmd(&repo).arg("undo").assert().success();
+    let old = repo
+        .read_leases()
+        .expect("read leases")
+        .into_iter()
+        .find(|lease| lease.pathspec == "src/old.rs")
+        .expect("old lease");
+    assert_ne!(old.status, sv::lease::LeaseStatus::Released);
+
+    let events = std::fs::read_to_string(events).expect("read events");
+    let lines: Vec<serde_json::Value> = events
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("event json"))
+        .collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["event"], "lease_expired");
+    assert_eq!(lines[0]["data"]["pathspec"], "src/old.rs");
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "--expired", "src/live.rs"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn release_selector_releases_matching_leases() {
+    let repo = setup_repo();
+
+    for (actor, path, ttl) in [
+        ("alice", "src/soon.rs", "30m"),
+        ("alice", "src/later.rs", "4h"),
+        ("bob", "src/bob.rs", "30m"),
+    ] {
+        sv_cmd(&repo)
+            .env("SV_ACTOR", actor)
+            .args(["take", path, "--ttl", ttl])
+            .assert()
+            .success();
+    }
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["lease", "ls", "--selector", "held(\"me\")", "--json"])
+        .assert()
+        .success()
+        .stdout(contains("src/later.rs").and(contains("src/bob.rs").not()));
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "--selector", "held(\"me\") & expiring(\"1h\")"])
+        .assert()
+        .success()
+        .stdout(contains("released 1 lease(s)"));
+    let active = |repo: &TestRepo| -> Vec<String> {
+        let mut paths: Vec<String> = repo
+            .read_leases()
+            .expect("read leases")
+            .into_iter()
+            .filter(|lease| lease.is_active())
+            .map(|lease| lease.pathspec)
+            .collect();
+        paths.sort();
+        paths
+    };
+    assert_eq!(active(&repo), ["src/bob.rs", "src/later.rs"]);
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "--selector", "expiring(\"1h\")"])
+        .assert()
+        .failure()
+        .stderr(contains("--force"));
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["release", "--selector", "expiring(\"1h\")", "--force"])
+        .assert()
+        .success();
+    assert_eq!(active(&repo), ["src/later.rs"]);
+
+    sv_cmd(&repo)
+        .args(["release", "--selector", "held("])
+        .assert()
+        .failure()
+        .code(2);
+}
+
+#[test]
+fn lease_blame_lists_coordination_history() {
+    let repo = setup_repo();
+
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/auth/**", "--note", "auth work"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "bob")
+        .args(["take", "docs/**"])
+        .assert()
+        .success();
+    let lease_id = repo
+        .read_leases()
+        .expect("read leases")
+        .into_iter()
+        .find(|lease| lease.pathspec == "src/auth/**")
+        .expect("auth lease")
+        .id
+        .to_string();
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "bob")
+        .args(["lease", "break", &lease_id, "--reason", "alice is away"])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&repo)
+        .args(["lease", "blame", "src/auth/token.rs", "--json"])
+        .output()
+        .expect("run blame");
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    let entries = report["entries"].as_array().expect("entries");
+    let actions: Vec<&str> = entries
+        .iter()
+        .map(|entry| entry["action"].as_str().unwrap_or(""))
+        .collect();
+    assert_eq!(actions, vec!["take", "break"]);
+    assert_eq!(entries[0]["holder"], "alice");
+    assert_eq!(entries[1]["by"], "bob");
+    assert_eq!(entries[1]["reason"], "alice is away");
+
+    sv_cmd(&repo)
+        .args(["lease", "blame", "src/auth/token.rs"])
+        .assert()
+        .success()
+        .stdout(contains("break").and(contains("by bob")));
+}
@@ -0,0 +1,41 @@
+mod support;
+
+use serde_json::Value;
+
+#[test]
+fn man_prints_top_level_page_and_writes_subcommand_pages() -> Result<(), Box<dyn std::error::Error>>
+{
+    let dir = tempfile::tempdir()?;
+
+    let output = support::sv_cmd()
+        .current_dir(dir.path())
+        .arg("man")
+        .output()?;
+    assert!(output.status.success());
+    let page = String::from_utf8(output.stdout)?;
+    assert!(page.contains(".TH sv 1"), "{page}");
+
+    let out = dir.path().join("man8");
+    let output = support::sv_cmd()
+        .current_dir(dir.path())
+        .args(["man", "--section", "8", "--out"])
+        .arg(&out)
+        .arg("--json")
+        .output()?;
+    assert!(output.status.success());
+    let payload: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(payload["data"]["section"], "8");
+    for name in ["sv.8", "sv-ws.8", "sv-ws-new.8", "sv-lease-blame.8"] {
+        assert!(out.join(name).is_file(), "missing {name}");
+    }
+    let ws_new = std::fs::read_to_string(out.join("sv-ws-new.8"))?;
+    assert!(ws_new.contains(".TH sv-ws-new 8"), "{ws_new}");
+
+    support::sv_cmd()
+        .current_dir(dir.path())
+        .args(["man", "--section", "../1"])
+        .assert()
+        .code(2);
+
+    Ok(())
+}
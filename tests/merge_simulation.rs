@@ -58,3 +58,37 @@ fn simulate_merge_returns_no_conflicts_for_disjoint_changes() {
         "expected no conflicts for disjoint changes"
     );
 }
+
+#[test]
+fn simulate_merge_flags_binary_conflicts() {
+    let repo = TestRepo::init().expect("init repo");
+    repo.write_file("logo.bin", "\0PNG base\0")
+        .expect("write base");
+    repo.commit_all("base").expect("commit base");
+
+    repo.create_branch("ours").expect("create ours");
+    repo.create_branch("theirs").expect("create theirs");
+
+    repo.checkout_branch("ours").expect("checkout ours");
+    repo.write_file("logo.bin", "\0PNG ours\0")
+        .expect("write ours");
+    repo.commit_all("ours").expect("commit ours");
+
+    repo.checkout_branch("theirs").expect("checkout theirs");
+    repo.write_file("logo.bin", "\0PNG theirs\0")
+        .expect("write theirs");
+    repo.commit_all("theirs").expect("commit theirs");
+
+    let simulation = simulate_merge(repo.repo(), "refs/heads/ours", "refs/heads/theirs", None)
+        .expect("simulate merge");
+
+    assert_eq!(simulation.conflicts.len(), 1);
+    let conflict = &simulation.conflicts[0];
+    assert_eq!(conflict.path, "logo.bin");
+    assert!(
+        matches!(conflict.kind, MergeConflictKind::Binary),
+        "expected binary conflict, got {:?}",
+        conflict.kind
+    );
+    assert_eq!(conflict.kind.label(), "binary conflict (manual)");
+}
@@ -0,0 +1,266 @@
+mod support;
+
+use std::path::Path;
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use serde_json::Value;
+
+use support::TestRepo;
+
+fn setup_repo() -> Result<(TestRepo, String), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    let base = repo.commit_file("README.md", "base\n", "initial commit")?;
+    Ok((repo, base.to_string()))
+}
+
+fn sv_cmd(dir: &Path) -> Command {
+    let mut cmd = support::sv_cmd();
+    cmd.current_dir(dir).env("SV_ACTOR", "alice");
+    cmd
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()?;
+    assert!(output.status.success(), "git {:?} failed", args);
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+fn commit_in(dir: &Path, file: &str, message: &str) -> Result<String, Box<dyn std::error::Error>> {
+    std::fs::write(dir.join(file), format!("{message}\n"))?;
+    git(dir, &["add", file])?;
+    git(
+        dir,
+        &[
+            "-c",
+            "user.name=sv-test",
+            "-c",
+            "user.email=sv-test@example.com",
+            "commit",
+            "-m",
+            message,
+        ],
+    )?;
+    git(dir, &["rev-parse", "HEAD"])
+}
+
+#[test]
+fn onto_cherry_pick_commits_picks_subset() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+    let ws1 = repo.path().join(".sv/worktrees/ws1");
+    let ws2 = repo.path().join(".sv/worktrees/ws2");
+
+    for name in ["ws1", "ws2"] {
+        sv_cmd(repo.path())
+            .args(["ws", "new", name, "--base", &base])
+            .assert()
+            .success();
+    }
+
+    let first = commit_in(&ws2, "a.txt", "first")?;
+    let second = commit_in(&ws2, "b.txt", "second")?;
+    let third = commit_in(&ws2, "c.txt", "third")?;
+
+    let range = format!("{first}..{third}");
+    let output = sv_cmd(&ws1)
+        .args([
+            "onto",
+            "ws2",
+            "--strategy",
+            "cherry-pick",
+            "--commits",
+            &range,
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    let commits: Vec<_> = value["data"]["commits"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|v| v.as_str().map(str::to_string))
+        .collect();
+    assert_eq!(commits, vec![second, third]);
+
+    assert!(!ws1.join("a.txt").exists());
+    assert!(ws1.join("b.txt").exists());
+    assert!(ws1.join("c.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+fn onto_cherry_pick_rejects_commits_outside_workspace() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+    let ws1 = repo.path().join(".sv/worktrees/ws1");
+    let ws2 = repo.path().join(".sv/worktrees/ws2");
+
+    for name in ["ws1", "ws2"] {
+        sv_cmd(repo.path())
+            .args(["ws", "new", name, "--base", &base])
+            .assert()
+            .success();
+    }
+
+    commit_in(&ws2, "a.txt", "ws2 work")?;
+    let foreign = commit_in(&ws1, "x.txt", "ws1 work")?;
+
+    sv_cmd(&ws1)
+        .args([
+            "onto",
+            "ws2",
+            "--strategy",
+            "cherry-pick",
+            "--commits",
+            &foreign,
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("not on workspace branch"));
+
+    Ok(())
+}
+
+#[test]
+fn onto_commits_requires_cherry_pick() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+    let ws1 = repo.path().join(".sv/worktrees/ws1");
+
+    for name in ["ws1", "ws2"] {
+        sv_cmd(repo.path())
+            .args(["ws", "new", name, "--base", &base])
+            .assert()
+            .success();
+    }
+
+    sv_cmd(&ws1)
+        .args(["onto", "ws2", "--commits", "HEAD"])
+        .assert()
+        .failure()
+        .stderr(contains("--commits requires --strategy cherry-pick"));
+
+    Ok(())
+}
+
+#[test]
+fn onto_dry_run_lists_commits_without_replaying() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+    let ws1 = repo.path().join(".sv/worktrees/ws1");
+    let ws2 = repo.path().join(".sv/worktrees/ws2");
+
+    for name in ["ws1", "ws2"] {
+        sv_cmd(repo.path())
+            .args(["ws", "new", name, "--base", &base])
+            .assert()
+            .success();
+    }
+
+    let picked = commit_in(&ws2, "a.txt", "ws2 work")?;
+    let head_before = git(&ws1, &["rev-parse", "HEAD"])?;
+
+    let output = sv_cmd(&ws1)
+        .args([
+            "onto",
+            "ws2",
+            "--strategy",
+            "cherry-pick",
+            "--dry-run",
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["data"]["would"], "onto");
+    assert_eq!(value["data"]["commits"][0], picked.as_str());
+    assert_eq!(git(&ws1, &["rev-parse", "HEAD"])?, head_before);
+    assert!(!ws1.join("a.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+fn onto_conflict_reports_conflicted_files() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+    let ws1 = repo.path().join(".sv/worktrees/ws1");
+    let ws2 = repo.path().join(".sv/worktrees/ws2");
+
+    for name in ["ws1", "ws2"] {
+        sv_cmd(repo.path())
+            .args(["ws", "new", name, "--base", &base])
+            .assert()
+            .success();
+    }
+
+    let ours = commit_in(&ws1, "a.txt", "ws1 edit")?;
+    commit_in(&ws2, "a.txt", "ws2 edit")?;
+
+    let output = sv_cmd(&ws1)
+        .args(["onto", "ws2", "--strategy", "rebase", "--json"])
+        .output()?;
+    assert_eq!(output.status.code(), Some(4));
+
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    let details = &value["error"]["details"];
+    assert_eq!(details["status"], "conflicted");
+    assert_eq!(details["commit"], ours.as_str());
+    assert_eq!(details["files"][0]["path"], "a.txt");
+    assert_eq!(details["files"][0]["hunks"], 1);
+    assert!(value["next_steps"][0]
+        .as_str()
+        .is_some_and(|step| step.contains("git rebase --continue")));
+
+    git(&ws1, &["rebase", "--abort"])?;
+    sv_cmd(&ws1)
+        .args(["onto", "ws2", "--strategy", "rebase"])
+        .assert()
+        .failure()
+        .stderr(contains("a.txt (1 hunk(s))"));
+    git(&ws1, &["rebase", "--abort"])?;
+
+    Ok(())
+}
+
+#[test]
+fn onto_into_rebases_onto_ref() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, base) = setup_repo()?;
+    let ws1 = repo.path().join(".sv/worktrees/ws1");
+
+    sv_cmd(repo.path())
+        .args(["ws", "new", "ws1", "--base", &base])
+        .assert()
+        .success();
+    commit_in(&ws1, "a.txt", "ws1 work")?;
+    let upstream = commit_in(repo.path(), "b.txt", "upstream work")?;
+    git(repo.path(), &["branch", "upstream"])?;
+
+    let output = sv_cmd(&ws1)
+        .args(["onto", "--into", "upstream", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["data"]["target_ref"], "upstream");
+    assert!(value["data"].get("target_workspace").is_none());
+    assert_eq!(git(&ws1, &["rev-parse", "HEAD~1"])?, upstream);
+    assert!(ws1.join("b.txt").exists());
+
+    sv_cmd(&ws1)
+        .args(["onto", "ws1", "--into", "upstream"])
+        .assert()
+        .failure()
+        .code(2);
+    sv_cmd(&ws1)
+        .args(["onto", "--into", "no-such-ref"])
+        .assert()
+        .failure()
+        .stderr(contains("cannot resolve ref"));
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use chrono::{TimeZone, Utc};
 use tempfile::TempDir;
 use uuid::Uuid;
 
-use sv::oplog::{format_record, OpLog, OpLogFilter, OpOutcome, OpRecord};
+use sv::oplog::{format_record, OpLog, OpLogFilter, OpOutcome, OpRecord, OperationKind};
 
 fn record_with(
     op_id: u128,
@@ -56,6 +56,7 @@ fn read_filtered_by_actor_operation_and_time() {
         since: Some(t1),
         until: Some(t1),
         operation: Some("init".to_string()),
+        touching: None,
     };
 
     let records = log.read_filtered(&filter, None).unwrap();
@@ -78,3 +79,200 @@ fn format_record_includes_core_fields() {
     assert!(formatted.contains("workspaces=[ws1]"));
     assert!(formatted.contains("failed (oops)"));
 }
+
+#[test]
+fn operation_kind_inferred_and_filtered_by_family() {
+    let temp = TempDir::new().unwrap();
+    let log = OpLog::new(temp.path().join("oplog"));
+
+    let t1 = Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap();
+    let t2 = Utc.with_ymd_and_hms(2024, 4, 2, 0, 0, 0).unwrap();
+    let t3 = Utc.with_ymd_and_hms(2024, 4, 3, 0, 0, 0).unwrap();
+
+    log.append(&record_with(20, t1, None, "sv ws new agent1"))
+        .unwrap();
+    log.append(&record_with(21, t2, None, "sv ws rm agent1"))
+        .unwrap();
+    log.append(&record_with(22, t3, None, "sv take src/lib.rs"))
+        .unwrap();
+    log.append(&record_with(23, t3, None, "sv lease renew 01HZ"))
+        .unwrap();
+    log.append(&record_with(24, t3, None, "sv refs prune"))
+        .unwrap();
+    log.append(&record_with(25, t3, None, "sv refs migrate"))
+        .unwrap();
+
+    let by = |operation: &str| {
+        let filter = OpLogFilter {
+            operation: Some(operation.to_string()),
+            ..OpLogFilter::default()
+        };
+        log.read_filtered(&filter, None)
+            .unwrap()
+            .into_iter()
+            .map(|record| record.kind())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(by("ws"), vec![OperationKind::WsRm, OperationKind::WsNew]);
+    assert_eq!(by("ws-new"), vec![OperationKind::WsNew]);
+    assert_eq!(by("take"), vec![OperationKind::Take]);
+    assert_eq!(by("lease"), vec![OperationKind::LeaseRenew]);
+    assert_eq!(by("lease-renew"), vec![OperationKind::LeaseRenew]);
+    assert_eq!(by("refs-prune"), vec![OperationKind::RefsPrune]);
+    assert_eq!(by("refs_migrate"), vec![OperationKind::RefsMigrate]);
+    assert_eq!(by("refs").len(), 2);
+    assert!(by("w").is_empty());
+}
+
+#[test]
+fn legacy_records_infer_kind_from_command() {
+    let json = r#"{
+        "op_id": "00000000-0000-0000-0000-000000000001",
+        "timestamp": "2024-01-01T00:00:00Z",
+        "command": "lease break",
+        "outcome": { "status": "success" }
+    }"#;
+    let record: OpRecord = serde_json::from_str(json).unwrap();
+    assert_eq!(record.kind, None);
+    assert_eq!(record.kind(), OperationKind::LeaseBreak);
+
+    let kind: OperationKind = serde_json::from_str(r#""renew""#).unwrap();
+    assert_eq!(kind, OperationKind::LeaseRenew);
+}
+
+#[test]
+fn validate_filter_lists_valid_kinds() {
+    assert!(OperationKind::validate_filter("ws").is_ok());
+    assert!(OperationKind::validate_filter("commit").is_ok());
+    let err = OperationKind::validate_filter("deploy").unwrap_err();
+    assert!(err.to_string().contains("ws_new"));
+}
+
+#[test]
+fn read_filtered_by_touching_path() {
+    let temp = TempDir::new().unwrap();
+    let log = OpLog::new(temp.path().join("oplog"));
+
+    let t1 = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+    let t2 = Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap();
+    let t3 = Utc.with_ymd_and_hms(2024, 5, 3, 0, 0, 0).unwrap();
+
+    let mut take = record_with(30, t1, Some("alice"), "sv take src/auth/**");
+    take.affected_paths = vec!["src/auth/**".to_string()];
+    let mut commit = record_with(31, t2, Some("bob"), "sv commit -m \"docs\"");
+    commit.affected_paths = vec!["README.md".to_string()];
+    let legacy = record_with(32, t3, Some("carol"), "sv take src/auth/token.rs");
+
+    log.append(&take).unwrap();
+    log.append(&commit).unwrap();
+    log.append(&legacy).unwrap();
+
+    let touching = |pathspec: &str| {
+        let filter = OpLogFilter {
+            touching: Some(pathspec.to_string()),
+            ..OpLogFilter::default()
+        };
+        log.read_filtered(&filter, None)
+            .unwrap()
+            .into_iter()
+            .map(|record| record.op_id)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(touching("src/auth/token.rs"), vec![Uuid::from_u128(30)]);
+    assert_eq!(touching("src/auth"), vec![Uuid::from_u128(30)]);
+    assert_eq!(touching("*.md"), vec![Uuid::from_u128(31)]);
+    assert!(touching("src/cli").is_empty());
+}
+
+#[test]
+fn leases_at_replays_take_and_release() {
+    use sv::lease::Lease;
+    use sv::oplog::{LeaseChange, OpDetails, UndoData};
+
+    let temp = TempDir::new().unwrap();
+    let log = OpLog::new(temp.path().join("oplog"));
+
+    let t1 = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    let t2 = Utc.with_ymd_and_hms(2024, 6, 1, 1, 0, 0).unwrap();
+    let t3 = Utc.with_ymd_and_hms(2024, 6, 1, 2, 0, 0).unwrap();
+
+    let mut lease = Lease::builder("src/auth/**")
+        .actor("alice")
+        .ttl("8h")
+        .build()
+        .unwrap();
+    lease.expires_at = t1 + chrono::Duration::hours(8);
+
+    let mut take = record_with(40, t1, Some("alice"), "sv take src/auth/**");
+    take.details = Some(OpDetails {
+        leases: vec![lease.clone()],
+        ..OpDetails::default()
+    });
+    let mut release = record_with(41, t3, Some("alice"), "sv release src/auth/**");
+    release.undo_data = Some(UndoData {
+        lease_changes: vec![LeaseChange {
+            lease_id: lease.id.to_string(),
+            action: "release".to_string(),
+        }],
+        ..UndoData::default()
+    });
+    log.append(&take).unwrap();
+    log.append(&release).unwrap();
+
+    let held = log.leases_at(t2).unwrap();
+    assert_eq!(held.len(), 1);
+    assert_eq!(held[0].actor.as_deref(), Some("alice"));
+    assert!(log.leases_at(t3).unwrap().is_empty());
+    assert!(log
+        .leases_at(t1 - chrono::Duration::seconds(1))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn leases_at_applies_lease_changes_from_any_command() {
+    use sv::lease::Lease;
+    use sv::oplog::{LeaseChange, OpDetails, UndoData};
+
+    let temp = TempDir::new().unwrap();
+    let log = OpLog::new(temp.path().join("oplog"));
+
+    let t1 = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    let t2 = Utc.with_ymd_and_hms(2024, 6, 1, 1, 0, 0).unwrap();
+    let t3 = Utc.with_ymd_and_hms(2024, 6, 1, 2, 0, 0).unwrap();
+
+    let mut lease = Lease::builder("src/auth/**")
+        .actor("alice")
+        .ttl("8h")
+        .build()
+        .unwrap();
+    lease.expires_at = t1 + chrono::Duration::hours(8);
+    let changes = |action: &str| UndoData {
+        lease_changes: vec![LeaseChange {
+            lease_id: lease.id.to_string(),
+            action: action.to_string(),
+        }],
+        ..UndoData::default()
+    };
+
+    let mut import = record_with(50, t1, Some("alice"), "sv lease import leases.jsonl");
+    import.details = Some(OpDetails {
+        leases: vec![lease.clone()],
+        ..OpDetails::default()
+    });
+    let mut archive = record_with(51, t2, Some("alice"), "sv ws archive alice");
+    archive.undo_data = Some(changes("release"));
+    // Undoing the archive reactivates the lease.
+    let mut undo = record_with(52, t3, Some("alice"), "sv undo");
+    undo.undoes = Some(archive.op_id);
+    undo.redo_data = Some(changes("create"));
+    for record in [&import, &archive, &undo] {
+        log.append(record).unwrap();
+    }
+
+    assert_eq!(log.leases_at(t1).unwrap().len(), 1);
+    assert!(log.leases_at(t2).unwrap().is_empty());
+    assert_eq!(log.leases_at(t3).unwrap().len(), 1);
+}
@@ -1,4 +1,4 @@
-use sv::output::{format_human, HumanOutput};
+use sv::output::{format_human, format_result_line, HumanOutput};
 
 #[test]
 fn format_human_includes_sections() {
@@ -26,3 +26,30 @@ fn format_human_omits_empty_sections() {
     let rendered = format_human(&human);
     assert_eq!(rendered, "sv init: already initialized");
 }
+
+#[test]
+fn format_human_renders_aligned_tables() {
+    let mut human = HumanOutput::new("Workspaces (2):");
+    human.push_table(
+        ["NAME", "BRANCH"],
+        [vec!["ws1", "sv/ws1"], vec!["agent-two", "sv/agent-two"]],
+    );
+
+    let rendered = format_human(&human);
+    assert_eq!(
+        rendered,
+        "Workspaces (2):\n\nNAME       BRANCH\nws1        sv/ws1\nagent-two  sv/agent-two"
+    );
+}
+
+#[test]
+fn format_result_line_joins_command_words() {
+    let counts = vec![
+        ("created".to_string(), "2".to_string()),
+        ("conflicts".to_string(), "0".to_string()),
+    ];
+    assert_eq!(
+        format_result_line("lease renew", "ok", &counts),
+        "SV_RESULT lease.renew ok created=2 conflicts=0"
+    );
+}
@@ -97,3 +97,196 @@ fn migrate_legacy_creates_project_entities_for_task_backed_projects(
 
     Ok(())
 }
+
+#[test]
+fn project_archive_refuses_active_members_unless_forced() -> Result<(), Box<dyn std::error::Error>>
+{
+    let repo = TestRepo::init()?;
+    let project_id = new_project(&repo, "Platform");
+    let active = new_task(&repo, "Active");
+    let done = new_task(&repo, "Done");
+    for task in [&active, &done] {
+        sv_cmd(&repo)
+            .args(["task", "project", "set", task, &project_id])
+            .assert()
+            .success();
+    }
+    sv_cmd(&repo)
+        .args(["task", "close", &done])
+        .assert()
+        .success();
+
+    sv_cmd(&repo)
+        .args(["project", "archive", &project_id])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(active.as_str()))
+        .stderr(predicates::str::contains("--force"));
+
+    let show = sv_cmd(&repo)
+        .args(["project", "show", &project_id, "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&show)?;
+    assert_eq!(value["data"]["archived"].as_bool(), Some(false));
+
+    let output = sv_cmd(&repo)
+        .args(["project", "archive", &project_id, "--force", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&output)?;
+    assert_eq!(value["data"]["changed"].as_bool(), Some(true));
+    assert_eq!(value["data"]["cleared_tasks"], serde_json::json!([active]));
+
+    let task = sv_cmd(&repo)
+        .args(["task", "show", &active, "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&task)?;
+    assert!(value["data"]["task"]["project"].is_null());
+
+    // Closed members keep their membership.
+    let closed = sv_cmd(&repo)
+        .args(["task", "show", &done, "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&closed)?;
+    assert_eq!(
+        value["data"]["task"]["project"].as_str(),
+        Some(project_id.as_str())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn project_archive_counts_members_inherited_from_epic() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    let project_id = new_project(&repo, "Platform");
+    let epic = new_task(&repo, "Epic");
+    let child = new_task(&repo, "Child");
+    sv_cmd(&repo)
+        .args(["task", "project", "set", &epic, &project_id])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["task", "epic", "set", &child, &epic])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["task", "close", &epic])
+        .assert()
+        .success();
+
+    sv_cmd(&repo)
+        .args(["project", "archive", &project_id])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(child.as_str()));
+
+    let output = sv_cmd(&repo)
+        .args(["project", "archive", &project_id, "--force", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&output)?;
+    assert_eq!(value["data"]["cleared_tasks"], serde_json::json!([epic]));
+    assert_eq!(value["data"]["detached_tasks"], serde_json::json!([child]));
+
+    let list = sv_cmd(&repo)
+        .args(["task", "list", "--project", &project_id, "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&list)?;
+    let listed = value["data"]["tasks"].as_array().map(Vec::len);
+    assert_eq!(listed, Some(0));
+
+    Ok(())
+}
+
+#[test]
+fn project_list_with_progress_counts_members() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    let project_id = new_project(&repo, "Platform");
+    let empty_id = new_project(&repo, "Empty");
+    let epic = new_task(&repo, "Epic");
+    let child = new_task(&repo, "Child");
+    let direct = new_task(&repo, "Direct");
+
+    sv_cmd(&repo)
+        .args(["task", "project", "set", &epic, &project_id])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["task", "project", "set", &direct, &project_id])
+        .assert()
+        .success();
+    // Child inherits the project through its epic.
+    sv_cmd(&repo)
+        .args(["task", "epic", "set", &child, &epic])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["task", "close", &direct])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&repo)
+        .args(["project", "list", "--with-progress", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&output)?;
+    let progress = value["data"]["progress"].as_array().expect("progress");
+    let find = |id: &str| {
+        progress
+            .iter()
+            .find(|entry| entry["project"].as_str() == Some(id))
+            .cloned()
+            .expect("project progress")
+    };
+    let platform = find(&project_id);
+    assert_eq!(platform["open"].as_u64(), Some(2));
+    assert_eq!(platform["closed"].as_u64(), Some(1));
+    assert_eq!(platform["percent"].as_u64(), Some(33));
+    let empty = find(&empty_id);
+    assert_eq!(empty["open"].as_u64(), Some(0));
+    assert_eq!(empty["percent"].as_u64(), Some(0));
+
+    sv_cmd(&repo)
+        .args(["project", "list", "--with-progress"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("1/3 closed (33%)"));
+
+    let plain = sv_cmd(&repo)
+        .args(["project", "list", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&plain)?;
+    assert!(value["data"].get("progress").is_none());
+
+    Ok(())
+}
@@ -75,3 +75,28 @@ fn list_branches_filters_by_pattern() -> Result<(), Box<dyn std::error::Error>>
     assert!(!filtered.contains(&"beta".to_string()));
     Ok(())
 }
+
+#[test]
+fn list_sv_refs_returns_managed_namespaces() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.write_file("file.txt", "base")?;
+    repo.commit_all("base")?;
+
+    refs::create_branch_from_ref(repo.repo(), "sv/ws/agent1", "HEAD", false)?;
+    refs::create_branch_from_ref(repo.repo(), "sv/hoist/main", "HEAD", false)?;
+    refs::create_branch_from_ref(repo.repo(), "feature", "HEAD", false)?;
+
+    let listed = refs::list_sv_refs(repo.repo())?;
+    let names: Vec<(&str, refs::SvRefKind)> = listed
+        .iter()
+        .map(|entry| (entry.name.as_str(), entry.kind))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("sv/hoist/main", refs::SvRefKind::Hoist),
+            ("sv/ws/agent1", refs::SvRefKind::Workspace),
+        ]
+    );
+    Ok(())
+}
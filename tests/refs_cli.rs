@@ -0,0 +1,146 @@
+mod support;
+
+use std::path::Path;
+
+use assert_cmd::Command;
+use serde_json::Value;
+
+use support::TestRepo;
+
+fn sv_cmd(dir: &Path) -> Command {
+    let mut cmd = support::sv_cmd();
+    cmd.current_dir(dir).env("SV_ACTOR", "alice");
+    cmd
+}
+
+fn names(value: &Value, key: &str) -> Vec<String> {
+    value["data"][key]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item["name"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn branch_exists(repo: &TestRepo, name: &str) -> bool {
+    repo.repo()
+        .find_branch(name, git2::BranchType::Local)
+        .is_ok()
+}
+
+#[test]
+fn refs_prune_removes_stale_refs_and_keeps_unmerged() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "base")?;
+    let head = repo.repo().head()?;
+    let base = head.shorthand().ok_or("no branch")?.to_string();
+    repo.write_sv_config(&format!("base = \"{base}\"\n"))?;
+
+    repo.create_branch("sv/ws/gone")?;
+    repo.create_branch("sv/hoist/old")?;
+    repo.create_branch("sv/ws/unmerged")?;
+    repo.checkout_branch("sv/ws/unmerged")?;
+    repo.commit_file("wip.txt", "wip\n", "work in progress")?;
+    repo.checkout_branch(&base)?;
+    sv_cmd(repo.path())
+        .args(["ws", "new", "agent1", "--base", "HEAD"])
+        .assert()
+        .success();
+
+    let output = sv_cmd(repo.path())
+        .args(["refs", "prune", "--dry-run", "--json"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(names(&value, "pruned"), vec!["sv/hoist/old", "sv/ws/gone"]);
+    assert_eq!(names(&value, "skipped"), vec!["sv/ws/unmerged"]);
+    assert!(branch_exists(&repo, "sv/ws/gone"));
+
+    sv_cmd(repo.path())
+        .args(["refs", "prune"])
+        .assert()
+        .success();
+    assert!(!branch_exists(&repo, "sv/ws/gone"));
+    assert!(!branch_exists(&repo, "sv/hoist/old"));
+    assert!(branch_exists(&repo, "sv/ws/unmerged"));
+    assert!(branch_exists(&repo, "sv/ws/agent1"));
+
+    // The op log keeps enough to restore the pruned refs.
+    let storage = sv::storage::Storage::for_repo(repo.path().to_path_buf());
+    sv::undo::undo(&storage, sv::undo::UndoOptions::default())?;
+    assert!(branch_exists(&repo, "sv/ws/gone"));
+
+    sv_cmd(repo.path())
+        .args(["refs", "prune", "--force"])
+        .assert()
+        .success();
+    assert!(!branch_exists(&repo, "sv/ws/unmerged"));
+    assert!(branch_exists(&repo, "sv/ws/agent1"));
+
+    Ok(())
+}
+
+#[test]
+fn refs_namespace_keeps_workspace_refs_out_of_branches() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "base")?;
+    repo.write_sv_config("[refs]\nnamespace = \"refs/sv\"\n")?;
+    sv_cmd(repo.path())
+        .args(["ws", "new", "agent1", "--base", "HEAD"])
+        .assert()
+        .success();
+
+    assert!(!branch_exists(&repo, "sv/ws/agent1"));
+    let before = repo.repo().refname_to_id("refs/sv/ws/agent1")?;
+
+    // Commits in the worktree advance the namespaced ref.
+    let worktree = git2::Repository::open(repo.path().join(".sv/worktrees/agent1"))?;
+    assert_eq!(
+        worktree.find_reference("HEAD")?.symbolic_target(),
+        Some("refs/sv/ws/agent1")
+    );
+    let signature = git2::Signature::now("sv", "sv@example.com")?;
+    let tree = worktree.head()?.peel_to_tree()?;
+    let parent = worktree.head()?.peel_to_commit()?;
+    let commit = worktree.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "work",
+        &tree,
+        &[&parent],
+    )?;
+    assert_ne!(commit, before);
+    assert_eq!(repo.repo().refname_to_id("refs/sv/ws/agent1")?, commit);
+    assert_eq!(repo.repo().revparse_single("sv/ws/agent1")?.id(), commit);
+
+    // Switching back to branches and migrating moves the ref and the worktree.
+    repo.write_sv_config("[refs]\nnamespace = \"refs/heads\"\n")?;
+    let output = sv_cmd(repo.path())
+        .args(["refs", "migrate", "--json"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(names(&value, "migrated"), vec!["sv/ws/agent1"]);
+    assert!(branch_exists(&repo, "sv/ws/agent1"));
+    assert!(repo.repo().find_reference("refs/sv/ws/agent1").is_err());
+    assert_eq!(
+        worktree.find_reference("HEAD")?.symbolic_target(),
+        Some("refs/heads/sv/ws/agent1")
+    );
+
+    Ok(())
+}
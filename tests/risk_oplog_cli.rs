@@ -2,6 +2,7 @@ mod support;
 
 use std::fs;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use assert_cmd::Command;
 use chrono::Utc;
@@ -85,6 +86,55 @@ fn op_log_reports_records_json() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn op_log_touching_filters_by_recorded_paths() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+
+    let storage = Storage::for_repo(repo.path().to_path_buf());
+    let log = OpLog::for_storage(&storage);
+    let legacy = OpRecord::new("sv take src/auth/token.rs", Some("alice".to_string()));
+    log.append(&legacy)?;
+    let mut take = OpRecord::new("sv take src/auth/**", Some("alice".to_string()));
+    take.affected_paths.push("src/auth/**".to_string());
+    log.append(&take)?;
+    let mut other = OpRecord::new("sv take src/auth/session.rs", Some("bob".to_string()));
+    other.affected_paths.push("src/auth/session.rs".to_string());
+    log.append(&other)?;
+
+    let output = sv_cmd(&repo)
+        .args([
+            "op",
+            "log",
+            "--touching",
+            "src/auth/token.rs",
+            "--actor",
+            "alice",
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["total"].as_u64(), Some(1));
+    assert_eq!(
+        report["records"][0]["command"].as_str(),
+        Some("sv take src/auth/**")
+    );
+    assert_eq!(report["skipped_untagged"].as_u64(), Some(1));
+
+    sv_cmd(&repo)
+        .args(["op", "log", "--touching", "src/auth"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("session.rs"))
+        .stdout(predicates::str::contains(
+            "skipped 1 entry without recorded paths",
+        ));
+
+    Ok(())
+}
+
 #[test]
 fn risk_reports_overlaps_for_shared_paths() -> Result<(), Box<dyn std::error::Error>> {
     let repo = TestRepo::init()?;
@@ -165,3 +215,455 @@ fn risk_reports_overlaps_for_shared_paths() -> Result<(), Box<dyn std::error::Er
 
     Ok(())
 }
+
+#[test]
+fn risk_mine_keeps_overlaps_involving_actor() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "base commit")?;
+
+    let git_repo = repo.repo();
+    let base = git_repo.head()?.target().ok_or("missing base head")?;
+
+    let storage = Storage::for_repo(repo.path().to_path_buf());
+    // ws-a and ws-b overlap on README.md; ws-b and ws-c overlap on notes.txt.
+    for (name, actor, files) in [
+        ("ws-a", "alice", &["README.md"][..]),
+        ("ws-b", "bob", &["README.md", "notes.txt"][..]),
+        ("ws-c", "carol", &["notes.txt"][..]),
+    ] {
+        let branch = format!("refs/heads/sv/ws/{name}");
+        let mut parent = base;
+        for file in files {
+            parent = commit_on_ref(
+                git_repo,
+                &branch,
+                Some(parent),
+                file,
+                &format!("{name}\n"),
+                &format!("{name} change"),
+            )?;
+        }
+        let worktree = repo.path().join(".sv/worktrees").join(name);
+        fs::create_dir_all(&worktree)?;
+        storage.add_workspace(WorkspaceEntry::new(
+            name.to_string(),
+            worktree,
+            format!("sv/ws/{name}"),
+            "HEAD".to_string(),
+            Some(actor.to_string()),
+            Utc::now().to_rfc3339(),
+            None,
+        ))?;
+    }
+
+    let overlap_paths = |actor: &str| -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let output = sv_cmd(&repo)
+            .args(["risk", "--base", "HEAD", "--mine", "--json"])
+            .env("SV_ACTOR", actor)
+            .output()?;
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let report: Value = serde_json::from_slice(&output.stdout)?;
+        Ok(report["overlaps"]
+            .as_array()
+            .ok_or("overlaps is not array")?
+            .iter()
+            .filter_map(|item| item["path"].as_str().map(str::to_string))
+            .collect())
+    };
+
+    assert_eq!(overlap_paths("alice")?, vec!["README.md"]);
+    assert_eq!(overlap_paths("bob")?, vec!["README.md", "notes.txt"]);
+    assert!(overlap_paths("dave")?.is_empty());
+
+    // A lease on the path brings the overlap into dave's view.
+    sv_cmd(&repo)
+        .args(["take", "notes.txt"])
+        .env("SV_ACTOR", "dave")
+        .assert()
+        .success();
+    assert_eq!(overlap_paths("dave")?, vec!["notes.txt"]);
+
+    Ok(())
+}
+
+#[test]
+fn risk_fail_on_exits_nonzero_at_or_above_severity() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "base commit")?;
+
+    let git_repo = repo.repo();
+    let base = git_repo.head()?.target().ok_or("missing base head")?;
+    let storage = Storage::for_repo(repo.path().to_path_buf());
+    for name in ["ws-a", "ws-b"] {
+        commit_on_ref(
+            git_repo,
+            &format!("refs/heads/sv/ws/{name}"),
+            Some(base),
+            "README.md",
+            &format!("{name}\n"),
+            &format!("{name} change"),
+        )?;
+        let worktree = repo.path().join(".sv/worktrees").join(name);
+        fs::create_dir_all(&worktree)?;
+        storage.add_workspace(WorkspaceEntry::new(
+            name.to_string(),
+            worktree,
+            format!("sv/ws/{name}"),
+            "HEAD".to_string(),
+            None,
+            Utc::now().to_rfc3339(),
+            None,
+        ))?;
+    }
+
+    let run = |fail_on: &str| -> Result<(i32, String), Box<dyn std::error::Error>> {
+        let output = sv_cmd(&repo)
+            .args(["risk", "--base", "HEAD", "--json", "--fail-on", fail_on])
+            .output()?;
+        let report: Value = serde_json::from_slice(&output.stdout)?;
+        let severity = report["overlaps"][0]["severity"]
+            .as_str()
+            .ok_or("missing severity")?
+            .to_string();
+        Ok((output.status.code().ok_or("no exit code")?, severity))
+    };
+
+    // Unleased two-way overlap scores 2; thresholds move it through each severity.
+    let cases = [
+        ("", "low"),
+        (
+            "[risk.thresholds]\nmedium = 2\nhigh = 3\ncritical = 4\n",
+            "medium",
+        ),
+        (
+            "[risk.thresholds]\nmedium = 1\nhigh = 2\ncritical = 3\n",
+            "high",
+        ),
+    ];
+    let severities = ["low", "medium", "high", "critical"];
+    for (config, expected) in cases {
+        repo.write_sv_config(config)?;
+        for (rank, fail_on) in severities.iter().enumerate() {
+            let (code, severity) = run(fail_on)?;
+            assert_eq!(severity, expected);
+            let reported = severities.iter().position(|s| *s == expected).unwrap();
+            let want = if rank <= reported { 3 } else { 0 };
+            assert_eq!(code, want, "report {expected}, --fail-on {fail_on}");
+        }
+    }
+
+    // A cooperative bugfix lease adds 1 + 2, reaching critical at 5.
+    repo.write_sv_config("[risk.thresholds]\nmedium = 1\nhigh = 3\ncritical = 5\n")?;
+    sv_cmd(&repo)
+        .args(["take", "README.md", "--intent", "bugfix"])
+        .env("SV_ACTOR", "alice")
+        .assert()
+        .success();
+    for fail_on in severities {
+        assert_eq!(run(fail_on)?, (3, "critical".to_string()));
+    }
+
+    // Without --fail-on the report never fails.
+    sv_cmd(&repo)
+        .args(["risk", "--base", "HEAD"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["risk", "--fail-on", "severe"])
+        .assert()
+        .code(2);
+
+    Ok(())
+}
+
+#[test]
+fn risk_since_scopes_overlaps_to_recent_changes() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "base commit")?;
+
+    let git_repo = repo.repo();
+    let base = git_repo.head()?.target().ok_or("missing base head")?;
+    // Shared older change both workspaces branched from
+    let shared = commit_on_ref(
+        git_repo,
+        "refs/heads/checkpoint",
+        Some(base),
+        "README.md",
+        "shared\n",
+        "shared change",
+    )?;
+    commit_on_ref(
+        git_repo,
+        "refs/heads/sv/ws/ws-a",
+        Some(shared),
+        "a.txt",
+        "a\n",
+        "ws-a change",
+    )?;
+    // The index is shared across commits; drop ws-a's file before ws-b
+    let mut index = git_repo.index()?;
+    index.read_tree(&git_repo.find_commit(shared)?.tree()?)?;
+    index.write()?;
+    commit_on_ref(
+        git_repo,
+        "refs/heads/sv/ws/ws-b",
+        Some(shared),
+        "b.txt",
+        "b\n",
+        "ws-b change",
+    )?;
+
+    let storage = Storage::for_repo(repo.path().to_path_buf());
+    for name in ["ws-a", "ws-b"] {
+        let worktree = repo.path().join(".sv/worktrees").join(name);
+        fs::create_dir_all(&worktree)?;
+        storage.add_workspace(WorkspaceEntry::new(
+            name.to_string(),
+            worktree,
+            format!("sv/ws/{name}"),
+            "HEAD".to_string(),
+            None,
+            Utc::now().to_rfc3339(),
+            None,
+        ))?;
+    }
+
+    let base_hex = base.to_string();
+    let output = sv_cmd(&repo)
+        .args(["risk", "--base", &base_hex, "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert!(report.get("since_ref").is_none());
+    assert!(report["overlaps"]
+        .as_array()
+        .is_some_and(|overlaps| overlaps.iter().any(|o| o["path"] == "README.md")));
+
+    let output = sv_cmd(&repo)
+        .args([
+            "risk",
+            "--base",
+            &base_hex,
+            "--since",
+            "checkpoint",
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["since_ref"], "checkpoint");
+    assert_eq!(report["overlaps"].as_array().map(Vec::len), Some(0));
+
+    let output = sv_cmd(&repo)
+        .args(["risk", "--since", "no-such-ref", "--json"])
+        .output()?;
+    assert_eq!(output.status.code(), Some(2));
+
+    Ok(())
+}
+
+#[test]
+fn risk_ignore_suppresses_generated_overlaps() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "base commit")?;
+
+    let git_repo = repo.repo();
+    let base = git_repo.head()?.target().ok_or("missing base head")?;
+    let storage = Storage::for_repo(repo.path().to_path_buf());
+    for name in ["ws-a", "ws-b"] {
+        let branch = format!("refs/heads/sv/ws/{name}");
+        let mut parent = base;
+        for path in [
+            "README.md",
+            "target/debug/out.txt",
+            "web/node_modules/x/index.js",
+        ] {
+            parent = commit_on_ref(git_repo, &branch, Some(parent), path, name, name)?;
+        }
+        let worktree = repo.path().join(".sv/worktrees").join(name);
+        fs::create_dir_all(&worktree)?;
+        storage.add_workspace(WorkspaceEntry::new(
+            name.to_string(),
+            worktree,
+            format!("sv/ws/{name}"),
+            "HEAD".to_string(),
+            None,
+            Utc::now().to_rfc3339(),
+            None,
+        ))?;
+    }
+
+    let base_hex = base.to_string();
+    let overlap_paths = |report: &Value| -> Vec<String> {
+        report["overlaps"]
+            .as_array()
+            .map(|overlaps| {
+                overlaps
+                    .iter()
+                    .filter_map(|o| o["path"].as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let output = sv_cmd(&repo)
+        .args(["risk", "--base", &base_hex, "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(overlap_paths(&report), vec!["README.md"]);
+    assert_eq!(report["suppressed_overlaps"], 2);
+
+    repo.write_sv_config("[risk]\nignore = []\n")?;
+    let output = sv_cmd(&repo)
+        .args(["risk", "--base", &base_hex, "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(overlap_paths(&report).len(), 3);
+    assert_eq!(report["suppressed_overlaps"], 0);
+
+    Ok(())
+}
+
+#[test]
+fn risk_flags_edit_vs_rename_collisions() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file(
+        "src/a.rs",
+        "fn a() {}\nfn b() {}\nfn c() {}\n",
+        "base commit",
+    )?;
+
+    let git_repo = repo.repo();
+    let base = git_repo.head()?.target().ok_or("missing base head")?;
+    let base_commit = git_repo.find_commit(base)?;
+
+    // ws-a renames src/a.rs -> src/b.rs without touching its content
+    let base_tree = base_commit.tree()?;
+    let src = base_tree
+        .get_path(Path::new("src"))?
+        .to_object(git_repo)?
+        .peel_to_tree()?;
+    let blob = src.get_name("a.rs").ok_or("missing a.rs")?.id();
+    let mut src_builder = git_repo.treebuilder(Some(&src))?;
+    src_builder.remove("a.rs")?;
+    src_builder.insert("b.rs", blob, 0o100644)?;
+    let src_id = src_builder.write()?;
+    let mut root_builder = git_repo.treebuilder(Some(&base_tree))?;
+    root_builder.insert("src", src_id, 0o040000)?;
+    let renamed_tree = git_repo.find_tree(root_builder.write()?)?;
+    let signature = git_repo.signature()?;
+    git_repo.commit(
+        Some("refs/heads/sv/ws/ws-a"),
+        &signature,
+        &signature,
+        "rename a.rs",
+        &renamed_tree,
+        &[&base_commit],
+    )?;
+
+    // ws-b edits the old path
+    commit_on_ref(
+        git_repo,
+        "refs/heads/sv/ws/ws-b",
+        Some(base),
+        "src/a.rs",
+        "fn a() {}\nfn b() {}\nfn c() { todo!() }\n",
+        "edit a.rs",
+    )?;
+
+    let storage = Storage::for_repo(repo.path().to_path_buf());
+    for name in ["ws-a", "ws-b"] {
+        let worktree = repo.path().join(".sv/worktrees").join(name);
+        fs::create_dir_all(&worktree)?;
+        storage.add_workspace(WorkspaceEntry::new(
+            name.to_string(),
+            worktree,
+            format!("sv/ws/{name}"),
+            "HEAD".to_string(),
+            None,
+            Utc::now().to_rfc3339(),
+            None,
+        ))?;
+    }
+
+    let output = sv_cmd(&repo)
+        .args(["risk", "--base", &base.to_string(), "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    let overlap = report["overlaps"]
+        .as_array()
+        .and_then(|overlaps| overlaps.iter().find(|o| o["path"] == "src/a.rs"))
+        .ok_or("src/a.rs overlap missing")?;
+    assert_eq!(overlap["workspaces"], serde_json::json!(["ws-a", "ws-b"]));
+    assert_eq!(overlap["renames"][0]["workspace"], "ws-a");
+    assert_eq!(overlap["renames"][0]["from"], "src/a.rs");
+    assert_eq!(overlap["renames"][0]["to"], "src/b.rs");
+
+    Ok(())
+}
+
+#[test]
+fn risk_simulate_stops_between_pairs_when_cancelled() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "base commit")?;
+
+    let git_repo = repo.repo();
+    let base = git_repo.head()?.target().ok_or("missing base head")?;
+    let storage = Storage::for_repo(repo.path().to_path_buf());
+    for name in ["ws-a", "ws-b", "ws-c"] {
+        commit_on_ref(
+            git_repo,
+            &format!("refs/heads/sv/ws/{name}"),
+            Some(base),
+            "README.md",
+            &format!("change {name}\n"),
+            &format!("{name} change"),
+        )?;
+        let worktree = repo.path().join(".sv/worktrees").join(name);
+        fs::create_dir_all(&worktree)?;
+        storage.add_workspace(WorkspaceEntry::new(
+            name.to_string(),
+            worktree,
+            format!("sv/ws/{name}"),
+            "HEAD".to_string(),
+            None,
+            Utc::now().to_rfc3339(),
+            None,
+        ))?;
+    }
+
+    let cancel = AtomicBool::new(false);
+    let report = sv::risk::simulate_conflicts_with_progress(
+        git_repo,
+        "HEAD",
+        &mut |n, _, _| {
+            if n == 2 {
+                cancel.store(true, Ordering::SeqCst);
+            }
+        },
+        &cancel,
+    )?;
+    assert!(report.cancelled);
+    assert_eq!(report.workspace_pairs.len(), 2);
+    assert_eq!(serde_json::to_value(&report)?["cancelled"], true);
+
+    let report = sv::risk::simulate_conflicts(git_repo, "HEAD")?;
+    assert!(!report.cancelled);
+    assert_eq!(report.workspace_pairs.len(), 3);
+    assert!(serde_json::to_value(&report)?.get("cancelled").is_none());
+
+    Ok(())
+}
@@ -84,3 +84,27 @@ fn honors_precedence() {
         )
     );
 }
+
+#[test]
+fn parses_lease_predicates() {
+    let expr = parse_selector("lease(held(\"me\")) & expiring(\"1h\")").expect("parse");
+    assert_eq!(
+        expr,
+        SelectorExpr::Intersection(
+            Box::new(SelectorExpr::Atom(SelectorAtom::Entity(EntitySelector {
+                kind: EntityKind::Lease,
+                predicate: Some(Predicate::Held("me".to_string())),
+            }))),
+            Box::new(SelectorExpr::Atom(SelectorAtom::Predicate(
+                Predicate::Expiring("1h".to_string())
+            )))
+        )
+    );
+    assert_eq!(
+        expr.predicates(),
+        [
+            &Predicate::Held("me".to_string()),
+            &Predicate::Expiring("1h".to_string())
+        ]
+    );
+}
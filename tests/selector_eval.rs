@@ -21,6 +21,7 @@ fn matcher(_kind: EntityKind, item: &SelectorItem, predicate: &Predicate) -> boo
         Predicate::Ahead(value) => name.contains(value),
         Predicate::Touching(value) => name.contains(value),
         Predicate::Overlaps(value) => name.contains(value),
+        Predicate::Held(value) | Predicate::Expiring(value) => name.contains(value),
         Predicate::NameMatches(_) => false,
     }
 }
@@ -51,3 +51,161 @@ fn status_runs_with_relation_events() -> Result<(), Box<dyn std::error::Error>>
 
     Ok(())
 }
+
+#[test]
+fn status_registers_checkout_and_reports_detached_head() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    let head = repo.commit_file("README.md", "base\n", "initial commit")?;
+
+    let status = |repo: &TestRepo| -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        let output = sv_cmd()
+            .current_dir(repo.path())
+            .args(["status", "--json"])
+            .output()?;
+        assert!(output.status.success());
+        Ok(serde_json::from_slice(&output.stdout)?)
+    };
+
+    // Status registers the checkout it runs in, as before.
+    let value = status(&repo)?;
+    assert_eq!(value["data"]["workspace"]["registered"], true);
+    assert_eq!(value["data"]["workspace"]["auto_registered"], true);
+    let again = status(&repo)?;
+    assert_eq!(again["data"]["workspace"]["auto_registered"], false);
+    assert_eq!(value["data"]["workspace"]["detached"], false);
+    let workspaces = sv_cmd()
+        .current_dir(repo.path())
+        .args(["ws", "list", "--json"])
+        .output()?;
+    assert!(String::from_utf8_lossy(&workspaces.stdout).contains(&*repo.path().to_string_lossy()));
+
+    let detach = std::process::Command::new("git")
+        .args(["checkout", "--detach"])
+        .current_dir(repo.path())
+        .output()?;
+    assert!(detach.status.success());
+    let value = status(&repo)?;
+    assert_eq!(value["data"]["workspace"]["detached"], true);
+    assert_eq!(
+        value["data"]["workspace"]["head"],
+        head.to_string().as_str()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn status_aggregates_repeated_repos() -> Result<(), Box<dyn std::error::Error>> {
+    let first = TestRepo::init()?;
+    first.init_sv_dirs()?;
+    let second = TestRepo::init()?;
+    second.init_sv_dirs()?;
+
+    let output = sv_cmd()
+        .arg("--repo")
+        .arg(first.path())
+        .arg("--repo")
+        .arg(second.path())
+        .args(["status", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let payload: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(payload["command"], "status");
+    let repos = payload["data"].as_object().expect("repos keyed by path");
+    assert_eq!(repos.len(), 2);
+    let key = first.path().display().to_string();
+    assert_eq!(repos[&key]["command"], "status");
+
+    let human = sv_cmd()
+        .env(
+            "SV_REPO",
+            std::env::join_paths([first.path(), second.path()])?,
+        )
+        .args(["lease", "ls"])
+        .output()?;
+    assert!(human.status.success());
+    let stdout = String::from_utf8_lossy(&human.stdout);
+    assert!(stdout.contains(&format!("==> {key} <==")));
+
+    // Commands that take one repository read SV_REPO as a single path
+    sv_cmd()
+        .env(
+            "SV_REPO",
+            std::env::join_paths([first.path(), second.path()])?,
+        )
+        .arg("version")
+        .assert()
+        .success();
+
+    sv_cmd()
+        .arg("--repo")
+        .arg(first.path())
+        .arg("--repo")
+        .arg(second.path())
+        .arg("init")
+        .assert()
+        .code(2);
+
+    Ok(())
+}
+
+#[test]
+fn status_short_prints_stable_lines() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "initial commit")?;
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args([
+            "take",
+            "src/auth/**",
+            "--strength",
+            "exclusive",
+            "--note",
+            "auth rewrite",
+            "--ttl",
+            "2h",
+            "--actor",
+            "alice",
+        ])
+        .assert()
+        .success();
+    repo.write_file("scratch.txt", "dirty\n")?;
+
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["status", "--short", "--actor", "alice"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{stdout}");
+
+    let workspace: Vec<&str> = lines[0].split(' ').collect();
+    assert_eq!(workspace[0], "W");
+    assert_eq!(workspace.len(), 4);
+    assert_eq!(workspace[3], "*");
+
+    let lease: Vec<&str> = lines[1].split(' ').collect();
+    assert_eq!(lease[..4], ["L", "src/auth/**", "alice", "excl"]);
+    assert!(lease[4] == "1h" || lease[4] == "2h", "{}", lines[1]);
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["take", "docs/my notes.md", "--actor", "alice"])
+        .assert()
+        .success();
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["status", "--short", "--actor", "alice"])
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(
+        stdout.contains("L \"docs/my notes.md\" alice coop "),
+        "{stdout}"
+    );
+
+    Ok(())
+}
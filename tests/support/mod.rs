@@ -26,6 +26,15 @@ impl TestRepo {
         Ok(Self { dir, repo })
     }
 
+    pub fn clone(url: &str, branch: &str) -> Result<Self, git2::Error> {
+        let dir = tempfile::tempdir().expect("failed to create tempdir");
+        let repo = git2::build::RepoBuilder::new()
+            .branch(branch)
+            .clone(url, dir.path())?;
+        set_identity(&repo)?;
+        Ok(Self { dir, repo })
+    }
+
     pub fn path(&self) -> &Path {
         self.dir.path()
     }
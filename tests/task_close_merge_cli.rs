@@ -0,0 +1,160 @@
+mod support;
+
+use std::path::Path;
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use serde_json::Value;
+
+use support::TestRepo;
+
+fn sv_cmd(repo: &TestRepo) -> Command {
+    let mut cmd = support::sv_cmd();
+    cmd.current_dir(repo.path()).env("SV_ACTOR", "alice");
+    cmd
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    let output = std::process::Command::new("git")
+        .args([
+            "-c",
+            "user.name=sv-test",
+            "-c",
+            "user.email=sv-test@example.com",
+        ])
+        .args(args)
+        .current_dir(dir)
+        .output()?;
+    assert!(output.status.success(), "git {:?} failed", args);
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+/// Create a repo with a task started in its own workspace; returns the task
+/// id, the base branch and the workspace path.
+fn setup_task() -> Result<(TestRepo, String, String, std::path::PathBuf), Box<dyn std::error::Error>>
+{
+    let repo = TestRepo::init()?;
+    repo.commit_file("README.md", "base\n", "initial commit")?;
+    sv_cmd(&repo).arg("init").assert().success();
+    let base = git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"])?;
+
+    let output = sv_cmd(&repo)
+        .args(["task", "new", "merge me", "--json"])
+        .output()?;
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    let task_id = value["data"]["id"].as_str().expect("task id").to_string();
+
+    sv_cmd(&repo)
+        .args([
+            "task",
+            "start",
+            &task_id,
+            "--create-workspace",
+            "--base",
+            &base,
+        ])
+        .assert()
+        .success();
+    let ws_path = repo.path().join(".sv/worktrees").join(&task_id);
+    Ok((repo, task_id, base, ws_path))
+}
+
+#[test]
+fn task_close_merge_fast_forwards_base() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, task_id, base, ws_path) = setup_task()?;
+    std::fs::write(ws_path.join("feature.txt"), "feature\n")?;
+    git(&ws_path, &["add", "feature.txt"])?;
+    git(&ws_path, &["commit", "-m", "add feature"])?;
+    let tip = git(&ws_path, &["rev-parse", "HEAD"])?;
+
+    let output = sv_cmd(&repo)
+        .args([
+            "task", "close", &task_id, "--merge", "--into", &base, "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["data"]["status"], "closed");
+    assert_eq!(value["data"]["merge"]["status"], "fast_forward");
+    assert_eq!(value["data"]["merge"]["commit"], tip.as_str());
+
+    assert_eq!(git(repo.path(), &["rev-parse", &base])?, tip);
+    assert!(repo.path().join("feature.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+fn task_list_and_show_flag_merged_branches() -> Result<(), Box<dyn std::error::Error>> {
+    let (repo, task_id, _base, ws_path) = setup_task()?;
+    std::fs::write(ws_path.join("feature.txt"), "feature\n")?;
+    git(&ws_path, &["add", "feature.txt"])?;
+    git(&ws_path, &["commit", "-m", "add feature"])?;
+
+    let shown = sv_cmd(&repo)
+        .args(["task", "show", &task_id, "--json"])
+        .output()?;
+    let value: Value = serde_json::from_slice(&shown.stdout)?;
+    assert_eq!(value["data"]["merged"], false);
+
+    let branch = format!("sv/ws/{task_id}");
+    git(repo.path(), &["merge", "--ff-only", &branch])?;
+
+    let listed = sv_cmd(&repo).args(["task", "list", "--json"]).output()?;
+    let value: Value = serde_json::from_slice(&listed.stdout)?;
+    assert_eq!(value["data"]["tasks"][0]["id"], task_id.as_str());
+    assert_eq!(value["data"]["tasks"][0]["merged"], true);
+
+    sv_cmd(&repo)
+        .args(["task", "list"])
+        .assert()
+        .success()
+        .stdout(contains("(merged)"));
+
+    Ok(())
+}
+
+#[test]
+fn task_close_merge_reports_conflicts_without_committing() -> Result<(), Box<dyn std::error::Error>>
+{
+    let (repo, task_id, base, ws_path) = setup_task()?;
+    std::fs::write(ws_path.join("README.md"), "task change\n")?;
+    git(&ws_path, &["commit", "-am", "task edit"])?;
+    repo.commit_file("README.md", "base change\n", "base edit")?;
+    let base_head = git(repo.path(), &["rev-parse", &base])?;
+
+    let output = sv_cmd(&repo)
+        .args([
+            "task", "close", &task_id, "--merge", "--into", &base, "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["data"]["status"], "closed");
+    assert_eq!(value["data"]["merge"]["status"], "conflicts");
+    assert_eq!(value["data"]["merge"]["conflicts"][0]["path"], "README.md");
+
+    assert_eq!(git(repo.path(), &["rev-parse", &base])?, base_head);
+
+    Ok(())
+}
+
+#[test]
+fn task_close_merge_skips_task_without_workspace() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.commit_file("README.md", "base\n", "initial commit")?;
+    sv_cmd(&repo).arg("init").assert().success();
+    let output = sv_cmd(&repo)
+        .args(["task", "new", "never started", "--json"])
+        .output()?;
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    let task_id = value["data"]["id"].as_str().expect("task id").to_string();
+
+    sv_cmd(&repo)
+        .args(["task", "close", &task_id, "--merge"])
+        .assert()
+        .success()
+        .stdout(contains("no bound workspace"));
+
+    Ok(())
+}
@@ -87,6 +87,50 @@ fn task_count_rejects_status_with_ready() -> Result<(), Box<dyn std::error::Erro
     Ok(())
 }
 
+#[test]
+fn task_count_fail_over_sets_exit_code() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+
+    for title in ["One", "Two"] {
+        sv_cmd(&repo)
+            .args(["task", "new", title])
+            .assert()
+            .success();
+    }
+
+    sv_cmd(&repo)
+        .args(["task", "count", "--fail-over", "2"])
+        .assert()
+        .success()
+        .stdout("2\n");
+
+    sv_cmd(&repo)
+        .args(["task", "count", "--fail-over", "1"])
+        .assert()
+        .code(3)
+        .stdout("2\n")
+        .stderr(contains("task count 2 exceeds 1"));
+
+    sv_cmd(&repo)
+        .args(["task", "count", "--ready", "--fail-over", "0"])
+        .assert()
+        .code(3);
+
+    // JSON output is the regular count; only the exit code changes.
+    let output = sv_cmd(&repo)
+        .args(["task", "count", "--fail-over", "1", "--json"])
+        .assert()
+        .code(3)
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&output)?;
+    assert_eq!(value["status"], "success");
+    assert_eq!(value["data"]["total"].as_u64(), Some(2));
+
+    Ok(())
+}
+
 #[test]
 fn task_queries_do_not_use_sv_actor_as_filter() -> Result<(), Box<dyn std::error::Error>> {
     let repo = TestRepo::init()?;
@@ -183,3 +227,143 @@ fn task_queries_support_sv_actor_filter_env() -> Result<(), Box<dyn std::error::
 
     Ok(())
 }
+
+#[test]
+fn task_list_and_ready_emit_tsv() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+
+    sv_cmd(&repo)
+        .args(["task", "new", "Tab\there", "--priority", "P1"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["task", "new", "Done", "--status", "closed"])
+        .assert()
+        .success();
+
+    let header = "id\ttitle\tstatus\tpriority\tepic\tproject\tworkspace\tupdated_at";
+    for (command, rows) in [("list", 2), ("ready", 1)] {
+        let output = sv_cmd(&repo)
+            .args(["task", command, "--format", "tsv"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let text = String::from_utf8(output)?;
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], header);
+        assert_eq!(lines.len(), rows + 1);
+        for line in &lines[1..] {
+            assert_eq!(line.split('\t').count(), 8);
+        }
+    }
+
+    let ready = sv_cmd(&repo)
+        .args(["task", "ready", "--format", "tsv", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let ready = String::from_utf8(ready)?;
+    let row: Vec<&str> = ready.lines().nth(1).unwrap().split('\t').collect();
+    assert_eq!(row[1], "Tab here");
+    assert_eq!(row[2], "open");
+    assert_eq!(row[3], "P1");
+
+    sv_cmd(&repo)
+        .args(["task", "list", "--format", "csv"])
+        .assert()
+        .failure()
+        .stderr(contains("invalid format 'csv'"));
+
+    Ok(())
+}
+
+#[test]
+fn task_status_filter_accepts_multiple_values() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+
+    sv_cmd(&repo)
+        .args(["task", "new", "Open"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["task", "new", "Started", "--status", "in_progress"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["task", "new", "Done", "--status", "closed"])
+        .assert()
+        .success();
+
+    sv_cmd(&repo)
+        .args(["task", "count", "--status", "open"])
+        .assert()
+        .success()
+        .stdout("1\n");
+
+    sv_cmd(&repo)
+        .args([
+            "task",
+            "count",
+            "--status",
+            "open",
+            "--status",
+            "in_progress",
+        ])
+        .assert()
+        .success()
+        .stdout("2\n");
+
+    // Non-status filters take the full-record path; the union still applies.
+    sv_cmd(&repo)
+        .args([
+            "task",
+            "count",
+            "--status",
+            "open",
+            "--status",
+            "in_progress",
+            "--priority",
+            "P2",
+        ])
+        .assert()
+        .success()
+        .stdout("2\n");
+
+    let output = sv_cmd(&repo)
+        .args([
+            "task",
+            "list",
+            "--status",
+            "open",
+            "--status",
+            "in_progress",
+            "--json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&output)?;
+    assert_eq!(value["data"]["total"].as_u64(), Some(2));
+    let mut titles: Vec<&str> = value["data"]["tasks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|task| task["title"].as_str().unwrap())
+        .collect();
+    titles.sort();
+    assert_eq!(titles, ["Open", "Started"]);
+
+    sv_cmd(&repo)
+        .args(["task", "list", "--status", "open", "--status", "bogus"])
+        .assert()
+        .failure()
+        .stderr(contains("unknown task status 'bogus'"));
+
+    Ok(())
+}
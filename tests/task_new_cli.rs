@@ -0,0 +1,69 @@
+mod support;
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use serde_json::Value;
+
+use support::TestRepo;
+
+fn sv_cmd(repo: &TestRepo) -> Command {
+    let mut cmd = support::sv_cmd();
+    cmd.current_dir(repo.path());
+    cmd
+}
+
+fn setup_repo() -> Result<TestRepo, Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.commit_file("README.md", "base\n", "initial commit")?;
+    sv_cmd(&repo).arg("init").assert().success();
+    Ok(repo)
+}
+
+fn new_task_id(repo: &TestRepo, args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    let output = sv_cmd(repo)
+        .args(["task", "new"])
+        .args(args)
+        .arg("--json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&output)?;
+    Ok(value["data"]["id"].as_str().ok_or("task id")?.to_string())
+}
+
+#[test]
+fn task_new_uses_explicit_id_and_rejects_collisions() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = setup_repo()?;
+
+    assert_eq!(
+        new_task_id(&repo, &["Imported", "--id", "jira1042"])?,
+        "sv-jira1042"
+    );
+    assert_eq!(new_task_id(&repo, &["Other", "--id", "ext-77"])?, "ext-77");
+
+    sv_cmd(&repo)
+        .args(["task", "show", "jira1042"])
+        .assert()
+        .success()
+        .stdout(contains("Imported"));
+
+    sv_cmd(&repo)
+        .args(["task", "new", "Again", "--id", "sv-jira1042"])
+        .assert()
+        .failure()
+        .stderr(contains("collides"));
+    sv_cmd(&repo)
+        .args(["task", "new", "Same suffix", "--id", "77"])
+        .assert()
+        .failure()
+        .stderr(contains("collides"));
+    sv_cmd(&repo)
+        .args(["task", "new", "Bad", "--id", "not valid!"])
+        .assert()
+        .failure()
+        .stderr(contains("invalid task id"));
+
+    Ok(())
+}
@@ -197,3 +197,81 @@ fn task_sync_warns_on_duplicate_creates() -> Result<(), Box<dyn std::error::Erro
 
     Ok(())
 }
+
+#[test]
+fn task_doctor_reports_effective_project_cycles() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    let mut ids = Vec::new();
+    for title in ["One", "Two"] {
+        let output = sv_cmd(&repo)
+            .args(["task", "new", title, "--json"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let value: Value = serde_json::from_slice(&output)?;
+        ids.push(value["data"]["id"].as_str().unwrap().to_string());
+    }
+    sv_cmd(&repo)
+        .args(["task", "epic", "set", &ids[0], &ids[1]])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["task", "epic", "set", &ids[1], &ids[0]])
+        .assert()
+        .success();
+
+    let doctor_output = sv_cmd(&repo)
+        .args(["task", "doctor", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let doctor: Value = serde_json::from_slice(&doctor_output)?;
+    let cycles = doctor["data"]["project_cycles"].as_array().unwrap();
+    assert_eq!(cycles.len(), 1);
+    let mut chain: Vec<&str> = cycles[0]["chain"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|id| id.as_str().unwrap())
+        .collect();
+    chain.sort();
+    let mut expected: Vec<&str> = ids.iter().map(String::as_str).collect();
+    expected.sort();
+    assert_eq!(chain, expected);
+    // The link set last is the one to clear.
+    assert_eq!(cycles[0]["clear_epic"].as_str(), Some(ids[1].as_str()));
+
+    sv_cmd(&repo)
+        .args(["task", "doctor"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(format!(
+            "sv task epic clear {}",
+            ids[1]
+        )));
+
+    sv_cmd(&repo)
+        .args(["task", "epic", "clear", &ids[1]])
+        .assert()
+        .success();
+    let doctor_after = sv_cmd(&repo)
+        .args(["task", "doctor", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let doctor_after: Value = serde_json::from_slice(&doctor_after)?;
+    assert_eq!(
+        doctor_after["data"]["project_cycles"]
+            .as_array()
+            .map(|items| items.len()),
+        Some(0)
+    );
+
+    Ok(())
+}
@@ -10,6 +10,7 @@ use predicates::str::contains;
 use serde_json::Value;
 
 use support::TestRepo;
+use sv::lease::LeaseStatus;
 
 fn sv_cmd(repo: &TestRepo) -> Command {
     let mut cmd = support::sv_cmd();
@@ -173,3 +174,228 @@ fn task_start_parallel_only_one_actor_wins() -> Result<(), Box<dyn std::error::E
 
     Ok(())
 }
+
+#[test]
+fn task_show_events_file_writes_task_history() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = setup_repo()?;
+    let task_id = new_task(&repo, "Archive me");
+    let other_id = new_task(&repo, "Unrelated");
+    let blocker_id = new_task(&repo, "Blocker");
+
+    sv_cmd(&repo)
+        .args(["task", "comment", &task_id, "first note"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["task", "comment", &other_id, "other note"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["task", "block", &task_id, &blocker_id])
+        .assert()
+        .success();
+
+    let events_path = repo.path().join("task.events.jsonl");
+    sv_cmd(&repo)
+        .args(["task", "show", &task_id, "--events-file"])
+        .arg(&events_path)
+        .assert()
+        .success()
+        .stdout(contains("events file"));
+
+    let contents = std::fs::read_to_string(&events_path)?;
+    let events: Vec<Value> = contents
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    let types: Vec<&str> = events
+        .iter()
+        .filter_map(|event| event["type"].as_str())
+        .collect();
+    assert_eq!(
+        types,
+        vec!["task_created", "task_commented", "task_blocked"]
+    );
+    assert!(events.iter().all(|event| {
+        event["task_id"] == task_id.as_str() || event["related_task_id"] == task_id.as_str()
+    }));
+
+    Ok(())
+}
+
+#[test]
+fn ws_info_and_task_show_link_workspace_and_tasks() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = setup_repo()?;
+    let task_id = new_task(&repo, "linked work");
+    new_task(&repo, "unstarted");
+
+    sv_cmd(&repo)
+        .args(["--actor", "alice", "task", "start", &task_id])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&repo)
+        .args(["ws", "info", "local", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let info: Value = serde_json::from_slice(&output.stdout)?;
+    let tasks = info["tasks"].as_array().expect("tasks");
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["id"], task_id.as_str());
+    assert_eq!(tasks[0]["title"], "linked work");
+
+    let shown = task_show_json(&repo, &task_id);
+    let status = &shown["data"]["workspace_status"];
+    assert_eq!(status["name"], "local");
+    assert_eq!(status["exists"], true);
+    assert_eq!(status["ahead_behind"]["ahead"], 0);
+
+    sv_cmd(&repo)
+        .args(["task", "show", &task_id])
+        .assert()
+        .success()
+        .stdout(contains("Divergence: 0 ahead / 0 behind"));
+
+    Ok(())
+}
+
+#[test]
+fn task_start_lease_takes_workspace_scoped_leases() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = setup_repo()?;
+    let task_id = new_task(&repo, "Fix auth redirect");
+
+    let output = sv_cmd(&repo)
+        .args([
+            "--actor",
+            "alice",
+            "task",
+            "start",
+            &task_id,
+            "--lease",
+            "src/auth/**",
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["data"]["leases"][0]["path"], "src/auth/**");
+    assert_eq!(value["data"]["leases"][0]["intent"], "bugfix");
+
+    let leases = repo.read_leases()?;
+    assert_eq!(leases.len(), 1);
+    assert_eq!(leases[0].actor.as_deref(), Some("alice"));
+    assert_eq!(leases[0].scope.to_string(), "ws:local");
+    assert_eq!(leases[0].strength.to_string(), "cooperative");
+
+    Ok(())
+}
+
+#[test]
+fn task_start_lease_conflict_leaves_task_unstarted() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = setup_repo()?;
+    let task_id = new_task(&repo, "schema change");
+
+    sv_cmd(&repo)
+        .args([
+            "--actor",
+            "bob",
+            "take",
+            "src/db.rs",
+            "--strength",
+            "exclusive",
+            "--note",
+            "migration",
+        ])
+        .assert()
+        .success();
+
+    sv_cmd(&repo)
+        .args(["--actor", "alice", "task", "start", &task_id])
+        .args(["--lease", "src/db.rs"])
+        .assert()
+        .failure();
+
+    let shown = task_show_json(&repo, &task_id);
+    assert_eq!(shown["data"]["task"]["status"], "open");
+    assert_eq!(repo.read_leases()?.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn task_start_failure_releases_new_leases() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = setup_repo()?;
+    let task_id = new_task(&repo, "shared work");
+
+    sv_cmd(&repo)
+        .args(["--actor", "alice", "task", "start", &task_id])
+        .assert()
+        .success();
+
+    sv_cmd(&repo)
+        .args(["--actor", "bob", "task", "start", &task_id])
+        .args(["--lease", "src/lib.rs"])
+        .assert()
+        .failure()
+        .stderr(contains("use --takeover"));
+
+    let leases = repo.read_leases()?;
+    assert_eq!(leases.len(), 1);
+    assert_eq!(leases[0].status, LeaseStatus::Released);
+
+    Ok(())
+}
+
+#[test]
+fn task_start_create_workspace_binds_task_to_new_worktree() -> Result<(), Box<dyn std::error::Error>>
+{
+    let repo = setup_repo()?;
+    let task_id = new_task(&repo, "isolated work");
+
+    let output = sv_cmd(&repo)
+        .args(["--actor", "alice", "task", "start", &task_id])
+        .args(["--create-workspace", "--base", "HEAD", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    let branch = format!("sv/ws/{task_id}");
+    assert_eq!(value["data"]["workspace"]["name"], task_id.as_str());
+    assert_eq!(value["data"]["workspace"]["branch"], branch.as_str());
+    assert!(repo.path().join(".sv/worktrees").join(&task_id).exists());
+
+    let shown = task_show_json(&repo, &task_id);
+    assert_eq!(shown["data"]["task"]["status"], "in_progress");
+    assert_eq!(shown["data"]["task"]["workspace"], task_id.as_str());
+    assert_eq!(shown["data"]["task"]["branch"], branch.as_str());
+
+    Ok(())
+}
+
+#[test]
+fn task_start_create_workspace_cleans_up_on_failure() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = setup_repo()?;
+    let task_id = new_task(&repo, "contended work");
+
+    sv_cmd(&repo)
+        .args(["--actor", "alice", "task", "start", &task_id])
+        .assert()
+        .success();
+
+    sv_cmd(&repo)
+        .args(["--actor", "bob", "task", "start", &task_id])
+        .args(["--create-workspace", "--base", "HEAD"])
+        .assert()
+        .failure()
+        .stderr(contains("use --takeover"));
+
+    assert!(!repo.path().join(".sv/worktrees").join(&task_id).exists());
+    let branch = format!("sv/ws/{task_id}");
+    assert!(repo
+        .repo()
+        .find_branch(&branch, git2::BranchType::Local)
+        .is_err());
+    let listed = sv_cmd(&repo).args(["ws", "list", "--json"]).output()?;
+    assert!(!String::from_utf8_lossy(&listed.stdout).contains(&branch));
+
+    Ok(())
+}
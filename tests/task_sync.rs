@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use sv::config::Config;
 use sv::storage::Storage;
 use sv::task::{TaskEvent, TaskEventType, TaskStore};
@@ -34,3 +36,225 @@ fn sync_merges_tracked_and_shared_logs() {
         .expect("read merged");
     assert_eq!(merged.len(), 2);
 }
+
+fn sv_json(repo: &support::TestRepo, args: &[&str]) -> serde_json::Value {
+    let output = support::sv_cmd()
+        .current_dir(repo.path())
+        .args(args)
+        .arg("--json")
+        .output()
+        .expect("run sv");
+    assert!(
+        output.status.success(),
+        "sv {:?} failed: {}{}",
+        args,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("json output")
+}
+
+#[test]
+fn sync_push_and_pull_share_events_through_remote() {
+    let remote_dir = tempfile::tempdir().expect("remote dir");
+    git2::Repository::init_bare(remote_dir.path()).expect("bare remote");
+    let remote_url = remote_dir.path().to_str().expect("utf8 path");
+
+    let alpha = support::TestRepo::init().expect("alpha repo");
+    alpha.write_file("README.md", "alpha\n").expect("write");
+    alpha.commit_all("init").expect("commit");
+    alpha.repo().remote("origin", remote_url).expect("remote");
+    sv_json(&alpha, &["task", "new", "Alpha"]);
+
+    let pushed = sv_json(&alpha, &["task", "sync", "--push"]);
+    assert!(pushed["data"]["pushed"]["commit"].is_string());
+    let branch = pushed["data"]["pushed"]["branch"]
+        .as_str()
+        .expect("branch")
+        .to_string();
+
+    // The push commits only the task files; the rest of the index is untouched.
+    let remote = git2::Repository::open_bare(remote_dir.path()).expect("open remote");
+    let tip = remote
+        .revparse_single(&branch)
+        .expect("remote branch")
+        .peel_to_commit()
+        .expect("commit");
+    assert_eq!(tip.message(), Some("sv: sync tasks"));
+    let tree = tip.tree().expect("tree");
+    assert!(tree.get_path(Path::new(".tasks/tasks.jsonl")).is_ok());
+    assert!(tree
+        .get_path(Path::new(".tasks/tasks.snapshot.json"))
+        .is_ok());
+
+    // Re-pushing with no task changes skips the commit.
+    let again = sv_json(&alpha, &["task", "sync", "--push"]);
+    assert!(again["data"]["pushed"]["commit"].is_null());
+
+    let beta = support::TestRepo::init().expect("beta repo");
+    beta.write_file("README.md", "beta\n").expect("write");
+    beta.commit_all("init").expect("commit");
+    beta.repo().remote("origin", remote_url).expect("remote");
+    sv_json(&beta, &["task", "new", "Beta"]);
+
+    let pulled = sv_json(&beta, &["task", "sync", "--pull"]);
+    assert_eq!(pulled["data"]["imported_events"], 1);
+    assert_eq!(pulled["data"]["total_tasks"], 2);
+
+    // Pulling again imports nothing: events are unioned by event_id.
+    let repeat = sv_json(&beta, &["task", "sync", "--pull"]);
+    assert_eq!(repeat["data"]["imported_events"], 0);
+    assert_eq!(repeat["data"]["total_events"], 2);
+
+    // Beta's branch has unrelated history, so its push is rejected, not forced.
+    support::sv_cmd()
+        .current_dir(beta.path())
+        .args(["task", "sync", "--push"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn sync_pull_push_integrates_commits_pushed_by_another_clone() {
+    let remote_dir = tempfile::tempdir().expect("remote dir");
+    git2::Repository::init_bare(remote_dir.path()).expect("bare remote");
+    let remote_url = remote_dir.path().to_str().expect("utf8 path");
+
+    let alpha = support::TestRepo::init().expect("alpha repo");
+    alpha.write_file("README.md", "v1\n").expect("write");
+    alpha.write_file(".gitignore", "*.lock\n").expect("write");
+    alpha.commit_all("init").expect("commit");
+    alpha.repo().remote("origin", remote_url).expect("remote");
+    sv_json(&alpha, &["task", "new", "Alpha"]);
+    let pushed = sv_json(&alpha, &["task", "sync", "--push"]);
+    let branch = pushed["data"]["pushed"]["branch"]
+        .as_str()
+        .expect("branch")
+        .to_string();
+
+    let beta = support::TestRepo::clone(remote_url, &branch).expect("beta clone");
+    beta.write_file("NOTES.md", "beta\n").expect("write");
+    beta.commit_all("beta notes").expect("commit");
+    sv_json(&beta, &["task", "new", "Beta"]);
+
+    // Alpha moves the remote on, including a file outside the task log.
+    alpha.write_file("README.md", "v2\n").expect("write");
+    alpha.commit_all("update readme").expect("commit");
+    sv_json(&alpha, &["task", "new", "Alpha two"]);
+    sv_json(&alpha, &["task", "sync", "--push"]);
+
+    // Beta diverged: the sync commit merges the fetched tip and fast-forwards it.
+    let synced = sv_json(&beta, &["task", "sync", "--pull", "--push"]);
+    assert_eq!(synced["data"]["total_tasks"], 3);
+    assert!(synced["data"]["pushed"]["commit"].is_string());
+
+    let remote = git2::Repository::open_bare(remote_dir.path()).expect("open remote");
+    let tip = remote
+        .revparse_single(&branch)
+        .expect("remote branch")
+        .peel_to_commit()
+        .expect("commit");
+    assert_eq!(tip.parent_count(), 2);
+    assert_eq!(tip.id(), beta.repo().head().unwrap().target().unwrap());
+    let blob = tip
+        .tree()
+        .expect("tree")
+        .get_path(Path::new(".tasks/tasks.jsonl"))
+        .expect("task log")
+        .to_object(&remote)
+        .expect("object")
+        .peel_to_blob()
+        .expect("blob");
+    let log = String::from_utf8_lossy(blob.content()).into_owned();
+    for title in ["Alpha", "Beta", "Alpha two"] {
+        assert!(log.contains(&format!("\"title\":\"{title}\"")), "{log}");
+    }
+    assert_eq!(
+        std::fs::read_to_string(beta.path().join("README.md")).expect("readme"),
+        "v2\n"
+    );
+
+    // Alpha is now behind: its sync lands on top of the tip instead.
+    sv_json(&alpha, &["task", "sync", "--pull", "--push"]);
+    let tip = remote
+        .revparse_single(&branch)
+        .expect("remote branch")
+        .peel_to_commit()
+        .expect("commit");
+    assert_eq!(tip.id(), alpha.repo().head().unwrap().target().unwrap());
+    let list = sv_json(&alpha, &["task", "list"]);
+    assert_eq!(list["data"]["total"], 3, "{list}");
+}
+
+fn switch_branch(repo: &support::TestRepo, name: &str) {
+    repo.repo()
+        .set_head(&format!("refs/heads/{name}"))
+        .expect("set head");
+    repo.repo()
+        .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+        .expect("checkout");
+}
+
+#[test]
+fn merge_driver_unions_task_logs_across_branches() {
+    let repo = support::TestRepo::init().expect("repo");
+    repo.write_sv_config("[tasks]\nsnapshot_writes = \"deferred\"\n")
+        .expect("config");
+    repo.write_file("README.md", "init\n").expect("write");
+    sv_json(&repo, &["task", "new", "Base"]);
+
+    let installed = sv_json(&repo, &["task", "install-merge-driver"]);
+    assert_eq!(installed["data"]["attributes_updated"], true);
+    let again = sv_json(&repo, &["task", "install-merge-driver"]);
+    assert_eq!(again["data"]["attributes_updated"], false);
+    let attributes = std::fs::read_to_string(repo.path().join(".gitattributes")).expect("attrs");
+    assert_eq!(attributes, ".tasks/tasks.jsonl merge=sv-tasks\n");
+
+    repo.commit_all("init").expect("commit");
+    let main_branch = repo
+        .repo()
+        .head()
+        .expect("head")
+        .shorthand()
+        .expect("branch")
+        .to_string();
+    repo.create_branch("other").expect("branch");
+
+    sv_json(&repo, &["task", "new", "Ours"]);
+    repo.commit_all("ours").expect("commit");
+
+    switch_branch(&repo, "other");
+    sv_json(&repo, &["task", "new", "Theirs"]);
+    repo.commit_all("theirs").expect("commit");
+
+    switch_branch(&repo, &main_branch);
+
+    let sv_bin = Path::new(env!("CARGO_BIN_EXE_sv"));
+    let path = std::env::join_paths(
+        std::iter::once(sv_bin.parent().expect("bin dir").to_path_buf()).chain(
+            std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()),
+        ),
+    )
+    .expect("PATH");
+    let merge = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .env("PATH", path)
+        .args(["merge", "--no-edit", "other"])
+        .output()
+        .expect("git merge");
+    assert!(
+        merge.status.success(),
+        "merge failed: {}{}",
+        String::from_utf8_lossy(&merge.stdout),
+        String::from_utf8_lossy(&merge.stderr)
+    );
+
+    let merged: Vec<TaskEvent> = std::fs::read_to_string(repo.path().join(".tasks/tasks.jsonl"))
+        .expect("log")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("event"))
+        .collect();
+    let mut titles: Vec<_> = merged.iter().filter_map(|e| e.title.clone()).collect();
+    titles.sort();
+    assert_eq!(titles, vec!["Base", "Ours", "Theirs"]);
+}
@@ -0,0 +1,81 @@
+mod support;
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use serde_json::Value;
+
+use support::TestRepo;
+
+fn sv_cmd(repo: &TestRepo) -> Command {
+    let mut cmd = support::sv_cmd();
+    cmd.current_dir(repo.path());
+    cmd
+}
+
+fn new_task(
+    repo: &TestRepo,
+    actor: &str,
+    title: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let output = sv_cmd(repo)
+        .env("SV_ACTOR", actor)
+        .args(["task", "new", title, "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    Ok(value["data"]["id"].as_str().unwrap_or_default().to_string())
+}
+
+#[test]
+fn task_timeline_orders_events_across_tasks() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+
+    let first = new_task(&repo, "alice", "First")?;
+    let second = new_task(&repo, "bob", "Second")?;
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["task", "comment", &first, "Looks good\nmore detail"])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&repo)
+        .args(["task", "timeline", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    let events = value["data"]["events"].as_array().expect("events");
+    let tasks: Vec<&str> = events
+        .iter()
+        .map(|event| event["task_id"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(tasks, vec![first.as_str(), second.as_str(), first.as_str()]);
+    assert_eq!(events[0]["type"], "task_created");
+    assert_eq!(events[2]["description"], "commented: Looks good");
+
+    let output = sv_cmd(&repo)
+        .args(["task", "timeline", "--actor", "bob", "--json"])
+        .output()?;
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["data"]["events"].as_array().map(Vec::len), Some(1));
+
+    let output = sv_cmd(&repo)
+        .args(["task", "timeline", "--limit", "1", "--json"])
+        .output()?;
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["data"]["events"][0]["type"], "task_commented");
+
+    sv_cmd(&repo)
+        .args(["task", "timeline", "--since", "1h"])
+        .assert()
+        .success()
+        .stdout(contains("Task timeline (3 events)"))
+        .stdout(contains("created \"Second\""));
+
+    sv_cmd(&repo)
+        .args(["task", "timeline", "--since", "soon"])
+        .assert()
+        .failure()
+        .stderr(contains("invalid --since"));
+
+    Ok(())
+}
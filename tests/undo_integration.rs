@@ -1,24 +1,103 @@
 mod support;
 
+use serde_json::Value;
 use support::{sv_cmd, TestRepo};
 
 #[test]
-#[ignore = "undo command not implemented yet"]
 fn undo_latest_operation() -> Result<(), Box<dyn std::error::Error>> {
     let repo = TestRepo::init()?;
     repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "base")?;
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["ws", "new", "agent1", "--base", "HEAD"])
+        .assert()
+        .success();
+    let worktree = repo.path().join(".sv/worktrees/agent1");
+    assert!(worktree.exists());
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .arg("undo")
+        .assert()
+        .success();
+    assert!(!worktree.exists());
+
+    Ok(())
+}
+
+#[test]
+fn undo_refuses_to_remove_dirty_worktree() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "base")?;
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["ws", "new", "agent1", "--base", "HEAD"])
+        .assert()
+        .success();
+    let worktree = repo.path().join(".sv/worktrees/agent1");
+    std::fs::write(worktree.join("wip.txt"), "unsaved\n")?;
 
     sv_cmd()
         .current_dir(repo.path())
         .arg("undo")
         .assert()
+        .code(4);
+    assert!(worktree.join("wip.txt").exists());
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["undo", "--keep-worktree"])
+        .assert()
+        .success();
+    assert!(worktree.join("wip.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+fn undo_dry_run_reports_changes_without_applying() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "base")?;
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["ws", "new", "agent1", "--base", "HEAD"])
+        .assert()
+        .success();
+    let worktree = repo.path().join(".sv/worktrees/agent1");
+
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["undo", "--dry-run", "--json"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    let data = &value["data"];
+    assert_eq!(data["dry_run"], true);
+    assert_eq!(data["command"], "sv ws new agent1");
+    assert_eq!(data["unregister_workspaces"][0], "agent1");
+    assert_eq!(data["remove_paths"].as_array().map(Vec::len), Some(1));
+
+    assert!(worktree.exists());
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["ws", "info", "agent1"])
+        .assert()
         .success();
 
     Ok(())
 }
 
 #[test]
-#[ignore = "undo command not implemented yet"]
 fn undo_specific_operation() -> Result<(), Box<dyn std::error::Error>> {
     let repo = TestRepo::init()?;
     repo.init_sv_dirs()?;
@@ -33,3 +112,70 @@ fn undo_specific_operation() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn redo_reapplies_undo_until_next_operation() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    repo.commit_file("README.md", "base\n", "base")?;
+    let base = repo
+        .repo()
+        .head()?
+        .shorthand()
+        .ok_or("no branch")?
+        .to_string();
+    repo.write_sv_config(&format!("base = \"{base}\"\n"))?;
+    repo.create_branch("sv/ws/gone")?;
+    let exists = || {
+        repo.repo()
+            .find_branch("sv/ws/gone", git2::BranchType::Local)
+            .is_ok()
+    };
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["refs", "prune"])
+        .assert()
+        .success();
+    assert!(!exists());
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .arg("undo")
+        .assert()
+        .success();
+    assert!(exists());
+
+    let output = sv_cmd()
+        .current_dir(repo.path())
+        .args(["redo", "--json"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["data"]["command"], "sv refs prune");
+    assert!(!exists());
+
+    sv_cmd()
+        .current_dir(repo.path())
+        .arg("undo")
+        .assert()
+        .success();
+    assert!(exists());
+    sv_cmd()
+        .current_dir(repo.path())
+        .args(["take", "src/**"])
+        .assert()
+        .success();
+    sv_cmd()
+        .current_dir(repo.path())
+        .arg("redo")
+        .assert()
+        .failure();
+    assert!(exists());
+
+    Ok(())
+}
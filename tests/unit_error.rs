@@ -12,6 +12,9 @@ fn exit_codes_map_correctly() {
 
     let op = Error::OperationFailed("boom".to_string());
     assert_eq!(op.exit_code(), exit_codes::OPERATION_FAILED);
+
+    let interrupted = Error::Interrupted("stopped".to_string());
+    assert_eq!(interrupted.exit_code(), exit_codes::INTERRUPTED);
 }
 
 #[test]
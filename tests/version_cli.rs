@@ -0,0 +1,26 @@
+mod support;
+
+use serde_json::Value;
+
+#[test]
+fn version_json_reports_build_metadata_outside_a_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+
+    let output = support::sv_cmd()
+        .current_dir(dir.path())
+        .args(["version", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let payload: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(payload["command"], "version");
+    let data = &payload["data"];
+    assert_eq!(data["version"], env!("CARGO_PKG_VERSION"));
+    assert!(data["git_sha"].as_str().is_some_and(|sha| !sha.is_empty()));
+    let build_date = data["build_date"].as_str().ok_or("missing build_date")?;
+    assert!(chrono::NaiveDate::parse_from_str(build_date, "%Y-%m-%d").is_ok());
+    let libgit2 = data["libgit2_version"].as_str().ok_or("missing libgit2")?;
+    assert_eq!(libgit2.split('.').count(), 3);
+    assert!(data["features"].is_array());
+
+    Ok(())
+}
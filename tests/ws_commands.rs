@@ -193,9 +193,7 @@ fn ws_switch_resolves_workspace_path() -> Result<(), Box<dyn std::error::Error>>
         .assert()
         .success();
 
-    let output = sv_cmd(&repo)
-        .args(["ws", "switch", "ws1"])
-        .output()?;
+    let output = sv_cmd(&repo).args(["ws", "switch", "ws1"]).output()?;
     assert!(output.status.success());
     let actual_path = std::path::PathBuf::from(String::from_utf8(output.stdout)?.trim());
     assert_eq!(
@@ -260,3 +258,192 @@ fn switch_resolves_workspace_path_without_path_flag() -> Result<(), Box<dyn std:
 
     Ok(())
 }
+
+#[test]
+fn ws_new_defaults_base_from_matching_base_rule() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = setup_repo()?;
+    let trunk = repo
+        .repo()
+        .head()?
+        .shorthand()
+        .ok_or("no branch")?
+        .to_string();
+    repo.create_branch("develop")?;
+    repo.write_sv_config(&format!(
+        "base = \"{trunk}\"\n\n[[base_rules]]\npattern = \"feature/*\"\nbase = \"develop\"\n"
+    ))?;
+
+    sv_cmd(&repo)
+        .args(["ws", "new", "feature/login"])
+        .assert()
+        .success();
+    sv_cmd(&repo)
+        .args(["ws", "new", "chore"])
+        .assert()
+        .success();
+
+    let storage = Storage::for_repo(repo.path().to_path_buf());
+    let registry = storage.read_workspaces()?;
+    assert_eq!(
+        registry
+            .find("feature/login")
+            .expect("feature workspace")
+            .base,
+        "develop"
+    );
+    assert_eq!(registry.find("chore").expect("chore workspace").base, trunk);
+
+    Ok(())
+}
+
+#[test]
+fn ws_list_stale_keeps_inactive_workspaces() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = TestRepo::init()?;
+    repo.init_sv_dirs()?;
+    // A base commit from long ago: workspaces without commits of their own
+    // are only as active as their bound tasks.
+    let status = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+        .args(["commit", "-q", "--allow-empty", "-m", "old"])
+        .env("GIT_AUTHOR_DATE", "2020-01-01T00:00:00Z")
+        .env("GIT_COMMITTER_DATE", "2020-01-01T00:00:00Z")
+        .status()?;
+    assert!(status.success());
+
+    for name in ["idle", "busy", "tasked"] {
+        sv_cmd(&repo)
+            .args(["ws", "new", name, "--base", "HEAD"])
+            .assert()
+            .success();
+    }
+    let busy = repo.path().join(".sv/worktrees/busy");
+    std::fs::write(busy.join("notes.txt"), "fresh\n")?;
+    for args in [
+        vec!["add", "notes.txt"],
+        vec!["commit", "-q", "-m", "fresh"],
+    ] {
+        let status = std::process::Command::new("git")
+            .current_dir(&busy)
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(args)
+            .status()?;
+        assert!(status.success());
+    }
+    let tasked = repo.path().join(".sv/worktrees/tasked");
+    let output = support::sv_cmd()
+        .current_dir(&tasked)
+        .args(["task", "new", "Keep going", "--json"])
+        .output()?;
+    let task: Value = serde_json::from_slice(&output.stdout)?;
+    let task_id = task["data"]["id"].as_str().ok_or("task id")?.to_string();
+    support::sv_cmd()
+        .current_dir(&tasked)
+        .env("SV_ACTOR", "alice")
+        .args(["task", "start", &task_id])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&repo)
+        .args(["ws", "list", "--stale", "30d", "--json"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let items: Value = serde_json::from_slice(&output.stdout)?;
+    let names: Vec<&str> = items
+        .as_array()
+        .ok_or("list")?
+        .iter()
+        .filter_map(|item| item["name"].as_str())
+        .collect();
+    assert_eq!(names, vec!["idle"]);
+    assert!(items[0]["last_commit_at"]
+        .as_str()
+        .unwrap_or_default()
+        .starts_with("2020-01-01"));
+
+    sv_cmd(&repo)
+        .args(["ws", "list", "--stale", "30d"])
+        .assert()
+        .success()
+        .stdout(contains("idle").and(contains("2020-01-01 00:00")));
+
+    Ok(())
+}
+
+#[test]
+fn ws_archive_keeps_branch_until_restore() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = setup_repo()?;
+    sv_cmd(&repo)
+        .args(["ws", "new", "done", "--base", "HEAD"])
+        .assert()
+        .success();
+    let worktree = repo.path().join(".sv/worktrees/done");
+    sv_cmd(&repo)
+        .env("SV_ACTOR", "alice")
+        .args(["take", "src/**", "--scope", "ws:done"])
+        .assert()
+        .success();
+
+    let output = sv_cmd(&repo)
+        .args(["ws", "archive", "done", "--json"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let archived: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        archived["released_leases"].as_array().map(Vec::len),
+        Some(1)
+    );
+    assert!(!worktree.exists());
+    assert!(repo
+        .repo()
+        .find_branch("sv/ws/done", git2::BranchType::Local)
+        .is_ok());
+
+    let list = |args: &[&str]| -> Result<Value, Box<dyn std::error::Error>> {
+        let output = sv_cmd(&repo).args(args).output()?;
+        Ok(serde_json::from_slice(&output.stdout)?)
+    };
+    assert_eq!(
+        list(&["ws", "list", "--json"])?.as_array().map(Vec::len),
+        Some(0)
+    );
+    let all = list(&["ws", "list", "--all", "--json"])?;
+    assert_eq!(all[0]["name"], "done");
+    assert_eq!(all[0]["archived"], true);
+    let leases = list(&["lease", "ls", "--json"])?;
+    assert!(!leases.to_string().contains("src/**"));
+
+    sv_cmd(&repo)
+        .args(["ws", "restore", "done"])
+        .assert()
+        .success();
+    assert!(worktree.join("README.md").exists());
+    let restored = list(&["ws", "list", "--json"])?;
+    assert_eq!(restored[0]["name"], "done");
+    assert_eq!(restored[0]["archived"], false);
+    sv_cmd(&repo)
+        .args(["ws", "restore", "done"])
+        .assert()
+        .failure()
+        .stderr(contains("not archived"));
+
+    let log = list(&["op", "log", "--operation", "ws", "--json"])?;
+    let kinds: Vec<_> = log["records"]
+        .as_array()
+        .ok_or("records")?
+        .iter()
+        .filter_map(|record| record["kind"].as_str())
+        .collect();
+    assert!(kinds.contains(&"ws_archive"), "{kinds:?}");
+    assert!(kinds.contains(&"ws_restore"), "{kinds:?}");
+
+    Ok(())
+}